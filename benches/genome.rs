@@ -0,0 +1,208 @@
+//! 文字列DNAとビット列ゲノム（`BitGenome`）の処理速度の比較と、
+//! エージェント単位の対戦・世代生成の処理速度
+//!
+//! 実行: `cargo bench --bench genome`
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ga_prisoners_dilemma::core::genome::BitGenome;
+use ga_prisoners_dilemma::engine::genetic::diversity_metric;
+use ga_prisoners_dilemma::models::model::{Agent, BaseModel};
+use ga_prisoners_dilemma::strategies::utils::{StrategyOperation, ThresholdSelectionStrategy};
+use ga_prisoners_dilemma::{GeneticOperations, RouletteSelection, SelectionStrategy};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const DNA_LENGTH: usize = 256;
+const MUTATION_RATE: f64 = 0.01;
+
+fn random_dna(rng: &mut StdRng, length: usize) -> String {
+    (0..length)
+        .map(|_| if rng.gen::<bool>() { '1' } else { '0' })
+        .collect()
+}
+
+fn population(size: usize) -> (Vec<String>, Vec<BitGenome>) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let strings: Vec<String> = (0..size)
+        .map(|_| random_dna(&mut rng, DNA_LENGTH))
+        .collect();
+    let genomes = strings.iter().map(|dna| dna.parse().unwrap()).collect();
+    (strings, genomes)
+}
+
+fn string_hamming(a: &str, b: &str) -> usize {
+    a.chars().zip(b.chars()).filter(|(x, y)| x != y).count()
+}
+
+fn string_mutate(dna: &str, rate: f64, rng: &mut StdRng) -> String {
+    dna.chars()
+        .map(|c| match (rng.gen::<f64>() < rate, c) {
+            (true, '0') => '1',
+            (true, '1') => '0',
+            _ => c,
+        })
+        .collect()
+}
+
+fn bench_popcount(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dna_sum");
+    for size in [1_000, 10_000] {
+        let (strings, genomes) = population(size);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("string", size), &strings, |b, strings| {
+            b.iter(|| {
+                strings
+                    .iter()
+                    .map(|dna| dna.chars().filter(|&c| c == '1').count() as u64)
+                    .sum::<u64>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("genome", size), &genomes, |b, genomes| {
+            b.iter(|| genomes.iter().map(BitGenome::count_ones).sum::<u64>())
+        });
+    }
+    group.finish();
+}
+
+fn bench_hamming(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pairwise_hamming");
+    for size in [100, 500] {
+        let (strings, genomes) = population(size);
+        group.throughput(Throughput::Elements((size * (size - 1) / 2) as u64));
+        group.bench_with_input(BenchmarkId::new("string", size), &strings, |b, strings| {
+            b.iter(|| {
+                let mut total = 0;
+                for i in 0..strings.len() {
+                    for j in i + 1..strings.len() {
+                        total += string_hamming(&strings[i], &strings[j]);
+                    }
+                }
+                total
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("genome", size), &genomes, |b, genomes| {
+            b.iter(|| {
+                let mut total = 0;
+                for i in 0..genomes.len() {
+                    for j in i + 1..genomes.len() {
+                        total += genomes[i].hamming_distance(&genomes[j]).unwrap();
+                    }
+                }
+                total
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_crossover_and_mutation(c: &mut Criterion) {
+    let size = 10_000;
+    let (strings, genomes) = population(size);
+    let point = DNA_LENGTH / 3;
+
+    let mut group = c.benchmark_group("breed");
+    group.throughput(Throughput::Elements(size as u64));
+    group.bench_function("string", |b| {
+        let mut rng = StdRng::seed_from_u64(7);
+        b.iter(|| {
+            strings
+                .iter()
+                .zip(strings.iter().rev())
+                .map(|(a, b)| {
+                    let child = format!("{}{}", &a[..point], &b[point..]);
+                    string_mutate(&child, MUTATION_RATE, &mut rng)
+                })
+                .count()
+        })
+    });
+    group.bench_function("genome", |b| {
+        let mut rng = StdRng::seed_from_u64(7);
+        b.iter(|| {
+            genomes
+                .iter()
+                .zip(genomes.iter().rev())
+                .map(|(a, b)| a.crossover(b, point).mutate(MUTATION_RATE, &mut rng))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+fn agents(size: usize) -> Vec<Agent> {
+    let (strings, _) = population(size);
+    strings
+        .into_iter()
+        .enumerate()
+        .map(|(i, dna)| BaseModel::new(i as u64, dna))
+        .collect()
+}
+
+fn bench_agent_diversity(c: &mut Criterion) {
+    let agents = agents(300);
+
+    c.bench_function("agent_diversity_metric_300", |b| {
+        b.iter(|| diversity_metric(black_box(&agents)))
+    });
+}
+
+fn bench_agent_matches(c: &mut Criterion) {
+    let size = 100;
+    let agents = agents(size);
+    let strategy = ThresholdSelectionStrategy::default();
+
+    let mut group = c.benchmark_group("agent_round_robin");
+    group.throughput(Throughput::Elements((size * (size - 1) / 2) as u64));
+    group.bench_function(BenchmarkId::from_parameter(size), |b| {
+        let mut rng = StdRng::seed_from_u64(7);
+        b.iter(|| {
+            let mut total = 0;
+            for i in 0..agents.len() {
+                for j in i + 1..agents.len() {
+                    let (first, second) = strategy.match_payoffs(&agents[i], &agents[j], &mut rng);
+                    total += first + second;
+                }
+            }
+            total
+        })
+    });
+    group.finish();
+}
+
+fn bench_agent_generation(c: &mut Criterion) {
+    let size = 1_000;
+    let agents: Vec<Agent> = agents(size)
+        .into_iter()
+        .enumerate()
+        .map(|(i, agent)| agent.with_points(i as u64 + 1))
+        .collect();
+    let selection = RouletteSelection::new();
+    let point = DNA_LENGTH / 3;
+
+    let mut group = c.benchmark_group("agent_generation");
+    group.throughput(Throughput::Elements(size as u64));
+    group.bench_function(BenchmarkId::from_parameter(size), |b| {
+        let mut rng = StdRng::seed_from_u64(7);
+        b.iter(|| {
+            (0..size)
+                .map(|_| {
+                    let (parent1, parent2) = selection
+                        .select_parents_with_rng(&agents, &mut rng)
+                        .unwrap();
+                    GeneticOperations::crossover(&parent1, &parent2, point)
+                        .mutate_with_rng(MUTATION_RATE, &mut rng)
+                })
+                .collect::<Vec<Agent>>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_popcount,
+    bench_hamming,
+    bench_crossover_and_mutation,
+    bench_agent_diversity,
+    bench_agent_matches,
+    bench_agent_generation
+);
+criterion_main!(benches);
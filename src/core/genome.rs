@@ -0,0 +1,503 @@
+/// 遺伝子型（`Genome`トレイト）の実装
+///
+/// * `BitGenome` - DNAを'0'/'1'の文字列ではなく`u64`のワード列として保持し、
+///   1の数（popcount）、XORによるハミング距離、ワード単位の交叉と突然変異を
+///   高速に計算します。長さに制限はなく、最後のワードの未使用ビットは常に0に保たれます。
+/// * `RealGenome` - 0.0-1.0の実数を遺伝子とするベクトルです。
+///
+/// 文字列表現（`Display`）はレポート出力などに使用します。
+use super::errors::{GAError, GAResult};
+use super::traits::Genome;
+use super::types::*;
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+/// 1ワードあたりのビット数
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// ビット列で保持するゲノム
+///
+/// ビット`i`はDNA文字列の`i`文字目に対応し、ワード`i / 64`の下位から
+/// `i % 64`番目のビットに格納されます。
+///
+/// # フィールド
+/// * `words` - ビットを格納するワード列
+/// * `len` - 遺伝子（ビット）の数
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitGenome {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitGenome {
+    /// 全ビットが0のゲノムを作成
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+
+    /// 各遺伝子が等確率で0または1のゲノムを作成
+    pub fn random<R: Rng>(len: usize, rng: &mut R) -> Self {
+        let mut genome = Self {
+            words: (0..len.div_ceil(WORD_BITS)).map(|_| rng.gen()).collect(),
+            len,
+        };
+        genome.clear_unused_bits();
+        genome
+    }
+
+    /// ビット列から作成
+    pub fn from_bits<I: IntoIterator<Item = bool>>(bits: I) -> Self {
+        let mut genome = Self::default();
+        for bit in bits {
+            genome.push(bit);
+        }
+        genome
+    }
+
+    /// 文字列から作成（'1'以外の文字は0として扱う）
+    ///
+    /// 検証済みのDNAを変換する場合に使用します。不正な文字をエラーにする場合は
+    /// `FromStr`を使用してください。
+    pub fn from_dna_lossy(dna: &str) -> Self {
+        Self::from_bits(dna.bytes().map(|b| b == b'1'))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// ビットを格納するワード列
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// `index`番目の遺伝子
+    ///
+    /// # パニック
+    /// `index`が長さ以上の場合
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len, "gene index {index} out of range");
+        self.words[index / WORD_BITS] >> (index % WORD_BITS) & 1 == 1
+    }
+
+    /// `index`番目の遺伝子を設定
+    ///
+    /// # パニック
+    /// `index`が長さ以上の場合
+    pub fn set(&mut self, index: usize, bit: bool) {
+        assert!(index < self.len, "gene index {index} out of range");
+        let mask = 1 << (index % WORD_BITS);
+        if bit {
+            self.words[index / WORD_BITS] |= mask;
+        } else {
+            self.words[index / WORD_BITS] &= !mask;
+        }
+    }
+
+    /// `index`番目の遺伝子を反転
+    pub fn flip(&mut self, index: usize) {
+        assert!(index < self.len, "gene index {index} out of range");
+        self.words[index / WORD_BITS] ^= 1 << (index % WORD_BITS);
+    }
+
+    /// 末尾に遺伝子を追加
+    pub fn push(&mut self, bit: bool) {
+        if self.len % WORD_BITS == 0 {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, bit);
+    }
+
+    /// 1の遺伝子の数（popcount）
+    pub fn count_ones(&self) -> u64 {
+        self.words.iter().map(|w| w.count_ones() as u64).sum()
+    }
+
+    /// 同じ長さのゲノムとの異なる遺伝子の数（XORとpopcountで計算）
+    ///
+    /// 長さが異なる場合は`None`を返します。
+    pub fn hamming_distance(&self, other: &BitGenome) -> Option<usize> {
+        (self.len == other.len).then(|| {
+            self.words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| (a ^ b).count_ones() as usize)
+                .sum()
+        })
+    }
+
+    /// 先頭から`point`個を自分、残りを`other`から取った子を作成
+    ///
+    /// `point`はそれぞれの長さで切り詰められるため、長さの異なるゲノム同士でも
+    /// 交叉でき、子の長さは`min(point, self.len) + other.len - min(point, other.len)`です。
+    /// 切断点をまたぐワード以外はワード単位でコピーします。
+    pub fn crossover(&self, other: &BitGenome, point: usize) -> BitGenome {
+        let head = point.min(self.len);
+        let tail_start = point.min(other.len);
+
+        if head == tail_start {
+            // 同じ位置で切断する場合はワード単位で合成できる
+            let mut words = other.words.clone();
+            let full = head / WORD_BITS;
+            words[..full].copy_from_slice(&self.words[..full]);
+            let rest = head % WORD_BITS;
+            if rest > 0 {
+                let mask = (1u64 << rest) - 1;
+                words[full] = (self.words[full] & mask) | (other.words[full] & !mask);
+            }
+            return BitGenome {
+                words,
+                len: other.len,
+            };
+        }
+
+        let mut child = BitGenome {
+            words: self.words[..head.div_ceil(WORD_BITS)].to_vec(),
+            len: head,
+        };
+        child.clear_unused_bits();
+        for index in tail_start..other.len {
+            child.push(other.get(index));
+        }
+        child
+    }
+
+    /// 各遺伝子を確率`rate`で反転した子を作成
+    ///
+    /// 反転する位置を幾何分布で飛ばしながら選ぶため、計算量は反転する遺伝子の数に
+    /// 比例し、低い突然変異率では長いゲノムでも高速です。
+    pub fn mutate<R: Rng>(&self, rate: f64, rng: &mut R) -> BitGenome {
+        let mut child = self.clone();
+        if rate <= 0.0 || self.len == 0 {
+            return child;
+        }
+        if rate >= 1.0 {
+            for word in &mut child.words {
+                *word = !*word;
+            }
+            child.clear_unused_bits();
+            return child;
+        }
+
+        let log_keep = (1.0 - rate).ln();
+        let mut index = 0usize;
+        loop {
+            let u: f64 = rng.gen();
+            let skip = ((1.0 - u).ln() / log_keep).floor();
+            if !skip.is_finite() || skip >= (self.len - index) as f64 {
+                break;
+            }
+            index += skip as usize;
+            child.flip(index);
+            index += 1;
+            if index >= self.len {
+                break;
+            }
+        }
+        child
+    }
+
+    /// 先頭64ビットまでを整数として解釈（先頭の遺伝子が最上位ビット）
+    ///
+    /// 長さが64を超える場合は`None`を返します。
+    pub fn to_u64(&self) -> Option<u64> {
+        (self.len <= WORD_BITS)
+            .then(|| (0..self.len).fold(0, |acc, i| acc << 1 | self.get(i) as u64))
+    }
+
+    /// 最後のワードの未使用ビットを0にする
+    fn clear_unused_bits(&mut self) {
+        let rest = self.len % WORD_BITS;
+        if rest > 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1u64 << rest) - 1;
+            }
+        }
+    }
+}
+
+impl fmt::Display for BitGenome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dna: String = (0..self.len)
+            .map(|i| if self.get(i) { '1' } else { '0' })
+            .collect();
+        f.write_str(&dna)
+    }
+}
+
+impl FromStr for BitGenome {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        super::errors::validation::validate_dna(s)?;
+        Ok(Self::from_dna_lossy(s))
+    }
+}
+
+impl Genome for BitGenome {
+    fn random<R: Rng>(length: usize, rng: &mut R) -> Self {
+        BitGenome::random(length, rng)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn mutate<R: Rng>(&self, rate: MutationRate, rng: &mut R) -> Self {
+        BitGenome::mutate(self, rate, rng)
+    }
+
+    fn crossover(&self, other: &Self, point: CrossoverPoint) -> Self {
+        BitGenome::crossover(self, other, point)
+    }
+
+    fn distance(&self, other: &Self) -> f64 {
+        self.hamming_distance(other)
+            .unwrap_or_else(|| self.len.max(other.len)) as f64
+    }
+
+    fn gene_sum(&self) -> f64 {
+        self.count_ones() as f64
+    }
+}
+
+/// 0.0-1.0の実数を遺伝子とする遺伝子型
+///
+/// 各遺伝子は、例えば各状況での協力確率のような連続値の形質を表します。
+/// 文字列表現は遺伝子をカンマで区切った形式（例: `0.25,1,0.5`）です。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RealGenome {
+    genes: Vec<f64>,
+}
+
+impl RealGenome {
+    /// 遺伝子の列から作成
+    ///
+    /// # エラー
+    /// 遺伝子が空の場合、または0.0-1.0の範囲外の値を含む場合
+    pub fn new(genes: Vec<f64>) -> GAResult<Self> {
+        if genes.is_empty() {
+            return Err(GAError::InvalidDna("Genome cannot be empty".to_string()));
+        }
+        if let Some(gene) = genes.iter().find(|g| !(0.0..=1.0).contains(*g)) {
+            return Err(GAError::InvalidDna(format!(
+                "Gene value must be between 0.0 and 1.0, got {gene}"
+            )));
+        }
+        Ok(Self { genes })
+    }
+
+    pub fn genes(&self) -> &[f64] {
+        &self.genes
+    }
+}
+
+impl Genome for RealGenome {
+    fn random<R: Rng>(length: usize, rng: &mut R) -> Self {
+        Self {
+            genes: (0..length).map(|_| rng.gen()).collect(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.genes.len()
+    }
+
+    /// 各遺伝子を確率`rate`で0.0-1.0の一様乱数に置き換える
+    fn mutate<R: Rng>(&self, rate: MutationRate, rng: &mut R) -> Self {
+        Self {
+            genes: self
+                .genes
+                .iter()
+                .map(|&gene| {
+                    if rng.gen::<f64>() < rate {
+                        rng.gen()
+                    } else {
+                        gene
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    fn crossover(&self, other: &Self, point: CrossoverPoint) -> Self {
+        let head = point.min(self.genes.len());
+        let tail_start = point.min(other.genes.len());
+        Self {
+            genes: self.genes[..head]
+                .iter()
+                .chain(&other.genes[tail_start..])
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// 遺伝子ごとの差の絶対値の合計（マンハッタン距離）
+    ///
+    /// 長さが異なる場合、相手のない遺伝子は差を1.0として数えます。
+    fn distance(&self, other: &Self) -> f64 {
+        let common: f64 = self
+            .genes
+            .iter()
+            .zip(&other.genes)
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+        common + self.genes.len().abs_diff(other.genes.len()) as f64
+    }
+
+    fn gene_sum(&self) -> f64 {
+        self.genes.iter().sum()
+    }
+}
+
+impl fmt::Display for RealGenome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, gene) in self.genes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{gene}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RealGenome {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let genes = s
+            .split(',')
+            .map(|gene| {
+                gene.trim()
+                    .parse()
+                    .map_err(|_| GAError::InvalidDna(format!("Invalid gene value: {gene}")))
+            })
+            .collect::<GAResult<Vec<f64>>>()?;
+        Self::new(genes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn long_dna(len: usize) -> String {
+        (0..len)
+            .map(|i| if i % 3 == 0 { '1' } else { '0' })
+            .collect()
+    }
+
+    #[test]
+    fn test_string_round_trip_and_popcount() {
+        for dna in ["0", "1011", &long_dna(64), &long_dna(130)] {
+            let genome: BitGenome = dna.parse().unwrap();
+            assert_eq!(genome.to_string(), dna);
+            assert_eq!(genome.len(), dna.len());
+            assert_eq!(
+                genome.count_ones(),
+                dna.chars().filter(|&c| c == '1').count() as u64
+            );
+        }
+        assert!("10x1".parse::<BitGenome>().is_err());
+        assert!("".parse::<BitGenome>().is_err());
+    }
+
+    #[test]
+    fn test_hamming_distance() {
+        let a: BitGenome = long_dna(100).parse().unwrap();
+        let mut b = a.clone();
+        b.flip(0);
+        b.flip(70);
+        b.flip(99);
+        assert_eq!(a.hamming_distance(&b), Some(3));
+        assert_eq!(a.hamming_distance(&BitGenome::zeros(99)), None);
+    }
+
+    #[test]
+    fn test_crossover_matches_string_splice() {
+        let a = long_dna(150);
+        let b: String = long_dna(150)
+            .chars()
+            .map(|c| if c == '1' { '0' } else { '1' })
+            .collect();
+        let c = long_dna(40);
+        let (ga, gb, gc): (BitGenome, BitGenome, BitGenome) =
+            (a.parse().unwrap(), b.parse().unwrap(), c.parse().unwrap());
+
+        for point in [0, 1, 63, 64, 65, 100, 150, 200] {
+            let expected = format!("{}{}", &a[..point.min(150)], &b[point.min(150)..]);
+            assert_eq!(ga.crossover(&gb, point).to_string(), expected);
+
+            let expected = format!("{}{}", &a[..point.min(150)], &c[point.min(40)..]);
+            assert_eq!(ga.crossover(&gc, point).to_string(), expected);
+
+            let expected = format!("{}{}", &c[..point.min(40)], &a[point.min(150)..]);
+            assert_eq!(gc.crossover(&ga, point).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_mutation_rate() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let genome = BitGenome::zeros(10_000);
+
+        assert_eq!(genome.mutate(0.0, &mut rng), genome);
+        assert_eq!(genome.mutate(1.0, &mut rng).count_ones(), 10_000);
+
+        let flipped = genome.mutate(0.05, &mut rng).count_ones();
+        assert!((400..600).contains(&flipped), "{flipped}");
+    }
+
+    #[test]
+    fn test_random_and_to_u64() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let genome = BitGenome::random(70, &mut rng);
+        assert_eq!(genome.len(), 70);
+        assert_eq!(genome.words().len(), 2);
+        assert_eq!(genome.words()[1] >> 6, 0);
+        assert_eq!(genome.to_u64(), None);
+
+        assert_eq!("1011".parse::<BitGenome>().unwrap().to_u64(), Some(0b1011));
+    }
+
+    #[test]
+    fn test_genome_trait_for_bit_genome() {
+        let a: BitGenome = "110010".parse().unwrap();
+        let b: BitGenome = "100011".parse().unwrap();
+        assert_eq!(Genome::distance(&a, &b), 2.0);
+        assert_eq!(a.gene_sum(), 3.0);
+        assert_eq!(Genome::crossover(&a, &b, 3).to_string(), "110011");
+    }
+
+    #[test]
+    fn test_real_genome() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let a: RealGenome = "0.25,1,0.5".parse().unwrap();
+        assert_eq!(a.to_string().parse::<RealGenome>().unwrap(), a);
+        assert_eq!(a.gene_sum(), 1.75);
+        assert!("0.5,1.5".parse::<RealGenome>().is_err());
+        assert!("0.5,x".parse::<RealGenome>().is_err());
+
+        let b = RealGenome::new(vec![0.0, 0.0, 0.0]).unwrap();
+        assert_eq!(a.crossover(&b, 1).genes(), &[0.25, 0.0, 0.0]);
+        assert_eq!(a.distance(&b), 1.75);
+        assert_eq!(a.distance(&RealGenome::new(vec![0.25]).unwrap()), 2.0);
+
+        assert_eq!(a.mutate(0.0, &mut rng), a);
+        let random = <RealGenome as Genome>::random(100, &mut rng);
+        assert_eq!(random.len(), 100);
+        assert!(random.genes().iter().all(|g| (0.0..1.0).contains(g)));
+    }
+}
@@ -7,7 +7,14 @@ pub mod traits;
 /// Error types and validation
 pub mod errors;
 
+/// Genome representations (bit-packed and real-valued)
+pub mod genome;
+
+/// Reproducible parallel evaluation behind the `parallel` feature
+pub mod parallel;
+
 // Re-export commonly used items
 pub use errors::{GAError, GAResult};
+pub use genome::{BitGenome, RealGenome};
 pub use traits::*;
 pub use types::*;
@@ -3,7 +3,11 @@
 /// このモジュールでは、遺伝的アルゴリズムの各構成要素が実装すべき
 /// トレイトを定義しています。Rustのトレイトシステムを活用し、
 /// 型安全性と拡張性を両立した設計を実現しています。
+use crate::core::errors::{GAError, GAResult};
 use crate::core::types::*;
+use rand::{Rng, RngCore};
+use std::fmt;
+use std::str::FromStr;
 
 /// 全ての遺伝的アルゴリズムエンティティの基底トレイト
 ///
@@ -27,7 +31,7 @@ pub trait BaseEntity: Clone + Send + Sync {
 ///
 /// # 必須メソッド
 /// * `crossover()` - 他の個体との交叉操作
-/// * `mutate()` - 突然変異操作
+/// * `mutate_with_rng()` - 与えられた乱数生成器による突然変異操作
 /// * `fitness()` - 適応度評価
 pub trait GeneticOperations: BaseEntity {
     /// 他の個体との交叉を実行
@@ -47,19 +51,51 @@ pub trait GeneticOperations: BaseEntity {
     ///
     /// # 戻り値
     /// 突然変異が適用された個体（変異が発生しない場合は元の個体のクローン）
-    fn mutate(&self, rate: MutationRate) -> Self;
+    fn mutate(&self, rate: MutationRate) -> Self {
+        self.mutate_with_rng(rate, &mut rand::thread_rng())
+    }
+
+    /// 与えられた乱数生成器で突然変異を実行
+    ///
+    /// シード付きの乱数生成器を与えると、同じシードからは同じ子が生まれます。
+    ///
+    /// # 引数
+    /// * `rate` - 突然変異率（0.0-1.0）
+    /// * `rng` - 突然変異に使う乱数生成器
+    fn mutate_with_rng<R: Rng>(&self, rate: MutationRate, rng: &mut R) -> Self;
 
     /// 個体の適応度を取得
     ///
     /// # 戻り値
     /// 環境への適応度を表す数値（通常は高いほど良い）
     fn fitness(&self) -> Fitness;
+
+    /// 多目的最適化用の適応度ベクトルを取得
+    ///
+    /// `fitness()`の多目的版です。各要素は`objectives`の順に並び、
+    /// 最小化目的は符号を反転して全て最大化方向に揃えられます。
+    ///
+    /// # 引数
+    /// * `objectives` - 評価する目的関数の並び
+    ///
+    /// # 戻り値
+    /// 目的関数ごとの値（高いほど良い）
+    fn fitness_vector(&self, objectives: &[Objective]) -> Vec<f64>
+    where
+        Self: DnaOperations + Sized,
+    {
+        objectives
+            .iter()
+            .map(|objective| objective.oriented_value(self))
+            .collect()
+    }
 }
 
 /// DNA操作に関するトレイト
 ///
 /// 遺伝子情報（DNA）に対する基本的な操作を定義します。
 /// 囚人のジレンマでは、DNAは戦略を表現する文字列として扱われます。
+/// 二値以外の遺伝子型（`Genome`）を持つ個体では、DNAは遺伝子型の文字列表現です。
 ///
 /// # 必須メソッド
 /// * `dna()` - DNA文字列の参照を取得
@@ -92,6 +128,104 @@ pub trait DnaOperations {
     /// # 戻り値
     /// DNAの文字列表現
     fn dna_binary(&self) -> &str;
+
+    /// 同じ長さのDNAとの間で値が異なる遺伝子の数を取得
+    ///
+    /// デフォルト実装は文字列を1文字ずつ比較します。ビット列でDNAを保持する
+    /// 実装はXORとpopcountで上書きできます。
+    ///
+    /// # 引数
+    /// * `other` - 比較するDNAを持つ個体（DNA長が等しいこと）
+    fn differing_genes(&self, other: &Self) -> usize
+    where
+        Self: Sized,
+    {
+        self.dna_binary()
+            .bytes()
+            .zip(other.dna_binary().bytes())
+            .filter(|(a, b)| a != b)
+            .count()
+    }
+}
+
+/// 遺伝子型の表現を定義するトレイト
+///
+/// 個体の遺伝情報の表現（ビット列、実数ベクトル、有限状態機械、木など）を
+/// エージェントから切り離し、個体群・選択・交叉などのエンジンを表現に依存せず
+/// 再利用できるようにします。`Display`/`FromStr`による文字列表現は、
+/// レポート出力と戦略ファイルの読み書きに使用されます。
+///
+/// # 必須メソッド
+/// * `random()` - ランダムな遺伝子型の生成
+/// * `len()` - 遺伝子の数
+/// * `mutate()` - 突然変異
+/// * `crossover()` - 一点交叉
+/// * `distance()` - 他の遺伝子型との距離
+/// * `gene_sum()` - 遺伝子の値の合計
+pub trait Genome: Clone + PartialEq + Send + Sync + fmt::Display + FromStr<Err = GAError> {
+    /// ランダムな遺伝子型を生成
+    ///
+    /// # 引数
+    /// * `length` - 遺伝子の数
+    /// * `rng` - 乱数生成器
+    fn random<R: Rng>(length: usize, rng: &mut R) -> Self;
+
+    /// 遺伝子の数を取得
+    fn len(&self) -> usize;
+
+    /// 遺伝子を持たないかどうか
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 突然変異を適用した遺伝子型を作成
+    ///
+    /// # 引数
+    /// * `rate` - 遺伝子ごとの突然変異率（0.0-1.0）
+    /// * `rng` - 乱数生成器
+    fn mutate<R: Rng>(&self, rate: MutationRate, rng: &mut R) -> Self;
+
+    /// 先頭から`point`個の遺伝子を自分、残りを`other`から取った遺伝子型を作成
+    ///
+    /// # 引数
+    /// * `other` - 交叉相手の遺伝子型
+    /// * `point` - 交叉点
+    fn crossover(&self, other: &Self, point: CrossoverPoint) -> Self;
+
+    /// 同じ長さの遺伝子型との距離を計算
+    ///
+    /// 遺伝子ごとの差の大きさの合計です（二値の遺伝子型ではハミング距離）。
+    /// 遺伝子の数で割ると0.0-1.0の正規化距離になるように定義します。
+    fn distance(&self, other: &Self) -> f64;
+
+    /// 遺伝子の値の合計を取得（二値の遺伝子型では1の数）
+    ///
+    /// 協力率などの統計に使用されます。
+    fn gene_sum(&self) -> f64;
+}
+
+/// 遺伝子型を`Genome`として保持する個体のトレイト
+///
+/// `DnaOperations`の文字列表現に加えて、遺伝子型そのものへのアクセスと
+/// 遺伝子型からの個体の生成を提供します。
+///
+/// # 必須メソッド
+/// * `genome()` - 遺伝子型の参照を取得
+/// * `with_genome()` - 遺伝子型を置き換えた新しいインスタンス
+pub trait GenomeOperations: Agent {
+    /// 個体が保持する遺伝子型の表現
+    type Genome: Genome;
+
+    /// 遺伝子型の参照を取得
+    fn genome(&self) -> &Self::Genome;
+
+    /// 指定された遺伝子型を持つ新しいインスタンスを作成
+    ///
+    /// 遺伝子型以外の遺伝情報（突然変異率遺伝子など）は引き継ぎ、ポイントは0になります。
+    ///
+    /// # 引数
+    /// * `genome` - 新しい遺伝子型
+    fn with_genome(&self, genome: Self::Genome) -> Self;
 }
 
 /// ゲーム内でのエージェント行動を定義するトレイト
@@ -137,10 +271,50 @@ pub trait Agent: GeneticOperations + DnaOperations {
     fn deactivate(&mut self);
 }
 
+/// 遺伝子長が変化するエージェントを定義するトレイト
+///
+/// 挿入・欠失突然変異や、長さの異なる親同士の交叉（messy crossover）で
+/// 任意の長さのDNAを持つ子を作成するために使用します。
+///
+/// # 必須メソッド
+/// * `with_dna()` - DNAを置き換えた新しいインスタンス
+pub trait VariableLengthDna: Agent {
+    /// 指定されたDNAを持つ新しいインスタンスを作成
+    ///
+    /// DNA以外の遺伝情報（突然変異率遺伝子など）は引き継ぎ、ポイントは0になります。
+    ///
+    /// # 引数
+    /// * `dna` - 新しいDNA
+    fn with_dna(&self, dna: Dna) -> Self;
+}
+
 /// Trait for selection strategies
+///
+/// 選択できない場合は代わりの個体を返さずにエラーを返します。
+/// 全個体の適応度が0（または全員同じ）の場合は、どの戦略も一様に選択します。
 pub trait SelectionStrategy<T: Agent> {
-    fn select_parents(&self, population: &[T]) -> (T, T);
-    fn select_survivors(&self, population: &[T], count: usize) -> Vec<T>;
+    /// 交叉に使う親を2体選択（同じ個体が2回選ばれることもあります）
+    ///
+    /// # エラー
+    /// 個体群が空の場合は`GAError::EmptyPopulation`
+    fn select_parents(&self, population: &[T]) -> GAResult<(T, T)> {
+        self.select_parents_with_rng(population, &mut rand::thread_rng())
+    }
+
+    /// 与えられた乱数生成器で交叉に使う親を2体選択
+    ///
+    /// シード付きの乱数生成器を与えると、同じシードからは同じ親が選ばれます。
+    ///
+    /// # エラー
+    /// 個体群が空の場合は`GAError::EmptyPopulation`
+    fn select_parents_with_rng(&self, population: &[T], rng: &mut dyn RngCore) -> GAResult<(T, T)>;
+
+    /// 次世代に残す`count`体を選択
+    ///
+    /// # エラー
+    /// 個体群が空の場合は`GAError::EmptyPopulation`、
+    /// `count`が個体数より多い場合は`GAError::InsufficientCandidates`
+    fn select_survivors(&self, population: &[T], count: usize) -> GAResult<Vec<T>>;
 }
 
 /// Trait for game strategies
@@ -148,6 +322,18 @@ pub trait GameStrategy<T: Agent> {
     fn play_match(&self, agent1: &T, agent2: &T) -> (T, T);
 }
 
+/// 個体の行動特性（behavioural characterisation）を記述するトレイト
+///
+/// 新規性探索で、固定の対戦相手（プローブ）に対する行動を数値ベクトルとして
+/// 比較するために使用します。行動は遺伝子型と戦略の組で決まるため、
+/// 対戦戦略が実装します。
+pub trait BehaviourDescriptor<T> {
+    /// `agent`の`probes`に対する行動を記述したベクトル
+    ///
+    /// 全ての個体で同じ長さ・同じ並びのベクトルを返す必要があります。
+    fn describe(&self, agent: &T, probes: &[T]) -> Vec<f64>;
+}
+
 /// Trait for genetic algorithm operations
 pub trait GeneticAlgorithm<T: Agent> {
     fn population(&self) -> &[T];
@@ -170,7 +356,7 @@ impl Statistics for Vec<Points> {
         if self.is_empty() {
             0.0
         } else {
-            self.iter().sum::<u64>() as f64 / self.len() as f64
+            self.iter().map(|&x| x as f64).sum::<f64>() / self.len() as f64
         }
     }
 
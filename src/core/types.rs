@@ -27,6 +27,83 @@ pub type CrossoverPoint = usize;
 /// 個体の適応度
 pub type Fitness = u64;
 
+/// 多目的最適化の目的関数
+///
+/// NSGA-IIなどの多目的進化で、各個体を評価する軸を表します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Objective {
+    /// 獲得ポイント（最大化）
+    Score,
+    /// 協力率（最大化）
+    ///
+    /// DNA中の'1'の割合で、確率的戦略における期待協力確率に相当します。
+    Cooperation,
+    /// ゲノムの複雑さ（最小化）
+    ///
+    /// 隣接する遺伝子の値が切り替わる箇所の数です。
+    Complexity,
+}
+
+impl Objective {
+    /// 最大化する目的かどうか
+    pub fn is_maximized(&self) -> bool {
+        !matches!(self, Objective::Complexity)
+    }
+
+    /// 目的関数の値をそのままの向きで計算
+    pub fn value<T>(&self, agent: &T) -> f64
+    where
+        T: crate::core::traits::GeneticOperations + crate::core::traits::DnaOperations,
+    {
+        match self {
+            Objective::Score => agent.fitness() as f64,
+            Objective::Cooperation => agent.dna_sum() as f64 / agent.dna_length().max(1) as f64,
+            Objective::Complexity => {
+                let dna = agent.dna_binary().as_bytes();
+                dna.windows(2).filter(|w| w[0] != w[1]).count() as f64
+            }
+        }
+    }
+
+    /// 最大化方向に揃えた目的関数の値を計算（最小化目的は符号を反転）
+    pub fn oriented_value<T>(&self, agent: &T) -> f64
+    where
+        T: crate::core::traits::GeneticOperations + crate::core::traits::DnaOperations,
+    {
+        let value = self.value(agent);
+        if self.is_maximized() {
+            value
+        } else {
+            -value
+        }
+    }
+}
+
+impl std::fmt::Display for Objective {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Objective::Score => write!(f, "score"),
+            Objective::Cooperation => write!(f, "cooperation"),
+            Objective::Complexity => write!(f, "complexity"),
+        }
+    }
+}
+
+impl std::str::FromStr for Objective {
+    type Err = crate::core::errors::GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "score" => Ok(Objective::Score),
+            "cooperation" => Ok(Objective::Cooperation),
+            "complexity" => Ok(Objective::Complexity),
+            _ => Err(crate::core::errors::GAError::ValidationError(format!(
+                "Unknown objective: {s}"
+            ))),
+        }
+    }
+}
+
 /// デフォルトの個体数
 pub const DEFAULT_POPULATION: Population = 20;
 
@@ -58,6 +135,56 @@ pub const COOPERATE_DEFECT_REWARD: Points = 0;
 /// 両者裏切り時の報酬
 pub const DEFECT_DEFECT_REWARD: Points = 1;
 
+/// 囚人のジレンマの利得表
+///
+/// ゲームの対戦と、進化ダイナミクス（Moran過程、Wright–Fisher過程、
+/// レプリケーター方程式）での期待利得の計算で共通に使用します。
+/// デフォルトは上記の報酬定数（R=3, S=0, T=5, P=1）です。
+///
+/// # フィールド
+/// * `reward` - 両者協力時の報酬（R）
+/// * `sucker` - 自分が協力し相手が裏切った時の報酬（S）
+/// * `temptation` - 自分が裏切り相手が協力した時の報酬（T）
+/// * `punishment` - 両者裏切り時の報酬（P）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PayoffMatrix {
+    pub reward: Points,
+    pub sucker: Points,
+    pub temptation: Points,
+    pub punishment: Points,
+}
+
+impl PayoffMatrix {
+    /// 自分と相手の選択（協力ならtrue）から自分の報酬を取得
+    pub fn payoff(&self, cooperate: bool, opponent_cooperates: bool) -> Points {
+        match (cooperate, opponent_cooperates) {
+            (true, true) => self.reward,
+            (true, false) => self.sucker,
+            (false, true) => self.temptation,
+            (false, false) => self.punishment,
+        }
+    }
+
+    /// 協力確率`p`の戦略が協力確率`q`の戦略と対戦した時の期待報酬
+    pub fn expected_payoff(&self, p: f64, q: f64) -> f64 {
+        p * q * self.reward as f64
+            + p * (1.0 - q) * self.sucker as f64
+            + (1.0 - p) * q * self.temptation as f64
+            + (1.0 - p) * (1.0 - q) * self.punishment as f64
+    }
+}
+
+impl Default for PayoffMatrix {
+    fn default() -> Self {
+        Self {
+            reward: COOPERATE_COOPERATE_REWARD,
+            sucker: COOPERATE_DEFECT_REWARD,
+            temptation: DEFECT_COOPERATE_REWARD,
+            punishment: DEFECT_DEFECT_REWARD,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,4 +204,14 @@ mod tests {
         // これらの定数は静的に検証可能なので、テストから除外
         // コンパイル時に値が正しいことは型システムによって保証される
     }
+
+    #[test]
+    fn test_payoff_matrix() {
+        let matrix = PayoffMatrix::default();
+        assert_eq!(matrix.payoff(true, true), 3);
+        assert_eq!(matrix.payoff(false, true), 5);
+        assert_eq!(matrix.expected_payoff(1.0, 0.0), 0.0);
+        assert_eq!(matrix.expected_payoff(0.0, 0.0), 1.0);
+        assert!((matrix.expected_payoff(0.5, 0.5) - 2.25).abs() < 1e-12);
+    }
 }
@@ -0,0 +1,833 @@
+/// 2集団の共進化シミュレーション
+///
+/// 集団Aの個体は集団Bの個体とだけ対戦し、集団Bの個体は集団Aの個体とだけ
+/// 対戦します（非対称ゲームの行プレイヤーと列プレイヤー、宿主と寄生者など）。
+/// 各集団はそれぞれの`Config`に従って、独自の選択・突然変異・置換で進化します。
+/// 模倣ダイナミクスの集団では、相手集団との対戦の利得をもとに自集団の近傍の
+/// 個体を手本にします。
+/// 殿堂を対戦相手にする設定の集団は、相手集団の殿堂の遺伝子型とも対戦します。
+use super::evolution::{
+    age_stats, evaluate_novelty, evolve_population, game_strategy, hall_of_fame_opponents,
+    hall_of_fame_rng, imitate_population, inherit_learning, initial_population, match_cache,
+    mean_cooperation, mean_learned_cooperation, mean_points, new_speciation,
+    normalized_child_fitness, normalized_fitness, payoff_per_match, points_against, speciate,
+    success_ratio,
+};
+use super::simulation::{
+    print_age, print_complexity, print_hall_of_fame, print_learning, print_novelty, print_species,
+    species_label, FinalStats, GenerationStats, PopulationResult, SimulationResult,
+};
+use crate::core::errors::{GAError, GAResult};
+use crate::core::parallel::{derive_seed, stream_rng};
+use crate::core::types::{AgentId, Points};
+use crate::engine::aging::MortalityRecord;
+use crate::engine::diversity::Speciation;
+use crate::engine::genetic::{
+    diversity_metric, edit_distance_diversity, ComplexityStats, Population,
+};
+use crate::engine::hall_of_fame::HallOfFame;
+use crate::engine::learning::Learning;
+use crate::engine::multi_objective::Nsga2;
+use crate::engine::mutation::MutationController;
+use crate::engine::novelty::NoveltyArchive;
+use crate::engine::selection::FitnessNormalization;
+use crate::engine::termination::{
+    CancellationToken, Termination, TerminationMonitor, TerminationReason,
+};
+use crate::infrastructure::config::Config;
+use crate::models::model::{Agent, BaseModel, Model};
+use crate::strategies::cache::{cached_payoffs, MatchCache};
+use crate::strategies::utils::{
+    calculate_payoff, Choice, RouletteSelectionStrategy, StrategyOperation,
+};
+use rand::{thread_rng, Rng};
+use std::sync::Arc;
+
+/// 集団の表示名
+const POPULATION_NAMES: [&str; 2] = ["A", "B"];
+
+/// 集団間の対戦の乱数を導出するときの系列番号
+const MATCH_STREAM: u64 = 0;
+
+/// 2集団の共進化シミュレーションの管理構造体
+///
+/// 世代数・報告間隔・世代あたりのラウンド数・遺伝子型のデコード方式・
+/// 対戦結果のキャッシュの有無は集団Aの設定を使用します。
+/// それ以外のパラメータ（個体数、DNA長、突然変異率とスキーム、多様性維持、
+/// 置換モデル、目的関数、終了条件）は集団ごとに独立して設定できます。
+/// いずれかの集団が自身の終了条件を満たすと、両集団ともその世代で終了します。
+///
+/// # フィールド
+/// * `configs` - 集団A・Bそれぞれの設定
+/// * `cancellation` - 実行を外部から中断するためのトークン
+/// * `match_cache` - 決定的な対戦の結果のキャッシュ（集団Aの設定で有効な場合）
+pub struct CoevolutionSimulation {
+    configs: [Config; 2],
+    cancellation: Option<CancellationToken>,
+    match_cache: Option<Arc<MatchCache>>,
+}
+
+/// 実行中の1集団の状態
+struct Side {
+    population: Population<Agent>,
+    mutation: MutationController,
+    nsga2: Option<Nsga2>,
+    parent_points: Vec<f64>,
+    hall_of_fame: HallOfFame,
+    novelty_archive: NoveltyArchive,
+    novelty_archive_history: Vec<usize>,
+    mortality: MortalityRecord,
+    speciation: Speciation<Agent>,
+    monitor: TerminationMonitor,
+    generation_results: Vec<GenerationStats>,
+    diversity_history: Vec<f64>,
+    mutation_rate_history: Vec<f64>,
+    payoff_history: Vec<f64>,
+    cooperation_history: Vec<f64>,
+    learned_cooperation_history: Vec<f64>,
+}
+
+/// 1世代分の統計を記録した集団の、選択に使う値
+///
+/// # フィールド
+/// * `selection_points` - 選択用の適応度（新規性探索では新規性を混合した評価値）
+/// * `payoffs` - 模倣に使う各個体の1回の対戦あたりの平均利得
+/// * `mutation_rate` - この世代の突然変異率
+struct Evaluation {
+    selection_points: Vec<Points>,
+    payoffs: Vec<f64>,
+    mutation_rate: f64,
+}
+
+impl CoevolutionSimulation {
+    /// 新しい共進化シミュレーションを作成
+    ///
+    /// # 引数
+    /// * `config_a` - 集団Aの設定
+    /// * `config_b` - 集団Bの設定
+    ///
+    /// # エラー
+    /// いずれかの設定の検証に失敗した場合、いずれかの設定が集団選択を使う場合
+    /// （集団間でのみ対戦するため、グループ内の対戦を定義できない）、
+    /// または2集団のデコード方式が異なる場合（対戦は共通の戦略で行うため）
+    pub fn new(config_a: Config, config_b: Config) -> GAResult<Self> {
+        config_a.validate()?;
+        config_b.validate()?;
+        if config_a.group_selection.is_some() || config_b.group_selection.is_some() {
+            return Err(GAError::ValidationError(
+                "Group selection is not supported in co-evolution".to_string(),
+            ));
+        }
+        if config_a.decoding != config_b.decoding {
+            return Err(GAError::ValidationError(format!(
+                "Both populations must use the same decoding in co-evolution, got {} and {}",
+                config_a.decoding, config_b.decoding
+            )));
+        }
+        Ok(Self {
+            match_cache: match_cache(&config_a),
+            configs: [config_a, config_b],
+            cancellation: None,
+        })
+    }
+
+    /// キャンセルトークンを設定
+    ///
+    /// トークンの`cancel`が呼ばれると、実行中の世代の評価が終わった時点で
+    /// 両集団の進化を終了します。
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    pub fn run(&self) -> GAResult<SimulationResult> {
+        let shared = &self.configs[0];
+        let mut sides = [self.new_side(0)?, self.new_side(1)?];
+
+        self.print_header(&sides);
+
+        let mut termination = Termination {
+            reason: TerminationReason::GenerationLimit,
+            generation: shared.generations.saturating_sub(1),
+            population: None,
+        };
+
+        // シードのない設定では実行ごとに1度だけシードを選び、以降の乱数はすべてそこから導出する
+        let run_seeds = self
+            .configs
+            .each_ref()
+            .map(|config| config.seed.unwrap_or_else(|| thread_rng().gen()));
+        let mut match_rng = stream_rng(run_seeds[0], &[MATCH_STREAM]);
+
+        for generation in 0..shared.generations {
+            // 世代と集団ごとに殿堂との対戦・模倣・子の生成の乱数のシードを導出する
+            let seeds = [0, 1]
+                .map(|index| derive_seed(run_seeds[index], &[generation as u64, index as u64]));
+            let (points_a, points_b) = self.play_generation(&mut sides, &mut match_rng);
+            Self::assign_points(&mut sides[0].population, &points_a);
+            Self::assign_points(&mut sides[1].population, &points_b);
+
+            // 殿堂は集団間の対戦のみのポイントで更新し、対戦相手には前世代までの殿堂を使う
+            let extra_opponents = [
+                hall_of_fame_opponents(&self.configs[0], &sides[1].hall_of_fame),
+                hall_of_fame_opponents(&self.configs[1], &sides[0].hall_of_fame),
+            ];
+            for side in sides.iter_mut() {
+                side.hall_of_fame
+                    .update(generation, side.population.agents());
+            }
+            for ((side, extra), &seed) in sides.iter_mut().zip(&extra_opponents).zip(&seeds) {
+                let mut rng = hall_of_fame_rng(seed);
+                for agent in side.population.agents_mut() {
+                    agent.points = agent.points.saturating_add(points_against(
+                        shared,
+                        self.match_cache.as_deref(),
+                        agent,
+                        extra,
+                        &mut rng,
+                    ));
+                }
+            }
+
+            let mut opponents = [
+                sides[1].population.agents().to_vec(),
+                sides[0].population.agents().to_vec(),
+            ];
+            for (opponents, extra) in opponents.iter_mut().zip(extra_opponents) {
+                opponents.extend(extra);
+            }
+
+            let evaluations: Vec<Evaluation> = sides
+                .iter_mut()
+                .enumerate()
+                .map(|(index, side)| {
+                    self.record_generation(index, side, generation, opponents[index].len())
+                })
+                .collect();
+
+            // どちらかの集団が終了条件を満たした時点で両集団とも終了する
+            let stopped = sides.iter_mut().enumerate().find_map(|(index, side)| {
+                side.monitor
+                    .check(generation, side.population.agents(), opponents[index].len())
+                    .map(|reason| Termination {
+                        reason,
+                        generation,
+                        population: (reason != TerminationReason::Cancelled).then_some(index),
+                    })
+            });
+            if let Some(stopped) = stopped {
+                termination = stopped;
+                break;
+            }
+
+            for ((index, side), evaluation) in sides.iter_mut().enumerate().zip(evaluations) {
+                let config = &self.configs[index];
+                Self::assign_points(&mut side.population, &evaluation.selection_points);
+                inherit_learning(config, side.population.agents_mut());
+
+                let seed = seeds[index];
+                if config.imitation.is_some() {
+                    imitate_population(
+                        config,
+                        &mut side.population,
+                        &evaluation.payoffs,
+                        evaluation.mutation_rate,
+                        seed,
+                    );
+                } else {
+                    side.parent_points = evolve_population(
+                        config,
+                        &mut side.population,
+                        evaluation.mutation_rate,
+                        &mut side.mortality,
+                        &mut side.speciation,
+                        seed,
+                        |child, rng| {
+                            let points = self.evaluate_against(child, &opponents[index], rng);
+                            normalized_child_fitness(config, points, opponents[index].len())
+                        },
+                    )?;
+                }
+                Self::reset_agents(&mut side.population);
+            }
+        }
+
+        let (points_a, points_b) = self.play_generation(&mut sides, &mut match_rng);
+        Self::assign_points(&mut sides[0].population, &points_a);
+        Self::assign_points(&mut sides[1].population, &points_b);
+
+        let populations: Vec<PopulationResult> = sides
+            .into_iter()
+            .zip(&self.configs)
+            .map(|(side, config)| PopulationResult {
+                config: config.clone(),
+                final_result: Self::collect_final_stats(side.population.agents()),
+                generation_results: side.generation_results,
+                diversity_history: side.diversity_history,
+                mutation_rate_history: side.mutation_rate_history,
+                payoff_history: side.payoff_history,
+                cooperation_history: side.cooperation_history,
+                learned_cooperation_history: side.learned_cooperation_history,
+                hall_of_fame: side.hall_of_fame.entries().to_vec(),
+                novelty_archive_history: side.novelty_archive_history,
+                species_history: side.speciation.events().to_vec(),
+            })
+            .collect();
+
+        for (name, population) in POPULATION_NAMES.iter().zip(&populations) {
+            self.print_final_report(name, &population.final_result, &termination);
+            print_hall_of_fame(&population.hall_of_fame);
+        }
+        let match_cache = self.match_cache.as_ref().map(|cache| cache.stats());
+        if let Some(stats) = &match_cache {
+            println!("\nMatch cache: {stats}");
+        }
+
+        let primary = populations[0].clone();
+        Ok(SimulationResult {
+            config: primary.config,
+            generation_results: primary.generation_results,
+            final_result: primary.final_result,
+            diversity_history: primary.diversity_history,
+            mutation_rate_history: primary.mutation_rate_history,
+            payoff_history: primary.payoff_history,
+            cooperation_history: primary.cooperation_history,
+            learned_cooperation_history: primary.learned_cooperation_history,
+            hall_of_fame: primary.hall_of_fame,
+            novelty_archive_history: primary.novelty_archive_history,
+            species_history: primary.species_history,
+            price_history: Vec::new(),
+            group_history: Vec::new(),
+            match_cache,
+            populations,
+            termination,
+        })
+    }
+
+    /// 評価済みの集団の1世代分の統計を記録し、選択に使う値を求める
+    ///
+    /// 報告間隔の世代では世代の報告を出力します。個体のポイントは変更しません。
+    ///
+    /// # 引数
+    /// * `index` - 集団の添字（A=0、B=1）
+    /// * `opponents` - 1ラウンドあたりの各個体の対戦数（相手集団と殿堂の個体の数）
+    fn record_generation(
+        &self,
+        index: usize,
+        side: &mut Side,
+        generation: usize,
+        opponents: usize,
+    ) -> Evaluation {
+        let config = &self.configs[index];
+        let agents = side.population.agents().to_vec();
+
+        let diversity = diversity_metric(&agents);
+        let fitness = normalized_fitness(config, &agents, opponents);
+        let success_ratio = success_ratio(&fitness, &side.parent_points);
+        let mutation_rate = side.mutation.update(generation, diversity, success_ratio);
+        let agent_mutation_rates: Vec<f64> = agents
+            .iter()
+            .filter_map(|a| a.mutation_rate_gene())
+            .collect();
+        let effective_rate = if agent_mutation_rates.is_empty() {
+            mutation_rate
+        } else {
+            agent_mutation_rates.iter().sum::<f64>() / agent_mutation_rates.len() as f64
+        };
+        side.diversity_history.push(diversity);
+        side.mutation_rate_history.push(effective_rate);
+        side.payoff_history.push(mean_points(&agents));
+        let innate_cooperation = mean_cooperation(config, &agents);
+        side.cooperation_history.push(innate_cooperation);
+        let learned_cooperation = config
+            .learning
+            .map(|_| mean_learned_cooperation(config, &agents));
+        side.learned_cooperation_history.extend(learned_cooperation);
+
+        let novelty = evaluate_novelty(config, &mut side.novelty_archive, &agents, &fitness);
+        if novelty.is_some() {
+            side.novelty_archive_history
+                .push(side.novelty_archive.len());
+        }
+        let species = speciate(config, &mut side.speciation, generation, &agents);
+
+        if generation % self.configs[0].report_interval == 0 {
+            let stats = GenerationStats {
+                diversity,
+                edit_distance_diversity: edit_distance_diversity(&agents),
+                complexity: ComplexityStats::measure(&agents),
+                mutation_rate: effective_rate,
+                agent_mutation_rates,
+                pareto_front: side
+                    .nsga2
+                    .as_ref()
+                    .map(|nsga2| nsga2.pareto_front(&agents))
+                    .unwrap_or_default(),
+                mean_novelty: novelty.as_ref().map_or(0.0, |n| n.mean_novelty),
+                novelty_archive_size: side.novelty_archive.len(),
+                age: age_stats(config, &agents, &side.mortality),
+                species,
+                innate_cooperation,
+                learned_cooperation: learned_cooperation.unwrap_or(innate_cooperation),
+                ..Self::collect_generation_stats(generation, &agents)
+            };
+            Self::print_generation_report(POPULATION_NAMES[index], &stats, config);
+            side.generation_results.push(stats);
+        }
+
+        // 模倣の利得は相手集団との対戦の平均利得で、手本は自集団の近傍から選ぶ
+        Evaluation {
+            payoffs: agents
+                .iter()
+                .map(|agent| payoff_per_match(config, agent.points, opponents))
+                .collect(),
+            selection_points: novelty.map_or(fitness, |novelty| novelty.points),
+            mutation_rate,
+        }
+    }
+
+    /// 集団の初期状態を作成
+    fn new_side(&self, index: usize) -> GAResult<Side> {
+        let config = &self.configs[index];
+        let nsga2 = if config.is_multi_objective() {
+            Some(Nsga2::new(config.objectives.clone())?)
+        } else {
+            None
+        };
+
+        Ok(Side {
+            population: initial_population(config)?,
+            mutation: MutationController::new(
+                config.mutation_scheme,
+                config.mutation_rate,
+                self.configs[0].generations,
+            ),
+            nsga2,
+            parent_points: Vec::new(),
+            hall_of_fame: HallOfFame::new(config.hall_of_fame_size),
+            novelty_archive: NoveltyArchive::new(
+                config.novelty.unwrap_or_default().archive_threshold,
+            ),
+            novelty_archive_history: Vec::new(),
+            mortality: MortalityRecord::default(),
+            speciation: new_speciation(config)?,
+            monitor: TerminationMonitor::new(config.termination.clone(), self.cancellation.clone())
+                .with_rounds_per_generation(self.configs[0].rounds_per_generation),
+            generation_results: Vec::new(),
+            diversity_history: Vec::with_capacity(self.configs[0].generations),
+            mutation_rate_history: Vec::with_capacity(self.configs[0].generations),
+            payoff_history: Vec::with_capacity(self.configs[0].generations),
+            cooperation_history: Vec::with_capacity(self.configs[0].generations),
+            learned_cooperation_history: Vec::new(),
+        })
+    }
+
+    /// 集団Aの全個体と集団Bの全個体を総当たりで対戦させる
+    ///
+    /// 対戦の行動は`rng`で決めます。
+    ///
+    /// # 戻り値
+    /// 集団A・Bそれぞれの個体の獲得ポイント
+    fn play_all<R: Rng>(
+        &self,
+        agents_a: &[Agent],
+        agents_b: &[Agent],
+        rng: &mut R,
+    ) -> (Vec<Points>, Vec<Points>) {
+        let strategy = game_strategy(&self.configs[0]);
+        let cache = self.match_cache.as_deref();
+        let mut points_a: Vec<Points> = vec![0; agents_a.len()];
+        let mut points_b: Vec<Points> = vec![0; agents_b.len()];
+
+        for (i, a) in agents_a.iter().enumerate() {
+            for (j, b) in agents_b.iter().enumerate() {
+                for _ in 0..self.configs[0].rounds_per_generation {
+                    let (earned_a, earned_b) = cached_payoffs(cache, &strategy, a, b, rng);
+                    points_a[i] = points_a[i].saturating_add(earned_a);
+                    points_b[j] = points_b[j].saturating_add(earned_b);
+                }
+            }
+        }
+
+        (points_a, points_b)
+    }
+
+    /// 1世代分の集団間の総当たりの対戦を行う
+    ///
+    /// 学習が有効な集団の個体は、対戦ごとに受け取った利得で協力確率を更新します。
+    /// どちらの集団も学習しない場合は`play_all`と同じです。
+    ///
+    /// # 戻り値
+    /// 集団A・Bそれぞれの個体の獲得ポイント
+    fn play_generation<R: Rng>(
+        &self,
+        sides: &mut [Side; 2],
+        rng: &mut R,
+    ) -> (Vec<Points>, Vec<Points>) {
+        let learning = [self.configs[0].learning, self.configs[1].learning];
+        if learning.iter().all(Option::is_none) {
+            return self.play_all(
+                sides[0].population.agents(),
+                sides[1].population.agents(),
+                rng,
+            );
+        }
+
+        let strategy = game_strategy(&self.configs[0]);
+        let [side_a, side_b] = sides;
+        let agents_a = side_a.population.agents_mut();
+        let agents_b = side_b.population.agents_mut();
+        let mut points_a: Vec<Points> = vec![0; agents_a.len()];
+        let mut points_b: Vec<Points> = vec![0; agents_b.len()];
+
+        for i in 0..agents_a.len() {
+            for j in 0..agents_b.len() {
+                for _ in 0..self.configs[0].rounds_per_generation {
+                    let (choice_a, choice_b) =
+                        strategy.match_choices(&agents_a[i], &agents_b[j], rng);
+                    let earned_a = calculate_payoff(&choice_a, &choice_b);
+                    let earned_b = calculate_payoff(&choice_b, &choice_a);
+                    points_a[i] = points_a[i].saturating_add(earned_a);
+                    points_b[j] = points_b[j].saturating_add(earned_b);
+                    learn(&strategy, learning[0], &mut agents_a[i], choice_a, earned_a);
+                    learn(&strategy, learning[1], &mut agents_b[j], choice_b, earned_b);
+                }
+            }
+        }
+
+        (points_a, points_b)
+    }
+
+    /// 候補個体を相手集団の全員と対戦させてポイントを計算
+    fn evaluate_against<R: Rng>(
+        &self,
+        candidate: &Agent,
+        opponents: &[Agent],
+        rng: &mut R,
+    ) -> Points {
+        let (points, _) = self.play_all(std::slice::from_ref(candidate), opponents, rng);
+        points[0]
+    }
+
+    fn assign_points(population: &mut Population<Agent>, points: &[Points]) {
+        for (agent, &points) in population.agents_mut().iter_mut().zip(points) {
+            agent.points = points;
+        }
+    }
+
+    /// 遺伝子を保ったままIDを振り直し、ポイントと学習した協力確率を消去
+    fn reset_agents(population: &mut Population<Agent>) {
+        for (i, agent) in population.agents_mut().iter_mut().enumerate() {
+            agent.id = i as AgentId;
+            agent.points = 0;
+            agent.active = true;
+            agent.learned = None;
+        }
+    }
+
+    fn collect_generation_stats(generation: usize, agents: &[Agent]) -> GenerationStats {
+        let points_list: Vec<u64> = agents.iter().map(|a| a.get_points()).collect();
+
+        GenerationStats {
+            generation,
+            dna_list: agents
+                .iter()
+                .map(|a| a.get_dna_binary().to_string())
+                .collect(),
+            avg_points: points_list.iter().map(|&p| p as f64).sum::<f64>()
+                / agents.len().max(1) as f64,
+            max_points: *points_list.iter().max().unwrap_or(&0),
+            min_points: *points_list.iter().min().unwrap_or(&0),
+            points_list,
+            ..Default::default()
+        }
+    }
+
+    fn collect_final_stats(agents: &[Agent]) -> FinalStats {
+        let points_list: Vec<u64> = agents.iter().map(|a| a.get_points()).collect();
+
+        FinalStats {
+            dna_list: agents
+                .iter()
+                .map(|a| a.get_dna_binary().to_string())
+                .collect(),
+            avg_points: points_list.iter().map(|&p| p as f64).sum::<f64>()
+                / agents.len().max(1) as f64,
+            points_list,
+        }
+    }
+
+    fn print_header(&self, sides: &[Side; 2]) {
+        println!("Genetic Algorithm - Prisoner's Dilemma (co-evolution)");
+        println!("=====================================================");
+        println!("Generations: {}", self.configs[0].generations);
+        println!("Decoding: {}", self.configs[0].decoding);
+
+        for ((name, config), side) in POPULATION_NAMES.iter().zip(&self.configs).zip(sides) {
+            println!("\nPopulation {name}:");
+            println!("  Population: {}", config.population);
+            println!("  Mutation rate: {}", config.mutation_rate);
+            println!("  DNA length: {}", config.dna_length);
+            println!("  Diversity scheme: {}", config.diversity_scheme);
+            println!("  Mutation scheme: {}", config.mutation_scheme);
+            println!("  Replacement model: {}", config.replacement_model);
+            if let Some(age_structure) = &config.age_structure {
+                println!("  Age structure: {age_structure}");
+            }
+            if let Some(learning) = &config.learning {
+                println!("  Learning: {learning}");
+            }
+            if let Some(imitation) = &config.imitation {
+                println!("  Imitation: {imitation} ({})", config.neighbourhood);
+            }
+            if let Some(novelty) = &config.novelty {
+                println!("  Selection: {novelty}");
+            }
+            if config.fitness_normalization != FitnessNormalization::Raw {
+                println!("  Fitness normalization: {}", config.fitness_normalization);
+            }
+
+            for (i, agent) in side.population.agents().iter().enumerate() {
+                println!("  Agent {i:2}: {}", agent.get_dna_binary());
+            }
+        }
+        println!();
+    }
+
+    fn print_generation_report(name: &str, stats: &GenerationStats, config: &Config) {
+        println!("\nGeneration {} [population {name}]", stats.generation);
+        println!("{}", "-".repeat(40));
+
+        for (i, dna) in stats.dna_list.iter().enumerate() {
+            match species_label(&stats.species, i) {
+                Some(species) => println!(
+                    "Agent {:2}: {} (points: {}, species: {})",
+                    i, dna, stats.points_list[i], species
+                ),
+                None => println!("Agent {:2}: {} (points: {})", i, dna, stats.points_list[i]),
+            }
+        }
+
+        println!("Average points: {:.2}", stats.avg_points);
+        println!("Max points: {}", stats.max_points);
+        println!("Min points: {}", stats.min_points);
+        println!("Diversity: {:.4}", stats.diversity);
+        println!("Mutation rate: {:.4}", stats.mutation_rate);
+        if config.is_variable_length() {
+            print_complexity(stats);
+        }
+        if config.novelty.is_some() {
+            print_novelty(stats);
+        }
+        if config.age_structure.is_some() {
+            print_age(stats);
+        }
+        if config.learning.is_some() {
+            print_learning(stats);
+        }
+        if !stats.species.sizes.is_empty() {
+            print_species(stats);
+        }
+
+        if !stats.pareto_front.is_empty() {
+            println!("Pareto front ({} solutions):", stats.pareto_front.len());
+            for point in &stats.pareto_front {
+                let values: Vec<String> =
+                    point.objectives.iter().map(|v| format!("{v:.2}")).collect();
+                println!("  {} ({})", point.dna, values.join(", "));
+            }
+        }
+    }
+
+    fn print_final_report(&self, name: &str, stats: &FinalStats, termination: &Termination) {
+        println!(
+            "\n\nFinal Results [population {name}] (Generation {})",
+            termination.generation + 1
+        );
+        println!("{}", "=".repeat(40));
+        if termination.reason != TerminationReason::GenerationLimit {
+            match termination.population {
+                Some(index) => println!(
+                    "Stopped after {} generations: {} (population {})",
+                    termination.generation + 1,
+                    termination.reason,
+                    POPULATION_NAMES[index]
+                ),
+                None => println!(
+                    "Stopped after {} generations: {}",
+                    termination.generation + 1,
+                    termination.reason
+                ),
+            }
+        }
+
+        for (i, dna) in stats.dna_list.iter().enumerate() {
+            println!("Agent {:2}: {} (points: {})", i, dna, stats.points_list[i]);
+        }
+
+        println!("\nFinal average points: {:.2}", stats.avg_points);
+    }
+}
+
+/// 学習が有効な場合、1回の対戦の行動と利得で個体の協力確率を更新
+fn learn(
+    strategy: &RouletteSelectionStrategy,
+    learning: Option<Learning>,
+    agent: &mut Agent,
+    choice: Choice,
+    payoff: Points,
+) {
+    if let Some(learning) = learning {
+        let probability = learning.reinforce(
+            strategy.cooperation(agent),
+            choice == Choice::Cooperate,
+            payoff,
+        );
+        *agent = agent.with_learned_cooperation(probability);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::config::ConfigBuilder;
+
+    #[test]
+    fn test_coevolution_runs_both_populations() {
+        let config_a = ConfigBuilder::new()
+            .generations(4)
+            .population(6)
+            .report_interval(2)
+            .build()
+            .unwrap();
+        let config_b = ConfigBuilder::new()
+            .generations(4)
+            .population(4)
+            .dna_length(8)
+            .report_interval(2)
+            .build()
+            .unwrap();
+
+        let result = CoevolutionSimulation::new(config_a, config_b)
+            .unwrap()
+            .run()
+            .unwrap();
+
+        assert_eq!(result.populations.len(), 2);
+        assert_eq!(result.populations[0].final_result.dna_list.len(), 6);
+        assert_eq!(result.populations[1].final_result.dna_list.len(), 4);
+        assert!(result.populations[1]
+            .final_result
+            .dna_list
+            .iter()
+            .all(|dna| dna.len() == 8));
+        assert_eq!(result.populations[0].generation_results.len(), 2);
+        assert_eq!(result.populations[1].diversity_history.len(), 4);
+        assert_eq!(result.final_result.dna_list.len(), 6);
+    }
+
+    #[test]
+    fn test_populations_must_share_decoding() {
+        use crate::strategies::decoding::Decoding;
+
+        let config_a = ConfigBuilder::new().build().unwrap();
+        let config_b = ConfigBuilder::new()
+            .decoding(Decoding::Gray)
+            .build()
+            .unwrap();
+        assert!(CoevolutionSimulation::new(config_a, config_b.clone()).is_err());
+        assert!(CoevolutionSimulation::new(config_b.clone(), config_b).is_ok());
+    }
+
+    #[test]
+    fn test_termination_records_population_and_criterion() {
+        use crate::engine::termination::TerminationCriterion;
+
+        let criterion = TerminationCriterion::Stagnation { generations: 1 };
+        let config_a = ConfigBuilder::new()
+            .generations(10_000)
+            .population(4)
+            .report_interval(10_000)
+            .build()
+            .unwrap();
+        let config_b = ConfigBuilder::new()
+            .generations(10_000)
+            .population(4)
+            .report_interval(10_000)
+            .termination(vec![criterion])
+            .build()
+            .unwrap();
+
+        let result = CoevolutionSimulation::new(config_a.clone(), config_b)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(
+            result.termination.reason,
+            TerminationReason::Criterion(criterion)
+        );
+        assert_eq!(result.termination.population, Some(1));
+        let generations = result.termination.generation + 1;
+        assert!(generations < 10_000);
+        assert_eq!(result.populations[0].diversity_history.len(), generations);
+        assert_eq!(result.populations[1].diversity_history.len(), generations);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = CoevolutionSimulation::new(config_a.clone(), config_a)
+            .unwrap()
+            .with_cancellation_token(token)
+            .run()
+            .unwrap();
+        assert_eq!(result.termination.reason, TerminationReason::Cancelled);
+        assert_eq!(result.termination.population, None);
+        assert_eq!(result.termination.generation, 0);
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_coevolution() {
+        use crate::engine::genetic::ReplacementModel;
+
+        let config_a = ConfigBuilder::new()
+            .generations(10)
+            .population(6)
+            .report_interval(100)
+            .hall_of_fame(2, true)
+            .seed(3)
+            .build()
+            .unwrap();
+        let config_b = ConfigBuilder::new()
+            .generations(10)
+            .population(4)
+            .replacement_model(ReplacementModel::MuPlusLambda { lambda: 4 })
+            .seed(4)
+            .build()
+            .unwrap();
+        let run = || {
+            CoevolutionSimulation::new(config_a.clone(), config_b.clone())
+                .unwrap()
+                .run()
+                .unwrap()
+        };
+        let (first, second) = (run(), run());
+        for (a, b) in first.populations.iter().zip(&second.populations) {
+            assert_eq!(a.final_result.dna_list, b.final_result.dna_list);
+            assert_eq!(a.final_result.points_list, b.final_result.points_list);
+            assert_eq!(a.payoff_history, b.payoff_history);
+        }
+    }
+
+    #[test]
+    fn test_play_all_only_pairs_across_populations() {
+        let config = ConfigBuilder::new().population(3).build().unwrap();
+        let simulation = CoevolutionSimulation::new(config.clone(), config).unwrap();
+
+        // 全員裏切りの集団Aと全員協力の集団Bでは、Aのみが誘惑の利得を得る
+        let defectors: Vec<Agent> = (0..3).map(|i| Agent::new(i, "000000".into())).collect();
+        let cooperators: Vec<Agent> = (0..2).map(|i| Agent::new(i, "111111".into())).collect();
+        let (points_a, points_b) = simulation.play_all(&defectors, &cooperators, &mut thread_rng());
+
+        assert_eq!(points_a, vec![10, 10, 10]);
+        assert_eq!(points_b, vec![0, 0]);
+    }
+}
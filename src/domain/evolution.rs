@@ -0,0 +1,891 @@
+/// 設定に基づく個体群の世代交代
+///
+/// 単一集団のシミュレーションと共進化シミュレーションの双方から使用される、
+/// 置換モデル・多様性維持スキーム・多目的進化に応じた世代交代処理を提供します。
+/// 子の評価方法は呼び出し側が`evaluate`として与えます。
+use crate::core::errors::{GAError, GAResult};
+use crate::core::parallel::stream_rng;
+use crate::core::traits::{
+    BehaviourDescriptor, GeneticOperations, GenomeOperations, SelectionStrategy,
+};
+use crate::core::types::{MutationRate, Points};
+use crate::engine::aging::{AgeStats, AgeStructure, MortalityRecord};
+use crate::engine::diversity::{
+    DeterministicCrowding, DiversityScheme, FitnessSharing, RestrictedTournamentReplacement,
+    Speciation, SpeciesStats,
+};
+use crate::engine::genetic::{breed, Population, ReplacementModel};
+use crate::engine::group_selection::{GroupEvent, PriceDecomposition};
+use crate::engine::hall_of_fame::HallOfFame;
+use crate::engine::multi_objective::Nsga2;
+use crate::engine::mutation::MutationScheme;
+use crate::engine::novelty::{novelty_scores, NoveltyArchive, NoveltySearch};
+use crate::engine::selection::normalization::squared_fitness;
+use crate::engine::selection::RouletteSelection;
+use crate::infrastructure::config::Config;
+use crate::models::model::{Agent, BaseModel, Model};
+use crate::strategies::cache::{cached_payoffs, MatchCache};
+use crate::strategies::utils::RouletteSelectionStrategy;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+use std::sync::Arc;
+
+/// 初期個体群の年齢を導出するときの系列番号
+const INITIAL_AGE_STREAM: u64 = 1;
+
+/// 模倣の手本を選ぶ乱数を導出するときの系列番号
+const IMITATION_STREAM: u64 = 3;
+
+/// グループの分裂と絶滅を決める乱数を導出するときの系列番号
+const GROUP_STREAM: u64 = 4;
+
+/// 親の選択と子の生成に使う乱数を導出するときの系列番号
+const OFFSPRING_STREAM: u64 = 5;
+
+/// 子を既存個体と対戦させて評価する乱数を導出するときの系列番号
+const EVALUATION_STREAM: u64 = 6;
+
+/// 殿堂の個体との対戦の乱数を導出するときの系列番号
+const HALL_OF_FAME_STREAM: u64 = 7;
+
+/// 設定に従って初期個体群を作成
+///
+/// 自己適応型の突然変異スキームでは、各個体の突然変異率遺伝子を
+/// `Config::mutation_rate`で初期化します。シードが設定されている場合は
+/// シードから初期個体群を生成します。齢構造モードでは各個体に
+/// `AgeStructure::initial_age`の年齢を割り当てます。
+pub(crate) fn initial_population(config: &Config) -> GAResult<Population<Agent>> {
+    let self_adaptive = config.mutation_scheme.is_self_adaptive();
+    let initial_rate = config.mutation_rate;
+    let factory = |id, dna| {
+        if self_adaptive {
+            Agent::with_mutation_rate_gene(id, dna, initial_rate)
+        } else {
+            Agent::new(id, dna)
+        }
+    };
+
+    let mut population = match config.seed {
+        Some(seed) => Population::random_with_rng(
+            config.population,
+            config.dna_length,
+            0,
+            factory,
+            &mut stream_rng(seed, &[]),
+        )?,
+        None => Population::random(config.population, config.dna_length, 0, factory)?,
+    };
+
+    if let Some(structure) = &config.age_structure {
+        let seed = config.seed.unwrap_or_else(|| thread_rng().gen());
+        let mut rng = stream_rng(seed, &[INITIAL_AGE_STREAM]);
+        for agent in population.agents_mut() {
+            agent.age = structure.initial_age(&mut rng);
+        }
+    }
+
+    Ok(population)
+}
+
+/// 従来の`create_next_generation`による世代交代で十分かどうか
+///
+/// 世代交代型・多様性維持なし・単一目的・固定長・齢構造なし・学習なし・模倣なし・
+/// 集団選択なしで、親子関係の
+/// 追跡が必要な突然変異スキーム（自己適応型、1/5成功則）を使わない場合にtrueを返します。
+pub(crate) fn uses_legacy_generation(config: &Config) -> bool {
+    config.replacement_model == ReplacementModel::Generational
+        && config.age_structure.is_none()
+        && config.learning.is_none()
+        && config.imitation.is_none()
+        && config.group_selection.is_none()
+        && config.diversity_scheme == DiversityScheme::None
+        && !config.is_multi_objective()
+        && !config.is_variable_length()
+        && !needs_lineage(config)
+}
+
+/// 子と親のポイントの対応を追跡する必要がある突然変異スキームかどうか
+fn needs_lineage(config: &Config) -> bool {
+    config.mutation_scheme.is_self_adaptive()
+        || matches!(config.mutation_scheme, MutationScheme::OneFifthRule { .. })
+}
+
+/// 設定に従って個体群を1世代進める
+///
+/// 個体群の各個体は評価済み（ポイント設定済み）である必要があります。
+/// 新しく生まれた子のうち、既存個体と競合させるものは`evaluate`で評価します。
+///
+/// # 引数
+/// * `config` - 置換モデル・多様性維持スキーム・目的関数を含む設定
+/// * `population` - 更新する個体群
+/// * `mutation_rate` - この世代の突然変異率
+/// * `mortality` - 齢構造モードで死亡した個体の寿命を記録する先
+/// * `speciation` - 種分化スキームで現個体群を種分けした結果（`speciate`で更新済み）。
+///   種ごとに子を生成した場合は各子の親の種を記録します
+/// * `seed` - 親の選択・子の生成（齢構造モードでは死亡判定も）と子の評価に使う乱数のシード
+/// * `evaluate` - 子のポイントを、与えられた乱数生成器で対戦させて計算する関数
+///
+/// # 戻り値
+/// 各子の親2体の平均ポイント（親を追跡しない方式では空）
+///
+/// # エラー
+/// スキームのパラメータが不正な場合、または置換処理に失敗した場合
+pub(crate) fn evolve_population<F>(
+    config: &Config,
+    population: &mut Population<Agent>,
+    mutation_rate: MutationRate,
+    mortality: &mut MortalityRecord,
+    speciation: &mut Speciation<Agent>,
+    seed: u64,
+    evaluate: F,
+) -> GAResult<Vec<f64>>
+where
+    F: Fn(&Agent, &mut StdRng) -> Points,
+{
+    let mut rng = stream_rng(seed, &[OFFSPRING_STREAM]);
+    let mut evaluation_rng = stream_rng(seed, &[EVALUATION_STREAM]);
+    let mut evaluate = |child: &Agent| evaluate(child, &mut evaluation_rng);
+    if let Some(structure) = &config.age_structure {
+        age_population(
+            config,
+            structure,
+            population,
+            mutation_rate,
+            mortality,
+            &mut rng,
+        )?;
+        return Ok(Vec::new());
+    }
+
+    if config.replacement_model != ReplacementModel::Generational {
+        match config.diversity_scheme {
+            DiversityScheme::FitnessSharing { radius, alpha } => replace_population(
+                config.replacement_model,
+                population,
+                &FitnessSharing::new(radius, alpha)?,
+                mutation_rate,
+                &mut rng,
+                &mut evaluate,
+            )?,
+            _ => replace_population(
+                config.replacement_model,
+                population,
+                &RouletteSelection::new(),
+                mutation_rate,
+                &mut rng,
+                &mut evaluate,
+            )?,
+        }
+        return Ok(Vec::new());
+    }
+
+    population.increment_generation();
+    let agents = population.agents().to_vec();
+
+    let (next_agents, parent_points) = if config.is_multi_objective() {
+        (
+            Nsga2::new(config.objectives.clone())?.next_generation(
+                &agents,
+                mutation_rate,
+                &mut rng,
+                &mut evaluate,
+            )?,
+            Vec::new(),
+        )
+    } else {
+        match config.diversity_scheme {
+            DiversityScheme::None => breed_generation(
+                config,
+                &RouletteSelection::new(),
+                &agents,
+                agents.len(),
+                mutation_rate,
+                &mut rng,
+            )?,
+            DiversityScheme::FitnessSharing { radius, alpha } => breed_generation(
+                config,
+                &FitnessSharing::new(radius, alpha)?,
+                &agents,
+                agents.len(),
+                mutation_rate,
+                &mut rng,
+            )?,
+            DiversityScheme::DeterministicCrowding => (
+                DeterministicCrowding::new().next_generation(
+                    &agents,
+                    mutation_rate,
+                    &mut rng,
+                    &mut evaluate,
+                )?,
+                Vec::new(),
+            ),
+            DiversityScheme::RestrictedTournament { window_size } => (
+                RestrictedTournamentReplacement::new(window_size)?.next_generation(
+                    &agents,
+                    mutation_rate,
+                    &mut rng,
+                    &mut evaluate,
+                )?,
+                Vec::new(),
+            ),
+            DiversityScheme::Speciation { .. } => {
+                breed_species(config, speciation, &agents, mutation_rate, &mut rng)?
+            }
+        }
+    };
+
+    population.replace_agents(next_agents)?;
+    Ok(parent_points)
+}
+
+/// 定常状態型・(μ+λ)/(μ,λ)型の置換で個体群を1世代進める
+///
+/// 定常状態型では個体群サイズと同数の子が生まれるまでステップを繰り返します。
+fn replace_population<S, R, F>(
+    model: ReplacementModel,
+    population: &mut Population<Agent>,
+    selection: &S,
+    mutation_rate: MutationRate,
+    rng: &mut R,
+    mut evaluate: F,
+) -> GAResult<()>
+where
+    S: SelectionStrategy<Agent>,
+    R: Rng,
+    F: FnMut(&Agent) -> Points,
+{
+    match model {
+        ReplacementModel::Generational => {}
+        ReplacementModel::SteadyState { offspring, policy } => {
+            while !population.steady_state_step(
+                selection,
+                offspring,
+                policy,
+                mutation_rate,
+                rng,
+                &mut evaluate,
+            )? {}
+        }
+        ReplacementModel::MuPlusLambda { lambda } => {
+            population.plus_selection_step(selection, lambda, mutation_rate, rng, evaluate)?
+        }
+        ReplacementModel::MuCommaLambda { lambda } => {
+            population.comma_selection_step(selection, lambda, mutation_rate, rng, evaluate)?
+        }
+    }
+
+    Ok(())
+}
+
+/// 齢構造に従って個体群を1世代進める
+///
+/// 各個体の選択用のポイントを生涯のポイントに加えて年齢を1つ進めた後、
+/// 死亡する個体を決めて非アクティブにします。死亡した個体の枠は、成熟した個体
+/// （この世代で死亡する個体を含む）から生涯のポイントによるルーレット選択で
+/// 選んだ親の子で埋めます。成熟した個体がいない場合は全個体を親の候補とします。
+///
+/// # エラー
+/// 子の生成または置き換えに失敗した場合
+fn age_population<R: Rng>(
+    config: &Config,
+    structure: &AgeStructure,
+    population: &mut Population<Agent>,
+    mutation_rate: MutationRate,
+    mortality: &mut MortalityRecord,
+    rng: &mut R,
+) -> GAResult<()> {
+    population.increment_generation();
+    for agent in population.agents_mut() {
+        agent.lifetime_points = agent.lifetime_points.saturating_add(agent.points);
+        agent.age = agent.age.saturating_add(1);
+        agent.active = !structure.dies(agent.age, rng);
+    }
+
+    let deaths = population.agents().iter().filter(|a| !a.active).count();
+    if deaths == 0 {
+        return Ok(());
+    }
+
+    let mature: Vec<&Agent> = population
+        .agents()
+        .iter()
+        .filter(|a| structure.is_mature(a.age))
+        .collect();
+    let candidates: Vec<Agent> = if mature.is_empty() {
+        population.agents().iter().collect::<Vec<_>>()
+    } else {
+        mature
+    }
+    .into_iter()
+    .map(|a| a.with_points(a.lifetime_points))
+    .collect();
+
+    let (children, _) = breed_generation(
+        config,
+        &RouletteSelection::new(),
+        &candidates,
+        deaths,
+        mutation_rate,
+        rng,
+    )?;
+    for dead in population.replace_inactive(children)? {
+        mortality.record(dead.age);
+    }
+    Ok(())
+}
+
+/// 齢構造モードでの個体群の年齢の統計（齢構造モード以外では既定値）
+pub(crate) fn age_stats(
+    config: &Config,
+    agents: &[Agent],
+    mortality: &MortalityRecord,
+) -> AgeStats {
+    config
+        .age_structure
+        .map_or_else(AgeStats::default, |structure| {
+            let ages: Vec<u32> = agents.iter().map(|a| a.age).collect();
+            AgeStats::measure(&ages, &structure, mortality)
+        })
+}
+
+/// 設定の種分化パラメータで種の追跡を作成（種分化以外のスキームでは既定値）
+///
+/// # エラー
+/// 種分化のパラメータが不正な場合
+pub(crate) fn new_speciation(config: &Config) -> GAResult<Speciation<Agent>> {
+    match config.diversity_scheme {
+        DiversityScheme::Speciation {
+            threshold,
+            target_species,
+        } => Speciation::new(threshold, target_species),
+        _ => Ok(Speciation::default()),
+    }
+}
+
+/// 種分化スキームで評価済みの個体群を種に分ける（種分化以外のスキームでは既定値）
+pub(crate) fn speciate(
+    config: &Config,
+    speciation: &mut Speciation<Agent>,
+    generation: usize,
+    agents: &[Agent],
+) -> SpeciesStats {
+    match config.diversity_scheme {
+        DiversityScheme::Speciation { .. } => speciation.speciate(generation, agents),
+        _ => SpeciesStats::default(),
+    }
+}
+
+/// 種ごとに割り当てた数の子を、同じ種の親同士のルーレット選択で生成
+///
+/// 種の中の選択では全個体の適応度が同じ種の個体数で割られるため、
+/// 共有後の適応度は子の種ごとの割り当てにのみ影響します。
+/// 各子の親の種は`Speciation::record_offspring`で記録します。
+///
+/// # エラー
+/// 個体群が種分けした個体群と一致しない場合、または親を選択できない場合
+fn breed_species<R: Rng>(
+    config: &Config,
+    speciation: &mut Speciation<Agent>,
+    agents: &[Agent],
+    mutation_rate: MutationRate,
+    rng: &mut R,
+) -> GAResult<(Vec<Agent>, Vec<f64>)> {
+    let allocation = speciation.allocate_offspring(agents, agents.len())?;
+    let mut children = Vec::with_capacity(agents.len());
+    let mut parent_points = Vec::with_capacity(agents.len());
+    let mut origins = Vec::with_capacity(agents.len());
+    for (species, count) in speciation.species().iter().zip(allocation) {
+        if count == 0 {
+            continue;
+        }
+        let members: Vec<Agent> = species
+            .members
+            .iter()
+            .map(|&member| agents[member].clone())
+            .collect();
+        let (offspring, points) = breed_generation(
+            config,
+            &RouletteSelection::new(),
+            &members,
+            count,
+            mutation_rate,
+            rng,
+        )?;
+        children.extend(offspring);
+        parent_points.extend(points);
+        origins.extend(std::iter::repeat(species.id).take(count));
+    }
+    speciation.record_offspring(origins);
+    Ok((children, parent_points))
+}
+
+/// 選択戦略で親を選び、`count`体の子を生成
+///
+/// # 戻り値
+/// 生成した子と、各子の親2体の平均ポイント
+///
+/// # エラー
+/// 親を選択できない場合、または可変長の交叉に失敗した場合
+fn breed_generation<S: SelectionStrategy<Agent>, R: Rng>(
+    config: &Config,
+    selection: &S,
+    agents: &[Agent],
+    count: usize,
+    mutation_rate: MutationRate,
+    rng: &mut R,
+) -> GAResult<(Vec<Agent>, Vec<f64>)> {
+    (0..count)
+        .map(|_| {
+            let (parent1, parent2) = selection.select_parents_with_rng(agents, rng)?;
+            let parent_points = (parent1.get_points() as f64 + parent2.get_points() as f64) / 2.0;
+            let child = if config.is_variable_length() {
+                breed_variable_length(config, &parent1, &parent2, mutation_rate, rng)?
+            } else {
+                breed(&parent1, &parent2, mutation_rate, rng)
+            };
+            Ok((child, parent_points))
+        })
+        .collect::<GAResult<Vec<_>>>()
+        .map(|pairs| pairs.into_iter().unzip())
+}
+
+/// 設定の交叉方式とビット反転・挿入・欠失突然変異で、長さの異なり得る2親から子を1つ生成
+fn breed_variable_length<R: Rng>(
+    config: &Config,
+    parent1: &Agent,
+    parent2: &Agent,
+    mutation_rate: MutationRate,
+    rng: &mut R,
+) -> GAResult<Agent> {
+    let length_range = config
+        .indel
+        .map(|indel| indel.min_length..=indel.max_length);
+    let (child, _) =
+        config
+            .crossover_scheme
+            .crossover_with_rng(parent1, parent2, length_range, rng)?;
+    let child = child.mutate_with_rng(mutation_rate, rng);
+    let child = match &config.indel {
+        Some(indel) => indel.mutate(&child, rng),
+        None => child,
+    };
+    Ok(child.with_points(0))
+}
+
+/// 1/5成功則の成功率: 親の平均適応度を上回った子の割合
+///
+/// 親の適応度が記録されていない（個体数と一致しない）場合は`None`を返します。
+///
+/// # 引数
+/// * `fitness` - 子の選択用の適応度（`normalized_fitness`の値）
+/// * `parent_points` - 各子の親2体の平均適応度
+pub(crate) fn success_ratio(fitness: &[Points], parent_points: &[f64]) -> Option<f64> {
+    (parent_points.len() == fitness.len()).then(|| {
+        fitness
+            .iter()
+            .zip(parent_points)
+            .filter(|(&fitness, &parent)| fitness as f64 > parent)
+            .count() as f64
+            / fitness.len() as f64
+    })
+}
+
+/// 累積ポイントを設定の正規化方式で選択用の適応度に変換
+///
+/// # 引数
+/// * `config` - 正規化方式と`rounds_per_generation`を含む設定
+/// * `agents` - 評価済みの個体
+/// * `opponents` - 1ラウンドあたりの各個体の対戦数
+pub(crate) fn normalized_fitness(
+    config: &Config,
+    agents: &[Agent],
+    opponents: usize,
+) -> Vec<Points> {
+    let points: Vec<Points> = agents.iter().map(|agent| agent.points).collect();
+    config.fitness_normalization.normalize(
+        &points,
+        config.rounds_per_generation as u64,
+        opponents as u64,
+    )
+}
+
+/// 新しく評価した子1体のポイントを選択用の適応度に変換
+///
+/// `normalized_fitness`の子1体版です。個体群に依存する正規化方式は
+/// 子を個別に評価する設定と組み合わせられないため、ここでは線形な方式のみが効きます。
+pub(crate) fn normalized_child_fitness(
+    config: &Config,
+    points: Points,
+    opponents: usize,
+) -> Points {
+    config.fitness_normalization.normalize_one(
+        points,
+        config.rounds_per_generation as u64,
+        opponents as u64,
+    )
+}
+
+/// 評価時の追加の対戦相手とする殿堂の個体
+///
+/// 殿堂を対戦相手にしない設定では空を返します。
+pub(crate) fn hall_of_fame_opponents(config: &Config, hall_of_fame: &HallOfFame) -> Vec<Agent> {
+    if config.hall_of_fame_opponents {
+        hall_of_fame.opponents(Agent::new)
+    } else {
+        Vec::new()
+    }
+}
+
+/// 設定のデコード方式で遺伝子型を読む対戦戦略
+pub(crate) fn game_strategy(config: &Config) -> RouletteSelectionStrategy {
+    RouletteSelectionStrategy::with_decoding(config.decoding.clone())
+}
+
+/// 殿堂の個体との対戦に使う乱数生成器
+///
+/// # 引数
+/// * `seed` - その世代の乱数のシード
+pub(crate) fn hall_of_fame_rng(seed: u64) -> StdRng {
+    stream_rng(seed, &[HALL_OF_FAME_STREAM])
+}
+
+/// 候補個体を各対戦相手と`rounds_per_generation`ラウンドずつ対戦させた獲得ポイント
+///
+/// `cache`を与えると、決定的な組の対戦結果を再利用します。対戦の行動は`rng`で決めます。
+pub(crate) fn points_against<R: Rng>(
+    config: &Config,
+    cache: Option<&MatchCache>,
+    candidate: &Agent,
+    opponents: &[Agent],
+    rng: &mut R,
+) -> Points {
+    let strategy = game_strategy(config);
+    let mut points: Points = 0;
+    for _ in 0..config.rounds_per_generation {
+        for opponent in opponents {
+            let (earned, _) = cached_payoffs(cache, &strategy, candidate, opponent, rng);
+            points = points.saturating_add(earned);
+        }
+    }
+    points
+}
+
+/// 設定でキャッシュが有効な場合に対戦結果のキャッシュを作成
+pub(crate) fn match_cache(config: &Config) -> Option<Arc<MatchCache>> {
+    config.match_cache.then(|| Arc::new(MatchCache::new()))
+}
+
+/// 新規性探索による1世代分の評価
+///
+/// # フィールド
+/// * `points` - 選択に使う評価値（新規性と適応度の混合）
+/// * `mean_novelty` - 個体群の平均新規性
+#[derive(Debug, Clone)]
+pub(crate) struct NoveltyEvaluation {
+    pub points: Vec<Points>,
+    pub mean_novelty: f64,
+}
+
+/// 評価済みの個体群の新規性を計算し、アーカイブを更新
+///
+/// 行動特性は`NoveltySearch::default_probes`のプローブに対する行動です。
+/// 新規性探索を使用しない設定では`None`を返します。
+///
+/// # 引数
+/// * `config` - 新規性探索の設定を含む設定
+/// * `archive` - 更新する新規性アーカイブ
+/// * `agents` - 評価済みの個体
+/// * `fitness` - 新規性と混合する各個体の適応度
+pub(crate) fn evaluate_novelty(
+    config: &Config,
+    archive: &mut NoveltyArchive,
+    agents: &[Agent],
+    fitness: &[Points],
+) -> Option<NoveltyEvaluation> {
+    let novelty = config.novelty?;
+    let strategy = game_strategy(config);
+    let probes: Vec<Agent> = NoveltySearch::default_probes(config.dna_length)
+        .into_iter()
+        .enumerate()
+        .map(|(id, dna)| Agent::new(id as u64, dna))
+        .collect();
+
+    let behaviours: Vec<Vec<f64>> = agents
+        .iter()
+        .map(|agent| strategy.describe(agent, &probes))
+        .collect();
+    let scores = novelty_scores(&behaviours, archive.behaviours(), novelty.neighbours);
+    archive.update(&behaviours, &scores);
+
+    Some(NoveltyEvaluation {
+        points: novelty.blend(fitness, &scores),
+        mean_novelty: scores.iter().sum::<f64>() / scores.len().max(1) as f64,
+    })
+}
+
+/// 個体群の平均ポイント
+pub(crate) fn mean_points(agents: &[Agent]) -> f64 {
+    agents.iter().map(|agent| agent.points as f64).sum::<f64>() / agents.len().max(1) as f64
+}
+
+/// 個体群の平均協力確率（設定のデコード方式による表現型の平均）
+pub(crate) fn mean_cooperation(config: &Config, agents: &[Agent]) -> f64 {
+    let total: f64 = agents
+        .iter()
+        .map(|agent| config.decoding.phenotype(agent))
+        .sum();
+    total / agents.len().max(1) as f64
+}
+
+/// 個体群の学習後の平均協力確率（学習していない個体は表現型の値）
+pub(crate) fn mean_learned_cooperation(config: &Config, agents: &[Agent]) -> f64 {
+    let total: f64 = agents
+        .iter()
+        .map(|agent| {
+            agent
+                .learned_cooperation()
+                .unwrap_or_else(|| config.decoding.phenotype(agent))
+        })
+        .sum();
+    total / agents.len().max(1) as f64
+}
+
+/// ラマルク型の学習で、学習した協力確率を各個体の遺伝子型に書き戻す
+///
+/// 遺伝子型は`Decoding::encode_towards`で表現型の値が学習した協力確率に
+/// 近づくように書き換えます。ボールドウィン型の学習や学習なしでは何もしません。
+pub(crate) fn inherit_learning(config: &Config, agents: &mut [Agent]) {
+    if !config
+        .learning
+        .is_some_and(|learning| learning.is_lamarckian())
+    {
+        return;
+    }
+
+    for agent in agents {
+        if let Some(probability) = agent.learned_cooperation() {
+            let dna = config
+                .decoding
+                .encode_towards(agent.get_dna_binary(), probability);
+            *agent = agent.with_acquired_dna(dna);
+        }
+    }
+}
+
+/// 個体群全体ではなく一部の個体とのみ対戦する場合の対戦する組
+///
+/// 構造化された近傍では近傍の個体同士、集団選択では同じグループの個体同士の組です。
+/// 個体群全体で対戦する場合は`None`を返します。
+pub(crate) fn local_pairs(config: &Config, population: usize) -> Option<Vec<(usize, usize)>> {
+    if let Some(group_selection) = &config.group_selection {
+        Some(group_selection.pairs(population))
+    } else if config.neighbourhood.is_structured() {
+        Some(config.neighbourhood.pairs(population))
+    } else {
+        None
+    }
+}
+
+/// 個体群内で各個体が対戦する相手の数
+///
+/// 構造化された近傍や集団選択では近傍やグループの大きさ（どの個体も同じ数）、
+/// 個体群全体で対戦する場合は`population - 1`です。殿堂の対戦相手は含みません。
+pub(crate) fn local_opponents(config: &Config, population: usize) -> usize {
+    match local_pairs(config, population) {
+        Some(pairs) => 2 * pairs.len() / population.max(1),
+        None => population.saturating_sub(1),
+    }
+}
+
+/// 模倣に使う各個体の1回の対戦あたりの平均利得（模倣ダイナミクスでなければ空）
+///
+/// 対戦相手の数は、構造化された近傍では近傍の個体数、よく混ざった個体群では
+/// 自分以外の個体数に`extra_opponents`（殿堂の個体など）を加えたものです。
+pub(crate) fn imitation_payoffs(
+    config: &Config,
+    agents: &[Agent],
+    extra_opponents: usize,
+) -> Vec<f64> {
+    if config.imitation.is_none() {
+        return Vec::new();
+    }
+
+    agents
+        .iter()
+        .enumerate()
+        .map(|(i, agent)| {
+            let neighbours = if config.neighbourhood.is_structured() {
+                config.neighbourhood.neighbours(i, agents.len()).len()
+            } else {
+                agents.len().saturating_sub(1)
+            };
+            payoff_per_match(config, agent.points, neighbours + extra_opponents)
+        })
+        .collect()
+}
+
+/// 獲得ポイントを1回の対戦あたりの平均利得に換算
+///
+/// # 引数
+/// * `points` - `opponents`体とそれぞれ`rounds_per_generation`ラウンド対戦した獲得ポイント
+pub(crate) fn payoff_per_match(config: &Config, points: Points, opponents: usize) -> f64 {
+    points as f64 / (opponents * config.rounds_per_generation).max(1) as f64
+}
+
+/// 模倣ダイナミクスで個体群を1世代進める
+///
+/// 各個体は`ImitationRule::models`で選んだ手本の遺伝子型を写し（同期更新）、
+/// 写した遺伝子型には突然変異率`mutation_rate`で模倣の誤りが入ります。
+/// 個体のIDは変わりません。
+///
+/// # 引数
+/// * `payoffs` - 各個体の1回の対戦あたりの平均利得
+/// * `seed` - 手本を選ぶ乱数のシード
+pub(crate) fn imitate_population(
+    config: &Config,
+    population: &mut Population<Agent>,
+    payoffs: &[f64],
+    mutation_rate: MutationRate,
+    seed: u64,
+) {
+    let Some(rule) = &config.imitation else {
+        return;
+    };
+
+    let mut rng = stream_rng(seed, &[IMITATION_STREAM]);
+    let models = rule.models(payoffs, &config.neighbourhood, &mut rng);
+    let previous = population.agents().to_vec();
+    for ((agent, model), own) in population
+        .agents_mut()
+        .iter_mut()
+        .zip(models)
+        .zip(&previous)
+    {
+        *agent = own
+            .with_genome(previous[model].genome().clone())
+            .mutation_with_rng(mutation_rate, &mut rng);
+    }
+    population.increment_generation();
+}
+
+/// 集団選択でグループ内の選択の効果とグループ間の選択の効果を分解（集団選択でなければ`None`）
+///
+/// 形質は設定のデコード方式による協力確率です。適応度は`breed_groups`が実際に使う
+/// 重みから求めた各個体の子の数の期待値で、グループ内の二乗適応度のルーレット選択で
+/// 親になる割合に、グループの分裂と絶滅による期待倍率を掛けたものです。
+///
+/// # 引数
+/// * `agents` - 評価済みの個体
+/// * `points` - 各個体の選択用の適応度（`breed_groups`に渡すポイント）
+pub(crate) fn price_decomposition(
+    config: &Config,
+    agents: &[Agent],
+    points: &[Points],
+) -> Option<PriceDecomposition> {
+    let group_selection = config.group_selection.as_ref()?;
+    let size = group_selection.group_size(agents.len());
+    let traits: Vec<f64> = agents
+        .iter()
+        .map(|agent| config.decoding.phenotype(agent))
+        .collect();
+    let group_weights = group_selection.group_weights(&group_totals(points, size));
+    let fitness: Vec<f64> = points
+        .chunks(size)
+        .zip(group_weights)
+        .flat_map(|(group, weight)| {
+            let squared: Vec<f64> = group.iter().map(|&p| squared_fitness(p)).collect();
+            let total: f64 = squared.iter().sum();
+            // 全員の適応度が0のグループでは親を一様に選ぶ
+            squared.into_iter().map(move |fitness| {
+                let share = if total > 0.0 {
+                    fitness * group.len() as f64 / total
+                } else {
+                    1.0
+                };
+                weight * share
+            })
+        })
+        .collect();
+    Some(PriceDecomposition::measure(&traits, &fitness, size))
+}
+
+/// 各グループの獲得ポイントの合計
+fn group_totals(points: &[Points], size: usize) -> Vec<f64> {
+    points
+        .chunks(size)
+        .map(|group| group.iter().map(|&p| p as f64).sum())
+        .collect()
+}
+
+/// 集団選択で個体群を1世代進める
+///
+/// 各グループの子は同じグループの親同士のルーレット選択で生成します（グループ内の選択）。
+/// その後`GroupSelection::group_event`で分裂したグループの子を2つに分け、片方は
+/// 元のグループに、もう片方は絶滅したグループの枠に入れ、それぞれを複製して
+/// グループの大きさに戻します（グループ間の選択）。
+///
+/// # 引数
+/// * `generation` - 分裂を記録する世代
+/// * `seed` - 子の生成とグループの分裂・絶滅を決める乱数のシード
+///
+/// # 戻り値
+/// この世代のグループの分裂と絶滅（分裂がなければ`None`）
+///
+/// # エラー
+/// 集団選択の設定がない場合、または親を選択できない場合
+pub(crate) fn breed_groups(
+    config: &Config,
+    population: &mut Population<Agent>,
+    mutation_rate: MutationRate,
+    generation: usize,
+    seed: u64,
+) -> GAResult<Option<GroupEvent>> {
+    let group_selection = config
+        .group_selection
+        .ok_or_else(|| GAError::ValidationError("Group selection is not configured".to_string()))?;
+    let agents = population.agents().to_vec();
+    let size = group_selection.group_size(agents.len());
+    let points: Vec<Points> = agents.iter().map(|agent| agent.points).collect();
+    let totals = group_totals(&points, size);
+
+    let mut offspring_rng = stream_rng(seed, &[OFFSPRING_STREAM]);
+    let mut children = Vec::with_capacity(agents.len());
+    for group in agents.chunks(size) {
+        let (offspring, _) = breed_generation(
+            config,
+            &RouletteSelection::new(),
+            group,
+            size,
+            mutation_rate,
+            &mut offspring_rng,
+        )?;
+        children.extend(offspring);
+    }
+
+    let mut rng = stream_rng(seed, &[GROUP_STREAM]);
+    let event = group_selection
+        .group_event(&totals, &mut rng)
+        .map(|(parent, extinct)| {
+            let mut members = children[parent * size..(parent + 1) * size].to_vec();
+            members.shuffle(&mut rng);
+            let (first, second) = members.split_at(size / 2);
+            for (group, half) in [(parent, first), (extinct, second)] {
+                for (slot, member) in children[group * size..(group + 1) * size]
+                    .iter_mut()
+                    .zip(half.iter().cycle())
+                {
+                    *slot = member.clone();
+                }
+            }
+            GroupEvent {
+                generation,
+                parent,
+                extinct,
+            }
+        });
+
+    population.increment_generation();
+    population.replace_agents(children)?;
+    Ok(event)
+}
@@ -1,5 +1,16 @@
 /// Simulation orchestration
 pub mod simulation;
 
+/// Two-population co-evolution
+pub mod coevolution;
+
+/// Hyperparameter search over simulation configs
+pub mod tuning;
+
+/// Generation replacement shared by the simulations
+pub(crate) mod evolution;
+
 // Re-export commonly used items
+pub use coevolution::CoevolutionSimulation;
 pub use simulation::Simulation;
+pub use tuning::Tuner;
@@ -3,12 +3,39 @@
 /// このモジュールでは、囚人のジレンマゲームを使用した遺伝的アルゴリズムの
 /// シミュレーションを管理します。設定に基づいてゲームを実行し、
 /// 世代を重ねながら個体群の進化を観察します。
+use super::evolution::{
+    age_stats, breed_groups, evaluate_novelty, evolve_population, game_strategy,
+    hall_of_fame_opponents, hall_of_fame_rng, imitate_population, imitation_payoffs,
+    inherit_learning, initial_population, local_opponents, local_pairs, match_cache,
+    mean_cooperation, mean_learned_cooperation, mean_points, new_speciation,
+    normalized_child_fitness, normalized_fitness, points_against, price_decomposition, speciate,
+    success_ratio, uses_legacy_generation,
+};
 use crate::core::errors::{GAError, GAResult};
-use crate::ga::algorithm::{create_next_generation, GAOperation};
+use crate::core::parallel::derive_seed;
+use crate::core::types::{AgentId, MutationRate, Points};
+use crate::engine::aging::{AgeStats, MortalityRecord};
+use crate::engine::diversity::{Speciation, SpeciesEvent, SpeciesStats};
+use crate::engine::genetic::{
+    diversity_metric, edit_distance_diversity, ComplexityStats, Population,
+};
+use crate::engine::group_selection::{GroupEvent, PriceDecomposition};
+use crate::engine::hall_of_fame::{HallOfFame, HallOfFameEntry};
+use crate::engine::multi_objective::{Nsga2, ParetoPoint};
+use crate::engine::mutation::MutationController;
+use crate::engine::novelty::NoveltyArchive;
+use crate::engine::selection::FitnessNormalization;
+use crate::engine::termination::{
+    CancellationToken, Termination, TerminationMonitor, TerminationReason,
+};
+use crate::ga::algorithm::{create_next_generation, GAOperation, GA};
 use crate::infrastructure::config::Config;
-use crate::models::game::{new_game, GameOperation};
+use crate::models::game::{generate_next_game, Game, GameOperation};
 use crate::models::model::Agent;
+use crate::strategies::cache::{MatchCache, MatchCacheStats};
 use crate::strategies::utils::RouletteSelectionStrategy;
+use rand::Rng;
+use std::sync::Arc;
 
 /// 遺伝的アルゴリズムシミュレーションの管理構造体
 ///
@@ -18,11 +45,25 @@ use crate::strategies::utils::RouletteSelectionStrategy;
 ///
 /// # フィールド
 /// * `config` - シミュレーションの設定パラメータ
+/// * `cancellation` - 実行を外部から中断するためのトークン
+/// * `quiet` - 進捗と結果を標準出力に表示しないかどうか
+/// * `match_cache` - 決定的な対戦の結果のキャッシュ（設定で有効な場合）
 pub struct Simulation {
     /// シミュレーションの設定
     ///
     /// 個体数、世代数、突然変異率などの重要なパラメータを含みます
     config: Config,
+
+    /// 実行を外部から中断するためのトークン
+    cancellation: Option<CancellationToken>,
+
+    /// 進捗と結果を標準出力に表示しないかどうか
+    quiet: bool,
+
+    /// 決定的な対戦の結果のキャッシュ
+    ///
+    /// 同じインスタンスで複数回実行した場合はキャッシュと統計を引き継ぎます。
+    match_cache: Option<Arc<MatchCache>>,
 }
 
 impl Simulation {
@@ -41,46 +82,405 @@ impl Simulation {
     pub fn new(config: Config) -> GAResult<Self> {
         // 設定の妥当性を事前検証
         config.validate()?;
-        Ok(Self { config })
+        Ok(Self {
+            match_cache: match_cache(&config),
+            config,
+            cancellation: None,
+            quiet: false,
+        })
+    }
+
+    /// キャンセルトークンを設定
+    ///
+    /// トークンの`cancel`が呼ばれると、実行中の世代の評価が終わった時点で
+    /// シミュレーションを終了します。
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// 進捗と結果の表示を抑制
+    ///
+    /// 多数のシミュレーションを続けて実行する場合（ハイパーパラメータ探索など）に使用します。
+    pub fn quiet(mut self) -> Self {
+        self.quiet = true;
+        self
     }
 
     pub fn run(&self) -> GAResult<SimulationResult> {
-        let mut game = new_game::<Agent, RouletteSelectionStrategy>(
-            self.config.population,
+        let mut population = initial_population(&self.config)?;
+        let mut game = self.game_from_agents(
+            population.agents().to_vec(),
             self.config.mutation_rate,
-            self.config.rounds_per_generation,
-            self.config.dna_length,
-            RouletteSelectionStrategy {},
+            self.config.seed,
         );
 
-        self.print_header(&game);
+        if !self.quiet {
+            self.print_header(&game);
+        }
 
         let mut results = Vec::new();
+        let mut diversity_history = Vec::with_capacity(self.config.generations);
+        let mut mutation_rate_history = Vec::with_capacity(self.config.generations);
+        let mut payoff_history = Vec::with_capacity(self.config.generations);
+        let mut cooperation_history = Vec::with_capacity(self.config.generations);
+        let mut learned_cooperation_history = Vec::new();
+        let mut price_history = Vec::new();
+        let mut group_history = Vec::new();
+        let mut mutation = MutationController::new(
+            self.config.mutation_scheme,
+            self.config.mutation_rate,
+            self.config.generations,
+        );
+        let mut parent_points: Vec<f64> = Vec::new();
+        let nsga2 = self.nsga2()?;
+        let mut monitor =
+            TerminationMonitor::new(self.config.termination.clone(), self.cancellation.clone())
+                .with_rounds_per_generation(self.config.rounds_per_generation);
+        let mut hall_of_fame = HallOfFame::new(self.config.hall_of_fame_size);
+        let mut novelty_archive =
+            NoveltyArchive::new(self.config.novelty.unwrap_or_default().archive_threshold);
+        let mut novelty_archive_history = Vec::new();
+        let mut mortality = MortalityRecord::default();
+        let mut speciation = new_speciation(&self.config)?;
+        let mut termination = Termination {
+            reason: TerminationReason::GenerationLimit,
+            generation: self.config.generations.saturating_sub(1),
+            population: None,
+        };
 
         for generation in 0..self.config.generations {
-            let ga_result = game
+            let mut ga_result = game
                 .run_generation()
                 .map_err(|_| GAError::GameExecutionError("Failed to run generation".to_string()))?;
 
+            // 殿堂は個体群内の対戦のみのポイントで更新し、対戦相手には前世代までの殿堂を使う
+            let mut agents: Vec<Agent> =
+                ga_result.old_agents.iter().map(|a| (**a).clone()).collect();
+            let extra_opponents = hall_of_fame_opponents(&self.config, &hall_of_fame);
+            hall_of_fame.update(generation, &agents);
+            if !extra_opponents.is_empty() {
+                let mut rng = hall_of_fame_rng(ga_result.seed);
+                for (agent, played) in agents.iter_mut().zip(ga_result.old_agents.iter_mut()) {
+                    agent.points = agent.points.saturating_add(points_against(
+                        &self.config,
+                        self.match_cache.as_deref(),
+                        agent,
+                        &extra_opponents,
+                        &mut rng,
+                    ));
+                    played.points = agent.points;
+                }
+            }
+            population.agents_mut().clone_from_slice(&agents);
+            let diversity = diversity_metric(&agents);
+            diversity_history.push(diversity);
+            payoff_history.push(mean_points(&agents));
+            let innate_cooperation = mean_cooperation(&self.config, &agents);
+            cooperation_history.push(innate_cooperation);
+            let learned_cooperation = self
+                .config
+                .learning
+                .map(|_| mean_learned_cooperation(&self.config, &agents));
+            learned_cooperation_history.extend(learned_cooperation);
+
+            // 報告と終了判定は累積ポイントで行い、選択にのみ正規化した適応度
+            // （新規性探索では新規性を混合した評価値）を使う
+            let opponents = local_opponents(&self.config, agents.len()) + extra_opponents.len();
+            let fitness = normalized_fitness(&self.config, &agents, opponents);
+            let novelty = evaluate_novelty(&self.config, &mut novelty_archive, &agents, &fitness);
+            if novelty.is_some() {
+                novelty_archive_history.push(novelty_archive.len());
+            }
+            let price = price_decomposition(
+                &self.config,
+                &agents,
+                novelty.as_ref().map_or(&fitness, |novelty| &novelty.points),
+            );
+            price_history.extend(price);
+            let species = speciate(&self.config, &mut speciation, generation, &agents);
+
+            let success_ratio = success_ratio(&fitness, &parent_points);
+
+            let mutation_rate = mutation.update(generation, diversity, success_ratio);
+            let agent_mutation_rates: Vec<MutationRate> = agents
+                .iter()
+                .filter_map(|a| a.mutation_rate_gene())
+                .collect();
+            let effective_rate = if agent_mutation_rates.is_empty() {
+                mutation_rate
+            } else {
+                agent_mutation_rates.iter().sum::<f64>() / agent_mutation_rates.len() as f64
+            };
+            mutation_rate_history.push(effective_rate);
+
             if generation % self.config.report_interval == 0 {
-                let generation_stats = self.collect_generation_stats(generation, &ga_result);
-                self.print_generation_report(&generation_stats);
+                let generation_stats = GenerationStats {
+                    diversity,
+                    edit_distance_diversity: edit_distance_diversity(&agents),
+                    complexity: ComplexityStats::measure(&agents),
+                    mutation_rate: effective_rate,
+                    agent_mutation_rates,
+                    pareto_front: nsga2
+                        .as_ref()
+                        .map(|nsga2| nsga2.pareto_front(&agents))
+                        .unwrap_or_default(),
+                    mean_novelty: novelty.as_ref().map_or(0.0, |n| n.mean_novelty),
+                    novelty_archive_size: novelty_archive.len(),
+                    age: age_stats(&self.config, &agents, &mortality),
+                    species,
+                    innate_cooperation,
+                    learned_cooperation: learned_cooperation.unwrap_or(innate_cooperation),
+                    price: price.unwrap_or_default(),
+                    ..self.collect_generation_stats(generation, &ga_result)
+                };
+                if !self.quiet {
+                    self.print_generation_report(&generation_stats);
+                }
                 results.push(generation_stats);
             }
 
-            game = create_next_generation(ga_result, RouletteSelectionStrategy {});
+            if let Some(reason) = monitor.check(generation, &agents, opponents) {
+                termination = Termination {
+                    reason,
+                    generation,
+                    population: None,
+                };
+                break;
+            }
+
+            let payoffs = imitation_payoffs(&self.config, &agents, extra_opponents.len());
+            let selection_points = novelty.map_or(fitness, |novelty| novelty.points);
+            for ((agent, played), points) in population
+                .agents_mut()
+                .iter_mut()
+                .zip(ga_result.old_agents.iter_mut())
+                .zip(selection_points)
+            {
+                agent.points = points;
+                played.points = points;
+            }
+            inherit_learning(&self.config, population.agents_mut());
+
+            if self.config.imitation.is_some() {
+                game = self.imitation_game(&ga_result, &mut population, &payoffs, mutation_rate);
+                continue;
+            }
+            if self.config.group_selection.is_some() {
+                let event = breed_groups(
+                    &self.config,
+                    &mut population,
+                    mutation_rate,
+                    generation,
+                    ga_result.seed,
+                )?;
+                group_history.extend(event);
+                let next_seed = self.config.seed.map(|_| derive_seed(ga_result.seed, &[]));
+                game =
+                    self.game_from_agents(population.agents().to_vec(), mutation_rate, next_seed);
+                continue;
+            }
+            (game, parent_points) = self.next_game(
+                ga_result,
+                &mut population,
+                mutation_rate,
+                &extra_opponents,
+                &mut mortality,
+                &mut speciation,
+            )?;
         }
 
         let final_stats = self.collect_final_stats(&game)?;
-        self.print_final_report(&final_stats);
+        let match_cache = self.match_cache.as_ref().map(|cache| cache.stats());
+        if !self.quiet {
+            self.print_final_report(&final_stats, &termination);
+            if let Some(stats) = &match_cache {
+                println!("Match cache: {stats}");
+            }
+            print_hall_of_fame(hall_of_fame.entries());
+            if self.config.group_selection.is_some() {
+                println!("Group splits: {}", group_history.len());
+            }
+        }
 
         Ok(SimulationResult {
             config: self.config.clone(),
             generation_results: results,
             final_result: final_stats,
+            diversity_history,
+            mutation_rate_history,
+            payoff_history,
+            cooperation_history,
+            learned_cooperation_history,
+            populations: Vec::new(),
+            termination,
+            hall_of_fame: hall_of_fame.entries().to_vec(),
+            novelty_archive_history,
+            species_history: speciation.events().to_vec(),
+            price_history,
+            group_history,
+            match_cache,
         })
     }
 
+    /// 多目的進化が有効な場合にNSGA-IIを作成
+    fn nsga2(&self) -> GAResult<Option<Nsga2>> {
+        if !self.config.is_multi_objective() {
+            return Ok(None);
+        }
+        Nsga2::new(self.config.objectives.clone()).map(Some)
+    }
+
+    /// エージェントの遺伝子を保ったままポイントと学習した協力確率を消去し、新しいゲームを作成
+    ///
+    /// `seed`が`None`の場合、ゲームのシードはランダムに選ばれます。
+    /// 学習が有効な場合、各個体は遺伝子型の協力確率から学習をやり直します。
+    /// 構造化された近傍や集団選択では、近傍や同じグループの個体同士のみを対戦させます。
+    fn game_from_agents(
+        &self,
+        agents: Vec<Agent>,
+        mutation_rate: MutationRate,
+        seed: Option<u64>,
+    ) -> Game<Agent, RouletteSelectionStrategy> {
+        let agents = agents
+            .into_iter()
+            .enumerate()
+            .map(|(i, agent)| {
+                Box::new(Agent {
+                    id: i as AgentId,
+                    points: 0,
+                    active: true,
+                    learned: None,
+                    ..agent
+                })
+            })
+            .collect();
+
+        let game = self.attach_match_cache(generate_next_game(
+            self.config.population,
+            mutation_rate,
+            self.config.rounds_per_generation,
+            self.config.dna_length,
+            agents,
+            game_strategy(&self.config),
+        ));
+        let game = match self.config.learning {
+            Some(learning) => game.with_learning(learning),
+            None => game,
+        };
+        let game = match local_pairs(&self.config, self.config.population) {
+            Some(pairs) => game.with_pairs(pairs),
+            None => game,
+        };
+        match seed {
+            Some(seed) => game.with_seed(seed),
+            None => game,
+        }
+    }
+
+    /// キャッシュが有効な場合、ゲームの対戦にキャッシュを使わせる
+    fn attach_match_cache(
+        &self,
+        game: Game<Agent, RouletteSelectionStrategy>,
+    ) -> Game<Agent, RouletteSelectionStrategy> {
+        match &self.match_cache {
+            Some(cache) => game.with_match_cache(Arc::clone(cache)),
+            None => game,
+        }
+    }
+
+    /// 設定された置換モデルと多様性維持スキームに従って次世代のゲームを生成
+    ///
+    /// 世代交代型・多様性維持なし・単一目的の場合は従来の`create_next_generation`を使用します。
+    /// それ以外では`evolve_population`で評価済みの個体群から遺伝子を保ったまま
+    /// 次世代を構築し、新しい子は現個体群の全員と`extra_opponents`（殿堂の個体）と
+    /// 対戦させて評価します。
+    ///
+    /// # 戻り値
+    /// 次世代のゲームと、各子の親の平均ポイント（親を追跡しない置換方式では空）
+    fn next_game(
+        &self,
+        mut ga_result: GA<Agent>,
+        population: &mut Population<Agent>,
+        mutation_rate: MutationRate,
+        extra_opponents: &[Agent],
+        mortality: &mut MortalityRecord,
+        speciation: &mut Speciation<Agent>,
+    ) -> GAResult<(Game<Agent, RouletteSelectionStrategy>, Vec<f64>)> {
+        let strategy = game_strategy(&self.config);
+        let next_seed = self.config.seed.map(|_| derive_seed(ga_result.seed, &[]));
+
+        if uses_legacy_generation(&self.config) {
+            population.increment_generation();
+            ga_result.mutation_rate = mutation_rate;
+            return Ok((
+                self.attach_match_cache(create_next_generation(ga_result, strategy)?),
+                Vec::new(),
+            ));
+        }
+
+        let mut opponents = population.agents().to_vec();
+        opponents.extend_from_slice(extra_opponents);
+        let parent_points = evolve_population(
+            &self.config,
+            population,
+            mutation_rate,
+            mortality,
+            speciation,
+            ga_result.seed,
+            |child, rng| self.evaluate_against(child, &opponents, rng),
+        )?;
+
+        Ok((
+            self.game_from_agents(population.agents().to_vec(), mutation_rate, next_seed),
+            parent_points,
+        ))
+    }
+
+    /// 模倣ダイナミクスで各個体が手本の戦略を写した次世代のゲームを生成
+    ///
+    /// # 引数
+    /// * `payoffs` - 各個体の1回の対戦あたりの平均利得
+    fn imitation_game(
+        &self,
+        ga_result: &GA<Agent>,
+        population: &mut Population<Agent>,
+        payoffs: &[f64],
+        mutation_rate: MutationRate,
+    ) -> Game<Agent, RouletteSelectionStrategy> {
+        let next_seed = self.config.seed.map(|_| derive_seed(ga_result.seed, &[]));
+        imitate_population(
+            &self.config,
+            population,
+            payoffs,
+            mutation_rate,
+            ga_result.seed,
+        );
+        self.game_from_agents(population.agents().to_vec(), mutation_rate, next_seed)
+    }
+
+    /// 候補個体を現個体群の全員（と殿堂の個体）と対戦させて適応度を計算
+    ///
+    /// 既存個体は自分以外の`n - 1`体と対戦しているため、
+    /// 獲得ポイントを`(n - 1) / n`倍して比較可能な尺度に揃えてから正規化します。
+    fn evaluate_against<R: Rng>(
+        &self,
+        candidate: &Agent,
+        opponents: &[Agent],
+        rng: &mut R,
+    ) -> Points {
+        let points = points_against(
+            &self.config,
+            self.match_cache.as_deref(),
+            candidate,
+            opponents,
+            rng,
+        );
+        let n = opponents.len() as u128;
+        let points = (points as u128 * n.saturating_sub(1) / n.max(1)) as Points;
+        normalized_child_fitness(&self.config, points, opponents.len().saturating_sub(1))
+    }
+
     fn print_header<T, U>(&self, game: &T)
     where
         T: GameOperation<Agent, U>,
@@ -92,6 +492,46 @@ impl Simulation {
         println!("Generations: {}", self.config.generations);
         println!("Mutation rate: {}", self.config.mutation_rate);
         println!("DNA length: {}", self.config.dna_length);
+        println!("Diversity scheme: {}", self.config.diversity_scheme);
+        println!("Mutation scheme: {}", self.config.mutation_scheme);
+        println!("Replacement model: {}", self.config.replacement_model);
+        if let Some(age_structure) = &self.config.age_structure {
+            println!("Age structure: {age_structure}");
+        }
+        if let Some(learning) = &self.config.learning {
+            println!("Learning: {learning}");
+        }
+        if let Some(imitation) = &self.config.imitation {
+            println!("Imitation: {imitation} ({})", self.config.neighbourhood);
+        }
+        if let Some(group_selection) = &self.config.group_selection {
+            println!("Group selection: {group_selection}");
+        }
+        println!("Decoding: {}", self.config.decoding);
+        if self.config.is_variable_length() {
+            println!("Crossover: {}", self.config.crossover_scheme);
+            if let Some(indel) = &self.config.indel {
+                println!("Indel mutation: {indel}");
+            }
+        }
+        if let Some(novelty) = &self.config.novelty {
+            println!("Selection: {novelty}");
+        }
+        if self.config.fitness_normalization != FitnessNormalization::Raw {
+            println!(
+                "Fitness normalization: {}",
+                self.config.fitness_normalization
+            );
+        }
+        if self.config.is_multi_objective() {
+            let objectives: Vec<String> = self
+                .config
+                .objectives
+                .iter()
+                .map(|o| o.to_string())
+                .collect();
+            println!("Objectives: {}", objectives.join(", "));
+        }
         println!("\nInitial population:");
 
         for (i, dna) in game.get_dna_list().iter().enumerate() {
@@ -106,7 +546,8 @@ impl Simulation {
     {
         let dna_list = ga_result.get_dna_list();
         let points_list = ga_result.get_points_list();
-        let avg_points = points_list.iter().sum::<u64>() as f64 / self.config.population as f64;
+        let avg_points =
+            points_list.iter().map(|&p| p as f64).sum::<f64>() / self.config.population as f64;
         let max_points = *points_list.iter().max().unwrap_or(&0);
         let min_points = *points_list.iter().min().unwrap_or(&0);
 
@@ -117,6 +558,7 @@ impl Simulation {
             avg_points,
             max_points,
             min_points,
+            ..Default::default()
         }
     }
 
@@ -127,7 +569,8 @@ impl Simulation {
     {
         let dna_list = game.get_dna_list();
         let points_list = game.get_points_list();
-        let avg_points = points_list.iter().sum::<u64>() as f64 / self.config.population as f64;
+        let avg_points =
+            points_list.iter().map(|&p| p as f64).sum::<f64>() / self.config.population as f64;
 
         Ok(FinalStats {
             dna_list,
@@ -141,20 +584,70 @@ impl Simulation {
         println!("{}", "-".repeat(40));
 
         for i in 0..self.config.population {
-            println!(
-                "Agent {:2}: {} (points: {})",
-                i, stats.dna_list[i], stats.points_list[i]
-            );
+            match species_label(&stats.species, i) {
+                Some(species) => println!(
+                    "Agent {:2}: {} (points: {}, species: {})",
+                    i, stats.dna_list[i], stats.points_list[i], species
+                ),
+                None => println!(
+                    "Agent {:2}: {} (points: {})",
+                    i, stats.dna_list[i], stats.points_list[i]
+                ),
+            }
         }
 
         println!("Average points: {:.2}", stats.avg_points);
         println!("Max points: {}", stats.max_points);
         println!("Min points: {}", stats.min_points);
+        println!("Diversity: {:.4}", stats.diversity);
+        println!("Mutation rate: {:.4}", stats.mutation_rate);
+        if self.config.is_variable_length() {
+            print_complexity(stats);
+        }
+        if self.config.novelty.is_some() {
+            print_novelty(stats);
+        }
+        if self.config.age_structure.is_some() {
+            print_age(stats);
+        }
+        if self.config.learning.is_some() {
+            print_learning(stats);
+        }
+        if !stats.species.sizes.is_empty() {
+            print_species(stats);
+        }
+        if self.config.group_selection.is_some() {
+            print_price(stats);
+        }
+
+        if !stats.pareto_front.is_empty() {
+            println!("Pareto front ({} solutions):", stats.pareto_front.len());
+            for point in &stats.pareto_front {
+                let values: Vec<String> = self
+                    .config
+                    .objectives
+                    .iter()
+                    .zip(&point.objectives)
+                    .map(|(objective, value)| format!("{objective}={value:.2}"))
+                    .collect();
+                println!("  {} ({})", point.dna, values.join(", "));
+            }
+        }
     }
 
-    fn print_final_report(&self, stats: &FinalStats) {
-        println!("\n\nFinal Results (Generation {})", self.config.generations);
+    fn print_final_report(&self, stats: &FinalStats, termination: &Termination) {
+        println!(
+            "\n\nFinal Results (Generation {})",
+            termination.generation + 1
+        );
         println!("{}", "=".repeat(40));
+        if termination.reason != TerminationReason::GenerationLimit {
+            println!(
+                "Stopped after {} generations: {}",
+                termination.generation + 1,
+                termination.reason
+            );
+        }
 
         for (i, dna) in stats.dna_list.iter().enumerate() {
             println!("Agent {:2}: {} (points: {})", i, dna, stats.points_list[i]);
@@ -164,7 +657,110 @@ impl Simulation {
     }
 }
 
-#[derive(Debug, Clone)]
+/// 可変長ゲノムの長さ・複雑さと編集距離による多様性を出力
+pub(crate) fn print_complexity(stats: &GenerationStats) {
+    let complexity = &stats.complexity;
+    println!(
+        "DNA length: {:.2} (min: {}, max: {})",
+        complexity.mean_length, complexity.min_length, complexity.max_length
+    );
+    println!("Complexity: {:.2}", complexity.mean_complexity);
+    println!(
+        "Edit-distance diversity: {:.4}",
+        stats.edit_distance_diversity
+    );
+}
+
+/// 新規性探索の平均新規性とアーカイブの大きさを出力
+pub(crate) fn print_novelty(stats: &GenerationStats) {
+    println!(
+        "Novelty: {:.4} (archive: {} behaviours)",
+        stats.mean_novelty, stats.novelty_archive_size
+    );
+}
+
+/// 齢構造モードの年齢分布と平均寿命を出力
+pub(crate) fn print_age(stats: &GenerationStats) {
+    let age = &stats.age;
+    println!(
+        "Age: {:.2} (max: {}, mature: {})",
+        age.mean_age, age.max_age, age.mature
+    );
+    let distribution: Vec<String> = age
+        .distribution
+        .iter()
+        .enumerate()
+        .filter(|(_, &count)| count > 0)
+        .map(|(age, count)| format!("{age}:{count}"))
+        .collect();
+    println!("Age distribution: {}", distribution.join(" "));
+    println!(
+        "Mean lifespan: {:.2} ({} deaths)",
+        age.mean_lifespan, age.deaths
+    );
+}
+
+/// 遺伝子型による平均協力確率と学習後の平均協力確率を出力
+pub(crate) fn print_learning(stats: &GenerationStats) {
+    println!(
+        "Cooperation: {:.4} innate, {:.4} learned",
+        stats.innate_cooperation, stats.learned_cooperation
+    );
+}
+
+/// 個体の属する種と、分かる場合は親の種（"種 from 親の種"）の表示（種分化以外では`None`）
+pub(crate) fn species_label(species: &SpeciesStats, index: usize) -> Option<String> {
+    let id = species.assignments.get(index)?;
+    Some(match species.origins.get(index) {
+        Some(origin) => format!("{id} from {origin}"),
+        None => id.to_string(),
+    })
+}
+
+/// 種分化スキームの種の数・閾値・誕生と絶滅、各種の個体数を出力
+pub(crate) fn print_species(stats: &GenerationStats) {
+    let species = &stats.species;
+    println!(
+        "Species: {} (threshold: {:.4}, born: {}, extinct: {})",
+        species.sizes.len(),
+        species.threshold,
+        species.births,
+        species.extinctions
+    );
+    let sizes: Vec<String> = species
+        .sizes
+        .iter()
+        .map(|(id, size)| format!("{id}:{size}"))
+        .collect();
+    println!("Species sizes: {}", sizes.join(" "));
+}
+
+/// 集団選択でのPrice方程式によるグループ間とグループ内の選択の成分を出力
+fn print_price(stats: &GenerationStats) {
+    println!(
+        "Selection on cooperation: {:.4} between groups, {:.4} within groups (total {:.4})",
+        stats.price.between,
+        stats.price.within,
+        stats.price.total()
+    );
+}
+
+/// 殿堂の遺伝子型を出力（殿堂を使用しない場合は何も出力しない）
+pub(crate) fn print_hall_of_fame(entries: &[HallOfFameEntry]) {
+    if entries.is_empty() {
+        return;
+    }
+
+    println!("\nHall of fame ({} genotypes):", entries.len());
+    for entry in entries {
+        println!(
+            "  {} (points: {}, since generation {})",
+            entry.dna, entry.points, entry.generation
+        );
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct GenerationStats {
     pub generation: usize,
     pub dna_list: Vec<String>,
@@ -172,6 +768,32 @@ pub struct GenerationStats {
     pub avg_points: f64,
     pub max_points: u64,
     pub min_points: u64,
+    /// 平均ペアワイズハミング距離（0.0-1.0、DNA長が異なる組は編集距離）
+    pub diversity: f64,
+    /// 平均ペアワイズ正規化編集距離（0.0-1.0）
+    pub edit_distance_diversity: f64,
+    /// DNA長と複雑さの統計
+    pub complexity: ComplexityStats,
+    /// この世代の実効突然変異率（自己適応型では個体の遺伝子の平均）
+    pub mutation_rate: f64,
+    /// 個体ごとの突然変異率遺伝子（自己適応型以外では空）
+    pub agent_mutation_rates: Vec<f64>,
+    /// 多目的進化でのパレートフロント（単一目的では空）
+    pub pareto_front: Vec<ParetoPoint>,
+    /// 個体群の平均新規性（新規性探索以外では0）
+    pub mean_novelty: f64,
+    /// 新規性探索のアーカイブに保存された行動の数（新規性探索以外では0）
+    pub novelty_archive_size: usize,
+    /// 年齢分布と平均寿命（齢構造モード以外では既定値）
+    pub age: AgeStats,
+    /// 各個体の属する種と種の誕生・絶滅（種分化スキーム以外では既定値）
+    pub species: SpeciesStats,
+    /// 遺伝子型による平均協力確率
+    pub innate_cooperation: f64,
+    /// 世代の対戦を終えた時点の学習後の平均協力確率（学習なしでは遺伝子型による値）
+    pub learned_cooperation: f64,
+    /// 協力確率に対するグループ間とグループ内の選択の成分（集団選択以外では既定値）
+    pub price: PriceDecomposition,
 }
 
 #[derive(Debug, Clone)]
@@ -186,4 +808,618 @@ pub struct SimulationResult {
     pub config: Config,
     pub generation_results: Vec<GenerationStats>,
     pub final_result: FinalStats,
+    /// 全世代の多様性（平均ペアワイズハミング距離）の推移
+    pub diversity_history: Vec<f64>,
+    /// 全世代の実効突然変異率の推移
+    pub mutation_rate_history: Vec<f64>,
+    /// 全世代の平均ポイント（評価時の対戦による）の推移
+    pub payoff_history: Vec<f64>,
+    /// 全世代の平均協力確率の推移
+    pub cooperation_history: Vec<f64>,
+    /// 全世代の学習後の平均協力確率の推移（学習なしでは空）
+    pub learned_cooperation_history: Vec<f64>,
+    /// 終了した理由と世代
+    pub termination: Termination,
+    /// 実行全体で獲得ポイントの高かった遺伝子型（殿堂を使用しない場合は空）
+    pub hall_of_fame: Vec<HallOfFameEntry>,
+    /// 全世代の新規性探索のアーカイブの大きさの推移（新規性探索以外では空）
+    pub novelty_archive_history: Vec<usize>,
+    /// 実行全体での種の誕生・絶滅の記録（種分化スキーム以外では空）
+    pub species_history: Vec<SpeciesEvent>,
+    /// 全世代の協力確率に対するPrice方程式の選択の成分の推移（集団選択以外では空）
+    pub price_history: Vec<PriceDecomposition>,
+    /// 実行全体でのグループの分裂と絶滅の記録（集団選択以外では空）
+    pub group_history: Vec<GroupEvent>,
+    /// 対戦結果のキャッシュの利用統計（キャッシュを使用しない場合は`None`）
+    pub match_cache: Option<MatchCacheStats>,
+    /// 共進化での集団ごとの結果（単一集団のシミュレーションでは空）
+    ///
+    /// 共進化では上記のフィールドは集団Aの結果と同じ内容になります。
+    pub populations: Vec<PopulationResult>,
+}
+
+/// 共進化における1集団分の結果
+#[derive(Debug, Clone)]
+pub struct PopulationResult {
+    pub config: Config,
+    pub generation_results: Vec<GenerationStats>,
+    pub final_result: FinalStats,
+    /// 全世代の多様性（平均ペアワイズハミング距離）の推移
+    pub diversity_history: Vec<f64>,
+    /// 全世代の実効突然変異率の推移
+    pub mutation_rate_history: Vec<f64>,
+    /// 全世代の平均ポイント（評価時の対戦による）の推移
+    pub payoff_history: Vec<f64>,
+    /// 全世代の平均協力確率の推移
+    pub cooperation_history: Vec<f64>,
+    /// 全世代の学習後の平均協力確率の推移（学習なしでは空）
+    pub learned_cooperation_history: Vec<f64>,
+    /// この集団の殿堂（殿堂を使用しない場合は空）
+    pub hall_of_fame: Vec<HallOfFameEntry>,
+    /// この集団の新規性探索のアーカイブの大きさの推移（新規性探索以外では空）
+    pub novelty_archive_history: Vec<usize>,
+    /// この集団の種の誕生・絶滅の記録（種分化スキーム以外では空）
+    pub species_history: Vec<SpeciesEvent>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::crossover::CrossoverScheme;
+    use crate::engine::genetic::ReplacementModel;
+    use crate::engine::mutation::IndelMutation;
+    use crate::engine::novelty::NoveltySearch;
+    use crate::engine::termination::TerminationCriterion;
+    use crate::infrastructure::config::ConfigBuilder;
+
+    fn config() -> ConfigBuilder {
+        ConfigBuilder::new()
+            .generations(50)
+            .population(6)
+            .report_interval(100)
+    }
+
+    #[test]
+    fn test_runs_all_generations_without_criteria() {
+        let result = Simulation::new(config().build().unwrap())
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(
+            result.termination.reason,
+            TerminationReason::GenerationLimit
+        );
+        assert_eq!(result.termination.generation, 49);
+        assert_eq!(result.diversity_history.len(), 50);
+    }
+
+    #[test]
+    fn test_cancellation_stops_after_first_generation() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = Simulation::new(config().build().unwrap())
+            .unwrap()
+            .with_cancellation_token(token)
+            .run()
+            .unwrap();
+        assert_eq!(result.termination.reason, TerminationReason::Cancelled);
+        assert_eq!(result.termination.generation, 0);
+        assert_eq!(result.diversity_history.len(), 1);
+    }
+
+    #[test]
+    fn test_stagnation_records_criterion() {
+        let criterion = TerminationCriterion::Stagnation { generations: 1 };
+        let config = config()
+            .generations(10_000)
+            .termination(vec![criterion])
+            .build()
+            .unwrap();
+        let result = Simulation::new(config).unwrap().run().unwrap();
+        assert_eq!(
+            result.termination.reason,
+            TerminationReason::Criterion(criterion)
+        );
+        assert!(result.termination.generation < 10_000);
+    }
+
+    #[test]
+    fn test_variable_length_genomes_evolve_within_range() {
+        let config = config()
+            .generations(40)
+            .report_interval(10)
+            .indel(IndelMutation::new(0.2, 0.2).with_length_range(2, 10))
+            .crossover_scheme(CrossoverScheme::Messy)
+            .build()
+            .unwrap();
+        let result = Simulation::new(config).unwrap().run().unwrap();
+
+        let lengths: Vec<usize> = result
+            .final_result
+            .dna_list
+            .iter()
+            .map(String::len)
+            .collect();
+        assert!(lengths.iter().all(|&len| (2..=10).contains(&len)));
+        assert!(result
+            .generation_results
+            .iter()
+            .any(|stats| stats.complexity.min_length != stats.complexity.max_length));
+    }
+
+    #[test]
+    fn test_hall_of_fame_is_recorded() {
+        let config = config()
+            .generations(20)
+            .hall_of_fame(3, true)
+            .build()
+            .unwrap();
+        let result = Simulation::new(config).unwrap().run().unwrap();
+
+        let hall_of_fame = &result.hall_of_fame;
+        assert_eq!(hall_of_fame.len(), 3);
+        assert!(hall_of_fame.windows(2).all(|w| w[0].points >= w[1].points));
+        assert!(hall_of_fame.iter().all(|entry| hall_of_fame
+            .iter()
+            .filter(|e| e.dna == entry.dna)
+            .count()
+            == 1));
+    }
+
+    #[test]
+    fn test_hall_of_fame_opponents_with_replacement_model() {
+        let config = config()
+            .generations(20)
+            .replacement_model(ReplacementModel::MuPlusLambda { lambda: 6 })
+            .hall_of_fame(2, true)
+            .build()
+            .unwrap();
+        let result = Simulation::new(config).unwrap().run().unwrap();
+        assert_eq!(result.hall_of_fame.len(), 2);
+    }
+
+    #[test]
+    fn test_novelty_archive_grows_monotonically() {
+        let config = config()
+            .generations(20)
+            .report_interval(5)
+            .novelty(NoveltySearch::new(3).with_novelty_weight(0.5))
+            .build()
+            .unwrap();
+        let result = Simulation::new(config).unwrap().run().unwrap();
+
+        let history = &result.novelty_archive_history;
+        assert_eq!(history.len(), 20);
+        assert!(history[0] > 0);
+        assert!(history.windows(2).all(|w| w[0] <= w[1]));
+        assert!(result
+            .generation_results
+            .iter()
+            .all(|stats| stats.novelty_archive_size > 0));
+    }
+
+    #[test]
+    fn test_fitness_normalization_on_large_population_and_long_run() {
+        let (population, rounds) = (120, 25);
+        let max_points = 5.0 * (population - 1) as f64 * rounds as f64;
+        for (normalization, replacement) in [
+            (FitnessNormalization::Raw, ReplacementModel::Generational),
+            (
+                FitnessNormalization::PerInteraction,
+                ReplacementModel::Generational,
+            ),
+            (
+                FitnessNormalization::PerRound,
+                ReplacementModel::MuPlusLambda { lambda: 20 },
+            ),
+            (FitnessNormalization::ZScore, ReplacementModel::Generational),
+            (FitnessNormalization::Rank, ReplacementModel::Generational),
+        ] {
+            let mut config = config()
+                .generations(3)
+                .population(population)
+                .replacement_model(replacement)
+                .fitness_normalization(normalization)
+                .seed(11)
+                .build()
+                .unwrap();
+            config.rounds_per_generation = rounds;
+            let result = Simulation::new(config).unwrap().quiet().run().unwrap();
+
+            // 報告される平均ポイントは正規化によらず累積ポイントのまま
+            assert_eq!(result.payoff_history.len(), 3);
+            assert!(result
+                .payoff_history
+                .iter()
+                .all(|&points| points > 0.0 && points <= max_points));
+        }
+    }
+
+    #[test]
+    fn test_age_structure_records_ages_and_lifespans() {
+        use crate::engine::aging::{AgeStructure, Mortality};
+
+        let config = config()
+            .generations(30)
+            .population(10)
+            .report_interval(5)
+            .age_structure(AgeStructure::new(Mortality::Lifespan(4)).with_maturity(2))
+            .seed(3)
+            .build()
+            .unwrap();
+        let result = Simulation::new(config).unwrap().quiet().run().unwrap();
+
+        for stats in &result.generation_results {
+            let age = &stats.age;
+            assert_eq!(age.distribution.iter().sum::<usize>(), 10);
+            assert!(age.max_age < 4);
+            assert!(age.mature <= 10);
+        }
+        // 最大寿命による死亡では、全個体がちょうど最大寿命で死亡する
+        let last = &result.generation_results.last().unwrap().age;
+        assert!(last.deaths > 0);
+        assert_eq!(last.mean_lifespan, 4.0);
+    }
+
+    #[test]
+    fn test_speciation_tracks_membership_and_history() {
+        use crate::engine::diversity::{DiversityScheme, SpeciesEventKind};
+
+        let config = config()
+            .generations(20)
+            .population(12)
+            .dna_length(8)
+            .report_interval(1)
+            .diversity_scheme(DiversityScheme::Speciation {
+                threshold: 0.2,
+                target_species: 3,
+            })
+            .seed(5)
+            .build()
+            .unwrap();
+        let result = Simulation::new(config).unwrap().quiet().run().unwrap();
+
+        for stats in &result.generation_results {
+            let species = &stats.species;
+            assert_eq!(species.assignments.len(), 12);
+            assert_eq!(
+                species.sizes.iter().map(|(_, size)| size).sum::<usize>(),
+                12
+            );
+            assert!(species
+                .assignments
+                .iter()
+                .all(|id| species.sizes.iter().any(|(species, _)| species == id)));
+        }
+        let births = result
+            .species_history
+            .iter()
+            .filter(|event| event.kind == SpeciesEventKind::Birth)
+            .count();
+        let extinctions = result.species_history.len() - births;
+        let alive = result
+            .generation_results
+            .last()
+            .unwrap()
+            .species
+            .sizes
+            .len();
+        assert_eq!(births - extinctions, alive);
+        assert!(result
+            .species_history
+            .windows(2)
+            .all(|pair| pair[0].generation <= pair[1].generation));
+
+        // 2世代目以降の各個体と新しい種は、前世代に生きていた種の親から生まれている
+        assert!(result.generation_results[0].species.origins.is_empty());
+        for pair in result.generation_results.windows(2) {
+            let (previous, current) = (&pair[0].species, &pair[1].species);
+            assert_eq!(current.origins.len(), 12);
+            assert!(current
+                .origins
+                .iter()
+                .all(|origin| previous.sizes.iter().any(|(id, _)| id == origin)));
+        }
+        assert!(result
+            .species_history
+            .iter()
+            .filter(|event| event.kind == SpeciesEventKind::Birth && event.generation > 0)
+            .all(|event| event.parent.is_some()));
+    }
+
+    #[test]
+    fn test_learning_records_innate_and_learned_cooperation() {
+        use crate::engine::learning::{Inheritance, Learning};
+
+        for inheritance in [Inheritance::Baldwinian, Inheritance::Lamarckian] {
+            let config = config()
+                .generations(15)
+                .population(8)
+                .report_interval(5)
+                .learning(Learning::new(inheritance).with_rate(0.3))
+                .seed(11)
+                .build()
+                .unwrap();
+            let result = Simulation::new(config).unwrap().quiet().run().unwrap();
+
+            assert_eq!(result.learned_cooperation_history.len(), 15);
+            assert!(result
+                .learned_cooperation_history
+                .iter()
+                .all(|p| (0.0..=1.0).contains(p)));
+            for stats in &result.generation_results {
+                assert!((0.0..=1.0).contains(&stats.innate_cooperation));
+                assert!((0.0..=1.0).contains(&stats.learned_cooperation));
+            }
+        }
+
+        // 学習なしでは学習後の協力確率を記録しない
+        let config = config().generations(3).build().unwrap();
+        let result = Simulation::new(config).unwrap().quiet().run().unwrap();
+        assert!(result.learned_cooperation_history.is_empty());
+    }
+
+    #[test]
+    fn test_imitation_well_mixed_and_on_lattice() {
+        use crate::engine::dynamics::{ImitationRule, Neighbourhood};
+
+        // 誤りのない最良個体の模倣では、よく混ざった個体群は1世代で全員が最良個体になる
+        let best = config()
+            .generations(3)
+            .population(10)
+            .mutation_rate(0.0)
+            .imitation(ImitationRule::BestNeighbour)
+            .seed(1)
+            .build()
+            .unwrap();
+        let result = Simulation::new(best).unwrap().quiet().run().unwrap();
+        assert!(result.diversity_history[0] > 0.0);
+        assert_eq!(result.diversity_history[1], 0.0);
+
+        let run = || {
+            let config = config()
+                .generations(20)
+                .population(16)
+                .report_interval(5)
+                .imitation(ImitationRule::Fermi { intensity: 2.0 })
+                .neighbourhood(Neighbourhood::Lattice { width: 4 })
+                .seed(9)
+                .build()
+                .unwrap();
+            Simulation::new(config).unwrap().quiet().run().unwrap()
+        };
+        let (first, second) = (run(), run());
+        assert_eq!(first.payoff_history.len(), 20);
+        assert_eq!(first.payoff_history, second.payoff_history);
+        assert_eq!(first.cooperation_history, second.cooperation_history);
+        // 格子では各個体は近傍の4体とのみ対戦する（1回の対戦の利得は最大5）
+        assert!(first.payoff_history.iter().all(|&p| p <= 4.0 * 5.0));
+    }
+
+    #[test]
+    fn test_group_selection_reports_price_decomposition() {
+        use crate::engine::group_selection::GroupSelection;
+
+        let grouped = config()
+            .generations(30)
+            .population(12)
+            .report_interval(10)
+            .group_selection(GroupSelection::new(3).with_split_probability(1.0))
+            .seed(4)
+            .build()
+            .unwrap();
+        let result = Simulation::new(grouped).unwrap().quiet().run().unwrap();
+
+        assert_eq!(result.price_history.len(), 30);
+        assert!(result
+            .price_history
+            .iter()
+            .all(|price| price.between.is_finite() && price.within.is_finite()));
+        // 分裂確率1では毎世代グループが分裂する
+        assert_eq!(result.group_history.len(), 30);
+        assert!(result
+            .group_history
+            .iter()
+            .all(|event| event.parent != event.extinct && event.extinct < 3));
+        for stats in &result.generation_results {
+            assert_eq!(stats.price, result.price_history[stats.generation]);
+        }
+        // 各個体は同じグループの3体とのみ対戦する
+        assert_eq!(local_opponents(&result.config, 12), 3);
+        assert!(result.payoff_history.iter().all(|&p| p <= 3.0 * 5.0));
+
+        let baseline = config().generations(3).build().unwrap();
+        let result = Simulation::new(baseline).unwrap().quiet().run().unwrap();
+        assert!(result.price_history.is_empty());
+        assert!(result.group_history.is_empty());
+    }
+
+    #[test]
+    fn test_price_decomposition_predicts_the_change_in_mean_trait() {
+        use crate::engine::group_selection::GroupSelection;
+        use crate::engine::group_selection::PriceDecomposition;
+        use crate::models::model::BaseModel;
+
+        let config = config()
+            .group_selection(GroupSelection::new(2).with_split_probability(0.5))
+            .build()
+            .unwrap();
+        // 1遺伝子の個体の子は親の一方の複製なので、形質は選択によってのみ変わる
+        let points: Vec<Points> = vec![4, 2, 1, 3, 3, 0];
+        let agents: Vec<Agent> = ["1", "0", "1", "0", "1", "0"]
+            .iter()
+            .zip(&points)
+            .enumerate()
+            .map(|(i, (dna, &points))| {
+                <Agent as BaseModel>::new(i as u64, dna.to_string()).with_points(points)
+            })
+            .collect();
+        let mean_trait = |agents: &[Agent]| {
+            agents
+                .iter()
+                .map(|agent| config.decoding.phenotype(agent))
+                .sum::<f64>()
+                / agents.len() as f64
+        };
+
+        let price = price_decomposition(&config, &agents, &points).unwrap();
+        let runs = 20_000;
+        let observed = (0..runs)
+            .map(|seed| {
+                let mut population = Population::new(agents.clone(), 0).unwrap();
+                breed_groups(&config, &mut population, 0.0, 0, seed).unwrap();
+                mean_trait(population.agents()) - mean_trait(&agents)
+            })
+            .sum::<f64>()
+            / runs as f64;
+        assert!(
+            (price.total() - observed).abs() < 0.005,
+            "{price:?} {observed}"
+        );
+
+        // 線形のポイントをそのまま適応度とすると、観測した変化と一致しない
+        let traits: Vec<f64> = agents
+            .iter()
+            .map(|agent| config.decoding.phenotype(agent))
+            .collect();
+        let linear: Vec<f64> = points.iter().map(|&p| p as f64).collect();
+        let naive = PriceDecomposition::measure(&traits, &linear, 3);
+        assert!(
+            (naive.total() - observed).abs() > 0.02,
+            "{naive:?} {observed}"
+        );
+    }
+
+    #[test]
+    fn test_single_generation_lifespan_replaces_everyone() {
+        use crate::engine::aging::{AgeStructure, Mortality};
+
+        let config = config()
+            .generations(6)
+            .report_interval(1)
+            .age_structure(AgeStructure::new(Mortality::Lifespan(1)))
+            .build()
+            .unwrap();
+        let result = Simulation::new(config).unwrap().quiet().run().unwrap();
+
+        for (generation, stats) in result.generation_results.iter().enumerate() {
+            assert_eq!(stats.age.distribution, vec![6]);
+            assert_eq!(stats.age.deaths, 6 * generation as u64);
+        }
+    }
+
+    #[test]
+    fn test_match_cache_does_not_change_results() {
+        let run = |match_cache: bool| {
+            let config = config()
+                .generations(30)
+                .population(10)
+                .dna_length(2)
+                .seed(5)
+                .match_cache(match_cache)
+                .build()
+                .unwrap();
+            Simulation::new(config).unwrap().quiet().run().unwrap()
+        };
+        let (cached, uncached) = (run(true), run(false));
+
+        assert_eq!(cached.payoff_history, uncached.payoff_history);
+        assert_eq!(cached.diversity_history, uncached.diversity_history);
+        assert!(uncached.match_cache.is_none());
+
+        // 2ビットのDNAでは協力確率0と1の個体が多く、決定的な組が繰り返し現れる
+        let stats = cached.match_cache.unwrap();
+        assert!(stats.hits > 0);
+        assert!(stats.bypassed > 0);
+        assert!(stats.entries <= 3);
+    }
+
+    /// 同じシードで2回実行し、個体群と履歴が一致することを確認
+    fn assert_reproducible(builder: ConfigBuilder) {
+        let config = builder
+            .generations(20)
+            .population(10)
+            .dna_length(8)
+            .seed(11)
+            .build()
+            .unwrap();
+        let run = || {
+            Simulation::new(config.clone())
+                .unwrap()
+                .quiet()
+                .run()
+                .unwrap()
+        };
+        let (first, second) = (run(), run());
+        assert_eq!(first.final_result.dna_list, second.final_result.dna_list);
+        assert_eq!(
+            first.final_result.points_list,
+            second.final_result.points_list
+        );
+        assert_eq!(first.payoff_history, second.payoff_history);
+        assert_eq!(first.cooperation_history, second.cooperation_history);
+        assert_eq!(first.diversity_history, second.diversity_history);
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_fitness_sharing() {
+        use crate::engine::diversity::DiversityScheme;
+
+        assert_reproducible(config().diversity_scheme(DiversityScheme::FitnessSharing {
+            radius: 0.3,
+            alpha: 1.0,
+        }));
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_age_structure() {
+        use crate::engine::aging::{AgeStructure, Mortality};
+
+        assert_reproducible(config().age_structure(AgeStructure::new(Mortality::Lifespan(3))));
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_steady_state() {
+        use crate::engine::genetic::ReplacementPolicy;
+
+        assert_reproducible(config().replacement_model(ReplacementModel::SteadyState {
+            offspring: 2,
+            policy: ReplacementPolicy::Worst,
+        }));
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_plus_selection() {
+        assert_reproducible(
+            config().replacement_model(ReplacementModel::MuPlusLambda { lambda: 4 }),
+        );
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_crowding() {
+        use crate::engine::diversity::DiversityScheme;
+
+        assert_reproducible(config().diversity_scheme(DiversityScheme::DeterministicCrowding));
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_restricted_tournament() {
+        use crate::engine::diversity::DiversityScheme;
+
+        assert_reproducible(
+            config().diversity_scheme(DiversityScheme::RestrictedTournament { window_size: 4 }),
+        );
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_multi_objective() {
+        use crate::core::types::Objective;
+
+        assert_reproducible(config().objectives(vec![Objective::Score, Objective::Cooperation]));
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_hall_of_fame_opponents() {
+        assert_reproducible(config().hall_of_fame(3, true));
+    }
 }
@@ -0,0 +1,904 @@
+/// シミュレーション設定のハイパーパラメータ探索
+///
+/// 基準の設定のうち指定したパラメータを探索空間から選んだ値に置き換えて
+/// `Simulation`を繰り返し実行し、目的関数の反復平均で設定を順位付けします。
+/// 探索方式はグリッド、ランダム、ラテン超方格、メタGA（パラメータの組そのものを
+/// 遺伝的アルゴリズムで進化させる）から選べます。
+use super::evolution::local_opponents;
+use super::simulation::{Simulation, SimulationResult};
+use crate::core::errors::{GAError, GAResult};
+use crate::core::parallel::{derive_seed, stream_rng};
+use crate::engine::genetic::ReplacementModel;
+use crate::infrastructure::config::Config;
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+use std::fmt;
+use std::str::FromStr;
+
+/// 1点あたりの反復回数の既定値
+pub const DEFAULT_REPLICATES: usize = 3;
+
+/// 協力到達とみなす平均協力確率の既定値
+pub const DEFAULT_COOPERATION_THRESHOLD: f64 = 0.9;
+
+/// メタGAで各座標を変異させる幅（探索範囲に対する割合）
+const META_GA_MUTATION_WIDTH: f64 = 0.1;
+
+/// 探索点を選ぶ乱数を導出するときの系列番号（反復の番号と重ならない値）
+const SAMPLING_STREAM: u64 = u64::MAX;
+
+/// 探索対象のパラメータ
+///
+/// 文字列表現はCLIのオプション名と同じ`population`、`mutation-rate`、
+/// `dna-length`、`lambda`です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TunedParameter {
+    /// 個体数
+    Population,
+    /// 突然変異率
+    MutationRate,
+    /// DNA長
+    DnaLength,
+    /// (μ+λ)・(μ,λ)置換で毎世代生成する子の数λ
+    ///
+    /// λ/μが大きいほど子の中から上位だけが残るため、選択圧が高くなります。
+    /// 基準の設定の置換モデルが(μ+λ)か(μ,λ)の場合にのみ探索できます。
+    Lambda,
+}
+
+impl TunedParameter {
+    /// 整数値のパラメータかどうか
+    pub fn is_integer(&self) -> bool {
+        !matches!(self, TunedParameter::MutationRate)
+    }
+
+    /// 設定にパラメータの値を適用
+    ///
+    /// 整数値のパラメータは四捨五入します。λは設定の置換モデルの種類を保ったまま
+    /// 子の数だけを置き換えます。
+    pub fn apply(&self, config: &mut Config, value: f64) {
+        let integer = value.round().max(0.0) as usize;
+        match self {
+            TunedParameter::Population => config.population = integer,
+            TunedParameter::MutationRate => config.mutation_rate = value,
+            TunedParameter::DnaLength => config.dna_length = integer,
+            TunedParameter::Lambda => {
+                config.replacement_model = match config.replacement_model {
+                    ReplacementModel::MuPlusLambda { .. } => {
+                        ReplacementModel::MuPlusLambda { lambda: integer }
+                    }
+                    ReplacementModel::MuCommaLambda { .. } => {
+                        ReplacementModel::MuCommaLambda { lambda: integer }
+                    }
+                    model => model,
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for TunedParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TunedParameter::Population => write!(f, "population"),
+            TunedParameter::MutationRate => write!(f, "mutation-rate"),
+            TunedParameter::DnaLength => write!(f, "dna-length"),
+            TunedParameter::Lambda => write!(f, "lambda"),
+        }
+    }
+}
+
+impl FromStr for TunedParameter {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "population" => Ok(TunedParameter::Population),
+            "mutation-rate" => Ok(TunedParameter::MutationRate),
+            "dna-length" => Ok(TunedParameter::DnaLength),
+            "lambda" => Ok(TunedParameter::Lambda),
+            _ => Err(GAError::ValidationError(format!(
+                "Invalid tuned parameter: {s}"
+            ))),
+        }
+    }
+}
+
+/// パラメータの探索範囲
+///
+/// 文字列表現は`NAME=MIN..MAX`です（例: `mutation-rate=0.001..0.1`）。
+///
+/// # フィールド
+/// * `parameter` - 探索するパラメータ
+/// * `min` - 最小値
+/// * `max` - 最大値
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParameterRange {
+    pub parameter: TunedParameter,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ParameterRange {
+    /// 新しい探索範囲を作成
+    ///
+    /// # エラー
+    /// 範囲が有限でない、負の値を含む、または最小値が最大値を超える場合
+    pub fn new(parameter: TunedParameter, min: f64, max: f64) -> GAResult<Self> {
+        if !(min.is_finite() && max.is_finite() && min >= 0.0 && min <= max) {
+            return Err(GAError::ValidationError(format!(
+                "Invalid range for {parameter}: {min}..{max}"
+            )));
+        }
+        Ok(Self {
+            parameter,
+            min,
+            max,
+        })
+    }
+
+    /// 範囲内の相対位置`t`（0.0-1.0）に対応する値
+    ///
+    /// 整数値のパラメータは四捨五入した値を返します。
+    pub fn value_at(&self, t: f64) -> f64 {
+        let value = self.min + (self.max - self.min) * t.clamp(0.0, 1.0);
+        if self.parameter.is_integer() {
+            value.round()
+        } else {
+            value
+        }
+    }
+}
+
+impl fmt::Display for ParameterRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}..{}", self.parameter, self.min, self.max)
+    }
+}
+
+impl FromStr for ParameterRange {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid parameter range: {s}"));
+        let (name, range) = s.split_once('=').ok_or_else(invalid)?;
+        let (min, max) = range.split_once("..").ok_or_else(invalid)?;
+
+        ParameterRange::new(
+            name.parse()?,
+            min.parse().map_err(|_| invalid())?,
+            max.parse().map_err(|_| invalid())?,
+        )
+    }
+}
+
+/// 探索方式
+///
+/// 文字列表現は`grid:STEPS`、`random:SAMPLES`、`lhs:SAMPLES`、
+/// `meta-ga:POPULATION:GENERATIONS`です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TuningMethod {
+    /// 各パラメータの範囲を`steps`等分した格子の全点
+    Grid { steps: usize },
+    /// 一様乱数で選んだ`samples`点
+    Random { samples: usize },
+    /// ラテン超方格法で選んだ`samples`点
+    ///
+    /// 各パラメータの範囲を`samples`個の区間に分け、どの区間からもちょうど1点を選びます。
+    LatinHypercube { samples: usize },
+    /// パラメータの組を個体とする遺伝的アルゴリズム
+    ///
+    /// 毎世代、上位半分を残し、残りを一様交叉と突然変異で作った子で置き換えます。
+    MetaGa {
+        population: usize,
+        generations: usize,
+    },
+}
+
+impl TuningMethod {
+    /// パラメータの妥当性を検証
+    ///
+    /// # エラー
+    /// 点数・世代数が0、またはメタGAの個体数が2未満の場合
+    pub fn validate(&self) -> GAResult<()> {
+        let valid = match *self {
+            TuningMethod::Grid { steps } => steps > 0,
+            TuningMethod::Random { samples } | TuningMethod::LatinHypercube { samples } => {
+                samples > 0
+            }
+            TuningMethod::MetaGa {
+                population,
+                generations,
+            } => population >= 2 && generations > 0,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(GAError::ValidationError(format!(
+                "Invalid tuning method: {self}"
+            )))
+        }
+    }
+}
+
+impl fmt::Display for TuningMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuningMethod::Grid { steps } => write!(f, "grid:{steps}"),
+            TuningMethod::Random { samples } => write!(f, "random:{samples}"),
+            TuningMethod::LatinHypercube { samples } => write!(f, "lhs:{samples}"),
+            TuningMethod::MetaGa {
+                population,
+                generations,
+            } => write!(f, "meta-ga:{population}:{generations}"),
+        }
+    }
+}
+
+impl FromStr for TuningMethod {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid tuning method: {s}"));
+        let mut parts = s.split(':');
+        let name = parts.next().unwrap_or_default();
+        let numbers = parts
+            .map(|p| p.parse::<usize>().map_err(|_| invalid()))
+            .collect::<GAResult<Vec<usize>>>()?;
+
+        let method = match (name, numbers.as_slice()) {
+            ("grid", &[steps]) => TuningMethod::Grid { steps },
+            ("random", &[samples]) => TuningMethod::Random { samples },
+            ("lhs", &[samples]) => TuningMethod::LatinHypercube { samples },
+            ("meta-ga", &[population, generations]) => TuningMethod::MetaGa {
+                population,
+                generations,
+            },
+            _ => return Err(invalid()),
+        };
+
+        method.validate()?;
+        Ok(method)
+    }
+}
+
+/// 探索の目的関数
+///
+/// 文字列表現は`payoff`、`cooperation[:THRESHOLD]`、`diversity`です。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TuningObjective {
+    /// 最終世代の評価時の1回の対戦あたりの平均利得（最大化）
+    ///
+    /// 累積ポイントは個体数やラウンド数に比例して増えるため、対戦相手の数と
+    /// `rounds_per_generation`で割り、個体数の異なる設定同士を比較できるようにします。
+    #[default]
+    AveragePayoff,
+    /// 平均協力確率が`threshold`に初めて達した世代（最小化）
+    ///
+    /// 到達しなかった場合は実行した世代数とします。
+    TimeToCooperation { threshold: f64 },
+    /// 最終世代の多様性（最大化）
+    Diversity,
+}
+
+impl TuningObjective {
+    /// 最大化する目的かどうか
+    pub fn is_maximized(&self) -> bool {
+        !matches!(self, TuningObjective::TimeToCooperation { .. })
+    }
+
+    /// シミュレーション結果から目的関数の値を計算
+    pub fn measure(&self, result: &SimulationResult) -> f64 {
+        match *self {
+            TuningObjective::AveragePayoff => {
+                let config = &result.config;
+                let hall_of_fame = if config.hall_of_fame_opponents {
+                    result.hall_of_fame.len()
+                } else {
+                    0
+                };
+                let opponents = local_opponents(config, config.population) + hall_of_fame;
+                let points = result.payoff_history.last().copied().unwrap_or(0.0);
+                points / (opponents * config.rounds_per_generation).max(1) as f64
+            }
+            TuningObjective::TimeToCooperation { threshold } => result
+                .cooperation_history
+                .iter()
+                .position(|&cooperation| cooperation >= threshold)
+                .unwrap_or(result.cooperation_history.len())
+                as f64,
+            TuningObjective::Diversity => result.diversity_history.last().copied().unwrap_or(0.0),
+        }
+    }
+
+    /// 大きいほど良くなるように向きを揃えた値
+    fn score(&self, value: f64) -> f64 {
+        if self.is_maximized() {
+            value
+        } else {
+            -value
+        }
+    }
+}
+
+impl fmt::Display for TuningObjective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuningObjective::AveragePayoff => write!(f, "payoff"),
+            TuningObjective::TimeToCooperation { threshold } => {
+                write!(f, "cooperation:{threshold}")
+            }
+            TuningObjective::Diversity => write!(f, "diversity"),
+        }
+    }
+}
+
+impl FromStr for TuningObjective {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid tuning objective: {s}"));
+        match s.split_once(':') {
+            None => match s {
+                "payoff" => Ok(TuningObjective::AveragePayoff),
+                "cooperation" => Ok(TuningObjective::TimeToCooperation {
+                    threshold: DEFAULT_COOPERATION_THRESHOLD,
+                }),
+                "diversity" => Ok(TuningObjective::Diversity),
+                _ => Err(invalid()),
+            },
+            Some(("cooperation", threshold)) => {
+                let threshold: f64 = threshold.parse().map_err(|_| invalid())?;
+                if !(0.0..=1.0).contains(&threshold) {
+                    return Err(invalid());
+                }
+                Ok(TuningObjective::TimeToCooperation { threshold })
+            }
+            Some(_) => Err(invalid()),
+        }
+    }
+}
+
+/// 1点（パラメータの値の組）の評価結果
+///
+/// # フィールド
+/// * `values` - 探索空間の順に並んだパラメータの値
+/// * `config` - 値を適用した設定
+/// * `scores` - 反復ごとの目的関数の値
+/// * `mean` - 目的関数の反復平均
+/// * `std_dev` - 目的関数の反復間の標準偏差
+#[derive(Debug, Clone)]
+pub struct TuningTrial {
+    pub values: Vec<f64>,
+    pub config: Config,
+    pub scores: Vec<f64>,
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+/// 探索結果
+///
+/// # フィールド
+/// * `space` - 探索空間
+/// * `objective` - 目的関数
+/// * `trials` - 評価した点（良い順）
+/// * `skipped` - 設定が不正（例: エリートサイズが個体数以上）で評価しなかった点の数
+#[derive(Debug, Clone)]
+pub struct TuningReport {
+    pub space: Vec<ParameterRange>,
+    pub objective: TuningObjective,
+    pub trials: Vec<TuningTrial>,
+    pub skipped: usize,
+}
+
+impl TuningReport {
+    /// 最も良かった点
+    pub fn best(&self) -> Option<&TuningTrial> {
+        self.trials.first()
+    }
+
+    /// 順位表を出力
+    pub fn print_table(&self) {
+        let direction = if self.objective.is_maximized() {
+            "higher is better"
+        } else {
+            "lower is better"
+        };
+        println!(
+            "\nTuning results ({} points, objective: {}, {direction})",
+            self.trials.len(),
+            self.objective
+        );
+        if self.skipped > 0 {
+            println!("Skipped {} invalid points", self.skipped);
+        }
+
+        let mut header = format!("{:>4}", "Rank");
+        for range in &self.space {
+            header += &format!("  {:>13}", range.parameter.to_string());
+        }
+        header += &format!("  {:>10}  {:>8}", "mean", "std dev");
+        println!("{header}");
+        println!("{}", "-".repeat(header.len()));
+
+        for (rank, trial) in self.trials.iter().enumerate() {
+            let mut row = format!("{:>4}", rank + 1);
+            for (range, value) in self.space.iter().zip(&trial.values) {
+                if range.parameter.is_integer() {
+                    row += &format!("  {value:>13.0}");
+                } else {
+                    row += &format!("  {value:>13.4}");
+                }
+            }
+            row += &format!("  {:>10.2}  {:>8.2}", trial.mean, trial.std_dev);
+            println!("{row}");
+        }
+    }
+}
+
+/// ハイパーパラメータ探索の管理構造体
+///
+/// # フィールド
+/// * `base` - 探索対象以外のパラメータを決める基準の設定
+/// * `space` - 探索空間（パラメータごとの範囲）
+/// * `method` - 探索方式
+/// * `objective` - 目的関数
+/// * `replicates` - 1点あたりのシミュレーションの反復回数
+pub struct Tuner {
+    base: Config,
+    space: Vec<ParameterRange>,
+    method: TuningMethod,
+    objective: TuningObjective,
+    replicates: usize,
+}
+
+impl Tuner {
+    /// 新しい探索を作成
+    ///
+    /// # 引数
+    /// * `base` - 基準の設定
+    /// * `space` - 探索空間
+    /// * `method` - 探索方式
+    ///
+    /// # エラー
+    /// 探索空間が空、同じパラメータを重複して含む、探索方式が不正な場合、
+    /// または基準の設定の置換モデルが(μ+λ)・(μ,λ)でないのにλを探索する場合
+    pub fn new(base: Config, space: Vec<ParameterRange>, method: TuningMethod) -> GAResult<Self> {
+        if space.is_empty() {
+            return Err(GAError::ValidationError(
+                "At least one tuned parameter is required".to_string(),
+            ));
+        }
+        for (i, range) in space.iter().enumerate() {
+            if space[..i].iter().any(|r| r.parameter == range.parameter) {
+                return Err(GAError::ValidationError(format!(
+                    "Duplicate tuned parameter: {}",
+                    range.parameter
+                )));
+            }
+        }
+        method.validate()?;
+        if space.iter().any(|r| r.parameter == TunedParameter::Lambda)
+            && !matches!(
+                base.replacement_model,
+                ReplacementModel::MuPlusLambda { .. } | ReplacementModel::MuCommaLambda { .. }
+            )
+        {
+            return Err(GAError::ValidationError(format!(
+                "Tuning lambda requires a plus or comma replacement model, got {}",
+                base.replacement_model
+            )));
+        }
+
+        Ok(Self {
+            base,
+            space,
+            method,
+            objective: TuningObjective::default(),
+            replicates: DEFAULT_REPLICATES,
+        })
+    }
+
+    pub fn with_objective(mut self, objective: TuningObjective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    pub fn with_replicates(mut self, replicates: usize) -> Self {
+        self.replicates = replicates.max(1);
+        self
+    }
+
+    /// 探索を実行
+    ///
+    /// 基準の設定にシードがある場合は、探索点の選択も含めて同じ結果を再現します。
+    ///
+    /// # エラー
+    /// 評価できる点が1つもなかった場合、またはシミュレーションの実行に失敗した場合
+    pub fn run(&self) -> GAResult<TuningReport> {
+        let mut trials = Vec::new();
+        let mut skipped = 0;
+        let seed = self.base.seed.unwrap_or_else(|| thread_rng().gen());
+        let mut rng = stream_rng(seed, &[SAMPLING_STREAM]);
+
+        match self.method {
+            TuningMethod::MetaGa {
+                population,
+                generations,
+            } => self.run_meta_ga(population, generations, &mut rng, &mut trials, &mut skipped)?,
+            _ => {
+                for point in self.sample_points(&mut rng) {
+                    match self.evaluate(&point)? {
+                        Some(trial) => trials.push(trial),
+                        None => skipped += 1,
+                    }
+                }
+            }
+        }
+
+        if trials.is_empty() {
+            return Err(GAError::ValidationError(
+                "No valid configuration in the search space".to_string(),
+            ));
+        }
+        trials.sort_by(|a, b| {
+            self.objective
+                .score(b.mean)
+                .total_cmp(&self.objective.score(a.mean))
+        });
+
+        Ok(TuningReport {
+            space: self.space.clone(),
+            objective: self.objective,
+            trials,
+            skipped,
+        })
+    }
+
+    /// グリッド・ランダム・ラテン超方格法の探索点（各座標は範囲内の相対位置0.0-1.0）
+    fn sample_points<R: Rng>(&self, rng: &mut R) -> Vec<Vec<f64>> {
+        let dimensions = self.space.len();
+
+        match self.method {
+            TuningMethod::Grid { steps } => {
+                let axis: Vec<f64> = if steps == 1 {
+                    vec![0.5]
+                } else {
+                    (0..steps).map(|i| i as f64 / (steps - 1) as f64).collect()
+                };
+                (0..dimensions).fold(vec![Vec::new()], |points, _| {
+                    points
+                        .iter()
+                        .flat_map(|point| {
+                            axis.iter().map(move |&t| {
+                                let mut point = point.clone();
+                                point.push(t);
+                                point
+                            })
+                        })
+                        .collect()
+                })
+            }
+            TuningMethod::Random { samples } => (0..samples)
+                .map(|_| (0..dimensions).map(|_| rng.gen()).collect())
+                .collect(),
+            TuningMethod::LatinHypercube { samples } => {
+                let strata: Vec<Vec<usize>> = (0..dimensions)
+                    .map(|_| {
+                        let mut stratum: Vec<usize> = (0..samples).collect();
+                        stratum.shuffle(rng);
+                        stratum
+                    })
+                    .collect();
+                (0..samples)
+                    .map(|i| {
+                        strata
+                            .iter()
+                            .map(|stratum| (stratum[i] as f64 + rng.gen::<f64>()) / samples as f64)
+                            .collect()
+                    })
+                    .collect()
+            }
+            TuningMethod::MetaGa { .. } => Vec::new(),
+        }
+    }
+
+    /// メタGAによる探索
+    ///
+    /// 評価した全ての点を`trials`に追加します。不正な設定になる点は最下位として扱います。
+    fn run_meta_ga<R: Rng>(
+        &self,
+        population: usize,
+        generations: usize,
+        rng: &mut R,
+        trials: &mut Vec<TuningTrial>,
+        skipped: &mut usize,
+    ) -> GAResult<()> {
+        let dimensions = self.space.len();
+        let mut points: Vec<Vec<f64>> = (0..population)
+            .map(|_| (0..dimensions).map(|_| rng.gen()).collect())
+            .collect();
+        let mut survivors: Vec<(Vec<f64>, f64)> = Vec::new();
+
+        for _ in 0..generations {
+            let mut evaluated = std::mem::take(&mut survivors);
+            for point in points {
+                let score = match self.evaluate(&point)? {
+                    Some(trial) => {
+                        let score = self.objective.score(trial.mean);
+                        trials.push(trial);
+                        score
+                    }
+                    None => {
+                        *skipped += 1;
+                        f64::NEG_INFINITY
+                    }
+                };
+                evaluated.push((point, score));
+            }
+
+            evaluated.sort_by(|a, b| b.1.total_cmp(&a.1));
+            evaluated.truncate(population.div_ceil(2));
+            points = (evaluated.len()..population)
+                .map(|_| {
+                    let a = &evaluated[rng.gen_range(0..evaluated.len())].0;
+                    let b = &evaluated[rng.gen_range(0..evaluated.len())].0;
+                    a.iter()
+                        .zip(b)
+                        .map(|(&x, &y)| {
+                            let gene = if rng.gen_bool(0.5) { x } else { y };
+                            if rng.gen_bool(1.0 / dimensions as f64) {
+                                let delta =
+                                    rng.gen_range(-META_GA_MUTATION_WIDTH..=META_GA_MUTATION_WIDTH);
+                                (gene + delta).clamp(0.0, 1.0)
+                            } else {
+                                gene
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+            survivors = evaluated;
+        }
+
+        Ok(())
+    }
+
+    /// 1点を`replicates`回のシミュレーションで評価
+    ///
+    /// # 戻り値
+    /// 評価結果。値を適用した設定が不正な場合は`None`
+    fn evaluate(&self, point: &[f64]) -> GAResult<Option<TuningTrial>> {
+        let mut config = self.base.clone();
+        let values: Vec<f64> = self
+            .space
+            .iter()
+            .zip(point)
+            .map(|(range, &t)| {
+                let value = range.value_at(t);
+                range.parameter.apply(&mut config, value);
+                value
+            })
+            .collect();
+        if config.validate().is_err() {
+            return Ok(None);
+        }
+
+        let scores = (0..self.replicates)
+            .map(|replicate| {
+                // シード付きの設定では反復ごとに異なるシードを導出する
+                let mut config = config.clone();
+                config.seed = config
+                    .seed
+                    .map(|seed| derive_seed(seed, &[replicate as u64]));
+                let result = Simulation::new(config)?.quiet().run()?;
+                Ok(self.objective.measure(&result))
+            })
+            .collect::<GAResult<Vec<f64>>>()?;
+        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        let variance =
+            scores.iter().map(|s| (s - mean) * (s - mean)).sum::<f64>() / scores.len() as f64;
+
+        Ok(Some(TuningTrial {
+            values,
+            config,
+            scores,
+            mean,
+            std_dev: variance.sqrt(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::config::ConfigBuilder;
+
+    fn base() -> Config {
+        ConfigBuilder::new()
+            .generations(5)
+            .population(6)
+            .report_interval(100)
+            .build()
+            .unwrap()
+    }
+
+    fn range(s: &str) -> ParameterRange {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_grid_search_ranks_all_points() {
+        let space = vec![range("population=4..8"), range("mutation-rate=0.0..0.1")];
+        let report = Tuner::new(base(), space, TuningMethod::Grid { steps: 2 })
+            .unwrap()
+            .with_replicates(2)
+            .run()
+            .unwrap();
+
+        assert_eq!(report.trials.len(), 4);
+        assert!(report.trials.iter().all(|t| t.scores.len() == 2));
+        assert!(report.trials.windows(2).all(|w| w[0].mean >= w[1].mean));
+        let best = report.best().unwrap();
+        assert_eq!(best.config.population as f64, best.values[0]);
+    }
+
+    #[test]
+    fn test_invalid_points_are_skipped() {
+        // 個体数が0になる点は評価しない
+        let report = Tuner::new(
+            base(),
+            vec![range("population=0..8")],
+            TuningMethod::Grid { steps: 3 },
+        )
+        .unwrap()
+        .with_replicates(1)
+        .run()
+        .unwrap();
+        assert_eq!(report.trials.len(), 2);
+        assert_eq!(report.skipped, 1);
+    }
+
+    #[test]
+    fn test_lambda_keeps_the_replacement_model() {
+        let space = || vec![range("lambda=2..12")];
+        assert!(Tuner::new(base(), space(), TuningMethod::Grid { steps: 2 }).is_err());
+
+        let mut config = base();
+        config.replacement_model = ReplacementModel::MuCommaLambda { lambda: 6 };
+        let report = Tuner::new(config, space(), TuningMethod::Grid { steps: 2 })
+            .unwrap()
+            .with_replicates(1)
+            .run()
+            .unwrap();
+        // λ=2は(μ,λ)ではμ未満になるため評価しない
+        assert_eq!(report.trials.len(), 1);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(
+            report.trials[0].config.replacement_model,
+            ReplacementModel::MuCommaLambda { lambda: 12 }
+        );
+    }
+
+    #[test]
+    fn test_latin_hypercube_covers_every_stratum() {
+        let space = vec![range("mutation-rate=0.0..1.0"), range("dna-length=1..10")];
+        let tuner = Tuner::new(base(), space, TuningMethod::LatinHypercube { samples: 5 }).unwrap();
+        let points = tuner.sample_points(&mut thread_rng());
+
+        assert_eq!(points.len(), 5);
+        for dimension in 0..2 {
+            let mut strata: Vec<usize> = points
+                .iter()
+                .map(|p| (p[dimension] * 5.0) as usize)
+                .collect();
+            strata.sort_unstable();
+            assert_eq!(strata, vec![0, 1, 2, 3, 4]);
+        }
+    }
+
+    #[test]
+    fn test_meta_ga_and_cooperation_objective() {
+        let objective = TuningObjective::TimeToCooperation { threshold: 0.6 };
+        let report = Tuner::new(
+            base(),
+            vec![range("mutation-rate=0.0..0.2")],
+            TuningMethod::MetaGa {
+                population: 4,
+                generations: 3,
+            },
+        )
+        .unwrap()
+        .with_objective(objective)
+        .with_replicates(1)
+        .run()
+        .unwrap();
+
+        // 初代の4点と、以降の世代で2点ずつ
+        assert_eq!(report.trials.len(), 8);
+        assert!(report.trials.windows(2).all(|w| w[0].mean <= w[1].mean));
+        assert!(report.trials.iter().all(|t| t.mean <= 5.0));
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_search() {
+        let mut config = base();
+        config.seed = Some(9);
+        let methods = [
+            TuningMethod::Random { samples: 3 },
+            TuningMethod::MetaGa {
+                population: 4,
+                generations: 2,
+            },
+        ];
+        for method in methods {
+            let run = || {
+                Tuner::new(
+                    config.clone(),
+                    vec![range("mutation-rate=0.0..0.2")],
+                    method,
+                )
+                .unwrap()
+                .with_replicates(1)
+                .run()
+                .unwrap()
+            };
+            let (first, second) = (run(), run());
+            let values = |report: &TuningReport| -> Vec<(Vec<f64>, f64)> {
+                report
+                    .trials
+                    .iter()
+                    .map(|t| (t.values.clone(), t.mean))
+                    .collect()
+            };
+            assert_eq!(values(&first), values(&second));
+        }
+    }
+
+    #[test]
+    fn test_payoff_objective_is_per_match() {
+        // 個体数が違っても1回の対戦あたりの利得は利得表の範囲に収まる
+        let temptation = crate::core::types::PayoffMatrix::default().temptation as f64;
+        for population in [4, 16] {
+            let mut config = base();
+            config.population = population;
+            config.seed = Some(7);
+            let result = Simulation::new(config.clone())
+                .unwrap()
+                .quiet()
+                .run()
+                .unwrap();
+            let payoff = TuningObjective::AveragePayoff.measure(&result);
+            let opponents = (population - 1) * config.rounds_per_generation;
+            assert!((0.0..=temptation).contains(&payoff));
+            let points = result.payoff_history.last().unwrap();
+            assert!((payoff * opponents as f64 - points).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            range("lambda=2..12"),
+            ParameterRange::new(TunedParameter::Lambda, 2.0, 12.0).unwrap()
+        );
+        assert!("population=10..5".parse::<ParameterRange>().is_err());
+        assert!("speed=1..2".parse::<ParameterRange>().is_err());
+        assert!("elite-size=0..2".parse::<ParameterRange>().is_err());
+
+        for text in ["grid:3", "random:10", "lhs:8", "meta-ga:6:4"] {
+            assert_eq!(text.parse::<TuningMethod>().unwrap().to_string(), text);
+        }
+        assert!("meta-ga:1:4".parse::<TuningMethod>().is_err());
+        assert!("grid".parse::<TuningMethod>().is_err());
+
+        assert_eq!(
+            "cooperation".parse::<TuningObjective>().unwrap(),
+            TuningObjective::TimeToCooperation {
+                threshold: DEFAULT_COOPERATION_THRESHOLD
+            }
+        );
+        assert!("cooperation:2".parse::<TuningObjective>().is_err());
+    }
+}
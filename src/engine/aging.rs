@@ -0,0 +1,332 @@
+/// 齢構造（年齢と寿命）
+///
+/// 世代ごとに個体群全体を置き換える代わりに、個体が年齢を持って複数の世代を
+/// 生き延びるモデルです。各個体は生涯の獲得ポイントを累積し、成熟齢に達した
+/// 個体だけが繁殖します。死亡した個体の枠は新しく生まれた子が埋めるため、
+/// 個体群サイズは一定です。
+///
+/// 死亡は最大寿命に達したときに起こるか、年齢とともに高くなる確率
+/// （ゴンペルツ型の死亡率）で起こります。
+use crate::core::errors::*;
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+/// 成熟齢の既定値（1世代を生き延びた個体から繁殖できる）
+pub const DEFAULT_MATURITY: u32 = 1;
+
+/// 寿命の中央値を求めるときに調べる最大の年齢
+const MAX_LIFESPAN_SEARCH: u32 = 100_000;
+
+/// 年齢に依存する死亡の仕方
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mortality {
+    /// 最大寿命（世代数）に達した個体が死亡する
+    Lifespan(u32),
+    /// 年齢`a`の個体が`base * exp(growth * a)`（最大1）の確率で死亡する
+    Gompertz { base: f64, growth: f64 },
+}
+
+/// 齢構造の設定
+///
+/// 文字列表現は`lifespan:MAX[:MATURITY]`または`gompertz:BASE:GROWTH[:MATURITY]`です
+/// （例: `lifespan:8:2`、`gompertz:0.05:0.3`）。
+///
+/// 個体の年齢は生き延びた世代数で、生まれた世代の対戦を終えた時点で1になります。
+/// 繁殖は死亡より先に行うため、最大寿命1・成熟齢1では世代交代型と同じになります。
+///
+/// # フィールド
+/// * `mortality` - 年齢に依存する死亡の仕方
+/// * `maturity` - 繁殖できるようになる年齢
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AgeStructure {
+    pub mortality: Mortality,
+    pub maturity: u32,
+}
+
+impl AgeStructure {
+    pub fn new(mortality: Mortality) -> Self {
+        Self {
+            mortality,
+            maturity: DEFAULT_MATURITY,
+        }
+    }
+
+    pub fn with_maturity(mut self, maturity: u32) -> Self {
+        self.maturity = maturity;
+        self
+    }
+
+    /// パラメータの妥当性を検証
+    ///
+    /// # エラー
+    /// 最大寿命が0か成熟齢より短い場合、または死亡率のパラメータが範囲外の場合
+    pub fn validate(&self) -> GAResult<()> {
+        match self.mortality {
+            Mortality::Lifespan(lifespan) => {
+                if lifespan == 0 || self.maturity > lifespan {
+                    return Err(GAError::ValidationError(format!(
+                        "Lifespan must be at least 1 and the maturity age ({}), got {lifespan}",
+                        self.maturity
+                    )));
+                }
+            }
+            Mortality::Gompertz { base, growth } => {
+                if !(base > 0.0 && base <= 1.0) {
+                    return Err(GAError::ValidationError(format!(
+                        "Gompertz base mortality must be in (0.0, 1.0], got {base}"
+                    )));
+                }
+                if !(growth.is_finite() && growth >= 0.0) {
+                    return Err(GAError::ValidationError(format!(
+                        "Gompertz mortality growth must be non-negative, got {growth}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 年齢`age`に達した個体が死亡する確率
+    pub fn death_probability(&self, age: u32) -> f64 {
+        match self.mortality {
+            Mortality::Lifespan(lifespan) => {
+                if age >= lifespan {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Mortality::Gompertz { base, growth } => (base * (growth * age as f64).exp()).min(1.0),
+        }
+    }
+
+    /// 年齢`age`に達した個体が死亡するかどうかを決める
+    pub fn dies<R: Rng>(&self, age: u32, rng: &mut R) -> bool {
+        let probability = self.death_probability(age);
+        probability >= 1.0 || (probability > 0.0 && rng.gen_bool(probability))
+    }
+
+    /// 年齢`age`の個体が繁殖できるかどうか
+    pub fn is_mature(&self, age: u32) -> bool {
+        age >= self.maturity
+    }
+
+    /// 生まれた個体の半数以上が死亡している最初の年齢（寿命の中央値）
+    ///
+    /// 最大寿命による死亡では最大寿命そのものです。
+    pub fn median_lifespan(&self) -> u32 {
+        let mut survival = 1.0;
+        for age in 1..MAX_LIFESPAN_SEARCH {
+            survival *= 1.0 - self.death_probability(age);
+            if survival <= 0.5 {
+                return age;
+            }
+        }
+        MAX_LIFESPAN_SEARCH
+    }
+
+    /// 初期個体群の個体の年齢
+    ///
+    /// 全個体が同時に生まれて同時に死亡しないよう、0から寿命の中央値未満までの
+    /// 一様な年齢を割り当てます。
+    pub fn initial_age<R: Rng>(&self, rng: &mut R) -> u32 {
+        rng.gen_range(0..self.median_lifespan().max(1))
+    }
+}
+
+impl fmt::Display for AgeStructure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mortality {
+            Mortality::Lifespan(lifespan) => write!(f, "lifespan:{lifespan}:{}", self.maturity),
+            Mortality::Gompertz { base, growth } => {
+                write!(f, "gompertz:{base}:{growth}:{}", self.maturity)
+            }
+        }
+    }
+}
+
+impl FromStr for AgeStructure {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid age structure: {s}"));
+        let parts: Vec<&str> = s.split(':').collect();
+
+        let (mortality, rest) = match parts.as_slice() {
+            ["lifespan", lifespan, rest @ ..] => (
+                Mortality::Lifespan(lifespan.parse().map_err(|_| invalid())?),
+                rest,
+            ),
+            ["gompertz", base, growth, rest @ ..] => (
+                Mortality::Gompertz {
+                    base: base.parse().map_err(|_| invalid())?,
+                    growth: growth.parse().map_err(|_| invalid())?,
+                },
+                rest,
+            ),
+            _ => return Err(invalid()),
+        };
+        let structure = match rest {
+            [] => AgeStructure::new(mortality),
+            [maturity] => {
+                AgeStructure::new(mortality).with_maturity(maturity.parse().map_err(|_| invalid())?)
+            }
+            _ => return Err(invalid()),
+        };
+
+        structure.validate()?;
+        Ok(structure)
+    }
+}
+
+/// 実行中に死亡した個体の寿命の記録
+///
+/// # フィールド
+/// * `deaths` - 死亡した個体の数
+/// * `total_lifespan` - 死亡した個体の年齢の合計
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MortalityRecord {
+    pub deaths: u64,
+    pub total_lifespan: u64,
+}
+
+impl MortalityRecord {
+    /// 年齢`age`で死亡した個体を記録
+    pub fn record(&mut self, age: u32) {
+        self.deaths += 1;
+        self.total_lifespan += age as u64;
+    }
+
+    /// 死亡した個体の平均寿命（まだ死亡した個体がいなければ0）
+    pub fn mean_lifespan(&self) -> f64 {
+        if self.deaths == 0 {
+            0.0
+        } else {
+            self.total_lifespan as f64 / self.deaths as f64
+        }
+    }
+}
+
+/// 個体群の年齢の統計
+///
+/// # フィールド
+/// * `distribution` - 年齢ごとの個体数（添字が年齢）
+/// * `mean_age` - 平均年齢
+/// * `max_age` - 最高齢
+/// * `mature` - 成熟齢に達している個体の数
+/// * `deaths` - 実行開始からの死亡数
+/// * `mean_lifespan` - 実行開始から死亡した個体の平均寿命
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgeStats {
+    pub distribution: Vec<usize>,
+    pub mean_age: f64,
+    pub max_age: u32,
+    pub mature: usize,
+    pub deaths: u64,
+    pub mean_lifespan: f64,
+}
+
+impl AgeStats {
+    /// 個体群の年齢と死亡の記録から統計を計算（空の個体群では年齢の統計は0）
+    pub fn measure(ages: &[u32], structure: &AgeStructure, record: &MortalityRecord) -> Self {
+        let max_age = ages.iter().copied().max().unwrap_or(0);
+        let mut distribution = vec![0; max_age as usize + 1];
+        for &age in ages {
+            distribution[age as usize] += 1;
+        }
+
+        Self {
+            distribution: if ages.is_empty() {
+                Vec::new()
+            } else {
+                distribution
+            },
+            mean_age: ages.iter().map(|&age| age as f64).sum::<f64>() / ages.len().max(1) as f64,
+            max_age,
+            mature: ages.iter().filter(|&&age| structure.is_mature(age)).count(),
+            deaths: record.deaths,
+            mean_lifespan: record.mean_lifespan(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        let lifespan: AgeStructure = "lifespan:8:2".parse().unwrap();
+        assert_eq!(
+            lifespan,
+            AgeStructure::new(Mortality::Lifespan(8)).with_maturity(2)
+        );
+        assert_eq!(lifespan.to_string(), "lifespan:8:2");
+
+        let gompertz: AgeStructure = "gompertz:0.05:0.3".parse().unwrap();
+        assert_eq!(gompertz.maturity, DEFAULT_MATURITY);
+        assert_eq!(
+            gompertz.to_string().parse::<AgeStructure>().unwrap(),
+            gompertz
+        );
+
+        for invalid in [
+            "lifespan",
+            "lifespan:0",
+            "lifespan:3:4",
+            "gompertz:0:0.1",
+            "gompertz:0.1:-1",
+            "gompertz:0.1",
+            "lifespan:3:1:1",
+            "logistic:3",
+        ] {
+            assert!(invalid.parse::<AgeStructure>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_lifespan_mortality() {
+        let structure = AgeStructure::new(Mortality::Lifespan(3)).with_maturity(2);
+        let mut rng = rand::thread_rng();
+        assert!(!structure.dies(2, &mut rng));
+        assert!(structure.dies(3, &mut rng));
+        assert!(!structure.is_mature(1));
+        assert!(structure.is_mature(2));
+        assert_eq!(structure.median_lifespan(), 3);
+        assert!((0..100).all(|_| structure.initial_age(&mut rng) < 3));
+    }
+
+    #[test]
+    fn test_gompertz_mortality_increases_with_age() {
+        let structure = AgeStructure::new(Mortality::Gompertz {
+            base: 0.1,
+            growth: 0.5,
+        });
+        assert!((structure.death_probability(0) - 0.1).abs() < 1e-12);
+        assert!(structure.death_probability(2) > structure.death_probability(1));
+        assert_eq!(structure.death_probability(100), 1.0);
+        assert!(structure.median_lifespan() >= 1);
+    }
+
+    #[test]
+    fn test_age_stats() {
+        let structure = AgeStructure::new(Mortality::Lifespan(5)).with_maturity(2);
+        let mut record = MortalityRecord::default();
+        record.record(5);
+        record.record(2);
+
+        let stats = AgeStats::measure(&[0, 2, 2, 3], &structure, &record);
+        assert_eq!(stats.distribution, vec![1, 0, 2, 1]);
+        assert_eq!(stats.mean_age, 1.75);
+        assert_eq!(stats.max_age, 3);
+        assert_eq!(stats.mature, 3);
+        assert_eq!(stats.deaths, 2);
+        assert_eq!(stats.mean_lifespan, 3.5);
+
+        assert_eq!(
+            AgeStats::measure(&[], &structure, &MortalityRecord::default()),
+            AgeStats::default()
+        );
+    }
+}
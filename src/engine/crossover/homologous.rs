@@ -0,0 +1,111 @@
+use crate::core::{errors::*, traits::*, types::*};
+use rand::{thread_rng, Rng};
+
+/// Homologous crossover for parents of possibly different lengths
+///
+/// 両親を先頭で揃え、短い方の長さの範囲で同じ交叉点を選んで後半を交換します。
+/// 子1は親1の前半と親2の後半（親2の長さ）、子2は親2の前半と親1の後半
+/// （親1の長さ）になるため、親の長さの組はそのまま子に引き継がれます。
+/// 長さが等しい親では`SinglePointCrossover`と同じ結果になります。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HomologousCrossover;
+
+impl HomologousCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn crossover<T: Agent>(&self, parent1: &T, parent2: &T) -> GAResult<(T, T)> {
+        self.crossover_with_rng(parent1, parent2, &mut thread_rng())
+    }
+
+    /// 与えられた乱数生成器で交叉点を選んで交叉
+    pub fn crossover_with_rng<T: Agent, R: Rng>(
+        &self,
+        parent1: &T,
+        parent2: &T,
+        rng: &mut R,
+    ) -> GAResult<(T, T)> {
+        let len1 = parent1.dna_length();
+        let len2 = parent2.dna_length();
+
+        if len1 == 0 || len2 == 0 {
+            return Err(GAError::InvalidDnaLength(0));
+        }
+
+        // 長さが等しい場合に両方の子が親の複製になる交叉点は除く
+        let upper = len1.min(len2).min(len1.max(len2) - 1);
+        let point = if upper == 0 {
+            1
+        } else {
+            rng.gen_range(1..=upper)
+        };
+
+        self.crossover_at_point(parent1, parent2, point)
+    }
+
+    pub fn crossover_at_point<T: Agent>(
+        &self,
+        parent1: &T,
+        parent2: &T,
+        point: CrossoverPoint,
+    ) -> GAResult<(T, T)> {
+        let shorter = parent1.dna_length().min(parent2.dna_length());
+        if point == 0 || point > shorter {
+            return Err(GAError::InvalidCrossoverPoint(point));
+        }
+
+        Ok((
+            parent1.crossover(parent2, point),
+            parent2.crossover(parent1, point),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn agent(dna: &str) -> ModelAgent {
+        BaseModel::new(0, dna.to_string())
+    }
+
+    #[test]
+    fn test_unequal_parents_swap_tails() {
+        let crossover = HomologousCrossover::new();
+        let (child1, child2) = crossover
+            .crossover_at_point(&agent("1111"), &agent("000000"), 2)
+            .unwrap();
+        assert_eq!(child1.dna_binary(), "110000");
+        assert_eq!(child2.dna_binary(), "0011");
+
+        let (child1, child2) = crossover
+            .crossover_at_point(&agent("11"), &agent("0000"), 2)
+            .unwrap();
+        assert_eq!(child1.dna_binary(), "1100");
+        assert_eq!(child2.dna_binary(), "00");
+    }
+
+    #[test]
+    fn test_random_point_keeps_length_pair() {
+        let crossover = HomologousCrossover::new();
+        for _ in 0..20 {
+            let (child1, child2) = crossover.crossover(&agent("101"), &agent("01010")).unwrap();
+            let mut lengths = [child1.dna_length(), child2.dna_length()];
+            lengths.sort_unstable();
+            assert_eq!(lengths, [3, 5]);
+        }
+    }
+
+    #[test]
+    fn test_invalid_point() {
+        let crossover = HomologousCrossover::new();
+        assert!(crossover
+            .crossover_at_point(&agent("11"), &agent("0000"), 3)
+            .is_err());
+        assert!(crossover
+            .crossover_at_point(&agent("11"), &agent("0000"), 0)
+            .is_err());
+    }
+}
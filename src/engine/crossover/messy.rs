@@ -0,0 +1,157 @@
+use crate::core::{errors::*, traits::*};
+use rand::{thread_rng, Rng};
+use std::ops::RangeInclusive;
+
+/// 子の長さが範囲内に収まる切断点を探す最大試行回数
+const MAX_CUT_ATTEMPTS: usize = 16;
+
+/// Messy (cut-and-splice) crossover
+///
+/// 両親で独立に切断点を選び、親1の前半と親2の後半、親2の前半と親1の後半を
+/// それぞれ繋ぎ合わせます。子の長さは親と異なり得るため、遺伝子長そのものが
+/// 進化します。子が空にならないよう、前半は必ず1遺伝子以上を含みます。
+///
+/// # フィールド
+/// * `length_range` - 子のDNA長の許容範囲（`None`の場合は制限なし）
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessyCrossover {
+    length_range: Option<RangeInclusive<usize>>,
+}
+
+impl MessyCrossover {
+    pub fn new() -> Self {
+        Self { length_range: None }
+    }
+
+    /// 子のDNA長の許容範囲を設定
+    ///
+    /// 両方の子の長さが範囲に収まる切断点を無作為に探し、見つからない場合は
+    /// 親の複製（両親とも末尾で切断）を返します。
+    pub fn with_length_range(length_range: RangeInclusive<usize>) -> Self {
+        Self {
+            length_range: Some(length_range),
+        }
+    }
+
+    pub fn crossover<T: VariableLengthDna>(&self, parent1: &T, parent2: &T) -> GAResult<(T, T)> {
+        self.crossover_with_rng(parent1, parent2, &mut thread_rng())
+    }
+
+    /// 与えられた乱数生成器で切断点を選んで交叉
+    pub fn crossover_with_rng<T: VariableLengthDna, R: Rng>(
+        &self,
+        parent1: &T,
+        parent2: &T,
+        rng: &mut R,
+    ) -> GAResult<(T, T)> {
+        let len1 = parent1.dna_length();
+        let len2 = parent2.dna_length();
+
+        if len1 == 0 || len2 == 0 {
+            return Err(GAError::InvalidDnaLength(0));
+        }
+
+        let (mut cut1, mut cut2) = (len1, len2);
+        for _ in 0..MAX_CUT_ATTEMPTS {
+            let (a, b) = (rng.gen_range(1..=len1), rng.gen_range(1..=len2));
+            let within = |length: usize| {
+                self.length_range
+                    .as_ref()
+                    .map_or(true, |range| range.contains(&length))
+            };
+            if within(a + len2 - b) && within(b + len1 - a) {
+                (cut1, cut2) = (a, b);
+                break;
+            }
+        }
+
+        self.crossover_at_points(parent1, parent2, cut1, cut2)
+    }
+
+    /// 親1を`cut1`、親2を`cut2`で切断して繋ぎ合わせる
+    ///
+    /// # エラー
+    /// 切断点が0、または親のDNA長を超える場合
+    pub fn crossover_at_points<T: VariableLengthDna>(
+        &self,
+        parent1: &T,
+        parent2: &T,
+        cut1: usize,
+        cut2: usize,
+    ) -> GAResult<(T, T)> {
+        let dna1 = parent1.dna_binary();
+        let dna2 = parent2.dna_binary();
+
+        if cut1 == 0 || cut1 > dna1.len() {
+            return Err(GAError::InvalidCrossoverPoint(cut1));
+        }
+        if cut2 == 0 || cut2 > dna2.len() {
+            return Err(GAError::InvalidCrossoverPoint(cut2));
+        }
+
+        Ok((
+            parent1.with_dna(format!("{}{}", &dna1[..cut1], &dna2[cut2..])),
+            parent2.with_dna(format!("{}{}", &dna2[..cut2], &dna1[cut1..])),
+        ))
+    }
+}
+
+impl Default for MessyCrossover {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn agent(dna: &str) -> ModelAgent {
+        BaseModel::new(0, dna.to_string())
+    }
+
+    #[test]
+    fn test_cut_and_splice() {
+        let (child1, child2) = MessyCrossover::new()
+            .crossover_at_points(&agent("1111"), &agent("000000"), 1, 2)
+            .unwrap();
+        assert_eq!(child1.dna_binary(), "10000");
+        assert_eq!(child2.dna_binary(), "00111");
+    }
+
+    #[test]
+    fn test_lengths_vary_and_preserve_total() {
+        let crossover = MessyCrossover::new();
+        let lengths: Vec<usize> = (0..50)
+            .map(|_| {
+                let (child1, child2) = crossover.crossover(&agent("1111"), &agent("00")).unwrap();
+                assert_eq!(child1.dna_length() + child2.dna_length(), 6);
+                assert!(child1.dna_length() > 0 && child2.dna_length() > 0);
+                child1.dna_length()
+            })
+            .collect();
+        assert!(lengths.iter().any(|&len| len != lengths[0]));
+    }
+
+    #[test]
+    fn test_length_range() {
+        let crossover = MessyCrossover::with_length_range(3..=5);
+        for _ in 0..50 {
+            let (child1, child2) = crossover.crossover(&agent("1111"), &agent("000")).unwrap();
+            assert!((3..=5).contains(&child1.dna_length()));
+            assert!((3..=5).contains(&child2.dna_length()));
+        }
+    }
+
+    #[test]
+    fn test_invalid_points() {
+        let crossover = MessyCrossover::new();
+        assert!(crossover
+            .crossover_at_points(&agent("11"), &agent("00"), 0, 1)
+            .is_err());
+        assert!(crossover
+            .crossover_at_points(&agent("11"), &agent("00"), 1, 3)
+            .is_err());
+    }
+}
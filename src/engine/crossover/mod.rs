@@ -1,3 +1,89 @@
+/// 交叉オペレータ
+///
+/// 同じ長さの親に対する一点交叉に加えて、長さの異なる親を扱える
+/// 相同交叉と切断・接合型（messy）交叉を提供します。
+pub mod homologous;
+pub mod messy;
 pub mod single_point;
 
+pub use homologous::HomologousCrossover;
+pub use messy::MessyCrossover;
 pub use single_point::SinglePointCrossover;
+
+use crate::core::{errors::*, traits::*};
+use rand::{thread_rng, Rng};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// 長さが変化し得る遺伝子型に対する交叉方式
+///
+/// 文字列表現は`homologous`、`messy`です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrossoverScheme {
+    /// 先頭を揃えて同じ交叉点で後半を交換（親の長さの組を保つ）
+    #[default]
+    Homologous,
+    /// 両親で独立に切断点を選んで繋ぎ合わせる（子の長さが変化する）
+    Messy,
+}
+
+impl CrossoverScheme {
+    /// 2親から子を2つ生成
+    ///
+    /// # 引数
+    /// * `length_range` - 子のDNA長の許容範囲（messy交叉のみ使用）
+    pub fn crossover<T: VariableLengthDna>(
+        &self,
+        parent1: &T,
+        parent2: &T,
+        length_range: Option<RangeInclusive<usize>>,
+    ) -> GAResult<(T, T)> {
+        self.crossover_with_rng(parent1, parent2, length_range, &mut thread_rng())
+    }
+
+    /// 与えられた乱数生成器で2親から子を2つ生成
+    ///
+    /// # 引数
+    /// * `length_range` - 子のDNA長の許容範囲（messy交叉のみ使用）
+    /// * `rng` - 交叉点の選択に使う乱数生成器
+    pub fn crossover_with_rng<T: VariableLengthDna, R: Rng>(
+        &self,
+        parent1: &T,
+        parent2: &T,
+        length_range: Option<RangeInclusive<usize>>,
+        rng: &mut R,
+    ) -> GAResult<(T, T)> {
+        match self {
+            CrossoverScheme::Homologous => {
+                HomologousCrossover::new().crossover_with_rng(parent1, parent2, rng)
+            }
+            CrossoverScheme::Messy => length_range
+                .map_or_else(MessyCrossover::new, MessyCrossover::with_length_range)
+                .crossover_with_rng(parent1, parent2, rng),
+        }
+    }
+}
+
+impl fmt::Display for CrossoverScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrossoverScheme::Homologous => write!(f, "homologous"),
+            CrossoverScheme::Messy => write!(f, "messy"),
+        }
+    }
+}
+
+impl FromStr for CrossoverScheme {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "homologous" => Ok(CrossoverScheme::Homologous),
+            "messy" => Ok(CrossoverScheme::Messy),
+            _ => Err(GAError::ValidationError(format!(
+                "Invalid crossover scheme: {s}"
+            ))),
+        }
+    }
+}
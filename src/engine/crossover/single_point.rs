@@ -2,6 +2,9 @@ use crate::core::{errors::*, traits::*, types::*};
 use rand::{thread_rng, Rng};
 
 /// Single-point crossover operation
+///
+/// 遺伝子の数（`dna_length()`）だけを使用するため、遺伝子型の表現に依存せず
+/// 任意の`Agent`に適用できます。
 #[derive(Debug, Clone, PartialEq)]
 pub struct SinglePointCrossover;
 
@@ -11,21 +14,14 @@ impl SinglePointCrossover {
     }
 
     pub fn crossover<T: Agent>(&self, parent1: &T, parent2: &T) -> GAResult<(T, T)> {
-        let dna1 = parent1.dna_binary();
-        let dna2 = parent2.dna_binary();
+        let length = Self::common_length(parent1, parent2)?;
 
-        if dna1.len() != dna2.len() {
-            return Err(GAError::InvalidDna(
-                "Parent DNA lengths must be equal".to_string(),
-            ));
-        }
-
-        if dna1.is_empty() {
+        if length == 0 {
             return Err(GAError::InvalidDnaLength(0));
         }
 
         let mut rng = thread_rng();
-        let crossover_point = rng.gen_range(1..dna1.len()); // 1 to len-1
+        let crossover_point = rng.gen_range(1..length); // 1 to len-1
 
         self.crossover_at_point(parent1, parent2, crossover_point)
     }
@@ -36,22 +32,8 @@ impl SinglePointCrossover {
         parent2: &T,
         point: CrossoverPoint,
     ) -> GAResult<(T, T)> {
-        let dna1 = parent1.dna_binary();
-        let dna2 = parent2.dna_binary();
-
-        if dna1.len() != dna2.len() {
-            return Err(GAError::InvalidDna(
-                "Parent DNA lengths must be equal".to_string(),
-            ));
-        }
-
-        crate::core::errors::validation::validate_crossover_point(point, dna1.len())?;
-
-        let offspring1_dna = format!("{}{}", &dna1[..point], &dna2[point..]);
-        let offspring2_dna = format!("{}{}", &dna2[..point], &dna1[point..]);
-
-        crate::core::errors::validation::validate_dna(&offspring1_dna)?;
-        crate::core::errors::validation::validate_dna(&offspring2_dna)?;
+        let length = Self::common_length(parent1, parent2)?;
+        crate::core::errors::validation::validate_crossover_point(point, length)?;
 
         let offspring1 = parent1.crossover(parent2, point);
         let offspring2 = parent2.crossover(parent1, point);
@@ -69,6 +51,16 @@ impl SinglePointCrossover {
 
         Ok(offspring)
     }
+
+    /// 両親に共通の遺伝子の数
+    fn common_length<T: Agent>(parent1: &T, parent2: &T) -> GAResult<usize> {
+        if parent1.dna_length() != parent2.dna_length() {
+            return Err(GAError::InvalidDna(
+                "Parent DNA lengths must be equal".to_string(),
+            ));
+        }
+        Ok(parent1.dna_length())
+    }
 }
 
 impl Default for SinglePointCrossover {
@@ -109,7 +101,7 @@ mod tests {
             }
         }
 
-        fn mutate(&self, _rate: MutationRate) -> Self {
+        fn mutate_with_rng<R: Rng>(&self, _rate: MutationRate, _rng: &mut R) -> Self {
             self.clone()
         }
 
@@ -0,0 +1,145 @@
+/// 決定論的クラウディング（Deterministic Crowding）の実装
+///
+/// 親をランダムにペアにして2つの子を生成し、各子を遺伝子型の近い親と競わせます。
+/// 子は対応する親より適応度が高い場合にのみ親を置き換えるため、
+/// 異なるニッチの個体同士が直接置き換わることが少なくなります。
+use crate::core::{errors::*, traits::*, types::*};
+use crate::engine::genetic::{breed, hamming_distance};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// 決定論的クラウディングによる置換スキーム
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeterministicCrowding;
+
+impl DeterministicCrowding {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 親と子を距離に基づいて対応付け、勝者を返す
+    ///
+    /// `d(p1,c1) + d(p2,c2) <= d(p1,c2) + d(p2,c1)` の場合は (p1,c1), (p2,c2) を、
+    /// それ以外は (p1,c2), (p2,c1) を対戦させます。子は評価済み（ポイント設定済み）
+    /// である必要があります。
+    pub fn compete<T: Agent>(&self, parents: (T, T), offspring: (T, T)) -> (T, T) {
+        let (p1, p2) = parents;
+        let (c1, c2) = offspring;
+
+        let straight = hamming_distance(&p1, &c1) + hamming_distance(&p2, &c2);
+        let crossed = hamming_distance(&p1, &c2) + hamming_distance(&p2, &c1);
+
+        let (c1, c2) = if straight <= crossed {
+            (c1, c2)
+        } else {
+            (c2, c1)
+        };
+
+        (Self::winner(p1, c1), Self::winner(p2, c2))
+    }
+
+    fn winner<T: Agent>(parent: T, child: T) -> T {
+        if child.fitness() > parent.fitness() {
+            child
+        } else {
+            parent
+        }
+    }
+
+    /// 1世代分の置換を実行
+    ///
+    /// 個体群をシャッフルしてペアを作り、各ペアから子を2つ生成して`evaluate`で評価し、
+    /// 親と競わせます。個体数が奇数の場合、余った1個体はそのまま残ります。
+    ///
+    /// # 引数
+    /// * `population` - 評価済みの現個体群
+    /// * `mutation_rate` - 子に適用する突然変異率
+    /// * `evaluate` - 子の適応度（獲得ポイント）を計算する関数
+    ///
+    /// # エラー
+    /// 個体群が空の場合
+    pub fn next_generation<T, R, F>(
+        &self,
+        population: &[T],
+        mutation_rate: MutationRate,
+        rng: &mut R,
+        mut evaluate: F,
+    ) -> GAResult<Vec<T>>
+    where
+        T: Agent,
+        R: Rng,
+        F: FnMut(&T) -> Points,
+    {
+        if population.is_empty() {
+            return Err(GAError::EmptyPopulation);
+        }
+
+        let mut shuffled = population.to_vec();
+        shuffled.shuffle(rng);
+
+        let mut next = Vec::with_capacity(population.len());
+        let mut pairs = shuffled.chunks_exact(2);
+
+        for pair in &mut pairs {
+            let (p1, p2) = (pair[0].clone(), pair[1].clone());
+            let c1 = breed(&p1, &p2, mutation_rate, rng);
+            let c2 = breed(&p2, &p1, mutation_rate, rng);
+            let c1 = c1.with_points(evaluate(&c1));
+            let c2 = c2.with_points(evaluate(&c2));
+
+            let (w1, w2) = self.compete((p1, p2), (c1, c2));
+            next.push(w1);
+            next.push(w2);
+        }
+
+        next.extend(pairs.remainder().iter().cloned());
+
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+    use rand::thread_rng;
+
+    fn agent(id: u64, dna: &str, points: u64) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, dna.to_string()), points)
+    }
+
+    #[test]
+    fn test_child_replaces_closest_parent() {
+        let parents = (agent(1, "000000", 10), agent(2, "111111", 10));
+        // c1はp2に、c2はp1に近い
+        let offspring = (agent(3, "111110", 20), agent(4, "000001", 5));
+
+        let crowding = DeterministicCrowding::new();
+        let (w1, w2) = crowding.compete(parents, offspring);
+
+        assert_eq!(w1.id(), 1); // p1 vs c2: 親が勝つ
+        assert_eq!(w2.id(), 3); // p2 vs c1: 子が勝つ
+    }
+
+    #[test]
+    fn test_next_generation_preserves_size() {
+        let population: Vec<ModelAgent> = (0..7)
+            .map(|i| agent(i, if i % 2 == 0 { "000000" } else { "111111" }, 10))
+            .collect();
+
+        let crowding = DeterministicCrowding::new();
+        let next = crowding
+            .next_generation(&population, 0.0, &mut thread_rng(), |child| child.dna_sum())
+            .unwrap();
+
+        assert_eq!(next.len(), population.len());
+    }
+
+    #[test]
+    fn test_empty_population() {
+        let population: Vec<ModelAgent> = vec![];
+        let crowding = DeterministicCrowding::new();
+        let result = crowding.next_generation(&population, 0.0, &mut thread_rng(), |_| 0);
+        assert!(matches!(result, Err(GAError::EmptyPopulation)));
+    }
+}
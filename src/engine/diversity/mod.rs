@@ -0,0 +1,223 @@
+/// 多様性維持スキーム
+///
+/// 個体群が単一の遺伝子型に収束するのを防ぐための選択・置換スキームを提供します。
+/// いずれのスキームも`population::hamming_distance`による遺伝子型間の距離を利用します。
+pub mod crowding;
+pub mod restricted_tournament;
+pub mod sharing;
+pub mod speciation;
+
+pub use crowding::DeterministicCrowding;
+pub use restricted_tournament::RestrictedTournamentReplacement;
+pub use sharing::FitnessSharing;
+pub use speciation::{Speciation, SpeciesEvent, SpeciesEventKind, SpeciesId, SpeciesStats};
+
+use crate::core::errors::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// 適応度共有のデフォルト共有半径（正規化ハミング距離）
+pub const DEFAULT_SHARING_RADIUS: f64 = 0.3;
+
+/// 適応度共有のデフォルト形状パラメータ
+pub const DEFAULT_SHARING_ALPHA: f64 = 1.0;
+
+/// 制限トーナメント置換のデフォルトウィンドウサイズ
+pub const DEFAULT_RTR_WINDOW: usize = 4;
+
+/// 種分化のデフォルト初期閾値（正規化ハミング距離）
+pub const DEFAULT_SPECIES_THRESHOLD: f64 = 0.3;
+
+/// 種分化のデフォルトの目標の種の数
+pub const DEFAULT_TARGET_SPECIES: usize = 5;
+
+/// シミュレーションで使用する多様性維持スキーム
+///
+/// 文字列表現は`none`、`sharing[:RADIUS]`、`crowding`、`rtr[:WINDOW]`、
+/// `species[:THRESHOLD[:TARGET]]`です。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DiversityScheme {
+    /// 多様性維持を行わない（従来の世代交代）
+    #[default]
+    None,
+    /// 適応度共有による親選択
+    FitnessSharing { radius: f64, alpha: f64 },
+    /// 決定論的クラウディングによる置換
+    DeterministicCrowding,
+    /// 制限トーナメント置換
+    RestrictedTournament { window_size: usize },
+    /// 種分化と種ごとの子の割り当て（NEAT型）
+    Speciation {
+        threshold: f64,
+        target_species: usize,
+    },
+}
+
+impl DiversityScheme {
+    /// スキームのパラメータを検証
+    pub fn validate(&self) -> GAResult<()> {
+        match *self {
+            DiversityScheme::FitnessSharing { radius, alpha } => {
+                if !(radius > 0.0 && radius <= 1.0) {
+                    return Err(GAError::ValidationError(
+                        "Sharing radius must be in (0.0, 1.0]".to_string(),
+                    ));
+                }
+                if alpha <= 0.0 {
+                    return Err(GAError::ValidationError(
+                        "Sharing alpha must be greater than 0".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            DiversityScheme::RestrictedTournament { window_size: 0 } => Err(
+                GAError::ValidationError("RTR window size must be greater than 0".to_string()),
+            ),
+            DiversityScheme::Speciation {
+                threshold,
+                target_species,
+            } => {
+                if !(threshold > 0.0 && threshold <= 1.0) {
+                    return Err(GAError::ValidationError(
+                        "Species threshold must be in (0.0, 1.0]".to_string(),
+                    ));
+                }
+                if target_species == 0 {
+                    return Err(GAError::ValidationError(
+                        "Target species count must be greater than 0".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for DiversityScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiversityScheme::None => write!(f, "none"),
+            DiversityScheme::FitnessSharing { radius, .. } => write!(f, "sharing:{radius}"),
+            DiversityScheme::DeterministicCrowding => write!(f, "crowding"),
+            DiversityScheme::RestrictedTournament { window_size } => {
+                write!(f, "rtr:{window_size}")
+            }
+            DiversityScheme::Speciation {
+                threshold,
+                target_species,
+            } => write!(f, "species:{threshold}:{target_species}"),
+        }
+    }
+}
+
+impl FromStr for DiversityScheme {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, param) = match s.split_once(':') {
+            Some((name, param)) => (name, Some(param)),
+            None => (s, None),
+        };
+        let invalid = || GAError::ValidationError(format!("Invalid diversity scheme: {s}"));
+
+        let scheme = match (name, param) {
+            ("none", None) => DiversityScheme::None,
+            ("sharing", param) => DiversityScheme::FitnessSharing {
+                radius: param
+                    .map(|p| p.parse().map_err(|_| invalid()))
+                    .transpose()?
+                    .unwrap_or(DEFAULT_SHARING_RADIUS),
+                alpha: DEFAULT_SHARING_ALPHA,
+            },
+            ("crowding", None) => DiversityScheme::DeterministicCrowding,
+            ("rtr", param) => DiversityScheme::RestrictedTournament {
+                window_size: param
+                    .map(|p| p.parse().map_err(|_| invalid()))
+                    .transpose()?
+                    .unwrap_or(DEFAULT_RTR_WINDOW),
+            },
+            ("species", param) => {
+                let (threshold, target) = match param.map(|p| p.split_once(':')) {
+                    None => (None, None),
+                    Some(None) => (param, None),
+                    Some(Some((threshold, target))) => (Some(threshold), Some(target)),
+                };
+                DiversityScheme::Speciation {
+                    threshold: threshold
+                        .map(|p| p.parse().map_err(|_| invalid()))
+                        .transpose()?
+                        .unwrap_or(DEFAULT_SPECIES_THRESHOLD),
+                    target_species: target
+                        .map(|p| p.parse().map_err(|_| invalid()))
+                        .transpose()?
+                        .unwrap_or(DEFAULT_TARGET_SPECIES),
+                }
+            }
+            _ => return Err(invalid()),
+        };
+
+        scheme.validate()?;
+        Ok(scheme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scheme() {
+        assert_eq!(
+            "none".parse::<DiversityScheme>().unwrap(),
+            DiversityScheme::None
+        );
+        assert_eq!(
+            "crowding".parse::<DiversityScheme>().unwrap(),
+            DiversityScheme::DeterministicCrowding
+        );
+        assert_eq!(
+            "sharing:0.5".parse::<DiversityScheme>().unwrap(),
+            DiversityScheme::FitnessSharing {
+                radius: 0.5,
+                alpha: DEFAULT_SHARING_ALPHA
+            }
+        );
+        assert_eq!(
+            "rtr".parse::<DiversityScheme>().unwrap(),
+            DiversityScheme::RestrictedTournament {
+                window_size: DEFAULT_RTR_WINDOW
+            }
+        );
+        assert_eq!(
+            "species".parse::<DiversityScheme>().unwrap(),
+            DiversityScheme::Speciation {
+                threshold: DEFAULT_SPECIES_THRESHOLD,
+                target_species: DEFAULT_TARGET_SPECIES
+            }
+        );
+        let species: DiversityScheme = "species:0.2:8".parse().unwrap();
+        assert_eq!(
+            species,
+            DiversityScheme::Speciation {
+                threshold: 0.2,
+                target_species: 8
+            }
+        );
+        assert_eq!(
+            species.to_string().parse::<DiversityScheme>().unwrap(),
+            species
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_scheme() {
+        assert!("unknown".parse::<DiversityScheme>().is_err());
+        assert!("sharing:0".parse::<DiversityScheme>().is_err());
+        assert!("sharing:abc".parse::<DiversityScheme>().is_err());
+        assert!("rtr:0".parse::<DiversityScheme>().is_err());
+        assert!("species:0".parse::<DiversityScheme>().is_err());
+        assert!("species:0.3:0".parse::<DiversityScheme>().is_err());
+        assert!("species:0.3:2:1".parse::<DiversityScheme>().is_err());
+    }
+}
@@ -0,0 +1,161 @@
+/// 制限トーナメント置換（Restricted Tournament Replacement）の実装
+///
+/// 子を1つ生成するたびに個体群から重複なしでランダムに`window_size`個体を抽出し、
+/// その中で遺伝子型が最も近い個体と子を競わせます。子の適応度が高い場合のみ
+/// その個体を置き換えるため、ニッチごとに個体が維持されます。
+use crate::core::{errors::*, traits::*, types::*};
+use crate::engine::genetic::{breed, hamming_distance};
+use rand::Rng;
+
+/// 制限トーナメント置換スキーム
+///
+/// # フィールド
+/// * `window_size` - 置換候補として抽出する個体数
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestrictedTournamentReplacement {
+    window_size: usize,
+}
+
+impl RestrictedTournamentReplacement {
+    /// ウィンドウサイズを指定して作成
+    ///
+    /// # エラー
+    /// ウィンドウサイズが0の場合
+    pub fn new(window_size: usize) -> GAResult<Self> {
+        super::DiversityScheme::RestrictedTournament { window_size }.validate()?;
+        Ok(Self { window_size })
+    }
+
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+
+    /// 評価済みの子を個体群に挿入
+    ///
+    /// ウィンドウ内で最も近い個体より子の適応度が高い場合に置き換えます。
+    ///
+    /// # 戻り値
+    /// 置き換えが発生した場合は置き換えた位置
+    pub fn insert<T: Agent, R: Rng>(
+        &self,
+        population: &mut [T],
+        offspring: T,
+        rng: &mut R,
+    ) -> Option<usize> {
+        if population.is_empty() {
+            return None;
+        }
+
+        let window = self.window_size.min(population.len());
+        let closest = rand::seq::index::sample(rng, population.len(), window)
+            .into_iter()
+            .min_by(|&a, &b| {
+                hamming_distance(&population[a], &offspring)
+                    .total_cmp(&hamming_distance(&population[b], &offspring))
+            })?;
+
+        if offspring.fitness() > population[closest].fitness() {
+            population[closest] = offspring;
+            Some(closest)
+        } else {
+            None
+        }
+    }
+
+    /// 1世代分の置換を実行
+    ///
+    /// 個体数と同じ数の子を一様ランダムに選んだ親から生成し、
+    /// `evaluate`で評価したうえで順に挿入します。
+    ///
+    /// # エラー
+    /// 個体群が空の場合
+    pub fn next_generation<T, R, F>(
+        &self,
+        population: &[T],
+        mutation_rate: MutationRate,
+        rng: &mut R,
+        mut evaluate: F,
+    ) -> GAResult<Vec<T>>
+    where
+        T: Agent,
+        R: Rng,
+        F: FnMut(&T) -> Points,
+    {
+        if population.is_empty() {
+            return Err(GAError::EmptyPopulation);
+        }
+
+        let mut next = population.to_vec();
+
+        for _ in 0..population.len() {
+            let parent1 = &next[rng.gen_range(0..next.len())];
+            let parent2 = &next[rng.gen_range(0..next.len())];
+            let child = breed(parent1, parent2, mutation_rate, rng);
+            let child = child.with_points(evaluate(&child));
+            self.insert(&mut next, child, rng);
+        }
+
+        Ok(next)
+    }
+}
+
+impl Default for RestrictedTournamentReplacement {
+    fn default() -> Self {
+        Self {
+            window_size: super::DEFAULT_RTR_WINDOW,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+    use rand::thread_rng;
+
+    fn agent(id: u64, dna: &str, points: u64) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, dna.to_string()), points)
+    }
+
+    #[test]
+    fn test_zero_window_rejected() {
+        assert!(RestrictedTournamentReplacement::new(0).is_err());
+        assert!(RestrictedTournamentReplacement::new(3).is_ok());
+    }
+
+    #[test]
+    fn test_insert_replaces_closest_in_window() {
+        let mut population = vec![agent(1, "000000", 10), agent(2, "111111", 10)];
+        let rtr = RestrictedTournamentReplacement::new(64).unwrap();
+        let mut rng = thread_rng();
+
+        let replaced = rtr.insert(&mut population, agent(3, "111110", 20), &mut rng);
+
+        assert_eq!(replaced, Some(1));
+        assert_eq!(population[0].id(), 1);
+        assert_eq!(population[1].id(), 3);
+    }
+
+    #[test]
+    fn test_weaker_offspring_is_rejected() {
+        let mut population = vec![agent(1, "000000", 10)];
+        let rtr = RestrictedTournamentReplacement::default();
+        let mut rng = thread_rng();
+
+        assert_eq!(
+            rtr.insert(&mut population, agent(2, "000001", 5), &mut rng),
+            None
+        );
+        assert_eq!(population[0].id(), 1);
+    }
+
+    #[test]
+    fn test_next_generation_preserves_size() {
+        let population: Vec<ModelAgent> = (0..6).map(|i| agent(i, "101010", 10)).collect();
+        let rtr = RestrictedTournamentReplacement::default();
+        let next = rtr
+            .next_generation(&population, 0.1, &mut thread_rng(), |child| child.dna_sum())
+            .unwrap();
+        assert_eq!(next.len(), 6);
+    }
+}
@@ -0,0 +1,190 @@
+/// 適応度共有（Fitness Sharing）の実装
+///
+/// 遺伝子型が近い個体同士で適応度を分け合うことで、同じニッチに個体が
+/// 集中するのを抑制します。共有後の適応度は `f_i / Σ_j sh(d_ij)` で計算され、
+/// 共有関数は `sh(d) = 1 - (d / σ)^α`（`d < σ`の場合）、それ以外は0です。
+use crate::core::{errors::*, traits::*};
+use crate::engine::genetic::hamming_distance;
+use crate::engine::selection::{check_parents, check_survivors};
+use rand::{thread_rng, Rng, RngCore};
+
+/// 適応度共有による親選択
+///
+/// 共有後の適応度に比例したルーレット選択で親を選びます。
+///
+/// # フィールド
+/// * `radius` - 共有半径σ（正規化ハミング距離、0.0-1.0）
+/// * `alpha` - 共有関数の形状パラメータα
+#[derive(Debug, Clone)]
+pub struct FitnessSharing {
+    radius: f64,
+    alpha: f64,
+}
+
+impl FitnessSharing {
+    /// 共有半径と形状パラメータを指定して作成
+    ///
+    /// # エラー
+    /// 半径が(0.0, 1.0]の範囲外、またはαが0以下の場合
+    pub fn new(radius: f64, alpha: f64) -> GAResult<Self> {
+        super::DiversityScheme::FitnessSharing { radius, alpha }.validate()?;
+        Ok(Self { radius, alpha })
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// 共有関数 sh(d)
+    fn sharing(&self, distance: f64) -> f64 {
+        if distance < self.radius {
+            1.0 - (distance / self.radius).powf(self.alpha)
+        } else {
+            0.0
+        }
+    }
+
+    /// 各個体のニッチ数 m_i = Σ_j sh(d_ij) を計算
+    ///
+    /// 自分自身との距離は0なので、ニッチ数は常に1以上になります。
+    pub fn niche_counts<T: Agent>(&self, population: &[T]) -> Vec<f64> {
+        population
+            .iter()
+            .map(|a| {
+                population
+                    .iter()
+                    .map(|b| self.sharing(hamming_distance(a, b)))
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// 共有後の適応度を計算
+    pub fn shared_fitness<T: Agent>(&self, population: &[T]) -> Vec<f64> {
+        population
+            .iter()
+            .zip(self.niche_counts(population))
+            .map(|(agent, niche)| agent.fitness() as f64 / niche)
+            .collect()
+    }
+
+    fn select_index<R: Rng>(shared: &[f64], rng: &mut R) -> usize {
+        let total: f64 = shared.iter().sum();
+        if total <= 0.0 {
+            return rng.gen_range(0..shared.len());
+        }
+
+        let mut point = rng.gen::<f64>() * total;
+        for (i, &fitness) in shared.iter().enumerate() {
+            point -= fitness;
+            if point <= 0.0 {
+                return i;
+            }
+        }
+
+        shared.len() - 1
+    }
+}
+
+impl Default for FitnessSharing {
+    fn default() -> Self {
+        Self {
+            radius: super::DEFAULT_SHARING_RADIUS,
+            alpha: super::DEFAULT_SHARING_ALPHA,
+        }
+    }
+}
+
+impl<T: Agent> SelectionStrategy<T> for FitnessSharing {
+    fn select_parents_with_rng(
+        &self,
+        population: &[T],
+        mut rng: &mut dyn RngCore,
+    ) -> GAResult<(T, T)> {
+        check_parents(population)?;
+        let shared = self.shared_fitness(population);
+        let parent1 = Self::select_index(&shared, &mut rng);
+        let parent2 = Self::select_index(&shared, &mut rng);
+        Ok((population[parent1].clone(), population[parent2].clone()))
+    }
+
+    fn select_survivors(&self, population: &[T], count: usize) -> GAResult<Vec<T>> {
+        check_survivors(population, count)?;
+        if count == population.len() {
+            return Ok(population.to_vec());
+        }
+
+        let shared = self.shared_fitness(population);
+        let mut rng = thread_rng();
+        Ok((0..count)
+            .map(|_| population[Self::select_index(&shared, &mut rng)].clone())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn agent(id: u64, dna: &str, points: u64) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, dna.to_string()), points)
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(FitnessSharing::new(0.0, 1.0).is_err());
+        assert!(FitnessSharing::new(1.5, 1.0).is_err());
+        assert!(FitnessSharing::new(0.5, 0.0).is_err());
+        assert!(FitnessSharing::new(0.5, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_identical_genotypes_share_fitness() {
+        let population = vec![
+            agent(1, "000000", 30),
+            agent(2, "000000", 30),
+            agent(3, "000000", 30),
+            agent(4, "111111", 30),
+        ];
+
+        let sharing = FitnessSharing::new(0.5, 1.0).unwrap();
+        let shared = sharing.shared_fitness(&population);
+
+        assert_eq!(shared[0], 10.0);
+        assert_eq!(shared[1], 10.0);
+        assert_eq!(shared[2], 10.0);
+        assert_eq!(shared[3], 30.0);
+    }
+
+    #[test]
+    fn test_sharing_function() {
+        let sharing = FitnessSharing::new(0.5, 1.0).unwrap();
+        assert_eq!(sharing.sharing(0.0), 1.0);
+        assert_eq!(sharing.sharing(0.25), 0.5);
+        assert_eq!(sharing.sharing(0.5), 0.0);
+        assert_eq!(sharing.sharing(1.0), 0.0);
+    }
+
+    #[test]
+    fn test_select_parents_from_population() {
+        let population = vec![agent(1, "000000", 10), agent(2, "111111", 20)];
+        let sharing = FitnessSharing::default();
+        let (parent1, parent2) = sharing.select_parents(&population).unwrap();
+
+        assert!(population.iter().any(|a| a.id() == parent1.id()));
+        assert!(population.iter().any(|a| a.id() == parent2.id()));
+    }
+
+    #[test]
+    fn test_zero_fitness_selects_randomly() {
+        let population = vec![agent(1, "000000", 0), agent(2, "111111", 0)];
+        let sharing = FitnessSharing::default();
+        let survivors = sharing.select_survivors(&population, 1).unwrap();
+        assert_eq!(survivors.len(), 1);
+    }
+}
@@ -0,0 +1,462 @@
+/// NEAT型の種分化（Speciation）の実装
+///
+/// 個体を遺伝子型の距離で種に分け、種の中で適応度を明示的に共有します。
+/// 各個体は前世代の種の代表と順に比較され、距離が閾値未満の最初の種に属します。
+/// どの種にも属さない個体は新しい種の代表になります。閾値は種の数が目標に
+/// 近づくよう世代ごとに調整されます。
+///
+/// 共有後の適応度は`f_i / |S|`（`|S|`は個体の属する種の個体数）で、
+/// 次世代の子は各種の共有後の適応度の合計に比例して種ごとに割り当てられます。
+use crate::core::{errors::*, traits::*};
+use crate::engine::genetic::hamming_distance;
+
+/// 種の識別子（実行中に生まれた順の通し番号）
+pub type SpeciesId = u64;
+
+/// 種の数が目標と異なるときに閾値を変化させる幅
+const THRESHOLD_STEP: f64 = 0.02;
+
+/// 閾値の下限（これ未満では全個体が別々の種になる）
+const MIN_THRESHOLD: f64 = 0.01;
+
+/// 1つの種
+///
+/// # フィールド
+/// * `id` - 種の識別子
+/// * `representative` - 次世代の個体と比較する代表個体
+/// * `members` - 現世代の個体群での所属個体の添字
+/// * `born` - 種が生まれた世代
+/// * `parent` - 種を創始した個体の親が属していた種（初期個体群から生まれた種では`None`）
+#[derive(Debug, Clone)]
+pub struct Species<T> {
+    pub id: SpeciesId,
+    pub representative: T,
+    pub members: Vec<usize>,
+    pub born: usize,
+    pub parent: Option<SpeciesId>,
+}
+
+/// 種の誕生・絶滅の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeciesEventKind {
+    Birth,
+    Extinction,
+}
+
+/// 種の誕生・絶滅の記録
+///
+/// # フィールド
+/// * `generation` - 誕生・絶滅した世代
+/// * `species` - 種の識別子
+/// * `kind` - 誕生か絶滅か
+/// * `parent` - 誕生した種の親の種（絶滅、または初期個体群から生まれた種では`None`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpeciesEvent {
+    pub generation: usize,
+    pub species: SpeciesId,
+    pub kind: SpeciesEventKind,
+    pub parent: Option<SpeciesId>,
+}
+
+/// 1世代分の種分化の統計
+///
+/// # フィールド
+/// * `assignments` - 各個体の属する種（個体群と同じ順）
+/// * `origins` - 各個体の親が属していた種（個体群と同じ順、親の種が不明な世代では空）
+/// * `sizes` - 各種の識別子と個体数
+/// * `threshold` - この世代の種分けに使った閾値
+/// * `births` - この世代に生まれた種の数
+/// * `extinctions` - この世代に絶滅した種の数
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpeciesStats {
+    pub assignments: Vec<SpeciesId>,
+    pub origins: Vec<SpeciesId>,
+    pub sizes: Vec<(SpeciesId, usize)>,
+    pub threshold: f64,
+    pub births: usize,
+    pub extinctions: usize,
+}
+
+/// 世代をまたいで種を追跡する種分化スキーム
+///
+/// # フィールド
+/// * `threshold` - 同じ種とみなす正規化ハミング距離の閾値（適応的に変化）
+/// * `target_species` - 目標とする種の数
+/// * `species` - 現世代の種
+/// * `next_id` - 次に生まれる種の識別子
+/// * `events` - 実行開始からの種の誕生・絶滅の記録
+/// * `origins` - 次に種分けする個体群の各個体の親が属していた種（`record_offspring`で記録）
+#[derive(Debug, Clone)]
+pub struct Speciation<T> {
+    threshold: f64,
+    target_species: usize,
+    species: Vec<Species<T>>,
+    next_id: SpeciesId,
+    events: Vec<SpeciesEvent>,
+    origins: Vec<SpeciesId>,
+}
+
+impl<T: Agent> Speciation<T> {
+    /// 初期閾値と目標の種の数を指定して作成
+    ///
+    /// # エラー
+    /// 閾値が(0.0, 1.0]の範囲外、または目標の種の数が0の場合
+    pub fn new(threshold: f64, target_species: usize) -> GAResult<Self> {
+        super::DiversityScheme::Speciation {
+            threshold,
+            target_species,
+        }
+        .validate()?;
+        Ok(Self {
+            threshold,
+            target_species,
+            species: Vec::new(),
+            next_id: 0,
+            events: Vec::new(),
+            origins: Vec::new(),
+        })
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    pub fn target_species(&self) -> usize {
+        self.target_species
+    }
+
+    pub fn species(&self) -> &[Species<T>] {
+        &self.species
+    }
+
+    pub fn events(&self) -> &[SpeciesEvent] {
+        &self.events
+    }
+
+    /// 次世代の各個体の親が属していた種を記録
+    ///
+    /// 次の`speciate`で個体群と個体数が一致すれば、各個体の親の種と
+    /// 新しく生まれた種の親の種として使われます。
+    ///
+    /// # 引数
+    /// * `origins` - 次世代の個体群と同じ順の、各個体の親の種
+    pub fn record_offspring(&mut self, origins: Vec<SpeciesId>) {
+        self.origins = origins;
+    }
+
+    /// 個体群を種に分ける
+    ///
+    /// 前世代の種の代表と比較して所属を決め、個体が1体も属さなかった種は絶滅します。
+    /// 各種の代表はこの世代の最初の所属個体に更新されます。最後に、種の数が目標より
+    /// 少なければ閾値を下げ、多ければ上げます。新しく生まれた種の親の種は、
+    /// `record_offspring`で記録した、種を創始した個体の親の種です。
+    ///
+    /// # 引数
+    /// * `generation` - 現在の世代（誕生・絶滅の記録に使用）
+    /// * `population` - 種に分ける個体群
+    ///
+    /// # 戻り値
+    /// この世代の種分化の統計
+    pub fn speciate(&mut self, generation: usize, population: &[T]) -> SpeciesStats {
+        let threshold = self.threshold;
+        let mut species: Vec<Species<T>> = std::mem::take(&mut self.species)
+            .into_iter()
+            .map(|s| Species {
+                members: Vec::new(),
+                ..s
+            })
+            .collect();
+        let mut births = 0;
+        let mut origins = std::mem::take(&mut self.origins);
+        if origins.len() != population.len() {
+            origins.clear();
+        }
+
+        for (i, agent) in population.iter().enumerate() {
+            let index = match species
+                .iter()
+                .position(|s| hamming_distance(&s.representative, agent) < threshold)
+            {
+                Some(index) => index,
+                None => {
+                    let parent = origins.get(i).copied();
+                    species.push(Species {
+                        id: self.next_id,
+                        representative: agent.clone(),
+                        members: Vec::new(),
+                        born: generation,
+                        parent,
+                    });
+                    self.events.push(SpeciesEvent {
+                        generation,
+                        species: self.next_id,
+                        kind: SpeciesEventKind::Birth,
+                        parent,
+                    });
+                    self.next_id += 1;
+                    births += 1;
+                    species.len() - 1
+                }
+            };
+            species[index].members.push(i);
+        }
+
+        let mut extinctions = 0;
+        species.retain(|s| {
+            if s.members.is_empty() {
+                self.events.push(SpeciesEvent {
+                    generation,
+                    species: s.id,
+                    kind: SpeciesEventKind::Extinction,
+                    parent: None,
+                });
+                extinctions += 1;
+            }
+            !s.members.is_empty()
+        });
+        for s in species.iter_mut() {
+            s.representative = population[s.members[0]].clone();
+        }
+        self.species = species;
+
+        self.threshold = match self.species.len().cmp(&self.target_species) {
+            std::cmp::Ordering::Less => (self.threshold - THRESHOLD_STEP).max(MIN_THRESHOLD),
+            std::cmp::Ordering::Greater => (self.threshold + THRESHOLD_STEP).min(1.0),
+            std::cmp::Ordering::Equal => self.threshold,
+        };
+
+        SpeciesStats {
+            assignments: self.assignments(population.len()),
+            origins,
+            sizes: self
+                .species
+                .iter()
+                .map(|s| (s.id, s.members.len()))
+                .collect(),
+            threshold,
+            births,
+            extinctions,
+        }
+    }
+
+    /// 各個体の属する種の識別子（個体群と同じ順）
+    fn assignments(&self, size: usize) -> Vec<SpeciesId> {
+        let mut assignments = vec![0; size];
+        for s in &self.species {
+            for &member in &s.members {
+                assignments[member] = s.id;
+            }
+        }
+        assignments
+    }
+
+    /// 種分けした個体群と同じ個体数かどうかを確認
+    fn check_population(&self, population: &[T]) -> GAResult<()> {
+        let speciated: usize = self.species.iter().map(|s| s.members.len()).sum();
+        if speciated != population.len() {
+            return Err(GAError::ValidationError(format!(
+                "Population of {} agents does not match the {speciated} speciated agents",
+                population.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// 種の中で共有した適応度 `f_i / |S|` を計算
+    ///
+    /// # エラー
+    /// 個体群が直前に種分けした個体群と個体数が異なる場合
+    pub fn shared_fitness(&self, population: &[T]) -> GAResult<Vec<f64>> {
+        self.check_population(population)?;
+        let mut shared = vec![0.0; population.len()];
+        for s in &self.species {
+            for &member in &s.members {
+                shared[member] = population[member].fitness() as f64 / s.members.len() as f64;
+            }
+        }
+        Ok(shared)
+    }
+
+    /// 次世代の子`total`体を種ごとに割り当てる
+    ///
+    /// 各種の割り当て数は共有後の適応度の合計に比例し、端数は最大剰余法で
+    /// 配分します。全種の適応度が0の場合は種ごとに均等に割り当てます。
+    ///
+    /// # 戻り値
+    /// `species()`と同じ順の各種の子の数（合計は`total`）
+    ///
+    /// # エラー
+    /// 個体群が直前に種分けした個体群と個体数が異なる場合
+    pub fn allocate_offspring(&self, population: &[T], total: usize) -> GAResult<Vec<usize>> {
+        let shared = self.shared_fitness(population)?;
+        let mut shares: Vec<f64> = self
+            .species
+            .iter()
+            .map(|s| s.members.iter().map(|&member| shared[member]).sum())
+            .collect();
+        let sum: f64 = shares.iter().sum();
+        if sum <= 0.0 {
+            shares.iter_mut().for_each(|share| *share = 1.0);
+        }
+        let sum: f64 = shares.iter().sum();
+
+        let quotas: Vec<f64> = shares
+            .iter()
+            .map(|share| share / sum * total as f64)
+            .collect();
+        let mut counts: Vec<usize> = quotas.iter().map(|q| q.floor() as usize).collect();
+        let mut order: Vec<usize> = (0..quotas.len()).collect();
+        order.sort_by(|&a, &b| {
+            (quotas[b] - quotas[b].floor()).total_cmp(&(quotas[a] - quotas[a].floor()))
+        });
+        let remaining = total.saturating_sub(counts.iter().sum());
+        for &index in order.iter().cycle().take(remaining) {
+            counts[index] += 1;
+        }
+        Ok(counts)
+    }
+}
+
+impl<T> Default for Speciation<T> {
+    fn default() -> Self {
+        Self {
+            threshold: super::DEFAULT_SPECIES_THRESHOLD,
+            target_species: super::DEFAULT_TARGET_SPECIES,
+            species: Vec::new(),
+            next_id: 0,
+            events: Vec::new(),
+            origins: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn agent(id: u64, dna: &str, points: u64) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, dna.to_string()), points)
+    }
+
+    fn population() -> Vec<ModelAgent> {
+        vec![
+            agent(0, "00000000", 10),
+            agent(1, "00000001", 20),
+            agent(2, "11111111", 30),
+            agent(3, "11111110", 10),
+            agent(4, "00000011", 30),
+        ]
+    }
+
+    #[test]
+    fn test_speciate_groups_close_genotypes() {
+        let mut speciation = Speciation::new(0.3, 2).unwrap();
+        let stats = speciation.speciate(0, &population());
+
+        assert_eq!(stats.assignments, vec![0, 0, 1, 1, 0]);
+        assert_eq!(stats.sizes, vec![(0, 3), (1, 2)]);
+        assert_eq!(stats.births, 2);
+        assert_eq!(stats.extinctions, 0);
+        assert_eq!(speciation.threshold(), 0.3);
+    }
+
+    #[test]
+    fn test_threshold_adapts_towards_target() {
+        let mut speciation = Speciation::new(0.3, 4).unwrap();
+        speciation.speciate(0, &population());
+        assert!(speciation.threshold() < 0.3);
+
+        let mut speciation = Speciation::new(0.3, 1).unwrap();
+        speciation.speciate(0, &population());
+        assert!(speciation.threshold() > 0.3);
+    }
+
+    #[test]
+    fn test_extinction_and_birth_are_recorded() {
+        let mut speciation = Speciation::new(0.3, 2).unwrap();
+        speciation.speciate(0, &population());
+
+        let next = vec![agent(0, "00000000", 1), agent(1, "01010101", 1)];
+        let stats = speciation.speciate(1, &next);
+        assert_eq!(stats.births, 1);
+        assert_eq!(stats.extinctions, 1);
+        assert_eq!(stats.assignments, vec![0, 2]);
+        assert_eq!(
+            speciation.events()[2..],
+            [
+                SpeciesEvent {
+                    generation: 1,
+                    species: 2,
+                    kind: SpeciesEventKind::Birth,
+                    parent: None,
+                },
+                SpeciesEvent {
+                    generation: 1,
+                    species: 1,
+                    kind: SpeciesEventKind::Extinction,
+                    parent: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_offspring_origins_link_species_to_parents() {
+        let mut speciation = Speciation::new(0.3, 2).unwrap();
+        let stats = speciation.speciate(0, &population());
+        assert!(stats.origins.is_empty());
+
+        // 種1の親から生まれた子が新しい種2を創始する
+        speciation.record_offspring(vec![0, 1]);
+        let next = vec![agent(0, "00000000", 1), agent(1, "01010101", 1)];
+        let stats = speciation.speciate(1, &next);
+        assert_eq!(stats.origins, vec![0, 1]);
+        assert_eq!(stats.assignments, vec![0, 2]);
+        assert_eq!(speciation.species()[1].parent, Some(1));
+        assert_eq!(speciation.events()[2].parent, Some(1));
+
+        // 個体数が一致しない記録は使わない
+        speciation.record_offspring(vec![0]);
+        let stats = speciation.speciate(2, &next);
+        assert!(stats.origins.is_empty());
+    }
+
+    #[test]
+    fn test_shared_fitness_divides_by_species_size() {
+        let mut speciation = Speciation::new(0.3, 2).unwrap();
+        let population = population();
+        speciation.speciate(0, &population);
+
+        let shared = speciation.shared_fitness(&population).unwrap();
+        assert_eq!(shared, vec![10.0 / 3.0, 20.0 / 3.0, 15.0, 5.0, 10.0]);
+        assert!(speciation.shared_fitness(&population[..2]).is_err());
+    }
+
+    #[test]
+    fn test_offspring_allocation_is_proportional() {
+        let mut speciation = Speciation::new(0.3, 2).unwrap();
+        let population = population();
+        speciation.speciate(0, &population);
+
+        // 共有後の適応度の合計は種0が20、種1が20
+        assert_eq!(
+            speciation.allocate_offspring(&population, 10).unwrap(),
+            vec![5, 5]
+        );
+        assert_eq!(
+            speciation
+                .allocate_offspring(&population, 5)
+                .unwrap()
+                .iter()
+                .sum::<usize>(),
+            5
+        );
+
+        let zero: Vec<ModelAgent> = population
+            .iter()
+            .map(|a| BaseModel::with_points(a, 0))
+            .collect();
+        assert_eq!(speciation.allocate_offspring(&zero, 4).unwrap(), vec![2, 2]);
+    }
+}
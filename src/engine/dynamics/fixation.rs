@@ -0,0 +1,231 @@
+/// 変異体の固定確率と固定時間の推定
+///
+/// 定住者の戦略で占められた個体群に1体だけ変異体を入れ、変異体が全体を
+/// 占める（固定）か消える（絶滅）まで進化過程を繰り返す試行を多数回行います。
+use super::EvolutionaryProcess;
+use crate::core::{errors::*, traits::*, types::*};
+use rand::Rng;
+use std::fmt;
+
+/// 固定確率の推定実験
+///
+/// # フィールド
+/// * `population_size` - 個体数（2以上）
+/// * `replicates` - 試行回数
+/// * `max_steps` - 1試行あたりの最大ステップ数（超えた試行は未決着として数える）
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixationExperiment {
+    population_size: usize,
+    replicates: usize,
+    max_steps: usize,
+}
+
+/// 固定確率の推定結果
+///
+/// 時間はいずれも世代単位（Moran過程では`N`ステップで1世代）です。
+///
+/// # フィールド
+/// * `replicates` - 試行回数
+/// * `fixations` - 変異体が固定した試行数
+/// * `extinctions` - 変異体が絶滅した試行数
+/// * `mean_fixation_time` - 固定した試行での平均固定時間
+/// * `mean_extinction_time` - 絶滅した試行での平均絶滅時間
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixationResult {
+    pub replicates: usize,
+    pub fixations: usize,
+    pub extinctions: usize,
+    pub mean_fixation_time: Option<f64>,
+    pub mean_extinction_time: Option<f64>,
+}
+
+impl FixationResult {
+    /// 固定確率の推定値（全試行に対する固定した試行の割合）
+    pub fn fixation_probability(&self) -> f64 {
+        self.fixations as f64 / self.replicates as f64
+    }
+
+    /// 最大ステップ数までに決着しなかった試行数
+    pub fn unresolved(&self) -> usize {
+        self.replicates - self.fixations - self.extinctions
+    }
+}
+
+impl fmt::Display for FixationResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = |t: Option<f64>| t.map_or("-".to_string(), |t| format!("{t:.2}"));
+        write!(
+            f,
+            "fixation probability: {:.4} ({}/{}), mean fixation time: {}, mean extinction time: {}, unresolved: {}",
+            self.fixation_probability(),
+            self.fixations,
+            self.replicates,
+            time(self.mean_fixation_time),
+            time(self.mean_extinction_time),
+            self.unresolved()
+        )
+    }
+}
+
+impl FixationExperiment {
+    /// 実験条件を指定して作成
+    ///
+    /// # エラー
+    /// 個体数が2未満、または試行回数・最大ステップ数が0の場合
+    pub fn new(population_size: usize, replicates: usize, max_steps: usize) -> GAResult<Self> {
+        if population_size < 2 {
+            return Err(GAError::InvalidPopulationSize(population_size));
+        }
+        if replicates == 0 || max_steps == 0 {
+            return Err(GAError::ValidationError(
+                "Replicates and max steps must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            population_size,
+            replicates,
+            max_steps,
+        })
+    }
+
+    /// 変異体の固定確率と固定時間を推定
+    ///
+    /// # 引数
+    /// * `process` - 使用する進化過程
+    /// * `resident` - 定住者のDNA
+    /// * `mutant` - 変異体のDNA
+    /// * `matrix` - 利得表
+    /// * `create` - IDとDNAから個体を生成する関数
+    /// * `rng` - 乱数生成器
+    ///
+    /// # エラー
+    /// 定住者と変異体のDNAが同じ場合
+    pub fn run<P, T, F, R>(
+        &self,
+        process: &P,
+        resident: &str,
+        mutant: &str,
+        matrix: &PayoffMatrix,
+        create: F,
+        rng: &mut R,
+    ) -> GAResult<FixationResult>
+    where
+        P: EvolutionaryProcess,
+        T: Agent,
+        F: Fn(AgentId, Dna) -> T,
+        R: Rng,
+    {
+        if resident == mutant {
+            return Err(GAError::InvalidDna(
+                "Mutant DNA must differ from resident DNA".to_string(),
+            ));
+        }
+
+        let step_length = process.generations_per_step(self.population_size);
+        let mut fixation_times = Vec::new();
+        let mut extinction_times = Vec::new();
+
+        for _ in 0..self.replicates {
+            let mut agents: Vec<T> = (0..self.population_size)
+                .map(|i| {
+                    let dna = if i == 0 { mutant } else { resident };
+                    create(i as AgentId, dna.to_string())
+                })
+                .collect();
+
+            for step in 1..=self.max_steps {
+                process.step(&mut agents, matrix, rng);
+                let mutants = agents.iter().filter(|a| a.dna_binary() == mutant).count();
+                if mutants == self.population_size {
+                    fixation_times.push(step as f64 * step_length);
+                    break;
+                }
+                if mutants == 0 {
+                    extinction_times.push(step as f64 * step_length);
+                    break;
+                }
+            }
+        }
+
+        let mean = |times: &[f64]| {
+            (!times.is_empty()).then(|| times.iter().sum::<f64>() / times.len() as f64)
+        };
+
+        Ok(FixationResult {
+            replicates: self.replicates,
+            fixations: fixation_times.len(),
+            extinctions: extinction_times.len(),
+            mean_fixation_time: mean(&fixation_times),
+            mean_extinction_time: mean(&extinction_times),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::dynamics::{MoranProcess, MoranUpdate, WrightFisherProcess};
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn create(id: AgentId, dna: Dna) -> ModelAgent {
+        ModelAgent::new(id, dna)
+    }
+
+    #[test]
+    fn test_moran_matches_analytic_fixation_probability() {
+        let matrix = PayoffMatrix::default();
+        let moran = MoranProcess::new(MoranUpdate::BirthDeath, 0.2).unwrap();
+        let experiment = FixationExperiment::new(6, 4000, 10_000).unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let result = experiment
+            .run(&moran, "1111", "0000", &matrix, create, &mut rng)
+            .unwrap();
+        let expected = moran.fixation_probability(0.0, 1.0, 6, &matrix);
+
+        assert_eq!(result.unresolved(), 0);
+        assert!(result.to_string().starts_with("fixation probability: "));
+        assert!((result.fixation_probability() - expected).abs() < 0.03);
+        assert!(result.mean_fixation_time.unwrap() > result.mean_extinction_time.unwrap());
+    }
+
+    #[test]
+    fn test_wright_fisher_neutral_fixation() {
+        let process = WrightFisherProcess::new(0.0).unwrap();
+        let experiment = FixationExperiment::new(5, 4000, 10_000).unwrap();
+        let mut rng = StdRng::seed_from_u64(11);
+
+        let result = experiment
+            .run(
+                &process,
+                "11",
+                "00",
+                &PayoffMatrix::default(),
+                create,
+                &mut rng,
+            )
+            .unwrap();
+        assert!((result.fixation_probability() - 0.2).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_invalid_experiments() {
+        assert!(FixationExperiment::new(1, 10, 10).is_err());
+        assert!(FixationExperiment::new(5, 0, 10).is_err());
+
+        let experiment = FixationExperiment::new(5, 1, 10).unwrap();
+        let moran = MoranProcess::new(MoranUpdate::DeathBirth, 1.0).unwrap();
+        let result = experiment.run(
+            &moran,
+            "11",
+            "11",
+            &PayoffMatrix::default(),
+            create,
+            &mut StdRng::seed_from_u64(0),
+        );
+        assert!(result.is_err());
+    }
+}
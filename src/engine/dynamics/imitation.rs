@@ -0,0 +1,359 @@
+/// 模倣ダイナミクス（文化進化）
+///
+/// 遺伝的な繁殖の代わりに、各個体が他の個体（手本）の戦略を社会的学習で写し取る
+/// 更新規則です。全個体が前世代の利得をもとに同時に手本を選び、手本の戦略を
+/// 写します（同期更新）。手本の候補は近傍構造（`Neighbourhood`）で決まり、
+/// よく混ざった個体群では自分以外の全個体、構造化された個体群では近傍の個体です。
+///
+/// 利得は尺度をそろえるため、1回の対戦あたりの平均利得で与えます。
+use super::validate_intensity;
+use crate::core::errors::*;
+use crate::core::types::PayoffMatrix;
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+/// フェルミ則の選択強度の既定値
+pub const DEFAULT_IMITATION_INTENSITY: f64 = 1.0;
+
+/// 手本の戦略を写すかどうかを決める更新規則
+///
+/// 文字列表現は`fermi[:BETA]`、`best`、`proportional`です（例: `fermi:0.5`）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImitationRule {
+    /// 無作為に選んだ手本`B`の戦略を確率`1 / (1 + exp(-β(π_B - π_A)))`で写す
+    ///
+    /// β = 0では利得によらず確率1/2、βが大きいほど利得の高い手本だけを写します。
+    Fermi { intensity: f64 },
+    /// 自分と近傍の中で最も利得の高い個体の戦略を写す（自分が最高なら変えない）
+    BestNeighbour,
+    /// 無作為に選んだ手本の利得が高い場合に、利得の差に比例する確率で写す
+    ///
+    /// 確率は利得の差を利得の最大差（`T - S`）で割った値です。
+    Proportional,
+}
+
+impl ImitationRule {
+    /// パラメータの妥当性を検証
+    ///
+    /// # エラー
+    /// フェルミ則の選択強度が負または有限でない場合
+    pub fn validate(&self) -> GAResult<()> {
+        match self {
+            ImitationRule::Fermi { intensity } => validate_intensity(*intensity),
+            ImitationRule::BestNeighbour | ImitationRule::Proportional => Ok(()),
+        }
+    }
+
+    /// 利得`own`の個体が利得`model`の手本の戦略を写す確率
+    ///
+    /// 最良近傍の模倣では、手本の利得が高ければ1、そうでなければ0です。
+    pub fn adoption_probability(&self, own: f64, model: f64) -> f64 {
+        match self {
+            ImitationRule::Fermi { intensity } => 1.0 / (1.0 + (-intensity * (model - own)).exp()),
+            ImitationRule::BestNeighbour => {
+                if model > own {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            ImitationRule::Proportional => {
+                let matrix = PayoffMatrix::default();
+                let range = (matrix.temptation - matrix.sucker) as f64;
+                ((model - own) / range).clamp(0.0, 1.0)
+            }
+        }
+    }
+
+    /// 各個体が次の世代に戦略を写す手本を決める
+    ///
+    /// # 引数
+    /// * `payoffs` - 各個体の1回の対戦あたりの平均利得
+    /// * `neighbourhood` - 手本の候補を決める近傍構造
+    /// * `rng` - 乱数生成器
+    ///
+    /// # 戻り値
+    /// 各個体の手本の添字（戦略を変えない個体は自分自身の添字）
+    pub fn models<R: Rng>(
+        &self,
+        payoffs: &[f64],
+        neighbourhood: &Neighbourhood,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        let n = payoffs.len();
+        (0..n)
+            .map(|i| {
+                let model = match self {
+                    ImitationRule::BestNeighbour => neighbourhood
+                        .neighbours(i, n)
+                        .into_iter()
+                        .fold(
+                            i,
+                            |best, j| if payoffs[j] > payoffs[best] { j } else { best },
+                        ),
+                    _ => match neighbourhood.sample(i, n, rng) {
+                        Some(j) => j,
+                        None => return i,
+                    },
+                };
+                let probability = self.adoption_probability(payoffs[i], payoffs[model]);
+                if probability >= 1.0 || (probability > 0.0 && rng.gen_bool(probability)) {
+                    model
+                } else {
+                    i
+                }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for ImitationRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImitationRule::Fermi { intensity } => write!(f, "fermi:{intensity}"),
+            ImitationRule::BestNeighbour => write!(f, "best"),
+            ImitationRule::Proportional => write!(f, "proportional"),
+        }
+    }
+}
+
+impl FromStr for ImitationRule {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid imitation rule: {s}"));
+        let rule = match s.split(':').collect::<Vec<_>>().as_slice() {
+            ["fermi"] => ImitationRule::Fermi {
+                intensity: DEFAULT_IMITATION_INTENSITY,
+            },
+            ["fermi", intensity] => ImitationRule::Fermi {
+                intensity: intensity.parse().map_err(|_| invalid())?,
+            },
+            ["best"] => ImitationRule::BestNeighbour,
+            ["proportional"] => ImitationRule::Proportional,
+            _ => return Err(invalid()),
+        };
+
+        rule.validate()?;
+        Ok(rule)
+    }
+}
+
+/// 個体群の近傍構造（誰と対戦し、誰を手本にできるか）
+///
+/// 文字列表現は`well-mixed`、`ring:RADIUS`、`lattice:WIDTH`です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Neighbourhood {
+    /// 自分以外の全個体が近傍
+    #[default]
+    WellMixed,
+    /// 環状に並べ、左右`radius`体ずつが近傍
+    Ring { radius: usize },
+    /// 幅`width`の周期境界の格子に並べ、上下左右の4体が近傍（フォン・ノイマン近傍）
+    Lattice { width: usize },
+}
+
+impl Neighbourhood {
+    /// 個体数`population`に対する妥当性を検証
+    ///
+    /// # エラー
+    /// 環の半径が0か個体群を一周する場合、または格子の幅が個体数を割り切らない場合
+    pub fn validate(&self, population: usize) -> GAResult<()> {
+        match *self {
+            Neighbourhood::WellMixed => Ok(()),
+            Neighbourhood::Ring { radius } if radius == 0 || 2 * radius >= population => {
+                Err(GAError::ValidationError(format!(
+                    "Ring radius must be at least 1 and less than half the population ({population}), got {radius}"
+                )))
+            }
+            Neighbourhood::Lattice { width } if width == 0 || population % width != 0 => {
+                Err(GAError::ValidationError(format!(
+                    "Lattice width must divide the population ({population}), got {width}"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 近傍が個体群の一部に限られるかどうか
+    pub fn is_structured(&self) -> bool {
+        *self != Neighbourhood::WellMixed
+    }
+
+    /// 個体数`population`の個体群での`index`番目の個体の近傍（自分を含まず重複なし）
+    pub fn neighbours(&self, index: usize, population: usize) -> Vec<usize> {
+        let mut neighbours: Vec<usize> = match *self {
+            Neighbourhood::WellMixed => (0..population).collect(),
+            Neighbourhood::Ring { radius } => (1..=radius)
+                .flat_map(|d| {
+                    let d = d % population;
+                    [
+                        (index + d) % population,
+                        (index + population - d) % population,
+                    ]
+                })
+                .collect(),
+            Neighbourhood::Lattice { width } => {
+                let height = population / width.max(1);
+                let (row, column) = (index / width, index % width);
+                vec![
+                    ((row + height - 1) % height) * width + column,
+                    ((row + 1) % height) * width + column,
+                    row * width + (column + width - 1) % width,
+                    row * width + (column + 1) % width,
+                ]
+            }
+        };
+        neighbours.sort_unstable();
+        neighbours.dedup();
+        neighbours.retain(|&j| j != index);
+        neighbours
+    }
+
+    /// 近傍から一様に1体を選ぶ（近傍がなければ`None`）
+    ///
+    /// よく混ざった個体群では近傍の一覧を作らずに選びます。
+    pub fn sample<R: Rng>(&self, index: usize, population: usize, rng: &mut R) -> Option<usize> {
+        match self {
+            Neighbourhood::WellMixed if population < 2 => None,
+            Neighbourhood::WellMixed => {
+                let j = rng.gen_range(0..population - 1);
+                Some(if j >= index { j + 1 } else { j })
+            }
+            _ => {
+                let neighbours = self.neighbours(index, population);
+                if neighbours.is_empty() {
+                    None
+                } else {
+                    Some(neighbours[rng.gen_range(0..neighbours.len())])
+                }
+            }
+        }
+    }
+
+    /// 対戦する組`(i, j)`（`i < j`）の一覧
+    pub fn pairs(&self, population: usize) -> Vec<(usize, usize)> {
+        (0..population)
+            .flat_map(|i| {
+                self.neighbours(i, population)
+                    .into_iter()
+                    .filter(move |&j| j > i)
+                    .map(move |j| (i, j))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Neighbourhood {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Neighbourhood::WellMixed => write!(f, "well-mixed"),
+            Neighbourhood::Ring { radius } => write!(f, "ring:{radius}"),
+            Neighbourhood::Lattice { width } => write!(f, "lattice:{width}"),
+        }
+    }
+}
+
+impl FromStr for Neighbourhood {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid neighbourhood: {s}"));
+        match s.split(':').collect::<Vec<_>>().as_slice() {
+            ["well-mixed"] => Ok(Neighbourhood::WellMixed),
+            ["ring", radius] => Ok(Neighbourhood::Ring {
+                radius: radius.parse().map_err(|_| invalid())?,
+            }),
+            ["lattice", width] => Ok(Neighbourhood::Lattice {
+                width: width.parse().map_err(|_| invalid())?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_parse_round_trip() {
+        for text in ["fermi:0.5", "best", "proportional"] {
+            let rule: ImitationRule = text.parse().unwrap();
+            assert_eq!(rule.to_string(), text);
+        }
+        assert_eq!(
+            "fermi".parse::<ImitationRule>().unwrap(),
+            ImitationRule::Fermi {
+                intensity: DEFAULT_IMITATION_INTENSITY
+            }
+        );
+        assert!("fermi:-1".parse::<ImitationRule>().is_err());
+        assert!("best:1".parse::<ImitationRule>().is_err());
+
+        for text in ["well-mixed", "ring:2", "lattice:4"] {
+            let neighbourhood: Neighbourhood = text.parse().unwrap();
+            assert_eq!(neighbourhood.to_string(), text);
+        }
+        assert!("ring".parse::<Neighbourhood>().is_err());
+        assert!("torus:3".parse::<Neighbourhood>().is_err());
+    }
+
+    #[test]
+    fn test_neighbourhoods() {
+        assert_eq!(Neighbourhood::WellMixed.neighbours(1, 4), vec![0, 2, 3]);
+        assert_eq!(
+            Neighbourhood::Ring { radius: 2 }.neighbours(0, 10),
+            vec![1, 2, 8, 9]
+        );
+        // 3x3の周期境界の格子の中央と角
+        let lattice = Neighbourhood::Lattice { width: 3 };
+        assert_eq!(lattice.neighbours(4, 9), vec![1, 3, 5, 7]);
+        assert_eq!(lattice.neighbours(0, 9), vec![1, 2, 3, 6]);
+        assert_eq!(lattice.pairs(9).len(), 18);
+        assert_eq!(Neighbourhood::WellMixed.pairs(5).len(), 10);
+
+        assert!(Neighbourhood::Ring { radius: 5 }.validate(10).is_err());
+        assert!(Neighbourhood::Lattice { width: 4 }.validate(10).is_err());
+        assert!(lattice.validate(9).is_ok());
+
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let j = Neighbourhood::WellMixed.sample(2, 5, &mut rng).unwrap();
+            assert!(j < 5 && j != 2);
+        }
+        assert_eq!(Neighbourhood::WellMixed.sample(0, 1, &mut rng), None);
+    }
+
+    #[test]
+    fn test_adoption_probability() {
+        let fermi = ImitationRule::Fermi { intensity: 0.0 };
+        assert_eq!(fermi.adoption_probability(1.0, 3.0), 0.5);
+        let strong = ImitationRule::Fermi { intensity: 10.0 };
+        assert!(strong.adoption_probability(1.0, 3.0) > 0.99);
+        assert!(strong.adoption_probability(3.0, 1.0) < 0.01);
+
+        // 比例模倣は利得の低い手本を写さず、差が最大（T - S）なら必ず写す
+        let proportional = ImitationRule::Proportional;
+        assert_eq!(proportional.adoption_probability(3.0, 1.0), 0.0);
+        assert_eq!(proportional.adoption_probability(1.0, 3.5), 0.5);
+        assert_eq!(proportional.adoption_probability(0.0, 5.0), 1.0);
+    }
+
+    #[test]
+    fn test_best_neighbour_copies_local_maximum() {
+        let payoffs = [1.0, 4.0, 2.0, 0.0, 3.0, 2.5];
+        let ring = Neighbourhood::Ring { radius: 1 };
+        let models = ImitationRule::BestNeighbour.models(&payoffs, &ring, &mut thread_rng());
+        assert_eq!(models, vec![1, 1, 1, 4, 4, 4]);
+
+        let global = ImitationRule::BestNeighbour.models(
+            &payoffs,
+            &Neighbourhood::WellMixed,
+            &mut thread_rng(),
+        );
+        assert_eq!(global, vec![1; 6]);
+    }
+}
@@ -0,0 +1,136 @@
+/// 進化ダイナミクス
+///
+/// 遺伝的アルゴリズムの世代交代（`ga::algorithm::create_next_generation`）とは別に、
+/// 集団遺伝学の標準的な出生死亡過程であるMoran過程とWright–Fisher過程、
+/// 無限個体群の決定論的なレプリケーター方程式、および社会的学習による
+/// 模倣ダイナミクスを提供します。
+/// いずれも同じ個体群と利得表（`PayoffMatrix`）を使い、個体の戦略は
+/// 確率的戦略（DNA中の'1'の割合で協力）として扱います。
+pub mod fixation;
+pub mod imitation;
+pub mod moran;
+pub mod replicator;
+pub mod wright_fisher;
+
+pub use fixation::{FixationExperiment, FixationResult};
+pub use imitation::{ImitationRule, Neighbourhood};
+pub use moran::{MoranProcess, MoranUpdate};
+pub use replicator::{ReplicatorDynamics, RestPoint, Stability, Trajectory};
+pub use wright_fisher::WrightFisherProcess;
+
+use crate::core::errors::*;
+use crate::core::traits::*;
+use crate::core::types::PayoffMatrix;
+use crate::strategies::decoding::Decoding;
+use crate::strategies::utils::cooperation_probability;
+use rand::Rng;
+
+/// 個体群を1回ずつ更新する進化過程
+pub trait EvolutionaryProcess {
+    /// 個体群を1ステップ更新
+    fn step<T: Agent, R: Rng>(&self, agents: &mut [T], matrix: &PayoffMatrix, rng: &mut R);
+
+    /// 1ステップが何世代に相当するか（Moran過程では`1 / N`、Wright–Fisher過程では1）
+    fn generations_per_step(&self, population_size: usize) -> f64;
+}
+
+/// 各個体が自分以外の全個体と対戦した時の平均期待利得
+///
+/// 期待利得は相手の協力確率について線形なので、自分以外の平均協力確率との
+/// 期待利得として`O(N)`で計算します。協力確率は`decoding`でDNAをデコードした値です。
+pub fn expected_payoffs<T: DnaOperations>(
+    agents: &[T],
+    matrix: &PayoffMatrix,
+    decoding: &Decoding,
+) -> Vec<f64> {
+    let probabilities: Vec<f64> = agents.iter().map(|a| cooperation(a, decoding)).collect();
+    let total: f64 = probabilities.iter().sum();
+    let others = agents.len().saturating_sub(1).max(1) as f64;
+
+    probabilities
+        .iter()
+        .map(|&p| matrix.expected_payoff(p, (total - p) / others))
+        .collect()
+}
+
+/// 個体の協力確率
+///
+/// 既定の`Decoding::Unary`では保持している'1'の数をそのまま使い、DNAを走査しません。
+pub(crate) fn cooperation<T: DnaOperations>(agent: &T, decoding: &Decoding) -> f64 {
+    match decoding {
+        Decoding::Unary => cooperation_probability(agent.dna_sum(), agent.dna_length()),
+        _ => decoding.decode(agent.dna_binary()),
+    }
+}
+
+/// 選択強度`intensity`（β）での適応度 `exp(β·π)`
+///
+/// β = 0で中立（全個体の適応度が等しい）、βが大きいほど利得の差が強く効きます。
+pub fn selection_fitness(payoff: f64, intensity: f64) -> f64 {
+    (intensity * payoff).exp()
+}
+
+/// 選択強度を検証
+pub(crate) fn validate_intensity(intensity: f64) -> GAResult<()> {
+    if !(intensity >= 0.0 && intensity.is_finite()) {
+        return Err(GAError::ValidationError(
+            "Selection intensity must be a non-negative finite number".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 個体群の適応度（`exp(β·π)`）をまとめて計算
+///
+/// 数値のオーバーフローを避けるため、最大利得を引いてから指数を取ります
+/// （比例選択の確率は変わりません）。
+pub(crate) fn fitness_weights<T: DnaOperations>(
+    agents: &[T],
+    matrix: &PayoffMatrix,
+    decoding: &Decoding,
+    intensity: f64,
+) -> Vec<f64> {
+    let payoffs = expected_payoffs(agents, matrix, decoding);
+    let max = payoffs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    payoffs
+        .into_iter()
+        .map(|payoff| selection_fitness(payoff - max, intensity))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    #[test]
+    fn test_expected_payoffs_exclude_self() {
+        // 協力者1体と裏切り者2体: 協力者は常に搾取され、裏切り者は半分の相手から搾取する
+        let agents = vec![
+            ModelAgent::new(0, "11".to_string()),
+            ModelAgent::new(1, "00".to_string()),
+            ModelAgent::new(2, "00".to_string()),
+        ];
+        let payoffs = expected_payoffs(&agents, &PayoffMatrix::default(), &Decoding::Unary);
+        assert_eq!(payoffs, vec![0.0, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn test_expected_payoffs_follow_decoding() {
+        // "01"は'1'の割合では0.5だが2進小数では0.25、"10"は索引表"0001"では0（索引2）
+        let agents = vec![
+            ModelAgent::new(0, "10".to_string()),
+            ModelAgent::new(1, "01".to_string()),
+        ];
+        let matrix = PayoffMatrix::default();
+        let table = Decoding::lookup_table("0001").unwrap();
+        assert_eq!(cooperation(&agents[0], &Decoding::Unary), 0.5);
+        assert_eq!(cooperation(&agents[1], &Decoding::Binary), 0.25);
+        assert_eq!(cooperation(&agents[0], &table), 0.0);
+
+        let binary = expected_payoffs(&agents, &matrix, &Decoding::Binary);
+        assert_eq!(binary[0], matrix.expected_payoff(0.5, 0.25));
+        assert_eq!(binary[1], matrix.expected_payoff(0.25, 0.5));
+        assert_ne!(binary, expected_payoffs(&agents, &matrix, &Decoding::Unary));
+    }
+}
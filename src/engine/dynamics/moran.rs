@@ -0,0 +1,169 @@
+/// Moran過程
+///
+/// 1ステップで1体が生まれ1体が死ぬ、個体数一定の出生死亡過程です。
+/// 出生死亡型（Birth–Death）では適応度に比例して親を選んでから死ぬ個体を一様に選び、
+/// 死亡出生型（Death–Birth）では死ぬ個体を一様に選んでから残りの個体の中から
+/// 適応度に比例して親を選びます。
+use super::{fitness_weights, validate_intensity, EvolutionaryProcess};
+use crate::core::{errors::*, traits::*, types::*};
+use crate::strategies::decoding::Decoding;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+
+/// Moran過程の更新順序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MoranUpdate {
+    /// 出生→死亡
+    #[default]
+    BirthDeath,
+    /// 死亡→出生
+    DeathBirth,
+}
+
+/// Moran過程
+///
+/// # フィールド
+/// * `update` - 更新順序
+/// * `intensity` - 選択強度β（適応度は`exp(β·π)`）
+/// * `decoding` - DNAから協力確率へのデコード方式
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoranProcess {
+    update: MoranUpdate,
+    intensity: f64,
+    decoding: Decoding,
+}
+
+impl MoranProcess {
+    /// 更新順序と選択強度を指定して作成
+    ///
+    /// # エラー
+    /// 選択強度が負または有限でない場合
+    pub fn new(update: MoranUpdate, intensity: f64) -> GAResult<Self> {
+        validate_intensity(intensity)?;
+        Ok(Self {
+            update,
+            intensity,
+            decoding: Decoding::default(),
+        })
+    }
+
+    /// DNAから協力確率へのデコード方式を指定（既定は`Decoding::Unary`）
+    pub fn with_decoding(mut self, decoding: Decoding) -> Self {
+        self.decoding = decoding;
+        self
+    }
+
+    /// 出生死亡型Moran過程で、`N`体中`1`体の変異体が固定する確率の解析解
+    ///
+    /// 変異体`i`体のときの変異体と定住者の平均期待利得から、
+    /// `ρ = 1 / (1 + Σ_{k=1}^{N-1} Π_{i=1}^{k} g_i / f_i)`を計算します。
+    ///
+    /// # 引数
+    /// * `mutant` - 変異体の協力確率
+    /// * `resident` - 定住者の協力確率
+    /// * `population_size` - 個体数（2以上）
+    /// * `matrix` - 利得表
+    pub fn fixation_probability(
+        &self,
+        mutant: f64,
+        resident: f64,
+        population_size: usize,
+        matrix: &PayoffMatrix,
+    ) -> f64 {
+        let n = population_size as f64;
+        let a = matrix.expected_payoff(mutant, mutant);
+        let b = matrix.expected_payoff(mutant, resident);
+        let c = matrix.expected_payoff(resident, mutant);
+        let d = matrix.expected_payoff(resident, resident);
+
+        let mut sum = 0.0;
+        let mut product = 1.0;
+        for i in 1..population_size {
+            let i = i as f64;
+            let mutant_payoff = ((i - 1.0) * a + (n - i) * b) / (n - 1.0);
+            let resident_payoff = (i * c + (n - i - 1.0) * d) / (n - 1.0);
+            product *= (self.intensity * (resident_payoff - mutant_payoff)).exp();
+            sum += product;
+        }
+
+        1.0 / (1.0 + sum)
+    }
+}
+
+impl EvolutionaryProcess for MoranProcess {
+    fn step<T: Agent, R: Rng>(&self, agents: &mut [T], matrix: &PayoffMatrix, rng: &mut R) {
+        if agents.len() < 2 {
+            return;
+        }
+
+        let mut weights = fitness_weights(agents, matrix, &self.decoding, self.intensity);
+        let (parent, dead) = match self.update {
+            MoranUpdate::BirthDeath => {
+                let parent = WeightedIndex::new(&weights)
+                    .map(|d| d.sample(rng))
+                    .unwrap_or_else(|_| rng.gen_range(0..agents.len()));
+                (parent, rng.gen_range(0..agents.len()))
+            }
+            MoranUpdate::DeathBirth => {
+                let dead = rng.gen_range(0..agents.len());
+                weights[dead] = 0.0;
+                let parent = WeightedIndex::new(&weights)
+                    .map(|d| d.sample(rng))
+                    .unwrap_or((dead + 1) % agents.len());
+                (parent, dead)
+            }
+        };
+
+        if parent != dead {
+            agents[dead] = agents[parent].clone();
+        }
+    }
+
+    fn generations_per_step(&self, population_size: usize) -> f64 {
+        1.0 / population_size.max(1) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+    use rand::thread_rng;
+
+    #[test]
+    fn test_neutral_fixation_probability_is_one_over_n() {
+        let moran = MoranProcess::new(MoranUpdate::BirthDeath, 0.0).unwrap();
+        let rho = moran.fixation_probability(0.0, 1.0, 10, &PayoffMatrix::default());
+        assert!((rho - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_defector_invades_cooperators() {
+        let moran = MoranProcess::new(MoranUpdate::BirthDeath, 1.0).unwrap();
+        let matrix = PayoffMatrix::default();
+        assert!(moran.fixation_probability(0.0, 1.0, 10, &matrix) > 0.1);
+        assert!(moran.fixation_probability(1.0, 0.0, 10, &matrix) < 0.1);
+    }
+
+    #[test]
+    fn test_step_keeps_population_size() {
+        let mut agents: Vec<ModelAgent> = (0..5)
+            .map(|i| ModelAgent::new(i, if i == 0 { "00" } else { "11" }.to_string()))
+            .collect();
+        let mut rng = thread_rng();
+
+        for update in [MoranUpdate::BirthDeath, MoranUpdate::DeathBirth] {
+            let moran = MoranProcess::new(update, 0.5).unwrap();
+            for _ in 0..20 {
+                moran.step(&mut agents, &PayoffMatrix::default(), &mut rng);
+            }
+            assert_eq!(agents.len(), 5);
+        }
+    }
+
+    #[test]
+    fn test_invalid_intensity() {
+        assert!(MoranProcess::new(MoranUpdate::BirthDeath, -1.0).is_err());
+        assert!(MoranProcess::new(MoranUpdate::DeathBirth, f64::NAN).is_err());
+    }
+}
@@ -0,0 +1,531 @@
+/// レプリケーター方程式による無限個体群のダイナミクス
+///
+/// 有限個の戦略の頻度`x`について、レプリケーター方程式
+/// `ẋ_i = x_i ((Ax)_i − xᵀAx)` と、突然変異を含むレプリケーター・ミューテーター方程式
+/// `ẋ_i = Σ_j x_j (Ax)_j Q_ji − φ x_i`（`φ = xᵀAx`）を4次のルンゲ＝クッタ法で数値積分します。
+/// 戦略はGAと同じDNA文字列（デコードした値の確率で協力する確率的戦略）で、利得はGAと同じ
+/// `PayoffMatrix`の期待利得です。`Simulation`の確率的な結果に決定論的な予測を
+/// 重ねて比較するために使用します。
+use crate::core::{errors::*, traits::*, types::*};
+use crate::strategies::decoding::Decoding;
+
+/// 静止点の近傍とみなす速度ベクトルのノルム
+const REST_TOLERANCE: f64 = 1e-8;
+
+/// 静止点探索・安定性判定で使う積分の刻み幅
+const SEARCH_STEP: f64 = 0.01;
+
+/// 静止点探索で1つの初期値から積分する最大ステップ数
+const SEARCH_STEPS: usize = 50_000;
+
+/// 安定性判定で静止点に与える摂動の大きさ
+const PERTURBATION: f64 = 1e-3;
+
+/// 静止点の安定性
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stability {
+    /// 漸近安定（近傍の軌道が静止点に戻る）
+    Stable,
+    /// 不安定（近傍から離れる軌道がある）
+    Unstable,
+}
+
+/// 静止点
+///
+/// # フィールド
+/// * `state` - 各戦略の頻度
+/// * `stability` - 数値的に判定した安定性
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestPoint {
+    pub state: Vec<f64>,
+    pub stability: Stability,
+}
+
+/// 数値積分の軌道
+///
+/// # フィールド
+/// * `times` - 記録した時刻（1単位がおおよそGAの1世代に相当）
+/// * `states` - 各時刻での戦略頻度
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trajectory {
+    pub times: Vec<f64>,
+    pub states: Vec<Vec<f64>>,
+}
+
+impl Trajectory {
+    /// 最後に記録した戦略頻度
+    pub fn final_state(&self) -> &[f64] {
+        self.states.last().map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+/// レプリケーター（・ミューテーター）ダイナミクス
+///
+/// # フィールド
+/// * `strategies` - 戦略を表すDNA
+/// * `decoding` - DNAから協力確率へのデコード方式
+/// * `cooperation` - 各戦略の協力確率
+/// * `payoffs` - `payoffs[i][j]`は戦略iが戦略jと対戦した時の期待利得
+/// * `mutation` - `mutation[i][j]`は戦略iの子が戦略jになる確率（なければ純粋なレプリケーター方程式）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicatorDynamics {
+    strategies: Vec<Dna>,
+    decoding: Decoding,
+    cooperation: Vec<f64>,
+    payoffs: Vec<Vec<f64>>,
+    mutation: Option<Vec<Vec<f64>>>,
+}
+
+impl ReplicatorDynamics {
+    /// 戦略のDNAと利得表から作成（協力確率は'1'の割合）
+    ///
+    /// # エラー
+    /// 戦略が空、重複している、またはDNAが不正な場合
+    pub fn new(strategies: Vec<Dna>, matrix: &PayoffMatrix) -> GAResult<Self> {
+        Self::with_decoding(strategies, matrix, Decoding::default())
+    }
+
+    /// 戦略のDNAと利得表から、DNAを`decoding`でデコードした協力確率で作成
+    ///
+    /// # エラー
+    /// 戦略が空、重複している、DNAが不正、またはデコード方式が不正な場合
+    pub fn with_decoding(
+        strategies: Vec<Dna>,
+        matrix: &PayoffMatrix,
+        decoding: Decoding,
+    ) -> GAResult<Self> {
+        decoding.validate()?;
+        if strategies.is_empty() {
+            return Err(GAError::EmptyPopulation);
+        }
+        for (i, dna) in strategies.iter().enumerate() {
+            validation::validate_dna(dna)?;
+            if strategies[..i].contains(dna) {
+                return Err(GAError::ValidationError(format!(
+                    "Duplicate strategy: {dna}"
+                )));
+            }
+        }
+
+        let cooperation: Vec<f64> = strategies.iter().map(|dna| decoding.decode(dna)).collect();
+        let payoffs = cooperation
+            .iter()
+            .map(|&p| {
+                cooperation
+                    .iter()
+                    .map(|&q| matrix.expected_payoff(p, q))
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            strategies,
+            decoding,
+            cooperation,
+            payoffs,
+            mutation: None,
+        })
+    }
+
+    /// GAと同じビット反転突然変異を加えたレプリケーター・ミューテーター方程式にする
+    ///
+    /// 戦略iの子が戦略jになる確率は、ハミング距離`h`とDNA長`L`から
+    /// `μ^h (1 − μ)^(L − h)`を戦略集合内で正規化した値です。
+    ///
+    /// # エラー
+    /// 突然変異率が範囲外、または戦略のDNA長が揃っていない場合
+    pub fn with_mutation(mut self, mutation_rate: MutationRate) -> GAResult<Self> {
+        validation::validate_mutation_rate(mutation_rate)?;
+        let length = self.strategies[0].len();
+        if self.strategies.iter().any(|dna| dna.len() != length) {
+            return Err(GAError::InvalidDna(
+                "Mutation requires strategies of equal DNA length".to_string(),
+            ));
+        }
+
+        let mutation = self
+            .strategies
+            .iter()
+            .map(|from| {
+                let row: Vec<f64> = self
+                    .strategies
+                    .iter()
+                    .map(|to| {
+                        let flips = from.bytes().zip(to.bytes()).filter(|(a, b)| a != b).count();
+                        mutation_rate.powi(flips as i32)
+                            * (1.0 - mutation_rate).powi((length - flips) as i32)
+                    })
+                    .collect();
+                let total: f64 = row.iter().sum();
+                row.into_iter().map(|q| q / total).collect()
+            })
+            .collect();
+
+        self.mutation = Some(mutation);
+        Ok(self)
+    }
+
+    pub fn strategies(&self) -> &[Dna] {
+        &self.strategies
+    }
+
+    pub fn payoffs(&self) -> &[Vec<f64>] {
+        &self.payoffs
+    }
+
+    /// GAの個体群から各戦略の頻度を求める
+    ///
+    /// DNAが一致する戦略に数え、一致する戦略がない個体は協力確率が最も近い
+    /// （同じならハミング距離が最も近い）戦略に数えます。
+    pub fn frequencies<T: DnaOperations>(&self, agents: &[T]) -> Vec<f64> {
+        let mut counts = vec![0.0; self.strategies.len()];
+        for agent in agents {
+            let p = super::cooperation(agent, &self.decoding);
+            let nearest = (0..self.strategies.len())
+                .min_by(|&a, &b| {
+                    let key = |i: usize| {
+                        let dna = &self.strategies[i];
+                        let distance = if dna.len() == agent.dna_length() {
+                            dna.bytes()
+                                .zip(agent.dna_binary().bytes())
+                                .filter(|(x, y)| x != y)
+                                .count()
+                        } else {
+                            usize::MAX
+                        };
+                        ((self.cooperation[i] - p).abs(), distance)
+                    };
+                    let (pa, da) = key(a);
+                    let (pb, db) = key(b);
+                    pa.total_cmp(&pb).then(da.cmp(&db))
+                })
+                .unwrap_or(0);
+            counts[nearest] += 1.0;
+        }
+
+        let total = agents.len().max(1) as f64;
+        counts.into_iter().map(|c| c / total).collect()
+    }
+
+    /// 戦略頻度`state`での時間微分
+    pub fn velocity(&self, state: &[f64]) -> Vec<f64> {
+        let fitness: Vec<f64> = self
+            .payoffs
+            .iter()
+            .map(|row| row.iter().zip(state).map(|(a, x)| a * x).sum())
+            .collect();
+        let mean: f64 = fitness.iter().zip(state).map(|(f, x)| f * x).sum();
+
+        match &self.mutation {
+            None => state
+                .iter()
+                .zip(&fitness)
+                .map(|(x, f)| x * (f - mean))
+                .collect(),
+            Some(mutation) => (0..state.len())
+                .map(|i| {
+                    let inflow: f64 = (0..state.len())
+                        .map(|j| state[j] * fitness[j] * mutation[j][i])
+                        .sum();
+                    inflow - mean * state[i]
+                })
+                .collect(),
+        }
+    }
+
+    /// 初期頻度から数値積分
+    ///
+    /// # 引数
+    /// * `initial` - 初期頻度（合計1に正規化されます）
+    /// * `dt` - 刻み幅（負の値で時間を遡ります）
+    /// * `steps` - 積分するステップ数
+    /// * `record_every` - 何ステップごとに軌道を記録するか
+    ///
+    /// # エラー
+    /// 初期頻度の次元が戦略数と一致しない、負の成分がある、合計が0の場合、
+    /// または刻み幅・記録間隔が0の場合
+    pub fn integrate(
+        &self,
+        initial: &[f64],
+        dt: f64,
+        steps: usize,
+        record_every: usize,
+    ) -> GAResult<Trajectory> {
+        if initial.len() != self.strategies.len() {
+            return Err(GAError::ValidationError(format!(
+                "Initial state must have {} components",
+                self.strategies.len()
+            )));
+        }
+        if initial.iter().any(|&x| x < 0.0 || !x.is_finite()) || initial.iter().sum::<f64>() <= 0.0
+        {
+            return Err(GAError::ValidationError(
+                "Initial state must be non-negative with a positive sum".to_string(),
+            ));
+        }
+        if dt == 0.0 || !dt.is_finite() || record_every == 0 {
+            return Err(GAError::ValidationError(
+                "Step size and record interval must be non-zero".to_string(),
+            ));
+        }
+
+        let mut state = Self::project(initial.to_vec());
+        let mut trajectory = Trajectory {
+            times: vec![0.0],
+            states: vec![state.clone()],
+        };
+
+        for step in 1..=steps {
+            state = self.rk4_step(&state, dt);
+            if step % record_every == 0 {
+                trajectory.times.push(step as f64 * dt);
+                trajectory.states.push(state.clone());
+            }
+        }
+
+        Ok(trajectory)
+    }
+
+    /// 静止点を探索し、安定性を判定
+    ///
+    /// 単体の頂点・辺の中点・重心・各頂点寄りの内点を初期値として、時間の順方向
+    /// （安定な静止点に収束）と逆方向（不安定な静止点に収束）に積分し、速度が
+    /// ほぼ0になった点を静止点とします。安定性は、静止点を各頂点の方向へ
+    /// わずかにずらした点から順方向に積分して元の点に戻るかで判定します。
+    pub fn rest_points(&self) -> Vec<RestPoint> {
+        let k = self.strategies.len();
+        let mut candidates: Vec<Vec<f64>> = Vec::new();
+        for i in 0..k {
+            candidates.push(Self::vertex(k, i));
+            candidates.push(
+                (0..k)
+                    .map(|j| {
+                        if i == j {
+                            0.7
+                        } else {
+                            0.3 / (k - 1).max(1) as f64
+                        }
+                    })
+                    .collect(),
+            );
+            for j in i + 1..k {
+                candidates.push(
+                    (0..k)
+                        .map(|l| if l == i || l == j { 0.5 } else { 0.0 })
+                        .collect(),
+                );
+            }
+        }
+        candidates.push(vec![1.0 / k as f64; k]);
+
+        let mut found: Vec<Vec<f64>> = Vec::new();
+        for start in &candidates {
+            for dt in [SEARCH_STEP, -SEARCH_STEP] {
+                if let Some(point) = self.settle(start.clone(), dt) {
+                    if !found
+                        .iter()
+                        .any(|p| Self::distance(p, &point) < PERTURBATION)
+                    {
+                        found.push(point);
+                    }
+                }
+            }
+        }
+
+        found
+            .into_iter()
+            .map(|state| RestPoint {
+                stability: self.stability(&state),
+                state,
+            })
+            .collect()
+    }
+
+    /// 速度がほぼ0になるまで積分（到達しなければ`None`）
+    fn settle(&self, mut state: Vec<f64>, dt: f64) -> Option<Vec<f64>> {
+        for _ in 0..SEARCH_STEPS {
+            if Self::norm(&self.velocity(&state)) < REST_TOLERANCE {
+                return Some(state);
+            }
+            state = self.rk4_step(&state, dt);
+        }
+        None
+    }
+
+    fn stability(&self, state: &[f64]) -> Stability {
+        let k = state.len();
+        let returns = (0..k).all(|j| {
+            let vertex = Self::vertex(k, j);
+            let perturbed: Vec<f64> = state
+                .iter()
+                .zip(&vertex)
+                .map(|(x, v)| x + PERTURBATION * (v - x))
+                .collect();
+            let initial = Self::distance(&perturbed, state);
+            if initial < PERTURBATION * 1e-3 {
+                return true;
+            }
+
+            let mut current = perturbed;
+            for _ in 0..SEARCH_STEPS / 10 {
+                current = self.rk4_step(&current, SEARCH_STEP);
+            }
+            Self::distance(&current, state) < initial / 2.0
+        });
+
+        if returns {
+            Stability::Stable
+        } else {
+            Stability::Unstable
+        }
+    }
+
+    fn rk4_step(&self, state: &[f64], dt: f64) -> Vec<f64> {
+        let shifted = |base: &[f64], k: &[f64], h: f64| -> Vec<f64> {
+            base.iter().zip(k).map(|(x, d)| x + h * d).collect()
+        };
+
+        let k1 = self.velocity(state);
+        let k2 = self.velocity(&shifted(state, &k1, dt / 2.0));
+        let k3 = self.velocity(&shifted(state, &k2, dt / 2.0));
+        let k4 = self.velocity(&shifted(state, &k3, dt));
+
+        let next = (0..state.len())
+            .map(|i| state[i] + dt / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]))
+            .collect();
+        Self::project(next)
+    }
+
+    /// 負の成分を0にして合計1に正規化（単体上に射影）
+    fn project(state: Vec<f64>) -> Vec<f64> {
+        let clamped: Vec<f64> = state.into_iter().map(|x| x.max(0.0)).collect();
+        let total: f64 = clamped.iter().sum();
+        if total <= 0.0 {
+            let k = clamped.len() as f64;
+            return vec![1.0 / k; clamped.len()];
+        }
+        clamped.into_iter().map(|x| x / total).collect()
+    }
+
+    fn vertex(k: usize, i: usize) -> Vec<f64> {
+        (0..k).map(|j| if i == j { 1.0 } else { 0.0 }).collect()
+    }
+
+    fn norm(v: &[f64]) -> f64 {
+        v.iter().map(|x| x * x).sum::<f64>().sqrt()
+    }
+
+    fn distance(a: &[f64], b: &[f64]) -> f64 {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| (x - y).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn dynamics(strategies: &[&str], matrix: PayoffMatrix) -> ReplicatorDynamics {
+        ReplicatorDynamics::new(strategies.iter().map(|s| s.to_string()).collect(), &matrix)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_defection_takes_over_in_prisoners_dilemma() {
+        let replicator = dynamics(&["00", "01", "11"], PayoffMatrix::default());
+        let trajectory = replicator
+            .integrate(&[0.1, 0.3, 0.6], 0.05, 4000, 100)
+            .unwrap();
+
+        assert_eq!(trajectory.states.len(), 41);
+        assert!(trajectory.final_state()[0] > 0.99);
+    }
+
+    #[test]
+    fn test_stag_hunt_rest_points() {
+        // 協力の利得 3x、裏切りの利得 x + 1 が x = 0.5 で等しくなる協調ゲーム
+        let stag_hunt = PayoffMatrix {
+            reward: 3,
+            sucker: 0,
+            temptation: 2,
+            punishment: 1,
+        };
+        let replicator = dynamics(&["11", "00"], stag_hunt);
+        let mut rest_points = replicator.rest_points();
+        rest_points.sort_by(|a, b| b.state[0].total_cmp(&a.state[0]));
+
+        assert_eq!(rest_points.len(), 3);
+        assert_eq!(rest_points[0].stability, Stability::Stable);
+        assert!((rest_points[1].state[0] - 0.5).abs() < 1e-3);
+        assert_eq!(rest_points[1].stability, Stability::Unstable);
+        assert_eq!(rest_points[2].stability, Stability::Stable);
+    }
+
+    #[test]
+    fn test_mutation_keeps_strategies_alive() {
+        let replicator = dynamics(&["00", "11"], PayoffMatrix::default())
+            .with_mutation(0.05)
+            .unwrap();
+        let trajectory = replicator.integrate(&[0.5, 0.5], 0.05, 4000, 4000).unwrap();
+        let state = trajectory.final_state();
+
+        assert!(state[0] > 0.9);
+        assert!(state[1] > 1e-4);
+        assert!((state.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_frequencies_from_population() {
+        let replicator = dynamics(&["00", "11"], PayoffMatrix::default());
+        let agents = vec![
+            ModelAgent::new(0, "00".to_string()),
+            ModelAgent::new(1, "11".to_string()),
+            ModelAgent::new(2, "10".to_string()),
+            ModelAgent::new(3, "1111".to_string()),
+        ];
+        let frequencies = replicator.frequencies(&agents);
+        assert_eq!(frequencies[0] + frequencies[1], 1.0);
+        assert_eq!(frequencies[1], 0.5);
+    }
+
+    #[test]
+    fn test_payoffs_follow_decoding() {
+        // '1'の割合では同じ戦略だが、2進小数では"01"が0.25、"10"が0.5の協力確率
+        let matrix = PayoffMatrix::default();
+        let strategies = vec!["01".to_string(), "10".to_string()];
+        let unary = ReplicatorDynamics::new(strategies.clone(), &matrix).unwrap();
+        assert_eq!(unary.payoffs()[0], unary.payoffs()[1]);
+
+        let binary =
+            ReplicatorDynamics::with_decoding(strategies, &matrix, Decoding::Binary).unwrap();
+        assert_eq!(binary.payoffs()[0][1], matrix.expected_payoff(0.25, 0.5));
+        assert!(binary.payoffs()[0][1] > binary.payoffs()[1][1]);
+
+        let agents = vec![ModelAgent::new(0, "01".to_string())];
+        assert_eq!(binary.frequencies(&agents), vec![1.0, 0.0]);
+        assert!(ReplicatorDynamics::with_decoding(
+            vec!["01".to_string()],
+            &matrix,
+            Decoding::LookupTable(vec![true; 3])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let matrix = PayoffMatrix::default();
+        assert!(ReplicatorDynamics::new(Vec::new(), &matrix).is_err());
+        assert!(ReplicatorDynamics::new(vec!["01".into(), "01".into()], &matrix).is_err());
+        assert!(dynamics(&["0", "11"], matrix).with_mutation(0.1).is_err());
+        assert!(dynamics(&["0", "1"], matrix)
+            .integrate(&[1.0], 0.1, 1, 1)
+            .is_err());
+        assert!(dynamics(&["0", "1"], matrix)
+            .integrate(&[0.0, 0.0], 0.1, 1, 1)
+            .is_err());
+    }
+}
@@ -0,0 +1,106 @@
+/// Wright–Fisher過程
+///
+/// 世代が重ならない過程で、次世代の`N`体全員を現世代から適応度に比例して
+/// 復元抽出（多項分布）します。
+use super::{fitness_weights, validate_intensity, EvolutionaryProcess};
+use crate::core::{errors::*, traits::*, types::*};
+use crate::strategies::decoding::Decoding;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+
+/// Wright–Fisher過程
+///
+/// # フィールド
+/// * `intensity` - 選択強度β（適応度は`exp(β·π)`）
+/// * `decoding` - DNAから協力確率へのデコード方式
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrightFisherProcess {
+    intensity: f64,
+    decoding: Decoding,
+}
+
+impl WrightFisherProcess {
+    /// 選択強度を指定して作成
+    ///
+    /// # エラー
+    /// 選択強度が負または有限でない場合
+    pub fn new(intensity: f64) -> GAResult<Self> {
+        validate_intensity(intensity)?;
+        Ok(Self {
+            intensity,
+            decoding: Decoding::default(),
+        })
+    }
+
+    /// DNAから協力確率へのデコード方式を指定（既定は`Decoding::Unary`）
+    pub fn with_decoding(mut self, decoding: Decoding) -> Self {
+        self.decoding = decoding;
+        self
+    }
+}
+
+impl EvolutionaryProcess for WrightFisherProcess {
+    fn step<T: Agent, R: Rng>(&self, agents: &mut [T], matrix: &PayoffMatrix, rng: &mut R) {
+        if agents.is_empty() {
+            return;
+        }
+
+        let weights = fitness_weights(agents, matrix, &self.decoding, self.intensity);
+        let parents = agents.to_vec();
+        match WeightedIndex::new(&weights) {
+            Ok(distribution) => {
+                for agent in agents.iter_mut() {
+                    *agent = parents[distribution.sample(rng)].clone();
+                }
+            }
+            Err(_) => {
+                for agent in agents.iter_mut() {
+                    *agent = parents[rng.gen_range(0..parents.len())].clone();
+                }
+            }
+        }
+    }
+
+    fn generations_per_step(&self, _population_size: usize) -> f64 {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+    use rand::thread_rng;
+
+    #[test]
+    fn test_strong_selection_favours_defectors() {
+        // β が大きいと、裏切り者（利得が常に高い）だけが次世代の親になる
+        let process = WrightFisherProcess::new(50.0).unwrap();
+        let mut agents: Vec<ModelAgent> = (0..6)
+            .map(|i| ModelAgent::new(i, if i < 3 { "00" } else { "11" }.to_string()))
+            .collect();
+
+        process.step(&mut agents, &PayoffMatrix::default(), &mut thread_rng());
+        assert!(agents.iter().all(|a| a.get_dna() == "00"));
+    }
+
+    #[test]
+    fn test_selection_uses_decoding() {
+        // '1'の数が同じでも、2進小数では"01"（0.25）が"10"（0.5）より裏切りやすく有利
+        let process = WrightFisherProcess::new(50.0)
+            .unwrap()
+            .with_decoding(Decoding::Binary);
+        let mut agents: Vec<ModelAgent> = (0..6)
+            .map(|i| ModelAgent::new(i, if i < 3 { "01" } else { "10" }.to_string()))
+            .collect();
+
+        process.step(&mut agents, &PayoffMatrix::default(), &mut thread_rng());
+        assert!(agents.iter().all(|a| a.get_dna() == "01"));
+    }
+
+    #[test]
+    fn test_invalid_intensity() {
+        assert!(WrightFisherProcess::new(-0.1).is_err());
+        assert!(WrightFisherProcess::new(0.0).is_ok());
+    }
+}
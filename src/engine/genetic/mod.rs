@@ -1,5 +1,11 @@
 pub mod algorithm;
 pub mod population;
+pub mod replacement;
 
 pub use algorithm::GeneticAlgorithmEngine;
-pub use population::Population;
+pub(crate) use population::breed;
+pub use population::{
+    diversity_metric, edit_distance, edit_distance_diversity, genome_diversity, hamming_distance,
+    normalized_edit_distance, ComplexityStats, Population,
+};
+pub use replacement::{ReplacementModel, ReplacementPolicy};
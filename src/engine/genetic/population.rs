@@ -2,8 +2,9 @@
 ///
 /// このモジュールでは、遺伝的アルゴリズムにおける個体群（Population）の管理を行います。
 /// 個体群は複数のエージェント（個体）から構成され、世代を重ねて進化していきます。
+use super::replacement::ReplacementPolicy;
 use crate::core::{errors::*, traits::*, types::*};
-use rand::{thread_rng, Rng};
+use rand::{seq::index, thread_rng, Rng};
 
 /// 遺伝的アルゴリズムの個体群を管理する構造体
 ///
@@ -17,6 +18,9 @@ use rand::{thread_rng, Rng};
 /// * `agents` - 個体群を構成するエージェントのベクタ
 /// * `generation` - 現在の世代番号
 /// * `elite_size` - 次世代に引き継がれるエリート個体の数
+/// * `births` - 各個体の誕生順
+/// * `next_birth` - 次に生まれる個体に割り当てる誕生順
+/// * `offspring_in_generation` - 現世代で定常状態置換により生まれた子の数
 #[derive(Debug, Clone)]
 pub struct Population<T: Agent> {
     /// 個体群を構成するエージェント
@@ -25,6 +29,12 @@ pub struct Population<T: Agent> {
     generation: Generation,
     /// エリート保存で残される個体数
     elite_size: usize,
+    /// 各個体の誕生順（`agents`と同じ並び、値が小さいほど古い）
+    births: Vec<u64>,
+    /// 次に生まれる個体に割り当てる誕生順
+    next_birth: u64,
+    /// 現世代で定常状態置換により生まれた子の数
+    offspring_in_generation: usize,
 }
 
 impl<T: Agent> Population<T> {
@@ -48,11 +58,7 @@ impl<T: Agent> Population<T> {
         // エリートサイズのバリデーション
         crate::core::errors::validation::validate_elite_size(elite_size, agents.len())?;
 
-        Ok(Self {
-            agents,
-            generation: 0,
-            elite_size,
-        })
+        Ok(Self::with_agents(agents, elite_size))
     }
 
     pub fn random<F>(
@@ -63,23 +69,53 @@ impl<T: Agent> Population<T> {
     ) -> GAResult<Self>
     where
         F: Fn(AgentId, Dna) -> T,
+    {
+        Self::random_with_rng(
+            size,
+            dna_length,
+            elite_size,
+            agent_factory,
+            &mut thread_rng(),
+        )
+    }
+
+    /// 与えられた乱数生成器でランダムなDNAの個体群を作成
+    ///
+    /// シード付きの乱数生成器を与えると、同じ初期個体群を再現できます。
+    pub fn random_with_rng<F, R>(
+        size: usize,
+        dna_length: usize,
+        elite_size: usize,
+        agent_factory: F,
+        rng: &mut R,
+    ) -> GAResult<Self>
+    where
+        F: Fn(AgentId, Dna) -> T,
+        R: Rng,
     {
         crate::core::errors::validation::validate_population_size(size)?;
         crate::core::errors::validation::validate_elite_size(elite_size, size)?;
 
         let mut agents = Vec::with_capacity(size);
-        let mut rng = thread_rng();
 
         for id in 0..size {
-            let dna = Self::generate_random_dna(dna_length, &mut rng);
+            let dna = Self::generate_random_dna(dna_length, rng);
             agents.push(agent_factory(id as AgentId, dna));
         }
 
-        Ok(Self {
+        Ok(Self::with_agents(agents, elite_size))
+    }
+
+    fn with_agents(agents: Vec<T>, elite_size: usize) -> Self {
+        let size = agents.len() as u64;
+        Self {
             agents,
             generation: 0,
             elite_size,
-        })
+            births: (0..size).collect(),
+            next_birth: size,
+            offspring_in_generation: 0,
+        }
     }
 
     fn generate_random_dna<R: Rng>(length: usize, rng: &mut R) -> Dna {
@@ -106,6 +142,12 @@ impl<T: Agent> Population<T> {
 
     pub fn increment_generation(&mut self) {
         self.generation += 1;
+        self.offspring_in_generation = 0;
+    }
+
+    /// 各個体の誕生順を取得（値が小さいほど古い個体）
+    pub fn births(&self) -> &[u64] {
+        &self.births
     }
 
     pub fn elite_size(&self) -> usize {
@@ -130,8 +172,8 @@ impl<T: Agent> Population<T> {
         if self.agents.is_empty() {
             0.0
         } else {
-            let total: Fitness = self.agents.iter().map(|agent| agent.fitness()).sum();
-            total as f64 / self.agents.len() as f64
+            let total: f64 = self.agents.iter().map(|agent| agent.fitness() as f64).sum();
+            total / self.agents.len() as f64
         }
     }
 
@@ -164,10 +206,38 @@ impl<T: Agent> Population<T> {
             return Err(GAError::InvalidPopulationSize(new_agents.len()));
         }
 
+        self.births = (self.next_birth..self.next_birth + new_agents.len() as u64).collect();
+        self.next_birth += new_agents.len() as u64;
         self.agents = new_agents;
         Ok(())
     }
 
+    /// 非アクティブな個体を並び順に`children`で置き換える
+    ///
+    /// 置き換えた枠には新しい誕生順を割り当てます。世代番号は進めません。
+    ///
+    /// # 戻り値
+    /// 置き換えられた個体（並び順）
+    ///
+    /// # エラー
+    /// 子の数が非アクティブな個体の数と一致しない場合
+    pub fn replace_inactive(&mut self, children: Vec<T>) -> GAResult<Vec<T>> {
+        let inactive: Vec<usize> = (0..self.size())
+            .filter(|&i| !self.agents[i].is_active())
+            .collect();
+        if children.len() != inactive.len() {
+            return Err(GAError::InvalidPopulationSize(children.len()));
+        }
+
+        let mut replaced = Vec::with_capacity(inactive.len());
+        for (i, child) in inactive.into_iter().zip(children) {
+            replaced.push(std::mem::replace(&mut self.agents[i], child));
+            self.births[i] = self.next_birth;
+            self.next_birth += 1;
+        }
+        Ok(replaced)
+    }
+
     pub fn apply_elitism(&mut self, offspring: &mut [T]) -> GAResult<()> {
         if offspring.len() < self.elite_size {
             return Err(GAError::ValidationError(
@@ -188,44 +258,413 @@ impl<T: Agent> Population<T> {
         Ok(())
     }
 
-    pub fn diversity_metric(&self) -> f64 {
-        if self.agents.len() < 2 {
-            return 0.0;
+    /// 定常状態置換を1ステップ実行
+    ///
+    /// `selection`で選んだ親から`offspring`体の子を生成して`evaluate`で評価し、
+    /// `policy`に従って同数の既存個体を置き換えます。個体群サイズと同数の子が
+    /// 生まれた時点で世代番号を1つ進めます。親の選択・子の生成・置き換える個体の
+    /// 抽選には`rng`を使います。
+    ///
+    /// # 戻り値
+    /// このステップで世代が進んだ場合true
+    ///
+    /// # エラー
+    /// 子の数が0または個体群サイズを超える場合
+    pub fn steady_state_step<S, R, F>(
+        &mut self,
+        selection: &S,
+        offspring: usize,
+        policy: ReplacementPolicy,
+        mutation_rate: MutationRate,
+        rng: &mut R,
+        mut evaluate: F,
+    ) -> GAResult<bool>
+    where
+        S: SelectionStrategy<T>,
+        R: Rng,
+        F: FnMut(&T) -> Points,
+    {
+        if offspring == 0 || offspring > self.size() {
+            return Err(GAError::InsufficientCandidates(self.size()));
         }
 
-        let mut total_distance = 0.0;
-        let mut comparisons = 0;
+        let mut children = Vec::with_capacity(offspring);
+        let mut parents = Vec::with_capacity(offspring);
 
-        for i in 0..self.agents.len() {
-            for j in (i + 1)..self.agents.len() {
-                let distance = self.hamming_distance(&self.agents[i], &self.agents[j]);
-                total_distance += distance;
-                comparisons += 1;
+        for _ in 0..offspring {
+            let (parent1, parent2) = selection.select_parents_with_rng(&self.agents, rng)?;
+            let child = breed(&parent1, &parent2, mutation_rate, rng);
+            children.push(child.with_points(evaluate(&child)));
+            parents.push((parent1, parent2));
+        }
+
+        let victims: Vec<usize> = match policy {
+            ReplacementPolicy::Worst => {
+                let mut order: Vec<usize> = (0..self.size()).collect();
+                order.sort_by_key(|&i| self.agents[i].fitness());
+                order.into_iter().take(offspring).collect()
+            }
+            ReplacementPolicy::Random => index::sample(rng, self.size(), offspring).into_vec(),
+            ReplacementPolicy::Oldest => {
+                let mut order: Vec<usize> = (0..self.size()).collect();
+                order.sort_by_key(|&i| self.births[i]);
+                order.into_iter().take(offspring).collect()
+            }
+            ReplacementPolicy::Parent => {
+                // 両親がすでに置き換え済みの場合は、まだ置き換えていない最弱の個体を使う
+                let mut worst_first: Vec<usize> = (0..self.size()).collect();
+                worst_first.sort_by_key(|&i| self.agents[i].fitness());
+
+                let mut taken: Vec<usize> = Vec::with_capacity(offspring);
+                for (parent1, parent2) in &parents {
+                    let (weaker, stronger) = if parent1.fitness() <= parent2.fitness() {
+                        (parent1, parent2)
+                    } else {
+                        (parent2, parent1)
+                    };
+                    let victim = [weaker, stronger]
+                        .into_iter()
+                        .filter_map(|parent| self.position_of(parent))
+                        .chain(worst_first.iter().copied())
+                        .find(|i| !taken.contains(i))
+                        .expect("offspring never exceeds the population size");
+                    taken.push(victim);
+                }
+                taken
             }
+        };
+
+        for (child, i) in children.into_iter().zip(victims) {
+            self.agents[i] = child;
+            self.births[i] = self.next_birth;
+            self.next_birth += 1;
         }
 
-        if comparisons > 0 {
-            total_distance / comparisons as f64
-        } else {
-            0.0
+        self.offspring_in_generation += offspring;
+        if self.offspring_in_generation >= self.size() {
+            self.increment_generation();
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// (μ+λ)選択で1世代進める
+    ///
+    /// 現個体群（μ体）からλ体の子を生成し、親子を合わせた中から適応度上位μ体を残します。
+    pub fn plus_selection_step<S, R, F>(
+        &mut self,
+        selection: &S,
+        lambda: usize,
+        mutation_rate: MutationRate,
+        rng: &mut R,
+        evaluate: F,
+    ) -> GAResult<()>
+    where
+        S: SelectionStrategy<T>,
+        R: Rng,
+        F: FnMut(&T) -> Points,
+    {
+        let offspring =
+            self.evaluated_offspring(selection, lambda, mutation_rate, rng, evaluate)?;
+        let parents = self.agents.iter().cloned().zip(self.births.iter().copied());
+        self.truncate_to_best(parents.chain(offspring).collect());
+        Ok(())
+    }
+
+    /// (μ,λ)選択で1世代進める
+    ///
+    /// 現個体群（μ体）からλ体の子を生成し、子の中から適応度上位μ体のみを残します。
+    ///
+    /// # エラー
+    /// λがμより小さい場合
+    pub fn comma_selection_step<S, R, F>(
+        &mut self,
+        selection: &S,
+        lambda: usize,
+        mutation_rate: MutationRate,
+        rng: &mut R,
+        evaluate: F,
+    ) -> GAResult<()>
+    where
+        S: SelectionStrategy<T>,
+        R: Rng,
+        F: FnMut(&T) -> Points,
+    {
+        if lambda < self.size() {
+            return Err(GAError::InsufficientCandidates(lambda));
+        }
+
+        let offspring =
+            self.evaluated_offspring(selection, lambda, mutation_rate, rng, evaluate)?;
+        self.truncate_to_best(offspring);
+        Ok(())
+    }
+
+    /// λ体の子を生成・評価し、誕生順と組にして返す
+    fn evaluated_offspring<S, R, F>(
+        &mut self,
+        selection: &S,
+        lambda: usize,
+        mutation_rate: MutationRate,
+        rng: &mut R,
+        mut evaluate: F,
+    ) -> GAResult<Vec<(T, u64)>>
+    where
+        S: SelectionStrategy<T>,
+        R: Rng,
+        F: FnMut(&T) -> Points,
+    {
+        if lambda == 0 {
+            return Err(GAError::InsufficientCandidates(0));
+        }
+
+        let mut offspring = Vec::with_capacity(lambda);
+        for _ in 0..lambda {
+            let (parent1, parent2) = selection.select_parents_with_rng(&self.agents, rng)?;
+            let child = breed(&parent1, &parent2, mutation_rate, rng);
+            offspring.push((child.with_points(evaluate(&child)), self.next_birth));
+            self.next_birth += 1;
+        }
+
+        Ok(offspring)
+    }
+
+    /// 候補を適応度の降順に並べ、上位μ体で個体群を置き換えて世代を進める
+    fn truncate_to_best(&mut self, mut candidates: Vec<(T, u64)>) {
+        let mu = self.size();
+        candidates.sort_by_key(|(agent, _)| std::cmp::Reverse(agent.fitness()));
+        candidates.truncate(mu);
+
+        let (agents, births) = candidates.into_iter().unzip();
+        self.agents = agents;
+        self.births = births;
+        self.increment_generation();
+    }
+
+    /// 選択戦略が返したクローンに対応する個体の位置を検索
+    ///
+    /// ID・DNA・ポイントが全て一致する個体は区別できないため、最初に見つかった位置を返します。
+    fn position_of(&self, agent: &T) -> Option<usize> {
+        self.agents.iter().position(|a| {
+            a.id() == agent.id() && a.points() == agent.points() && a.dna() == agent.dna()
+        })
+    }
+
+    pub fn diversity_metric(&self) -> f64 {
+        diversity_metric(&self.agents)
+    }
+
+    pub fn hamming_distance(&self, agent1: &T, agent2: &T) -> f64 {
+        hamming_distance(agent1, agent2)
+    }
+}
+
+impl<T: GenomeOperations> Population<T> {
+    /// ランダムな遺伝子型を持つ個体群を作成
+    ///
+    /// `random`の遺伝子型版で、遺伝子型の生成は`Genome::random`に委ねます。
+    ///
+    /// # 引数
+    /// * `size` - 個体数
+    /// * `genome_length` - 遺伝子の数
+    /// * `elite_size` - エリート保存で残される個体数
+    /// * `agent_factory` - IDと遺伝子型から個体を作成する関数
+    pub fn random_genomes<F>(
+        size: usize,
+        genome_length: usize,
+        elite_size: usize,
+        agent_factory: F,
+    ) -> GAResult<Self>
+    where
+        F: Fn(AgentId, T::Genome) -> T,
+    {
+        crate::core::errors::validation::validate_population_size(size)?;
+        crate::core::errors::validation::validate_elite_size(elite_size, size)?;
+
+        let mut rng = thread_rng();
+        let agents = (0..size)
+            .map(|id| agent_factory(id as AgentId, T::Genome::random(genome_length, &mut rng)))
+            .collect();
+
+        Ok(Self::with_agents(agents, elite_size))
+    }
+
+    pub fn genome_diversity(&self) -> f64 {
+        genome_diversity(&self.agents)
+    }
+}
+
+/// 2親から一点交叉と突然変異で子を1つ生成
+///
+/// 交叉点はDNA長の範囲で一様に選ばれ、子のポイントは0で初期化されます。
+pub(crate) fn breed<T: Agent, R: Rng>(
+    parent1: &T,
+    parent2: &T,
+    mutation_rate: MutationRate,
+    rng: &mut R,
+) -> T {
+    let point = rng.gen_range(0..parent1.dna_length().max(1));
+    parent1
+        .crossover(parent2, point)
+        .mutate_with_rng(mutation_rate, rng)
+        .with_points(0)
+}
+
+/// 個体群の平均ペアワイズハミング距離を計算
+///
+/// 全ての個体ペアについて正規化ハミング距離を求め、その平均を返します。
+/// 個体数が2未満の場合は0.0を返します。
+pub fn diversity_metric<T: DnaOperations>(agents: &[T]) -> f64 {
+    if agents.len() < 2 {
+        return 0.0;
+    }
+
+    let mut total_distance = 0.0;
+    let mut comparisons = 0;
+
+    for i in 0..agents.len() {
+        for j in (i + 1)..agents.len() {
+            total_distance += hamming_distance(&agents[i], &agents[j]);
+            comparisons += 1;
         }
     }
 
-    fn hamming_distance(&self, agent1: &T, agent2: &T) -> f64 {
-        let dna1 = agent1.dna_binary();
-        let dna2 = agent2.dna_binary();
+    total_distance / comparisons as f64
+}
+
+/// 2個体間の正規化ハミング距離を計算
+///
+/// 異なるビットの割合（0.0-1.0）を返します。DNA長が異なる場合はハミング距離が
+/// 定義できないため、代わりに正規化編集距離を返します。
+pub fn hamming_distance<T: DnaOperations>(agent1: &T, agent2: &T) -> f64 {
+    if agent1.dna_length() != agent2.dna_length() {
+        return normalized_edit_distance(agent1, agent2);
+    }
+
+    agent1.differing_genes(agent2) as f64 / agent1.dna_length() as f64
+}
+
+/// 個体群の平均ペアワイズ遺伝子型距離を計算
+///
+/// `Genome::distance`を長い方の遺伝子の数で割った正規化距離の平均で、
+/// 実数値などの二値以外の遺伝子型にも使用できる多様性指標です。
+/// 個体数が2未満の場合は0.0を返します。
+pub fn genome_diversity<T: GenomeOperations>(agents: &[T]) -> f64 {
+    if agents.len() < 2 {
+        return 0.0;
+    }
 
-        if dna1.len() != dna2.len() {
-            return 0.0;
+    let mut total_distance = 0.0;
+    let mut comparisons = 0;
+
+    for i in 0..agents.len() {
+        for j in (i + 1)..agents.len() {
+            let (genome1, genome2) = (agents[i].genome(), agents[j].genome());
+            let longer = genome1.len().max(genome2.len()).max(1);
+            total_distance += genome1.distance(genome2) / longer as f64;
+            comparisons += 1;
         }
+    }
 
-        let differences = dna1
-            .chars()
-            .zip(dna2.chars())
-            .filter(|(a, b)| a != b)
-            .count();
+    total_distance / comparisons as f64
+}
 
-        differences as f64 / dna1.len() as f64
+/// 2つのDNA間の編集距離（レーベンシュタイン距離）を計算
+///
+/// 一方を他方に変換するのに必要な遺伝子の挿入・欠失・置換の最小回数です。
+pub fn edit_distance(dna1: &str, dna2: &str) -> usize {
+    let (dna1, dna2) = (dna1.as_bytes(), dna2.as_bytes());
+    let mut previous: Vec<usize> = (0..=dna2.len()).collect();
+    let mut current = vec![0; dna2.len() + 1];
+
+    for (i, &a) in dna1.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, &b) in dna2.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[dna2.len()]
+}
+
+/// 2個体間の正規化編集距離を計算
+///
+/// 編集距離を長い方のDNA長で割った値（0.0-1.0）を返します。
+pub fn normalized_edit_distance<T: DnaOperations>(agent1: &T, agent2: &T) -> f64 {
+    let dna1 = agent1.dna_binary();
+    let dna2 = agent2.dna_binary();
+    let longer = dna1.len().max(dna2.len());
+    if longer == 0 {
+        return 0.0;
+    }
+
+    edit_distance(dna1, dna2) as f64 / longer as f64
+}
+
+/// 個体群の平均ペアワイズ正規化編集距離を計算
+///
+/// DNA長が異なる個体を含む個体群の多様性指標です。
+/// 個体数が2未満の場合は0.0を返します。
+pub fn edit_distance_diversity<T: DnaOperations>(agents: &[T]) -> f64 {
+    if agents.len() < 2 {
+        return 0.0;
+    }
+
+    let mut total_distance = 0.0;
+    let mut comparisons = 0;
+
+    for i in 0..agents.len() {
+        for j in (i + 1)..agents.len() {
+            total_distance += normalized_edit_distance(&agents[i], &agents[j]);
+            comparisons += 1;
+        }
+    }
+
+    total_distance / comparisons as f64
+}
+
+/// 遺伝子型の長さと複雑さの統計
+///
+/// # フィールド
+/// * `mean_length` - 平均DNA長
+/// * `min_length` - 最短のDNA長
+/// * `max_length` - 最長のDNA長
+/// * `mean_complexity` - 隣接する遺伝子の値が切り替わる箇所の数の平均
+///   （`Objective::Complexity`と同じ尺度）
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ComplexityStats {
+    pub mean_length: f64,
+    pub min_length: usize,
+    pub max_length: usize,
+    pub mean_complexity: f64,
+}
+
+impl ComplexityStats {
+    /// 個体群の統計を計算（空の個体群ではすべて0）
+    pub fn measure<T: DnaOperations>(agents: &[T]) -> Self {
+        if agents.is_empty() {
+            return Self::default();
+        }
+
+        let n = agents.len() as f64;
+        let lengths = agents.iter().map(|agent| agent.dna_length());
+        let complexity: usize = agents
+            .iter()
+            .map(|agent| {
+                let dna = agent.dna_binary().as_bytes();
+                dna.windows(2).filter(|w| w[0] != w[1]).count()
+            })
+            .sum();
+
+        Self {
+            mean_length: lengths.clone().sum::<usize>() as f64 / n,
+            min_length: lengths.clone().min().unwrap_or(0),
+            max_length: lengths.max().unwrap_or(0),
+            mean_complexity: complexity as f64 / n,
+        }
     }
 }
 
@@ -274,8 +713,7 @@ mod tests {
             }
         }
 
-        fn mutate(&self, rate: MutationRate) -> Self {
-            let mut rng = thread_rng();
+        fn mutate_with_rng<R: Rng>(&self, rate: MutationRate, rng: &mut R) -> Self {
             let new_dna: String = self
                 .dna
                 .chars()
@@ -610,4 +1048,262 @@ mod tests {
         // Identical DNA
         assert_eq!(population.hamming_distance(&agent1, &agent3), 0.0);
     }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("0101", "0101"), 0);
+        assert_eq!(edit_distance("0101", "1101"), 1);
+        assert_eq!(edit_distance("0101", "01011"), 1);
+        assert_eq!(edit_distance("0101", "1010"), 2);
+        assert_eq!(edit_distance("111", ""), 3);
+    }
+
+    #[test]
+    fn test_variable_length_distance_and_complexity() {
+        let agent = |dna: &str| TestAgent {
+            id: 0,
+            points: 0,
+            dna: dna.to_string(),
+            active: true,
+        };
+        let agents = vec![agent("0101"), agent("01011"), agent("11")];
+
+        assert_eq!(hamming_distance(&agents[0], &agents[1]), 0.2);
+        assert!(diversity_metric(&agents) > 0.0);
+        assert!(edit_distance_diversity(&agents) > 0.0);
+
+        let stats = ComplexityStats::measure(&agents);
+        assert_eq!((stats.min_length, stats.max_length), (2, 5));
+        assert!((stats.mean_length - 11.0 / 3.0).abs() < 1e-12);
+        assert_eq!(stats.mean_complexity, (3.0 + 3.0 + 0.0) / 3.0);
+    }
+
+    fn scored_population(points: &[Points]) -> Population<TestAgent> {
+        let agents = points
+            .iter()
+            .enumerate()
+            .map(|(i, &points)| TestAgent {
+                id: i as AgentId,
+                points,
+                dna: "101010".to_string(),
+                active: true,
+            })
+            .collect();
+        Population::new(agents, 0).unwrap()
+    }
+
+    #[test]
+    fn test_steady_state_replace_worst() {
+        let mut population = scored_population(&[10, 20, 30, 40]);
+        let selection = crate::engine::selection::TournamentSelection::default();
+
+        let advanced = population
+            .steady_state_step(
+                &selection,
+                1,
+                ReplacementPolicy::Worst,
+                0.0,
+                &mut thread_rng(),
+                |_| 100,
+            )
+            .unwrap();
+
+        assert!(!advanced);
+        assert_eq!(population.generation(), 0);
+        assert_eq!(population.worst_agent().unwrap().points, 20);
+        assert_eq!(population.best_agent().unwrap().points, 100);
+    }
+
+    #[test]
+    fn test_steady_state_counts_generations() {
+        let mut population = scored_population(&[10, 20, 30, 40]);
+        let selection = crate::engine::selection::TournamentSelection::default();
+
+        for _ in 0..3 {
+            assert!(!population
+                .steady_state_step(
+                    &selection,
+                    1,
+                    ReplacementPolicy::Random,
+                    0.0,
+                    &mut thread_rng(),
+                    |_| 1
+                )
+                .unwrap());
+        }
+        assert!(population
+            .steady_state_step(
+                &selection,
+                1,
+                ReplacementPolicy::Random,
+                0.0,
+                &mut thread_rng(),
+                |_| 1
+            )
+            .unwrap());
+        assert_eq!(population.generation(), 1);
+    }
+
+    #[test]
+    fn test_steady_state_replace_oldest() {
+        let mut population = scored_population(&[10, 20, 30, 40]);
+        let selection = crate::engine::selection::TournamentSelection::default();
+
+        population
+            .steady_state_step(
+                &selection,
+                2,
+                ReplacementPolicy::Oldest,
+                0.0,
+                &mut thread_rng(),
+                |_| 5,
+            )
+            .unwrap();
+
+        assert_eq!(population.births(), &[4, 5, 2, 3]);
+        assert_eq!(population.agents()[0].points, 5);
+        assert_eq!(population.agents()[1].points, 5);
+    }
+
+    #[test]
+    fn test_steady_state_replace_parent() {
+        let mut population = scored_population(&[10, 20]);
+        let selection = crate::engine::selection::TournamentSelection::default();
+
+        population
+            .steady_state_step(
+                &selection,
+                1,
+                ReplacementPolicy::Parent,
+                0.0,
+                &mut thread_rng(),
+                |_| 50,
+            )
+            .unwrap();
+
+        assert_eq!(population.size(), 2);
+        assert!(population.agents().iter().any(|a| a.points == 50));
+    }
+
+    #[test]
+    fn test_steady_state_replace_parent_falls_back_when_parents_are_taken() {
+        // 上位1体だけが親になるため、2体目の子の両親はすでに置き換え済み
+        let mut population = scored_population(&[10, 20, 30]);
+        let selection = crate::engine::selection::TruncationSelection::new(0.3).unwrap();
+
+        population
+            .steady_state_step(
+                &selection,
+                2,
+                ReplacementPolicy::Parent,
+                0.0,
+                &mut thread_rng(),
+                |_| 50,
+            )
+            .unwrap();
+
+        let points: Vec<Points> = population.agents().iter().map(|a| a.points).collect();
+        assert_eq!(points, vec![50, 20, 50]);
+        assert_eq!(population.births(), &[4, 1, 3]);
+    }
+
+    #[test]
+    fn test_steady_state_invalid_offspring_count() {
+        let mut population = scored_population(&[10, 20]);
+        let selection = crate::engine::selection::TournamentSelection::default();
+
+        let result = population.steady_state_step(
+            &selection,
+            3,
+            ReplacementPolicy::Worst,
+            0.0,
+            &mut thread_rng(),
+            |_| 0,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_replace_inactive() {
+        let mut population = scored_population(&[10, 20, 30]);
+        population.agents_mut()[0].deactivate();
+        population.agents_mut()[2].deactivate();
+        let child = |points| TestAgent {
+            id: 9,
+            points,
+            dna: "000000".to_string(),
+            active: true,
+        };
+
+        assert!(population.replace_inactive(vec![child(1)]).is_err());
+        let replaced = population
+            .replace_inactive(vec![child(1), child(3)])
+            .unwrap();
+
+        let points: Vec<Points> = replaced.iter().map(|a| a.points).collect();
+        assert_eq!(points, vec![10, 30]);
+        let points: Vec<Points> = population.agents().iter().map(|a| a.points).collect();
+        assert_eq!(points, vec![1, 20, 3]);
+        assert_eq!(population.births(), &[3, 1, 4]);
+        assert_eq!(population.generation(), 0);
+    }
+
+    #[test]
+    fn test_plus_selection_keeps_best_of_parents_and_offspring() {
+        let mut population = scored_population(&[10, 20, 30]);
+        let selection = crate::engine::selection::TournamentSelection::default();
+
+        population
+            .plus_selection_step(&selection, 3, 0.0, &mut thread_rng(), |_| 25)
+            .unwrap();
+
+        let mut points: Vec<Points> = population.agents().iter().map(|a| a.points).collect();
+        points.sort();
+        assert_eq!(points, vec![25, 25, 30]);
+        assert_eq!(population.generation(), 1);
+    }
+
+    #[test]
+    fn test_comma_selection_discards_parents() {
+        let mut population = scored_population(&[10, 20, 30]);
+        let selection = crate::engine::selection::TournamentSelection::default();
+
+        assert!(population
+            .comma_selection_step(&selection, 2, 0.0, &mut thread_rng(), |_| 1)
+            .is_err());
+
+        population
+            .comma_selection_step(&selection, 4, 0.0, &mut thread_rng(), |_| 1)
+            .unwrap();
+        assert_eq!(population.size(), 3);
+        assert!(population.agents().iter().all(|a| a.points == 1));
+        assert!(population.births().iter().all(|&b| b >= 3));
+    }
+
+    #[test]
+    fn test_real_valued_population_evolves() {
+        use crate::core::genome::RealGenome;
+        use crate::models::model::Agent as ModelAgent;
+
+        let evaluate =
+            |agent: &ModelAgent<RealGenome>| (agent.genome().gene_sum() * 100.0).round() as Points;
+        let mut population = Population::random_genomes(20, 8, 0, ModelAgent::from_genome).unwrap();
+        let initial_diversity = population.genome_diversity();
+        assert!(initial_diversity > 0.0);
+        assert!(population.agents()[0].dna().contains(','));
+
+        let selection = crate::engine::selection::TournamentSelection::default();
+        for agent in population.agents_mut() {
+            *agent = agent.with_points(evaluate(agent));
+        }
+        let initial = population.average_fitness();
+        for _ in 0..30 {
+            population
+                .plus_selection_step(&selection, 20, 0.1, &mut thread_rng(), evaluate)
+                .unwrap();
+        }
+        assert!(population.average_fitness() > initial);
+        assert!(population.agents().iter().all(|a| a.dna_length() == 8));
+    }
 }
@@ -0,0 +1,201 @@
+/// 世代交代（置換）モデル
+///
+/// 個体群全体を子で置き換える世代交代型に加えて、少数の子だけを入れ替える
+/// 定常状態型と、親子を合わせて選抜する(μ+λ)/(μ,λ)型を定義します。
+/// 実際の置換処理は`Population`のメソッドとして実装されています。
+use crate::core::errors::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// 定常状態モードで子に置き換えられる個体の選び方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplacementPolicy {
+    /// 適応度が最も低い個体を置き換える
+    #[default]
+    Worst,
+    /// ランダムに選んだ個体を置き換える
+    Random,
+    /// 最も早く生まれた個体を置き換える
+    Oldest,
+    /// 子の親のうち適応度の低い方を置き換える
+    ///
+    /// 両親がともに同じステップの別の子で置き換え済みの場合は、まだ置き換えていない
+    /// 最も適応度の低い個体を置き換えます。
+    Parent,
+}
+
+impl fmt::Display for ReplacementPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplacementPolicy::Worst => write!(f, "worst"),
+            ReplacementPolicy::Random => write!(f, "random"),
+            ReplacementPolicy::Oldest => write!(f, "oldest"),
+            ReplacementPolicy::Parent => write!(f, "parent"),
+        }
+    }
+}
+
+impl FromStr for ReplacementPolicy {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "worst" => Ok(ReplacementPolicy::Worst),
+            "random" => Ok(ReplacementPolicy::Random),
+            "oldest" => Ok(ReplacementPolicy::Oldest),
+            "parent" => Ok(ReplacementPolicy::Parent),
+            _ => Err(GAError::ValidationError(format!(
+                "Invalid replacement policy: {s}"
+            ))),
+        }
+    }
+}
+
+/// 世代交代モデル
+///
+/// μは常に個体群サイズです。文字列表現は`generational`、`steady:K[:POLICY]`、
+/// `plus:LAMBDA`、`comma:LAMBDA`です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplacementModel {
+    /// 毎世代、個体群全体を子で置き換える
+    #[default]
+    Generational,
+    /// 1ステップごとに`offspring`体の子を生成し、同数の個体を置き換える
+    ///
+    /// 個体群サイズと同数の子が生まれるごとに1世代と数えます。
+    SteadyState {
+        offspring: usize,
+        policy: ReplacementPolicy,
+    },
+    /// μ体の親からλ体の子を生成し、親子μ+λ体から上位μ体を残す
+    MuPlusLambda { lambda: usize },
+    /// μ体の親からλ体の子を生成し、子λ体のみから上位μ体を残す
+    MuCommaLambda { lambda: usize },
+}
+
+impl ReplacementModel {
+    /// 個体群サイズμに対してモデルのパラメータを検証
+    pub fn validate(&self, population: usize) -> GAResult<()> {
+        match *self {
+            ReplacementModel::Generational => Ok(()),
+            ReplacementModel::SteadyState { offspring, .. } => {
+                if offspring == 0 || offspring > population {
+                    return Err(GAError::ValidationError(format!(
+                        "Steady-state offspring count must be between 1 and {population}"
+                    )));
+                }
+                Ok(())
+            }
+            ReplacementModel::MuPlusLambda { lambda } => {
+                if lambda == 0 {
+                    return Err(GAError::ValidationError(
+                        "Lambda must be greater than 0".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            ReplacementModel::MuCommaLambda { lambda } => {
+                if lambda < population {
+                    return Err(GAError::ValidationError(format!(
+                        "Lambda must be at least mu ({population}) for (mu,lambda) selection"
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for ReplacementModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplacementModel::Generational => write!(f, "generational"),
+            ReplacementModel::SteadyState { offspring, policy } => {
+                write!(f, "steady:{offspring}:{policy}")
+            }
+            ReplacementModel::MuPlusLambda { lambda } => write!(f, "plus:{lambda}"),
+            ReplacementModel::MuCommaLambda { lambda } => write!(f, "comma:{lambda}"),
+        }
+    }
+}
+
+impl FromStr for ReplacementModel {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid replacement model: {s}"));
+        let parts: Vec<&str> = s.split(':').collect();
+        let count = |i: usize| -> GAResult<usize> {
+            parts
+                .get(i)
+                .ok_or_else(invalid)?
+                .parse()
+                .map_err(|_| invalid())
+        };
+
+        match parts.as_slice() {
+            ["generational"] => Ok(ReplacementModel::Generational),
+            ["steady", _] => Ok(ReplacementModel::SteadyState {
+                offspring: count(1)?,
+                policy: ReplacementPolicy::default(),
+            }),
+            ["steady", _, policy] => Ok(ReplacementModel::SteadyState {
+                offspring: count(1)?,
+                policy: policy.parse()?,
+            }),
+            ["plus", _] => Ok(ReplacementModel::MuPlusLambda { lambda: count(1)? }),
+            ["comma", _] => Ok(ReplacementModel::MuCommaLambda { lambda: count(1)? }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_model() {
+        assert_eq!(
+            "generational".parse::<ReplacementModel>().unwrap(),
+            ReplacementModel::Generational
+        );
+        assert_eq!(
+            "steady:2".parse::<ReplacementModel>().unwrap(),
+            ReplacementModel::SteadyState {
+                offspring: 2,
+                policy: ReplacementPolicy::Worst
+            }
+        );
+        assert_eq!(
+            "steady:1:oldest".parse::<ReplacementModel>().unwrap(),
+            ReplacementModel::SteadyState {
+                offspring: 1,
+                policy: ReplacementPolicy::Oldest
+            }
+        );
+        assert_eq!(
+            "comma:30".parse::<ReplacementModel>().unwrap(),
+            ReplacementModel::MuCommaLambda { lambda: 30 }
+        );
+        assert!("steady".parse::<ReplacementModel>().is_err());
+        assert!("steady:2:best".parse::<ReplacementModel>().is_err());
+        assert!("plus:x".parse::<ReplacementModel>().is_err());
+    }
+
+    #[test]
+    fn test_validate_against_population() {
+        let steady = ReplacementModel::SteadyState {
+            offspring: 5,
+            policy: ReplacementPolicy::Random,
+        };
+        assert!(steady.validate(10).is_ok());
+        assert!(steady.validate(4).is_err());
+        assert!(ReplacementModel::MuCommaLambda { lambda: 5 }
+            .validate(10)
+            .is_err());
+        assert!(ReplacementModel::MuPlusLambda { lambda: 0 }
+            .validate(10)
+            .is_err());
+    }
+}
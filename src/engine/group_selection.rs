@@ -0,0 +1,374 @@
+/// 集団（マルチレベル）選択
+///
+/// 個体群を同じ大きさのグループに分け、各個体は同じグループの個体とのみ対戦します
+/// （Traulsen–Nowakのモデル）。個体はグループの中で個体の適応度に応じて繁殖し、
+/// グループはグループ全体の獲得ポイントに応じて分裂（グループの繁殖）し、
+/// 獲得ポイントの低いグループほど絶滅しやすくなります。分裂したグループの片方は
+/// 絶滅したグループの枠を埋めるため、グループ数と個体群サイズは一定です。
+///
+/// 選択の効果はPrice方程式で、グループ間の選択（グループ平均の適応度と形質の共分散）と
+/// グループ内の選択（グループ内の共分散の平均）に分解して報告します。
+use crate::core::errors::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+/// 分裂確率の既定値
+pub const DEFAULT_SPLIT_PROBABILITY: f64 = 0.1;
+
+/// 集団選択の設定
+///
+/// 文字列表現は`groups:COUNT[:SPLIT]`です（例: `groups:5`、`groups:4:0.2`）。
+///
+/// # フィールド
+/// * `groups` - グループ数（2以上、個体数を割り切る）
+/// * `split_probability` - 各世代にグループの分裂（と別のグループの絶滅）が起こる確率
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroupSelection {
+    pub groups: usize,
+    pub split_probability: f64,
+}
+
+impl GroupSelection {
+    pub fn new(groups: usize) -> Self {
+        Self {
+            groups,
+            split_probability: DEFAULT_SPLIT_PROBABILITY,
+        }
+    }
+
+    pub fn with_split_probability(mut self, split_probability: f64) -> Self {
+        self.split_probability = split_probability;
+        self
+    }
+
+    /// 個体数`population`に対する妥当性を検証
+    ///
+    /// # エラー
+    /// グループ数が2未満か個体数を割り切らない場合、グループの大きさが2未満の場合、
+    /// または分裂確率が0.0-1.0の範囲外の場合
+    pub fn validate(&self, population: usize) -> GAResult<()> {
+        if self.groups < 2 || population % self.groups != 0 || population / self.groups < 2 {
+            return Err(GAError::ValidationError(format!(
+                "Group count must be at least 2 and divide the population ({population}) into groups of at least 2, got {}",
+                self.groups
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.split_probability) {
+            return Err(GAError::ValidationError(format!(
+                "Split probability must be between 0.0 and 1.0, got {}",
+                self.split_probability
+            )));
+        }
+        Ok(())
+    }
+
+    /// 個体数`population`での1グループの個体数
+    pub fn group_size(&self, population: usize) -> usize {
+        population / self.groups.max(1)
+    }
+
+    /// 同じグループの個体同士の組`(i, j)`（`i < j`）の一覧
+    ///
+    /// グループ`k`は`k * group_size`番目から`group_size`体の個体です。
+    pub fn pairs(&self, population: usize) -> Vec<(usize, usize)> {
+        let size = self.group_size(population);
+        (0..population)
+            .flat_map(|i| {
+                let end = (i / size + 1) * size;
+                ((i + 1)..end).map(move |j| (i, j))
+            })
+            .collect()
+    }
+
+    /// グループの分裂と絶滅を決める
+    ///
+    /// 確率`split_probability`で、獲得ポイントの合計に比例してグループを1つ選んで
+    /// 分裂させ、残りのグループから合計が低いほど選ばれやすい（最大の合計との差に
+    /// 比例する）グループを1つ絶滅させます。
+    ///
+    /// # 引数
+    /// * `totals` - 各グループの獲得ポイントの合計
+    ///
+    /// # 戻り値
+    /// 分裂したグループと絶滅したグループの添字（この世代に分裂がなければ`None`）
+    pub fn group_event<R: Rng>(&self, totals: &[f64], rng: &mut R) -> Option<(usize, usize)> {
+        if totals.len() < 2 || !rng.gen_bool(self.split_probability) {
+            return None;
+        }
+
+        let parent = weighted_choice(totals, rng);
+        let max = totals.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = totals
+            .iter()
+            .enumerate()
+            .map(|(k, &total)| if k == parent { 0.0 } else { max - total })
+            .collect();
+        let extinct = if weights.iter().any(|&w| w > 0.0) {
+            weighted_choice(&weights, rng)
+        } else {
+            let other = rng.gen_range(0..totals.len() - 1);
+            if other >= parent {
+                other + 1
+            } else {
+                other
+            }
+        };
+        Some((parent, extinct))
+    }
+
+    /// グループの分裂と絶滅による各グループの子の数の期待倍率
+    ///
+    /// `group_event`と同じ確率で、分裂したグループは子が2倍、絶滅したグループは
+    /// 0倍になるとした期待値です。分裂が起こらなければ全グループ1倍です。
+    ///
+    /// # 引数
+    /// * `totals` - 各グループの獲得ポイントの合計
+    pub fn group_weights(&self, totals: &[f64]) -> Vec<f64> {
+        let mut weights = vec![1.0; totals.len()];
+        if totals.len() < 2 {
+            return weights;
+        }
+
+        let parents = choice_probabilities(totals);
+        let max = totals.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        for (parent, &chosen) in parents.iter().enumerate() {
+            let extinction: Vec<f64> = totals
+                .iter()
+                .enumerate()
+                .map(|(k, &total)| if k == parent { 0.0 } else { max - total })
+                .collect();
+            let extinction = if extinction.iter().any(|&w| w > 0.0) {
+                choice_probabilities(&extinction)
+            } else {
+                let other = 1.0 / (totals.len() - 1) as f64;
+                (0..totals.len())
+                    .map(|k| if k == parent { 0.0 } else { other })
+                    .collect()
+            };
+            weights[parent] += self.split_probability * chosen;
+            for (weight, extinct) in weights.iter_mut().zip(extinction) {
+                *weight -= self.split_probability * chosen * extinct;
+            }
+        }
+        weights
+    }
+}
+
+/// `weighted_choice`で各添字が選ばれる確率
+fn choice_probabilities(weights: &[f64]) -> Vec<f64> {
+    let total: f64 = weights.iter().sum();
+    if WeightedIndex::new(weights).is_ok() {
+        weights.iter().map(|w| w / total).collect()
+    } else {
+        vec![1.0 / weights.len() as f64; weights.len()]
+    }
+}
+
+/// 重みに比例して添字を選ぶ（全ての重みが0なら一様に選ぶ）
+fn weighted_choice<R: Rng>(weights: &[f64], rng: &mut R) -> usize {
+    WeightedIndex::new(weights)
+        .map(|d| d.sample(rng))
+        .unwrap_or_else(|_| rng.gen_range(0..weights.len()))
+}
+
+impl fmt::Display for GroupSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "groups:{}:{}", self.groups, self.split_probability)
+    }
+}
+
+impl FromStr for GroupSelection {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid group selection: {s}"));
+        match s.split(':').collect::<Vec<_>>().as_slice() {
+            ["groups", groups] => Ok(GroupSelection::new(groups.parse().map_err(|_| invalid())?)),
+            ["groups", groups, split] => {
+                Ok(GroupSelection::new(groups.parse().map_err(|_| invalid())?)
+                    .with_split_probability(split.parse().map_err(|_| invalid())?))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// グループの分裂と絶滅の記録
+///
+/// # フィールド
+/// * `generation` - 分裂が起こった世代
+/// * `parent` - 分裂したグループ
+/// * `extinct` - 絶滅し、分裂したグループの片方に置き換えられたグループ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupEvent {
+    pub generation: usize,
+    pub parent: usize,
+    pub extinct: usize,
+}
+
+/// Price方程式による選択の効果の分解
+///
+/// 形質`z`（協力確率）の平均の選択による変化`Cov(w, z) / w̄`を、
+/// グループ間の成分`Cov_k(W_k, Z_k) / w̄`（グループ平均の適応度と形質の共分散）と
+/// グループ内の成分`E_k[Cov_i(w, z)] / w̄`（グループ内の共分散の平均）に分けます。
+/// グループの大きさが等しいため、両者の和は個体群全体の共分散による値と一致します。
+/// 協力はグループ間の選択で増え、グループ内の選択で減る傾向があります。
+///
+/// # フィールド
+/// * `between` - グループ間の選択の成分
+/// * `within` - グループ内の選択の成分
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PriceDecomposition {
+    pub between: f64,
+    pub within: f64,
+}
+
+impl PriceDecomposition {
+    /// 各個体の形質と適応度から選択の成分を計算
+    ///
+    /// 平均適応度が0の場合や個体群が空の場合は両成分とも0です。
+    ///
+    /// # 引数
+    /// * `traits` - 各個体の形質
+    /// * `fitness` - 各個体の適応度
+    /// * `group_size` - 1グループの個体数（先頭から順に区切る）
+    pub fn measure(traits: &[f64], fitness: &[f64], group_size: usize) -> Self {
+        let mean_fitness = mean(fitness);
+        if traits.is_empty() || group_size == 0 || mean_fitness == 0.0 {
+            return Self::default();
+        }
+
+        let groups: Vec<(f64, f64, f64)> = traits
+            .chunks(group_size)
+            .zip(fitness.chunks(group_size))
+            .map(|(z, w)| (mean(z), mean(w), covariance(z, w)))
+            .collect();
+        let group_traits: Vec<f64> = groups.iter().map(|&(z, _, _)| z).collect();
+        let group_fitness: Vec<f64> = groups.iter().map(|&(_, w, _)| w).collect();
+        let within: Vec<f64> = groups.iter().map(|&(_, _, cov)| cov).collect();
+
+        Self {
+            between: covariance(&group_traits, &group_fitness) / mean_fitness,
+            within: mean(&within) / mean_fitness,
+        }
+    }
+
+    /// 選択による形質の平均の変化（両成分の和）
+    pub fn total(&self) -> f64 {
+        self.between + self.within
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len().max(1) as f64
+}
+
+/// 母共分散（`1 / n`で割る）
+fn covariance(x: &[f64], y: &[f64]) -> f64 {
+    let (mean_x, mean_y) = (mean(x), mean(y));
+    x.iter()
+        .zip(y)
+        .map(|(a, b)| (a - mean_x) * (b - mean_y))
+        .sum::<f64>()
+        / x.len().max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_parse_round_trip() {
+        let groups: GroupSelection = "groups:4:0.2".parse().unwrap();
+        assert_eq!(groups, GroupSelection::new(4).with_split_probability(0.2));
+        assert_eq!(groups.to_string(), "groups:4:0.2");
+        assert_eq!(
+            "groups:5"
+                .parse::<GroupSelection>()
+                .unwrap()
+                .split_probability,
+            DEFAULT_SPLIT_PROBABILITY
+        );
+
+        for invalid in ["groups", "groups:x", "groups:2:0.1:1", "demes:2"] {
+            assert!(invalid.parse::<GroupSelection>().is_err(), "{invalid}");
+        }
+        assert!(GroupSelection::new(3).validate(20).is_err());
+        assert!(GroupSelection::new(10).validate(10).is_err());
+        assert!(GroupSelection::new(2)
+            .with_split_probability(1.5)
+            .validate(10)
+            .is_err());
+        assert!(groups.validate(20).is_ok());
+    }
+
+    #[test]
+    fn test_pairs_stay_within_groups() {
+        let groups = GroupSelection::new(3);
+        let pairs = groups.pairs(9);
+        assert_eq!(pairs.len(), 9);
+        assert!(pairs.iter().all(|&(i, j)| i / 3 == j / 3 && i < j));
+    }
+
+    #[test]
+    fn test_group_event_favours_high_payoff_groups() {
+        let mut rng = thread_rng();
+        let never = GroupSelection::new(3).with_split_probability(0.0);
+        assert_eq!(never.group_event(&[1.0, 2.0, 3.0], &mut rng), None);
+
+        // 合計0のグループは分裂せず、最大のグループは絶滅しない
+        let always = GroupSelection::new(3).with_split_probability(1.0);
+        for _ in 0..50 {
+            let (parent, extinct) = always.group_event(&[0.0, 5.0, 10.0], &mut rng).unwrap();
+            assert_ne!(parent, 0);
+            assert_ne!(extinct, 2);
+            assert_ne!(parent, extinct);
+        }
+        let (parent, extinct) = always.group_event(&[0.0, 0.0], &mut rng).unwrap();
+        assert_ne!(parent, extinct);
+    }
+
+    #[test]
+    fn test_group_weights_match_group_events() {
+        let groups = GroupSelection::new(3).with_split_probability(0.5);
+        assert_eq!(
+            GroupSelection::new(3)
+                .with_split_probability(0.0)
+                .group_weights(&[1.0, 2.0, 3.0]),
+            vec![1.0; 3]
+        );
+
+        // 合計0のグループは分裂せず、合計が最大のグループは絶滅しない
+        let weights = groups.group_weights(&[0.0, 5.0, 10.0]);
+        assert!((weights.iter().sum::<f64>() - 3.0).abs() < 1e-12);
+        let expected = [
+            1.0 - 0.5 * (1.0 / 3.0 + 2.0 / 3.0 * 2.0 / 3.0),
+            1.0 + 0.5 * (1.0 / 3.0 - 2.0 / 3.0 * 1.0 / 3.0),
+            1.0 + 0.5 * 2.0 / 3.0,
+        ];
+        for (weight, expected) in weights.iter().zip(expected) {
+            assert!((weight - expected).abs() < 1e-12, "{weights:?}");
+        }
+    }
+
+    #[test]
+    fn test_price_decomposition_sums_to_total_covariance() {
+        // 協力者の多いグループほど適応度が高いが、グループ内では裏切り者が有利
+        let traits = [1.0, 1.0, 0.0, 1.0, 0.0, 0.0];
+        let fitness = [3.0, 3.0, 5.0, 1.0, 1.5, 1.5];
+        let price = PriceDecomposition::measure(&traits, &fitness, 3);
+        assert!(price.between > 0.0);
+        assert!(price.within < 0.0);
+
+        let total = covariance(&traits, &fitness) / mean(&fitness);
+        assert!((price.total() - total).abs() < 1e-12);
+
+        assert_eq!(
+            PriceDecomposition::measure(&traits, &[0.0; 6], 3),
+            PriceDecomposition::default()
+        );
+    }
+}
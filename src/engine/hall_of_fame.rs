@@ -0,0 +1,301 @@
+/// 殿堂（ホール・オブ・フェイム）
+///
+/// 実行全体を通して出現した遺伝子型のうち、獲得ポイントの高いものを
+/// DNAで重複を除いて一定数まで保存するアーカイブです。過去の優秀な戦略を
+/// 追加の対戦相手として評価に加えることで、戦略が循環する（じゃんけん的な
+/// 勝ち負けを繰り返す）現象を抑えます。戦略ファイルとして書き出し、
+/// 後のトーナメントで読み込むこともできます。
+use crate::core::{errors::*, traits::*, types::*};
+use std::fs;
+use std::path::Path;
+
+/// 戦略ファイルの先頭に書き出すコメント行
+const STRATEGY_FILE_HEADER: &str = "# hall of fame: dna points generation";
+
+/// 殿堂入りした遺伝子型
+///
+/// # フィールド
+/// * `dna` - 遺伝子型
+/// * `points` - この遺伝子型の個体が記録した最高ポイント
+/// * `generation` - 殿堂に初めて入った世代
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HallOfFameEntry {
+    pub dna: Dna,
+    pub points: Points,
+    pub generation: usize,
+}
+
+/// 殿堂
+///
+/// エントリはポイントの降順（同点なら初出世代の早い順）に並び、
+/// 容量を超えた分は末尾から取り除かれます。取り除かれた遺伝子型が
+/// 後の世代で再び殿堂に入った場合、初出世代はその世代になります。
+///
+/// # フィールド
+/// * `capacity` - 保存する遺伝子型の最大数（0の場合は何も保存しない）
+/// * `entries` - 殿堂入りした遺伝子型
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HallOfFame {
+    capacity: usize,
+    entries: Vec<HallOfFameEntry>,
+}
+
+impl HallOfFame {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// 保存済みのエントリ（読み込んだ戦略ファイルなど）から作成
+    ///
+    /// 容量はエントリ数になります。
+    pub fn with_entries(mut entries: Vec<HallOfFameEntry>) -> Self {
+        Self::sort_entries(&mut entries);
+        Self {
+            capacity: entries.len(),
+            entries,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// ポイントの降順に並んだエントリ
+    pub fn entries(&self) -> &[HallOfFameEntry] {
+        &self.entries
+    }
+
+    /// 最高ポイントのエントリ
+    pub fn best(&self) -> Option<&HallOfFameEntry> {
+        self.entries.first()
+    }
+
+    /// 初出世代の早い順に並んだエントリ
+    pub fn by_generation(&self) -> Vec<&HallOfFameEntry> {
+        let mut entries: Vec<&HallOfFameEntry> = self.entries.iter().collect();
+        entries.sort_by(|a, b| {
+            a.generation
+                .cmp(&b.generation)
+                .then(b.points.cmp(&a.points))
+        });
+        entries
+    }
+
+    /// 評価済みの個体群で殿堂を更新
+    ///
+    /// 既に殿堂にある遺伝子型は最高ポイントのみ更新し、初出世代は変えません。
+    ///
+    /// # 引数
+    /// * `generation` - 現在の世代
+    /// * `agents` - ポイントを評価済みの個体群
+    pub fn update<T: Agent>(&mut self, generation: usize, agents: &[T]) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        for agent in agents {
+            let dna = agent.dna_binary();
+            match self.entries.iter_mut().find(|entry| entry.dna == dna) {
+                Some(entry) => entry.points = entry.points.max(agent.points()),
+                None => self.entries.push(HallOfFameEntry {
+                    dna: dna.to_string(),
+                    points: agent.points(),
+                    generation,
+                }),
+            }
+        }
+
+        Self::sort_entries(&mut self.entries);
+        self.entries.truncate(self.capacity);
+    }
+
+    /// ポイントの降順（同点なら初出世代の早い順）に並べる
+    fn sort_entries(entries: &mut [HallOfFameEntry]) {
+        entries.sort_by(|a, b| {
+            b.points
+                .cmp(&a.points)
+                .then(a.generation.cmp(&b.generation))
+        });
+    }
+
+    /// 殿堂の遺伝子型から対戦相手の個体を作成
+    ///
+    /// # 引数
+    /// * `create` - IDとDNAから個体を作成する関数
+    pub fn opponents<T, F>(&self, create: F) -> Vec<T>
+    where
+        F: Fn(AgentId, Dna) -> T,
+    {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| create(i as AgentId, entry.dna.clone()))
+            .collect()
+    }
+
+    /// 戦略ファイルの内容を作成
+    ///
+    /// 1行に1つの遺伝子型を`DNA ポイント 初出世代`の形式で、ポイントの降順に
+    /// 書き出します。`#`で始まる行はコメントです。
+    pub fn to_strategy_file(&self) -> String {
+        let mut contents = format!("{STRATEGY_FILE_HEADER}\n");
+        for entry in &self.entries {
+            contents.push_str(&format!(
+                "{} {} {}\n",
+                entry.dna, entry.points, entry.generation
+            ));
+        }
+        contents
+    }
+
+    /// 戦略ファイルに書き出す
+    ///
+    /// # エラー
+    /// ファイルの書き込みに失敗した場合
+    pub fn write_strategy_file<P: AsRef<Path>>(&self, path: P) -> GAResult<()> {
+        fs::write(path.as_ref(), self.to_strategy_file()).map_err(|e| {
+            GAError::OutputError(format!("Failed to write {}: {e}", path.as_ref().display()))
+        })
+    }
+
+    /// 戦略ファイルの内容を解析
+    ///
+    /// 空行と`#`で始まる行は無視します。ポイントと初出世代は省略でき、
+    /// 省略した場合は0になります。
+    ///
+    /// # エラー
+    /// DNAが不正な場合、または数値の解析に失敗した場合
+    pub fn parse_strategy_file(contents: &str) -> GAResult<Vec<HallOfFameEntry>> {
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let mut fields = line.split_whitespace();
+                let dna = fields.next().unwrap_or_default().to_string();
+                validation::validate_dna(&dna)?;
+
+                let mut number = |name: &str| -> GAResult<u64> {
+                    fields.next().map_or(Ok(0), |value| {
+                        value.parse().map_err(|_| {
+                            GAError::ConfigurationFileError(format!(
+                                "Invalid {name} in strategy line: {line}"
+                            ))
+                        })
+                    })
+                };
+                let points = number("points")?;
+                let generation = number("generation")? as usize;
+
+                Ok(HallOfFameEntry {
+                    dna,
+                    points,
+                    generation,
+                })
+            })
+            .collect()
+    }
+
+    /// 戦略ファイルを読み込む
+    ///
+    /// # エラー
+    /// ファイルの読み込みに失敗した場合、または内容が不正な場合
+    pub fn read_strategy_file<P: AsRef<Path>>(path: P) -> GAResult<Vec<HallOfFameEntry>> {
+        let contents = fs::read_to_string(path.as_ref()).map_err(|e| {
+            GAError::ConfigurationFileError(format!(
+                "Failed to read {}: {e}",
+                path.as_ref().display()
+            ))
+        })?;
+        Self::parse_strategy_file(&contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn agent(id: AgentId, dna: &str, points: Points) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, dna.to_string()), points)
+    }
+
+    #[test]
+    fn test_keeps_best_distinct_genotypes() {
+        let mut hall_of_fame = HallOfFame::new(2);
+        hall_of_fame.update(
+            0,
+            &[agent(0, "000", 10), agent(1, "000", 12), agent(2, "111", 5)],
+        );
+        hall_of_fame.update(1, &[agent(0, "101", 8), agent(1, "000", 7)]);
+
+        let entries = hall_of_fame.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0],
+            HallOfFameEntry {
+                dna: "000".to_string(),
+                points: 12,
+                generation: 0
+            }
+        );
+        assert_eq!(entries[1].dna, "101");
+        assert_eq!(entries[1].generation, 1);
+        assert_eq!(hall_of_fame.by_generation()[0].dna, "000");
+    }
+
+    #[test]
+    fn test_ties_prefer_earlier_generation() {
+        let mut hall_of_fame = HallOfFame::new(1);
+        hall_of_fame.update(0, &[agent(0, "01", 4)]);
+        hall_of_fame.update(1, &[agent(0, "10", 4)]);
+        assert_eq!(hall_of_fame.best().unwrap().dna, "01");
+    }
+
+    #[test]
+    fn test_zero_capacity_is_disabled() {
+        let mut hall_of_fame = HallOfFame::new(0);
+        hall_of_fame.update(0, &[agent(0, "01", 4)]);
+        assert!(hall_of_fame.is_empty());
+    }
+
+    #[test]
+    fn test_strategy_file_round_trip() {
+        let mut hall_of_fame = HallOfFame::new(3);
+        hall_of_fame.update(4, &[agent(0, "0110", 9), agent(1, "1111", 3)]);
+
+        let contents = hall_of_fame.to_strategy_file();
+        assert!(contents.starts_with('#'));
+        let parsed = HallOfFame::parse_strategy_file(&contents).unwrap();
+        assert_eq!(parsed, hall_of_fame.entries());
+        assert_eq!(
+            HallOfFame::with_entries(parsed).entries(),
+            hall_of_fame.entries()
+        );
+
+        let opponents: Vec<ModelAgent> = hall_of_fame.opponents(ModelAgent::new);
+        assert_eq!(opponents[0].dna_binary(), "0110");
+    }
+
+    #[test]
+    fn test_parse_strategy_file() {
+        let parsed = HallOfFame::parse_strategy_file("\n# comment\n101\n0011 7 2\n").unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!((parsed[0].points, parsed[0].generation), (0, 0));
+        assert_eq!((parsed[1].points, parsed[1].generation), (7, 2));
+
+        assert!(HallOfFame::parse_strategy_file("10a1").is_err());
+        assert!(HallOfFame::parse_strategy_file("101 x").is_err());
+    }
+}
@@ -0,0 +1,230 @@
+/// 世代内の学習（ボールドウィン型とラマルク型）
+///
+/// 各個体は世代の始めに遺伝子型の表現型の値を協力確率として持ち、対戦で受け取った
+/// 利得による強化学習（Bush-Mostellerモデル）で世代の間に協力確率を変化させます。
+/// 利得が期待水準（aspiration）を上回れば取った行動の確率を上げ、下回れば下げます。
+///
+/// ボールドウィン型では学習した協力確率は適応度（獲得ポイント）にのみ影響し、
+/// 子は親の遺伝子型の値から学習をやり直します。ラマルク型では繁殖の前に
+/// 学習した協力確率を遺伝子型に書き戻すため、学習の結果が子に遺伝します。
+use crate::core::errors::*;
+use crate::core::types::{PayoffMatrix, Points};
+use std::fmt;
+use std::str::FromStr;
+
+/// 学習率の既定値
+pub const DEFAULT_LEARNING_RATE: f64 = 0.1;
+
+/// 期待水準の既定値（相互裏切りの利得と相互協力の利得の中間）
+pub const DEFAULT_ASPIRATION: f64 = 2.0;
+
+/// 学習した形質の遺伝の仕方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inheritance {
+    /// 学習は適応度にのみ影響し、遺伝子型は変化しない
+    Baldwinian,
+    /// 学習した協力確率を繁殖の前に遺伝子型に書き戻す
+    Lamarckian,
+}
+
+impl fmt::Display for Inheritance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Inheritance::Baldwinian => write!(f, "baldwin"),
+            Inheritance::Lamarckian => write!(f, "lamarck"),
+        }
+    }
+}
+
+/// 世代内の学習の設定
+///
+/// 文字列表現は`baldwin[:RATE[:ASPIRATION]]`または`lamarck[:RATE[:ASPIRATION]]`です
+/// （例: `lamarck:0.2`、`baldwin:0.1:2.5`）。
+///
+/// # フィールド
+/// * `inheritance` - 学習した形質の遺伝の仕方
+/// * `rate` - 学習率（0.0より大きく1.0以下）
+/// * `aspiration` - 利得の期待水準（0以上、裏切りの誘惑の利得以下）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Learning {
+    pub inheritance: Inheritance,
+    pub rate: f64,
+    pub aspiration: f64,
+}
+
+impl Learning {
+    pub fn new(inheritance: Inheritance) -> Self {
+        Self {
+            inheritance,
+            rate: DEFAULT_LEARNING_RATE,
+            aspiration: DEFAULT_ASPIRATION,
+        }
+    }
+
+    pub fn with_rate(mut self, rate: f64) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    pub fn with_aspiration(mut self, aspiration: f64) -> Self {
+        self.aspiration = aspiration;
+        self
+    }
+
+    /// 学習した協力確率を遺伝子型に書き戻すかどうか
+    pub fn is_lamarckian(&self) -> bool {
+        self.inheritance == Inheritance::Lamarckian
+    }
+
+    /// パラメータの妥当性を検証
+    ///
+    /// # エラー
+    /// 学習率が(0.0, 1.0]の範囲外、または期待水準が利得の範囲外の場合
+    pub fn validate(&self) -> GAResult<()> {
+        if !(self.rate > 0.0 && self.rate <= 1.0) {
+            return Err(GAError::ValidationError(format!(
+                "Learning rate must be in (0.0, 1.0], got {}",
+                self.rate
+            )));
+        }
+        let matrix = PayoffMatrix::default();
+        if !(self.aspiration >= matrix.sucker as f64 && self.aspiration <= matrix.temptation as f64)
+        {
+            return Err(GAError::ValidationError(format!(
+                "Aspiration must be between the sucker's payoff ({}) and the temptation ({}), got {}",
+                matrix.sucker, matrix.temptation, self.aspiration
+            )));
+        }
+        Ok(())
+    }
+
+    /// 1回の対戦の結果から協力確率を更新
+    ///
+    /// 刺激`s = (利得 - 期待水準) / max(T - 期待水準, 期待水準 - S)`（-1から1）に対し、
+    /// 取った行動の確率`p`を`s >= 0`なら`p + rate * s * (1 - p)`、
+    /// `s < 0`なら`p + rate * s * p`に更新します。
+    ///
+    /// # 引数
+    /// * `probability` - 更新前の協力確率
+    /// * `cooperated` - この対戦で協力したかどうか
+    /// * `payoff` - この対戦で受け取った利得
+    ///
+    /// # 戻り値
+    /// 更新後の協力確率（0.0-1.0）
+    pub fn reinforce(&self, probability: f64, cooperated: bool, payoff: Points) -> f64 {
+        let matrix = PayoffMatrix::default();
+        let scale = (matrix.temptation as f64 - self.aspiration)
+            .max(self.aspiration - matrix.sucker as f64);
+        let stimulus = ((payoff as f64 - self.aspiration) / scale).clamp(-1.0, 1.0);
+
+        let chosen = if cooperated {
+            probability
+        } else {
+            1.0 - probability
+        };
+        let chosen = if stimulus >= 0.0 {
+            chosen + self.rate * stimulus * (1.0 - chosen)
+        } else {
+            chosen + self.rate * stimulus * chosen
+        };
+
+        let probability = if cooperated { chosen } else { 1.0 - chosen };
+        probability.clamp(0.0, 1.0)
+    }
+}
+
+impl fmt::Display for Learning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.inheritance, self.rate, self.aspiration)
+    }
+}
+
+impl FromStr for Learning {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid learning mode: {s}"));
+        let parts: Vec<&str> = s.split(':').collect();
+
+        let (inheritance, rest) = match parts.as_slice() {
+            ["baldwin", rest @ ..] => (Inheritance::Baldwinian, rest),
+            ["lamarck", rest @ ..] => (Inheritance::Lamarckian, rest),
+            _ => return Err(invalid()),
+        };
+        let mut learning = Learning::new(inheritance);
+        match rest {
+            [] => {}
+            [rate] => learning = learning.with_rate(rate.parse().map_err(|_| invalid())?),
+            [rate, aspiration] => {
+                learning = learning
+                    .with_rate(rate.parse().map_err(|_| invalid())?)
+                    .with_aspiration(aspiration.parse().map_err(|_| invalid())?)
+            }
+            _ => return Err(invalid()),
+        }
+
+        learning.validate()?;
+        Ok(learning)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        let lamarck: Learning = "lamarck:0.2".parse().unwrap();
+        assert_eq!(
+            lamarck,
+            Learning::new(Inheritance::Lamarckian).with_rate(0.2)
+        );
+        assert!(lamarck.is_lamarckian());
+        assert_eq!(lamarck.to_string().parse::<Learning>().unwrap(), lamarck);
+
+        let baldwin: Learning = "baldwin:0.1:2.5".parse().unwrap();
+        assert_eq!(baldwin.inheritance, Inheritance::Baldwinian);
+        assert_eq!(baldwin.aspiration, 2.5);
+        assert_eq!(baldwin.to_string(), "baldwin:0.1:2.5");
+
+        for invalid in [
+            "baldwin:0",
+            "lamarck:1.5",
+            "lamarck:0.1:6",
+            "lamarck:0.1:-1",
+            "lamarck:0.1:2:3",
+            "darwin",
+        ] {
+            assert!(invalid.parse::<Learning>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_reinforce_follows_payoff() {
+        let learning = Learning::new(Inheritance::Baldwinian).with_rate(0.5);
+
+        // 相互協力（3 > 2）は協力を強化し、搾取される（0 < 2）と協力を弱める
+        assert!(learning.reinforce(0.5, true, 3) > 0.5);
+        assert!(learning.reinforce(0.5, true, 0) < 0.5);
+        // 誘惑（5）は裏切りを強化し、相互裏切り（1）は裏切りを弱める
+        assert!(learning.reinforce(0.5, false, 5) < 0.5);
+        assert!(learning.reinforce(0.5, false, 1) > 0.5);
+
+        // 誘惑の利得は刺激1で、裏切りの確率を残りの半分だけ上げる
+        assert!((learning.reinforce(0.5, false, 5) - 0.25).abs() < 1e-12);
+        // 期待水準ちょうどの利得では変化しない
+        let neutral = Learning::new(Inheritance::Baldwinian).with_aspiration(3.0);
+        assert_eq!(neutral.reinforce(0.4, true, 3), 0.4);
+    }
+
+    #[test]
+    fn test_reinforce_stays_in_range() {
+        let learning = Learning::new(Inheritance::Lamarckian).with_rate(1.0);
+        for probability in [0.0, 0.3, 1.0] {
+            for (cooperated, payoff) in [(true, 0), (true, 3), (false, 1), (false, 5)] {
+                let updated = learning.reinforce(probability, cooperated, payoff);
+                assert!((0.0..=1.0).contains(&updated));
+            }
+        }
+    }
+}
@@ -7,6 +7,55 @@ pub mod selection;
 /// Crossover operations
 pub mod crossover;
 
+/// Diversity preservation schemes
+pub mod diversity;
+
+/// Mutation rate control schemes
+pub mod mutation;
+
+/// Multi-objective evolution
+pub mod multi_objective;
+
+/// Termination criteria
+pub mod termination;
+
+/// Moran, Wright-Fisher, replicator and imitation dynamics
+pub mod dynamics;
+
+/// Archive of the best genotypes across a run
+pub mod hall_of_fame;
+
+/// Novelty search over behavioural characterisations
+pub mod novelty;
+
+/// Age-structured populations with lifespans and maturity
+pub mod aging;
+
+/// Within-lifetime learning with Baldwinian and Lamarckian inheritance
+pub mod learning;
+
+/// Group (multilevel) selection with a Price equation decomposition
+pub mod group_selection;
+
 // Re-export commonly used items
+pub use aging::{AgeStats, AgeStructure, Mortality, MortalityRecord};
+pub use crossover::{CrossoverScheme, HomologousCrossover, MessyCrossover, SinglePointCrossover};
+pub use diversity::{
+    DeterministicCrowding, DiversityScheme, FitnessSharing, RestrictedTournamentReplacement,
+};
+pub use dynamics::{
+    FixationExperiment, ImitationRule, MoranProcess, MoranUpdate, Neighbourhood,
+    ReplicatorDynamics, WrightFisherProcess,
+};
 pub use genetic::{GeneticAlgorithmEngine, Population};
-pub use selection::{RankSelection, RouletteSelection, TournamentSelection};
+pub use group_selection::{GroupEvent, GroupSelection, PriceDecomposition};
+pub use hall_of_fame::{HallOfFame, HallOfFameEntry};
+pub use learning::{Inheritance, Learning};
+pub use multi_objective::{Nsga2, ParetoPoint};
+pub use mutation::{IndelMutation, MutationController, MutationScheme};
+pub use novelty::{NoveltyArchive, NoveltySearch};
+pub use selection::{
+    BoltzmannSelection, LexicaseSelection, RankSelection, RouletteSelection, SelectionPressure,
+    StochasticUniversalSampling, TournamentSelection, TruncationSelection,
+};
+pub use termination::{CancellationToken, Termination, TerminationCriterion, TerminationReason};
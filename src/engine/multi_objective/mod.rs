@@ -0,0 +1,60 @@
+/// 多目的進化
+///
+/// 複数の目的関数（`core::types::Objective`）を同時に最適化するための
+/// パレート支配に基づく選択を提供します。
+pub mod nsga2;
+
+pub use nsga2::{crowding_distance, dominates, non_dominated_sort, Nsga2};
+
+use crate::core::types::Objective;
+
+/// パレートフロント上の1個体
+///
+/// # フィールド
+/// * `dna` - 個体のDNA
+/// * `objectives` - 目的関数の値（元の向き、`Nsga2::objectives`の順）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParetoPoint {
+    pub dna: String,
+    pub objectives: Vec<f64>,
+}
+
+/// 目的関数リストを`score,cooperation`形式の文字列から解析
+///
+/// # エラー
+/// 未知の目的関数名、または同じ目的関数が重複している場合
+pub fn parse_objectives(s: &str) -> crate::core::errors::GAResult<Vec<Objective>> {
+    let objectives = s
+        .split(',')
+        .map(|name| name.trim().parse())
+        .collect::<crate::core::errors::GAResult<Vec<Objective>>>()?;
+    validate_objectives(&objectives)?;
+    Ok(objectives)
+}
+
+/// 目的関数リストの重複を検証
+pub fn validate_objectives(objectives: &[Objective]) -> crate::core::errors::GAResult<()> {
+    for (i, objective) in objectives.iter().enumerate() {
+        if objectives[..i].contains(objective) {
+            return Err(crate::core::errors::GAError::ValidationError(format!(
+                "Duplicate objective: {objective}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_objectives() {
+        assert_eq!(
+            parse_objectives("score,cooperation").unwrap(),
+            vec![Objective::Score, Objective::Cooperation]
+        );
+        assert!(parse_objectives("score,score").is_err());
+        assert!(parse_objectives("score,speed").is_err());
+    }
+}
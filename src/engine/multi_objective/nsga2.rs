@@ -0,0 +1,331 @@
+/// NSGA-II（非優越ソートと混雑距離による多目的選択）の実装
+///
+/// 個体群を非優越ソートでフロントに分割し、同じフロント内では混雑距離の大きい
+/// （周囲が空いている）個体を優先します。親選択には混雑度トーナメントを、
+/// 生存選択には親子を合わせた個体群からの切り捨てを使用します。
+use super::{validate_objectives, ParetoPoint};
+use crate::core::{errors::*, traits::*, types::*};
+use crate::engine::genetic::breed;
+use crate::engine::selection::{check_parents, check_survivors};
+use rand::{Rng, RngCore};
+use std::cmp::Ordering;
+
+/// `a`が`b`をパレート支配するかどうか（全目的を最大化として比較）
+pub fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x >= y) && a.iter().zip(b).any(|(x, y)| x > y)
+}
+
+/// 高速非優越ソート
+///
+/// # 戻り値
+/// フロントごとの個体インデックス（先頭が第1フロント）
+pub fn non_dominated_sort(values: &[Vec<f64>]) -> Vec<Vec<usize>> {
+    let n = values.len();
+    let mut dominated_by: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut domination_count = vec![0usize; n];
+    let mut fronts = vec![Vec::new()];
+
+    for p in 0..n {
+        for q in 0..n {
+            if dominates(&values[p], &values[q]) {
+                dominated_by[p].push(q);
+            } else if dominates(&values[q], &values[p]) {
+                domination_count[p] += 1;
+            }
+        }
+        if domination_count[p] == 0 {
+            fronts[0].push(p);
+        }
+    }
+
+    let mut current = 0;
+    while !fronts[current].is_empty() {
+        let mut next = Vec::new();
+        for &p in &fronts[current] {
+            for &q in &dominated_by[p] {
+                domination_count[q] -= 1;
+                if domination_count[q] == 0 {
+                    next.push(q);
+                }
+            }
+        }
+        fronts.push(next);
+        current += 1;
+    }
+
+    fronts.pop();
+    fronts
+}
+
+/// フロント内の各個体の混雑距離を計算
+///
+/// 各目的で両端の個体は無限大、それ以外は隣接個体との差を目的の値域で
+/// 正規化した値の合計になります。戻り値は`front`と同じ並びです。
+pub fn crowding_distance(values: &[Vec<f64>], front: &[usize]) -> Vec<f64> {
+    let mut distance = vec![0.0; front.len()];
+    if front.len() <= 2 {
+        return vec![f64::INFINITY; front.len()];
+    }
+
+    let objectives = values[front[0]].len();
+    let columns = (0..objectives).map(|m| front.iter().map(|&i| values[i][m]).collect::<Vec<_>>());
+    for column in columns {
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|&a, &b| column[a].total_cmp(&column[b]));
+
+        let (first, last) = (order[0], order[front.len() - 1]);
+        let range = column[last] - column[first];
+        distance[first] = f64::INFINITY;
+        distance[last] = f64::INFINITY;
+
+        if range <= 0.0 {
+            continue;
+        }
+
+        for window in order.windows(3) {
+            distance[window[1]] += (column[window[2]] - column[window[0]]) / range;
+        }
+    }
+
+    distance
+}
+
+/// NSGA-IIによる多目的選択
+///
+/// # フィールド
+/// * `objectives` - 最適化する目的関数
+#[derive(Debug, Clone, PartialEq)]
+pub struct Nsga2 {
+    objectives: Vec<Objective>,
+}
+
+impl Nsga2 {
+    /// 目的関数を指定して作成
+    ///
+    /// # エラー
+    /// 目的関数が空、または重複している場合
+    pub fn new(objectives: Vec<Objective>) -> GAResult<Self> {
+        if objectives.is_empty() {
+            return Err(GAError::ValidationError(
+                "At least one objective is required".to_string(),
+            ));
+        }
+        validate_objectives(&objectives)?;
+        Ok(Self { objectives })
+    }
+
+    pub fn objectives(&self) -> &[Objective] {
+        &self.objectives
+    }
+
+    /// 各個体のフロント番号（0が最良）と混雑距離を計算
+    pub fn rank<T: Agent>(&self, population: &[T]) -> (Vec<usize>, Vec<f64>) {
+        let values: Vec<Vec<f64>> = population
+            .iter()
+            .map(|agent| agent.fitness_vector(&self.objectives))
+            .collect();
+
+        let mut ranks = vec![0; population.len()];
+        let mut crowding = vec![0.0; population.len()];
+        for (rank, front) in non_dominated_sort(&values).iter().enumerate() {
+            for (&i, distance) in front.iter().zip(crowding_distance(&values, front)) {
+                ranks[i] = rank;
+                crowding[i] = distance;
+            }
+        }
+
+        (ranks, crowding)
+    }
+
+    /// 混雑度比較: フロント番号が小さい方、同じなら混雑距離が大きい方を優先
+    fn crowded_compare(ranks: &[usize], crowding: &[f64], a: usize, b: usize) -> Ordering {
+        ranks[a]
+            .cmp(&ranks[b])
+            .then_with(|| crowding[b].total_cmp(&crowding[a]))
+    }
+
+    /// 混雑度による二項トーナメントで親を1体選択
+    fn tournament<R: Rng>(ranks: &[usize], crowding: &[f64], rng: &mut R) -> usize {
+        let a = rng.gen_range(0..ranks.len());
+        let b = rng.gen_range(0..ranks.len());
+        match Self::crowded_compare(ranks, crowding, a, b) {
+            Ordering::Greater => b,
+            _ => a,
+        }
+    }
+
+    /// 候補から混雑度比較で上位`count`体を選ぶ
+    pub fn select_best<T: Agent>(&self, candidates: Vec<T>, count: usize) -> Vec<T> {
+        let (ranks, crowding) = self.rank(&candidates);
+        let mut order: Vec<usize> = (0..candidates.len()).collect();
+        order.sort_by(|&a, &b| Self::crowded_compare(&ranks, &crowding, a, b));
+        order.truncate(count);
+        order.sort_unstable();
+
+        candidates
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| order.binary_search(i).is_ok())
+            .map(|(_, agent)| agent)
+            .collect()
+    }
+
+    /// NSGA-IIの1世代を実行
+    ///
+    /// 混雑度トーナメントで親を選んで個体数と同数の子を生成し、`evaluate`で
+    /// 獲得ポイントを設定した後、親子を合わせた中から上位を選んで次世代とします。
+    ///
+    /// # エラー
+    /// 個体群が空の場合
+    pub fn next_generation<T, R, F>(
+        &self,
+        population: &[T],
+        mutation_rate: MutationRate,
+        rng: &mut R,
+        mut evaluate: F,
+    ) -> GAResult<Vec<T>>
+    where
+        T: Agent,
+        R: Rng,
+        F: FnMut(&T) -> Points,
+    {
+        if population.is_empty() {
+            return Err(GAError::EmptyPopulation);
+        }
+
+        let (ranks, crowding) = self.rank(population);
+        let mut combined = population.to_vec();
+
+        for _ in 0..population.len() {
+            let parent1 = &population[Self::tournament(&ranks, &crowding, rng)];
+            let parent2 = &population[Self::tournament(&ranks, &crowding, rng)];
+            let child = breed(parent1, parent2, mutation_rate, rng);
+            combined.push(child.with_points(evaluate(&child)));
+        }
+
+        Ok(self.select_best(combined, population.len()))
+    }
+
+    /// 第1フロント（非劣解）の個体を目的関数の値とともに返す
+    ///
+    /// 同じDNAの個体は1つにまとめます。
+    pub fn pareto_front<T: Agent>(&self, population: &[T]) -> Vec<ParetoPoint> {
+        let values: Vec<Vec<f64>> = population
+            .iter()
+            .map(|agent| agent.fitness_vector(&self.objectives))
+            .collect();
+
+        let mut front: Vec<ParetoPoint> = Vec::new();
+        for &i in non_dominated_sort(&values).first().into_iter().flatten() {
+            let agent = &population[i];
+            if front.iter().any(|p| p.dna == *agent.dna()) {
+                continue;
+            }
+            front.push(ParetoPoint {
+                dna: agent.dna().clone(),
+                objectives: self.objectives.iter().map(|o| o.value(agent)).collect(),
+            });
+        }
+
+        front
+    }
+}
+
+impl<T: Agent> SelectionStrategy<T> for Nsga2 {
+    fn select_parents_with_rng(
+        &self,
+        population: &[T],
+        mut rng: &mut dyn RngCore,
+    ) -> GAResult<(T, T)> {
+        check_parents(population)?;
+        let (ranks, crowding) = self.rank(population);
+        let parent1 = Self::tournament(&ranks, &crowding, &mut rng);
+        let parent2 = Self::tournament(&ranks, &crowding, &mut rng);
+        Ok((population[parent1].clone(), population[parent2].clone()))
+    }
+
+    fn select_survivors(&self, population: &[T], count: usize) -> GAResult<Vec<T>> {
+        check_survivors(population, count)?;
+        Ok(self.select_best(population.to_vec(), count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+    use rand::thread_rng;
+
+    fn agent(id: u64, dna: &str, points: u64) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, dna.to_string()), points)
+    }
+
+    #[test]
+    fn test_dominates() {
+        assert!(dominates(&[2.0, 2.0], &[1.0, 2.0]));
+        assert!(!dominates(&[2.0, 1.0], &[1.0, 2.0]));
+        assert!(!dominates(&[1.0, 1.0], &[1.0, 1.0]));
+    }
+
+    #[test]
+    fn test_non_dominated_sort() {
+        let values = vec![
+            vec![1.0, 5.0],
+            vec![5.0, 1.0],
+            vec![3.0, 3.0],
+            vec![2.0, 2.0],
+            vec![0.0, 0.0],
+        ];
+        let fronts = non_dominated_sort(&values);
+        assert_eq!(fronts, vec![vec![0, 1, 2], vec![3], vec![4]]);
+    }
+
+    #[test]
+    fn test_crowding_distance_boundaries_are_infinite() {
+        let values = vec![vec![1.0, 5.0], vec![3.0, 3.0], vec![5.0, 1.0]];
+        let distance = crowding_distance(&values, &[0, 1, 2]);
+        assert!(distance[0].is_infinite());
+        assert!(distance[2].is_infinite());
+        assert!((distance[1] - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pareto_front_of_score_and_cooperation() {
+        let population = vec![
+            agent(1, "1111", 10), // 協力率最大
+            agent(2, "0000", 40), // スコア最大
+            agent(3, "1100", 20), // トレードオフ上
+            agent(4, "1000", 5),  // 劣解
+        ];
+        let nsga2 = Nsga2::new(vec![Objective::Score, Objective::Cooperation]).unwrap();
+        let front = nsga2.pareto_front(&population);
+
+        let dnas: Vec<&str> = front.iter().map(|p| p.dna.as_str()).collect();
+        assert_eq!(dnas, vec!["1111", "0000", "1100"]);
+        assert_eq!(front[2].objectives, vec![20.0, 0.5]);
+    }
+
+    #[test]
+    fn test_complexity_is_minimised() {
+        let population = vec![agent(1, "0101", 10), agent(2, "0011", 10)];
+        let nsga2 = Nsga2::new(vec![Objective::Score, Objective::Complexity]).unwrap();
+        let best = nsga2.select_best(population, 1);
+        assert_eq!(best[0].get_dna(), "0011");
+    }
+
+    #[test]
+    fn test_next_generation_preserves_size() {
+        let population: Vec<ModelAgent> = (0..6).map(|i| agent(i, "101010", i * 3)).collect();
+        let nsga2 = Nsga2::new(vec![Objective::Score, Objective::Cooperation]).unwrap();
+        let next = nsga2
+            .next_generation(&population, 0.1, &mut thread_rng(), |child| child.dna_sum())
+            .unwrap();
+        assert_eq!(next.len(), 6);
+    }
+
+    #[test]
+    fn test_invalid_objectives() {
+        assert!(Nsga2::new(vec![]).is_err());
+        assert!(Nsga2::new(vec![Objective::Score, Objective::Score]).is_err());
+    }
+}
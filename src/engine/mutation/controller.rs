@@ -0,0 +1,144 @@
+/// 世代ごとの突然変異率の決定
+///
+/// `MutationScheme`に従い、各世代の観測値（多様性・成功率）から
+/// 次世代の生成に用いる実効突然変異率を計算します。
+use super::{MutationScheme, MAX_MUTATION_RATE, MIN_MUTATION_RATE};
+use crate::core::types::{Generation, MutationRate};
+
+/// 1/5成功則の目標成功率
+const TARGET_SUCCESS_RATIO: f64 = 0.2;
+
+/// 突然変異率コントローラ
+///
+/// # フィールド
+/// * `scheme` - 制御スキーム
+/// * `base_rate` - 基準となる突然変異率（`Config::mutation_rate`）
+/// * `generations` - 総世代数（線形減衰の計算に使用）
+/// * `current` - 直近に決定した突然変異率
+#[derive(Debug, Clone)]
+pub struct MutationController {
+    scheme: MutationScheme,
+    base_rate: MutationRate,
+    generations: Generation,
+    current: MutationRate,
+}
+
+impl MutationController {
+    pub fn new(scheme: MutationScheme, base_rate: MutationRate, generations: Generation) -> Self {
+        Self {
+            scheme,
+            base_rate,
+            generations,
+            current: base_rate,
+        }
+    }
+
+    pub fn scheme(&self) -> MutationScheme {
+        self.scheme
+    }
+
+    /// 直近に決定した突然変異率を取得
+    pub fn current_rate(&self) -> MutationRate {
+        self.current
+    }
+
+    /// 世代の観測値から次世代の生成に使う突然変異率を決定
+    ///
+    /// # 引数
+    /// * `generation` - 観測した世代番号
+    /// * `diversity` - その世代の多様性（平均ペアワイズハミング距離）
+    /// * `success_ratio` - 子が親の平均適応度を上回った割合（親が不明な場合は`None`）
+    ///
+    /// # 戻り値
+    /// 決定された突然変異率。自己適応型では基準値を返し、実際の率は各個体の遺伝子が決めます。
+    pub fn update(
+        &mut self,
+        generation: Generation,
+        diversity: f64,
+        success_ratio: Option<f64>,
+    ) -> MutationRate {
+        self.current = match self.scheme {
+            MutationScheme::Fixed | MutationScheme::SelfAdaptive => self.base_rate,
+            MutationScheme::LinearDecay { final_rate } => {
+                let progress = if self.generations > 1 {
+                    generation as f64 / (self.generations - 1) as f64
+                } else {
+                    1.0
+                };
+                self.base_rate + (final_rate - self.base_rate) * progress.min(1.0)
+            }
+            MutationScheme::ExponentialDecay { decay } => {
+                self.base_rate * decay.powi(generation.min(i32::MAX as usize) as i32)
+            }
+            MutationScheme::Hypermutation { rate, threshold } => {
+                if diversity < threshold {
+                    rate
+                } else {
+                    self.base_rate
+                }
+            }
+            MutationScheme::OneFifthRule { factor } => match success_ratio {
+                Some(ratio) if ratio > TARGET_SUCCESS_RATIO => {
+                    (self.current / factor).clamp(MIN_MUTATION_RATE, MAX_MUTATION_RATE)
+                }
+                Some(ratio) if ratio < TARGET_SUCCESS_RATIO => {
+                    (self.current * factor).clamp(MIN_MUTATION_RATE, MAX_MUTATION_RATE)
+                }
+                _ => self.current,
+            },
+        };
+
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_rate() {
+        let mut controller = MutationController::new(MutationScheme::Fixed, 0.01, 100);
+        assert_eq!(controller.update(0, 0.5, None), 0.01);
+        assert_eq!(controller.update(99, 0.0, Some(1.0)), 0.01);
+    }
+
+    #[test]
+    fn test_linear_decay() {
+        let scheme = MutationScheme::LinearDecay { final_rate: 0.0 };
+        let mut controller = MutationController::new(scheme, 0.1, 11);
+        assert!((controller.update(0, 0.5, None) - 0.1).abs() < 1e-12);
+        assert!((controller.update(5, 0.5, None) - 0.05).abs() < 1e-12);
+        assert!(controller.update(10, 0.5, None).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_exponential_decay() {
+        let scheme = MutationScheme::ExponentialDecay { decay: 0.5 };
+        let mut controller = MutationController::new(scheme, 0.1, 100);
+        assert_eq!(controller.update(0, 0.5, None), 0.1);
+        assert_eq!(controller.update(2, 0.5, None), 0.025);
+    }
+
+    #[test]
+    fn test_hypermutation_triggered_by_low_diversity() {
+        let scheme = MutationScheme::Hypermutation {
+            rate: 0.3,
+            threshold: 0.1,
+        };
+        let mut controller = MutationController::new(scheme, 0.01, 100);
+        assert_eq!(controller.update(0, 0.4, None), 0.01);
+        assert_eq!(controller.update(1, 0.05, None), 0.3);
+        assert_eq!(controller.update(2, 0.2, None), 0.01);
+    }
+
+    #[test]
+    fn test_one_fifth_rule() {
+        let scheme = MutationScheme::OneFifthRule { factor: 0.5 };
+        let mut controller = MutationController::new(scheme, 0.04, 100);
+        assert_eq!(controller.update(0, 0.5, None), 0.04);
+        assert_eq!(controller.update(1, 0.5, Some(0.5)), 0.08);
+        assert_eq!(controller.update(2, 0.5, Some(0.0)), 0.04);
+        assert_eq!(controller.update(3, 0.5, Some(0.2)), 0.04);
+    }
+}
@@ -0,0 +1,172 @@
+/// 挿入・欠失（indel）突然変異
+///
+/// ビット反転に加えて遺伝子の挿入と欠失を起こし、DNA長を世代を通じて
+/// 変化させます。DNA長は`min_length`以上`max_length`以下に保たれます。
+use crate::core::{errors::*, traits::*, types::*};
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+/// DNA長の下限のデフォルト値
+pub const DEFAULT_MIN_DNA_LENGTH: usize = 1;
+
+/// DNA長の上限のデフォルト値
+pub const DEFAULT_MAX_DNA_LENGTH: usize = 64;
+
+/// 挿入・欠失突然変異
+///
+/// 文字列表現は`INSERTION:DELETION[:MIN[:MAX]]`です。
+///
+/// # フィールド
+/// * `insertion_rate` - 各遺伝子の後ろにランダムな遺伝子を挿入する確率
+/// * `deletion_rate` - 各遺伝子を削除する確率
+/// * `min_length` - DNA長の下限
+/// * `max_length` - DNA長の上限
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndelMutation {
+    pub insertion_rate: f64,
+    pub deletion_rate: f64,
+    pub min_length: usize,
+    pub max_length: usize,
+}
+
+impl IndelMutation {
+    pub fn new(insertion_rate: f64, deletion_rate: f64) -> Self {
+        Self {
+            insertion_rate,
+            deletion_rate,
+            min_length: DEFAULT_MIN_DNA_LENGTH,
+            max_length: DEFAULT_MAX_DNA_LENGTH,
+        }
+    }
+
+    /// DNA長の範囲を設定
+    pub fn with_length_range(mut self, min_length: usize, max_length: usize) -> Self {
+        self.min_length = min_length;
+        self.max_length = max_length;
+        self
+    }
+
+    /// パラメータを検証
+    pub fn validate(&self) -> GAResult<()> {
+        validation::validate_mutation_rate(self.insertion_rate)?;
+        validation::validate_mutation_rate(self.deletion_rate)?;
+        if self.min_length == 0 || self.min_length > self.max_length {
+            return Err(GAError::ValidationError(format!(
+                "Invalid DNA length range: {}..={}",
+                self.min_length, self.max_length
+            )));
+        }
+        Ok(())
+    }
+
+    /// DNAに挿入・欠失を適用
+    ///
+    /// 範囲外の長さのDNAを与えた場合、その長さから範囲に近づく方向の変化のみ起こります。
+    pub fn apply<R: Rng>(&self, dna: &str, rng: &mut R) -> Dna {
+        let genes = dna.as_bytes();
+        let mut mutated = String::with_capacity(dna.len() + 1);
+
+        for (i, &gene) in genes.iter().enumerate() {
+            // この遺伝子を含めた、変異後に見込まれるDNA長
+            let length = mutated.len() + genes.len() - i;
+            if length > self.min_length && rng.gen::<f64>() < self.deletion_rate {
+                continue;
+            }
+            mutated.push(gene as char);
+
+            if length < self.max_length && rng.gen::<f64>() < self.insertion_rate {
+                mutated.push(if rng.gen::<bool>() { '1' } else { '0' });
+            }
+        }
+
+        mutated
+    }
+
+    /// 個体に挿入・欠失を適用した子を作成
+    pub fn mutate<T: VariableLengthDna, R: Rng>(&self, agent: &T, rng: &mut R) -> T {
+        agent.with_dna(self.apply(agent.dna_binary(), rng))
+    }
+}
+
+impl fmt::Display for IndelMutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}:{}",
+            self.insertion_rate, self.deletion_rate, self.min_length, self.max_length
+        )
+    }
+}
+
+impl FromStr for IndelMutation {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid indel mutation: {s}"));
+        let parts: Vec<&str> = s.split(':').collect();
+        if !(2..=4).contains(&parts.len()) {
+            return Err(invalid());
+        }
+
+        let rate = |i: usize| parts[i].parse::<f64>().map_err(|_| invalid());
+        let length = |i: usize, default: usize| {
+            parts
+                .get(i)
+                .map_or(Ok(default), |p| p.parse::<usize>().map_err(|_| invalid()))
+        };
+
+        let indel = IndelMutation::new(rate(0)?, rate(1)?).with_length_range(
+            length(2, DEFAULT_MIN_DNA_LENGTH)?,
+            length(3, DEFAULT_MAX_DNA_LENGTH)?,
+        );
+        indel.validate()?;
+        Ok(indel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_length_changes_within_range() {
+        let indel = IndelMutation::new(0.3, 0.3).with_length_range(3, 8);
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut dna = "101010".to_string();
+        let mut lengths = Vec::new();
+
+        for _ in 0..200 {
+            dna = indel.apply(&dna, &mut rng);
+            assert!((3..=8).contains(&dna.len()), "{dna}");
+            assert!(dna.chars().all(|c| c == '0' || c == '1'));
+            lengths.push(dna.len());
+        }
+        assert!(lengths.iter().any(|&len| len != lengths[0]));
+    }
+
+    #[test]
+    fn test_zero_rates_keep_dna() {
+        let indel = IndelMutation::new(0.0, 0.0);
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(indel.apply("0110", &mut rng), "0110");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "0.1:0.2".parse::<IndelMutation>().unwrap(),
+            IndelMutation::new(0.1, 0.2)
+        );
+        let indel = "0.1:0.2:2:12".parse::<IndelMutation>().unwrap();
+        assert_eq!((indel.min_length, indel.max_length), (2, 12));
+        assert_eq!(indel.to_string().parse::<IndelMutation>().unwrap(), indel);
+
+        assert!("0.1".parse::<IndelMutation>().is_err());
+        assert!("0.1:2".parse::<IndelMutation>().is_err());
+        assert!("0.1:0.1:5:4".parse::<IndelMutation>().is_err());
+        assert!("0.1:0.1:0".parse::<IndelMutation>().is_err());
+    }
+}
@@ -0,0 +1,215 @@
+/// 突然変異率の制御スキーム
+///
+/// 固定の`Config::mutation_rate`に加えて、世代に応じた減衰スケジュール、
+/// 多様性低下時のハイパーミューテーション、1/5成功則、
+/// ゲノムに埋め込まれた自己適応型突然変異率を提供します。
+/// また、DNA長を変化させる挿入・欠失突然変異も提供します。
+pub mod controller;
+pub mod indel;
+pub mod self_adaptive;
+
+pub use controller::MutationController;
+pub use indel::IndelMutation;
+pub use self_adaptive::{self_adapt_rate, self_adaptation_tau};
+
+use crate::core::errors::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// 突然変異率の下限
+pub const MIN_MUTATION_RATE: f64 = 1e-4;
+
+/// 突然変異率の上限
+///
+/// 0.5を超える反転確率は探索としての意味を持たないため、ここで頭打ちにします。
+pub const MAX_MUTATION_RATE: f64 = 0.5;
+
+/// 指数減衰のデフォルト減衰係数（世代あたり）
+pub const DEFAULT_EXPONENTIAL_DECAY: f64 = 0.999;
+
+/// ハイパーミューテーションのデフォルト突然変異率
+pub const DEFAULT_HYPERMUTATION_RATE: f64 = 0.2;
+
+/// ハイパーミューテーションを発動するデフォルトの多様性閾値
+pub const DEFAULT_HYPERMUTATION_THRESHOLD: f64 = 0.05;
+
+/// 1/5成功則のデフォルト調整係数
+pub const DEFAULT_ONE_FIFTH_FACTOR: f64 = 0.85;
+
+/// 突然変異率の制御スキーム
+///
+/// いずれのスキームも`Config::mutation_rate`を初期値（基準値）として使用します。
+/// 文字列表現は`fixed`、`linear[:FINAL]`、`exp[:DECAY]`、
+/// `hyper[:RATE[:THRESHOLD]]`、`one-fifth[:FACTOR]`、`self-adaptive`です。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MutationScheme {
+    /// 全世代で基準値を使用
+    #[default]
+    Fixed,
+    /// 基準値から`final_rate`まで世代数に比例して線形に減衰
+    LinearDecay { final_rate: f64 },
+    /// 世代ごとに`decay`倍する指数減衰
+    ExponentialDecay { decay: f64 },
+    /// 多様性が`threshold`を下回った世代のみ`rate`を使用
+    Hypermutation { rate: f64, threshold: f64 },
+    /// 成功率が1/5を上回れば率を`1/factor`倍、下回れば`factor`倍
+    OneFifthRule { factor: f64 },
+    /// 各個体のゲノムに突然変異率を持たせ、対数正規摂動で自己適応させる
+    SelfAdaptive,
+}
+
+impl MutationScheme {
+    /// スキームのパラメータを検証
+    pub fn validate(&self) -> GAResult<()> {
+        match *self {
+            MutationScheme::LinearDecay { final_rate } => {
+                validation::validate_mutation_rate(final_rate)
+            }
+            MutationScheme::ExponentialDecay { decay } if !(decay > 0.0 && decay <= 1.0) => Err(
+                GAError::ValidationError("Decay factor must be in (0.0, 1.0]".to_string()),
+            ),
+            MutationScheme::Hypermutation { rate, threshold } => {
+                validation::validate_mutation_rate(rate)?;
+                if !(0.0..=1.0).contains(&threshold) {
+                    return Err(GAError::ValidationError(
+                        "Hypermutation threshold must be between 0.0 and 1.0".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            MutationScheme::OneFifthRule { factor } if !(factor > 0.0 && factor < 1.0) => Err(
+                GAError::ValidationError("One-fifth factor must be in (0.0, 1.0)".to_string()),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// 自己適応型スキームかどうか
+    pub fn is_self_adaptive(&self) -> bool {
+        matches!(self, MutationScheme::SelfAdaptive)
+    }
+}
+
+impl fmt::Display for MutationScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MutationScheme::Fixed => write!(f, "fixed"),
+            MutationScheme::LinearDecay { final_rate } => write!(f, "linear:{final_rate}"),
+            MutationScheme::ExponentialDecay { decay } => write!(f, "exp:{decay}"),
+            MutationScheme::Hypermutation { rate, threshold } => {
+                write!(f, "hyper:{rate}:{threshold}")
+            }
+            MutationScheme::OneFifthRule { factor } => write!(f, "one-fifth:{factor}"),
+            MutationScheme::SelfAdaptive => write!(f, "self-adaptive"),
+        }
+    }
+}
+
+impl FromStr for MutationScheme {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let name = parts.next().unwrap_or_default();
+        let params = parts
+            .map(|p| {
+                p.parse::<f64>()
+                    .map_err(|_| GAError::ValidationError(format!("Invalid mutation scheme: {s}")))
+            })
+            .collect::<GAResult<Vec<f64>>>()?;
+        let param = |i: usize, default: f64| params.get(i).copied().unwrap_or(default);
+
+        let (scheme, max_params) = match name {
+            "fixed" => (MutationScheme::Fixed, 0),
+            "linear" => (
+                MutationScheme::LinearDecay {
+                    final_rate: param(0, 0.0),
+                },
+                1,
+            ),
+            "exp" => (
+                MutationScheme::ExponentialDecay {
+                    decay: param(0, DEFAULT_EXPONENTIAL_DECAY),
+                },
+                1,
+            ),
+            "hyper" => (
+                MutationScheme::Hypermutation {
+                    rate: param(0, DEFAULT_HYPERMUTATION_RATE),
+                    threshold: param(1, DEFAULT_HYPERMUTATION_THRESHOLD),
+                },
+                2,
+            ),
+            "one-fifth" => (
+                MutationScheme::OneFifthRule {
+                    factor: param(0, DEFAULT_ONE_FIFTH_FACTOR),
+                },
+                1,
+            ),
+            "self-adaptive" => (MutationScheme::SelfAdaptive, 0),
+            _ => {
+                return Err(GAError::ValidationError(format!(
+                    "Invalid mutation scheme: {s}"
+                )))
+            }
+        };
+
+        if params.len() > max_params {
+            return Err(GAError::ValidationError(format!(
+                "Too many parameters for mutation scheme: {s}"
+            )));
+        }
+
+        scheme.validate()?;
+        Ok(scheme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scheme() {
+        assert_eq!(
+            "fixed".parse::<MutationScheme>().unwrap(),
+            MutationScheme::Fixed
+        );
+        assert_eq!(
+            "linear:0.001".parse::<MutationScheme>().unwrap(),
+            MutationScheme::LinearDecay { final_rate: 0.001 }
+        );
+        assert_eq!(
+            "hyper:0.3".parse::<MutationScheme>().unwrap(),
+            MutationScheme::Hypermutation {
+                rate: 0.3,
+                threshold: DEFAULT_HYPERMUTATION_THRESHOLD
+            }
+        );
+        assert_eq!(
+            "self-adaptive".parse::<MutationScheme>().unwrap(),
+            MutationScheme::SelfAdaptive
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_scheme() {
+        assert!("unknown".parse::<MutationScheme>().is_err());
+        assert!("exp:0".parse::<MutationScheme>().is_err());
+        assert!("one-fifth:1.5".parse::<MutationScheme>().is_err());
+        assert!("fixed:0.1".parse::<MutationScheme>().is_err());
+        assert!("linear:abc".parse::<MutationScheme>().is_err());
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        let scheme = MutationScheme::Hypermutation {
+            rate: 0.25,
+            threshold: 0.1,
+        };
+        assert_eq!(
+            scheme.to_string().parse::<MutationScheme>().unwrap(),
+            scheme
+        );
+    }
+}
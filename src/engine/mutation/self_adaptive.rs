@@ -0,0 +1,53 @@
+/// 自己適応型突然変異率
+///
+/// 進化戦略（ES）と同様に、各個体が自身の突然変異率を遺伝子として保持し、
+/// 繁殖時に対数正規分布で摂動させます。`σ' = σ · exp(τ · N(0, 1))`
+use super::{MAX_MUTATION_RATE, MIN_MUTATION_RATE};
+use rand::Rng;
+
+/// ゲノム長に応じた学習率τ（`1 / √L`）を返す
+pub fn self_adaptation_tau(dna_length: usize) -> f64 {
+    1.0 / (dna_length.max(1) as f64).sqrt()
+}
+
+/// 突然変異率遺伝子を対数正規摂動させた新しい値を返す
+///
+/// 結果は`[MIN_MUTATION_RATE, MAX_MUTATION_RATE]`に丸められます。
+pub fn self_adapt_rate<R: Rng>(rate: f64, tau: f64, rng: &mut R) -> f64 {
+    let perturbed = rate.max(MIN_MUTATION_RATE) * (tau * standard_normal(rng)).exp();
+    perturbed.clamp(MIN_MUTATION_RATE, MAX_MUTATION_RATE)
+}
+
+/// Box-Muller法による標準正規乱数
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_tau() {
+        assert_eq!(self_adaptation_tau(4), 0.5);
+        assert_eq!(self_adaptation_tau(0), 1.0);
+    }
+
+    #[test]
+    fn test_adapted_rate_is_bounded() {
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            let rate = self_adapt_rate(0.01, 2.0, &mut rng);
+            assert!((MIN_MUTATION_RATE..=MAX_MUTATION_RATE).contains(&rate));
+        }
+    }
+
+    #[test]
+    fn test_zero_tau_keeps_rate() {
+        let mut rng = thread_rng();
+        assert_eq!(self_adapt_rate(0.05, 0.0, &mut rng), 0.05);
+    }
+}
@@ -0,0 +1,313 @@
+/// 新規性探索（novelty search）
+///
+/// 個体を獲得ポイントではなく、行動特性（behavioural characterisation）が
+/// 現個体群と過去の行動のアーカイブからどれだけ離れているかで評価します。
+/// 行動特性は固定の対戦相手（プローブ）に対する行動を数値ベクトルで表したもので、
+/// `BehaviourDescriptor`を実装した戦略が計算します。
+use crate::core::{errors::*, types::*};
+use std::fmt;
+use std::str::FromStr;
+
+/// 近傍数の既定値
+pub const DEFAULT_NOVELTY_NEIGHBOURS: usize = 15;
+
+/// アーカイブに追加する新規性のしきい値の既定値
+pub const DEFAULT_ARCHIVE_THRESHOLD: f64 = 0.05;
+
+/// 新規性探索の設定
+///
+/// 文字列表現は`K[:THRESHOLD[:WEIGHT]]`です（例: `15:0.05:0.5`）。
+///
+/// # フィールド
+/// * `neighbours` - 新規性の計算に使う最近傍の数k
+/// * `archive_threshold` - この値を超える新規性の行動をアーカイブに追加する
+/// * `novelty_weight` - 評価値における新規性の重み（1.0で新規性のみ、0.0でポイントのみ）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoveltySearch {
+    pub neighbours: usize,
+    pub archive_threshold: f64,
+    pub novelty_weight: f64,
+}
+
+impl NoveltySearch {
+    pub fn new(neighbours: usize) -> Self {
+        Self {
+            neighbours,
+            archive_threshold: DEFAULT_ARCHIVE_THRESHOLD,
+            novelty_weight: 1.0,
+        }
+    }
+
+    pub fn with_archive_threshold(mut self, threshold: f64) -> Self {
+        self.archive_threshold = threshold;
+        self
+    }
+
+    pub fn with_novelty_weight(mut self, weight: f64) -> Self {
+        self.novelty_weight = weight;
+        self
+    }
+
+    /// パラメータの妥当性を検証
+    ///
+    /// # エラー
+    /// 近傍数が0、しきい値が負、または重みが0.0-1.0の範囲外の場合
+    pub fn validate(&self) -> GAResult<()> {
+        if self.neighbours == 0 {
+            return Err(GAError::ValidationError(
+                "Novelty neighbours must be greater than 0".to_string(),
+            ));
+        }
+        if self.archive_threshold.is_nan() || self.archive_threshold < 0.0 {
+            return Err(GAError::ValidationError(format!(
+                "Novelty archive threshold must be non-negative, got {}",
+                self.archive_threshold
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.novelty_weight) {
+            return Err(GAError::ValidationError(format!(
+                "Novelty weight must be between 0.0 and 1.0, got {}",
+                self.novelty_weight
+            )));
+        }
+        Ok(())
+    }
+
+    /// 行動特性の計算に使う既定のプローブ（対戦相手）のDNA
+    ///
+    /// 全て'0'、全て'1'、'0'と'1'の交互の3体です。
+    pub fn default_probes(dna_length: usize) -> Vec<Dna> {
+        vec![
+            "0".repeat(dna_length),
+            "1".repeat(dna_length),
+            (0..dna_length)
+                .map(|i| if i % 2 == 0 { '0' } else { '1' })
+                .collect(),
+        ]
+    }
+
+    /// 獲得ポイントと新規性を混合した評価値を計算
+    ///
+    /// それぞれ個体群内の最大値で正規化してから`novelty_weight`で重み付けし、
+    /// 最大ポイント（最低1）を掛けてポイントの尺度に戻します。
+    pub fn blend(&self, points: &[Points], novelty: &[f64]) -> Vec<Points> {
+        let max_points = points.iter().copied().max().unwrap_or(0).max(1) as f64;
+        let max_novelty = novelty.iter().copied().fold(0.0, f64::max);
+
+        points
+            .iter()
+            .zip(novelty)
+            .map(|(&p, &n)| {
+                let novelty = if max_novelty > 0.0 {
+                    n / max_novelty
+                } else {
+                    0.0
+                };
+                let score = (1.0 - self.novelty_weight) * (p as f64 / max_points)
+                    + self.novelty_weight * novelty;
+                (score * max_points).round() as Points
+            })
+            .collect()
+    }
+}
+
+impl Default for NoveltySearch {
+    fn default() -> Self {
+        Self::new(DEFAULT_NOVELTY_NEIGHBOURS)
+    }
+}
+
+impl fmt::Display for NoveltySearch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "novelty (k={}, threshold={}, weight={})",
+            self.neighbours, self.archive_threshold, self.novelty_weight
+        )
+    }
+}
+
+impl FromStr for NoveltySearch {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid novelty search: {s}"));
+        let mut parts = s.split(':');
+
+        let neighbours = parts
+            .next()
+            .and_then(|k| k.parse().ok())
+            .ok_or_else(invalid)?;
+        let mut novelty = NoveltySearch::new(neighbours);
+        if let Some(threshold) = parts.next() {
+            novelty.archive_threshold = threshold.parse().map_err(|_| invalid())?;
+        }
+        if let Some(weight) = parts.next() {
+            novelty.novelty_weight = weight.parse().map_err(|_| invalid())?;
+        }
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        novelty.validate()?;
+        Ok(novelty)
+    }
+}
+
+/// 2つの行動特性のユークリッド距離
+pub fn behaviour_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// 各個体の新規性を計算
+///
+/// 新規性は、自分以外の個体群の行動とアーカイブの行動のうち
+/// 近い順に`k`個までとの距離の平均です。比較対象がない場合は0.0です。
+///
+/// # 引数
+/// * `behaviours` - 個体群の行動特性
+/// * `archive` - 過去の行動のアーカイブ
+/// * `k` - 最近傍の数
+pub fn novelty_scores(behaviours: &[Vec<f64>], archive: &[Vec<f64>], k: usize) -> Vec<f64> {
+    behaviours
+        .iter()
+        .enumerate()
+        .map(|(i, behaviour)| {
+            let mut distances: Vec<f64> = behaviours
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, other)| other)
+                .chain(archive)
+                .map(|other| behaviour_distance(behaviour, other))
+                .collect();
+            if distances.is_empty() || k == 0 {
+                return 0.0;
+            }
+
+            let nearest = k.min(distances.len());
+            distances.select_nth_unstable_by(nearest - 1, f64::total_cmp);
+            distances[..nearest].iter().sum::<f64>() / nearest as f64
+        })
+        .collect()
+}
+
+/// 過去の新規な行動のアーカイブ
+///
+/// # フィールド
+/// * `threshold` - この値を超える新規性の行動を追加する
+/// * `behaviours` - アーカイブされた行動特性
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NoveltyArchive {
+    threshold: f64,
+    behaviours: Vec<Vec<f64>>,
+}
+
+impl NoveltyArchive {
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            behaviours: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.behaviours.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.behaviours.is_empty()
+    }
+
+    pub fn behaviours(&self) -> &[Vec<f64>] {
+        &self.behaviours
+    }
+
+    /// 新規性がしきい値を超えた行動をアーカイブに追加
+    ///
+    /// # 戻り値
+    /// 追加した行動の数
+    pub fn update(&mut self, behaviours: &[Vec<f64>], scores: &[f64]) -> usize {
+        let before = self.behaviours.len();
+        self.behaviours.extend(
+            behaviours
+                .iter()
+                .zip(scores)
+                .filter(|&(_, &score)| score > self.threshold)
+                .map(|(behaviour, _)| behaviour.clone()),
+        );
+        self.behaviours.len() - before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_novelty_scores() {
+        let behaviours = vec![vec![0.0], vec![0.1], vec![1.0]];
+        let scores = novelty_scores(&behaviours, &[], 1);
+        assert!((scores[0] - 0.1).abs() < 1e-12);
+        assert!((scores[2] - 0.9).abs() < 1e-12);
+
+        // アーカイブの行動に近い個体は新規性が下がる
+        let with_archive = novelty_scores(&behaviours, &[vec![0.95]], 1);
+        assert!((with_archive[2] - 0.05).abs() < 1e-12);
+
+        assert_eq!(novelty_scores(&[vec![0.5]], &[], 3), vec![0.0]);
+    }
+
+    #[test]
+    fn test_archive_growth() {
+        let mut archive = NoveltyArchive::new(0.05);
+        let behaviours = vec![vec![0.0], vec![0.1], vec![1.0]];
+        let scores = novelty_scores(&behaviours, archive.behaviours(), 1);
+        assert_eq!(archive.update(&behaviours, &scores), 3);
+
+        // 既にアーカイブにある行動は新規性0で追加されない
+        let scores = novelty_scores(&behaviours, archive.behaviours(), 1);
+        assert_eq!(archive.update(&behaviours, &scores), 0);
+        assert_eq!(archive.len(), 3);
+    }
+
+    #[test]
+    fn test_blend() {
+        let points = [10, 20, 40];
+        let novelty = [1.0, 0.5, 0.0];
+        assert_eq!(
+            NoveltySearch::new(1).blend(&points, &novelty),
+            vec![40, 20, 0]
+        );
+        assert_eq!(
+            NoveltySearch::new(1)
+                .with_novelty_weight(0.0)
+                .blend(&points, &novelty),
+            vec![10, 20, 40]
+        );
+        assert_eq!(
+            NoveltySearch::new(1)
+                .with_novelty_weight(0.5)
+                .blend(&points, &novelty),
+            vec![25, 20, 20]
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "10:0.1:0.5".parse::<NoveltySearch>().unwrap(),
+            NoveltySearch::new(10)
+                .with_archive_threshold(0.1)
+                .with_novelty_weight(0.5)
+        );
+        assert_eq!("5".parse::<NoveltySearch>().unwrap(), NoveltySearch::new(5));
+        assert!("0".parse::<NoveltySearch>().is_err());
+        assert!("5:0.1:1.5".parse::<NoveltySearch>().is_err());
+        assert!("5:x".parse::<NoveltySearch>().is_err());
+    }
+}
@@ -0,0 +1,243 @@
+/// ボルツマン選択の実装
+///
+/// 適応度`f`の個体を`exp(f / T)`に比例した確率で選択します。温度`T`が高いと
+/// ほぼ一様な選択になり、温度が下がるにつれて適応度の高い個体に選択が集中します。
+/// 温度はスケジュールに従って世代ごとに変化させます（焼きなまし）。
+use super::{check_parents, check_survivors};
+use crate::core::{errors::*, traits::*, types::*};
+use rand::{thread_rng, Rng, RngCore};
+
+/// 温度スケジュール
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemperatureSchedule {
+    /// 一定温度
+    Constant(f64),
+    /// 初期温度から最終温度まで`generations`世代かけて線形に冷却
+    Linear {
+        initial: f64,
+        final_temperature: f64,
+        generations: usize,
+    },
+    /// 世代ごとに`decay`倍して冷却（`minimum`を下回らない）
+    Exponential {
+        initial: f64,
+        decay: f64,
+        minimum: f64,
+    },
+}
+
+impl TemperatureSchedule {
+    /// 指定世代の温度を計算
+    pub fn temperature(&self, generation: Generation) -> f64 {
+        match *self {
+            TemperatureSchedule::Constant(temperature) => temperature,
+            TemperatureSchedule::Linear {
+                initial,
+                final_temperature,
+                generations,
+            } => {
+                let progress = (generation as f64 / generations.max(1) as f64).min(1.0);
+                initial + (final_temperature - initial) * progress
+            }
+            TemperatureSchedule::Exponential {
+                initial,
+                decay,
+                minimum,
+            } => (initial * decay.powi(generation.min(i32::MAX as usize) as i32)).max(minimum),
+        }
+    }
+
+    /// スケジュールのパラメータを検証
+    ///
+    /// # エラー
+    /// 温度が正でない場合、または減衰率が(0, 1]の範囲外の場合
+    pub fn validate(&self) -> GAResult<()> {
+        let valid = match *self {
+            TemperatureSchedule::Constant(temperature) => temperature > 0.0,
+            TemperatureSchedule::Linear {
+                initial,
+                final_temperature,
+                ..
+            } => initial > 0.0 && final_temperature > 0.0,
+            TemperatureSchedule::Exponential {
+                initial,
+                decay,
+                minimum,
+            } => initial > 0.0 && minimum > 0.0 && decay > 0.0 && decay <= 1.0,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(GAError::ValidationError(
+                "Temperatures must be positive and decay must be in (0, 1]".to_string(),
+            ))
+        }
+    }
+}
+
+/// ボルツマン選択戦略
+///
+/// # フィールド
+/// * `schedule` - 温度スケジュール
+/// * `generation` - 現在の世代（温度の計算に使用）
+#[derive(Debug, Clone)]
+pub struct BoltzmannSelection {
+    schedule: TemperatureSchedule,
+    generation: Generation,
+}
+
+impl BoltzmannSelection {
+    /// 温度スケジュールを指定して作成
+    ///
+    /// # エラー
+    /// スケジュールのパラメータが不正な場合
+    pub fn new(schedule: TemperatureSchedule) -> GAResult<Self> {
+        schedule.validate()?;
+        Ok(Self {
+            schedule,
+            generation: 0,
+        })
+    }
+
+    /// 現在の温度
+    pub fn temperature(&self) -> f64 {
+        self.schedule.temperature(self.generation)
+    }
+
+    /// 温度計算に使う世代を設定
+    pub fn set_generation(&mut self, generation: Generation) {
+        self.generation = generation;
+    }
+
+    /// 世代を1つ進めて冷却
+    pub fn advance(&mut self) {
+        self.generation += 1;
+    }
+
+    /// 各個体の選択確率を計算
+    ///
+    /// オーバーフローを避けるため、最大適応度を引いてから指数を取ります。
+    pub fn probabilities<T: Agent>(&self, population: &[T]) -> Vec<f64> {
+        let temperature = self.temperature();
+        let max = population.iter().map(|a| a.fitness()).max().unwrap_or(0) as f64;
+        let weights: Vec<f64> = population
+            .iter()
+            .map(|a| ((a.fitness() as f64 - max) / temperature).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+
+        weights.into_iter().map(|w| w / total).collect()
+    }
+
+    fn select_index<R: Rng>(&self, probabilities: &[f64], rng: &mut R) -> GAResult<usize> {
+        if probabilities.is_empty() {
+            return Err(GAError::EmptyPopulation);
+        }
+
+        let mut point = rng.gen::<f64>();
+        for (i, &p) in probabilities.iter().enumerate() {
+            point -= p;
+            if point <= 0.0 {
+                return Ok(i);
+            }
+        }
+
+        Ok(probabilities.len() - 1)
+    }
+}
+
+impl<T: Agent> SelectionStrategy<T> for BoltzmannSelection {
+    fn select_parents_with_rng(
+        &self,
+        population: &[T],
+        mut rng: &mut dyn RngCore,
+    ) -> GAResult<(T, T)> {
+        check_parents(population)?;
+        let probabilities = self.probabilities(population);
+        let parent1 = self.select_index(&probabilities, &mut rng)?;
+        let parent2 = self.select_index(&probabilities, &mut rng)?;
+        Ok((population[parent1].clone(), population[parent2].clone()))
+    }
+
+    fn select_survivors(&self, population: &[T], count: usize) -> GAResult<Vec<T>> {
+        check_survivors(population, count)?;
+        if count == population.len() {
+            return Ok(population.to_vec());
+        }
+
+        let probabilities = self.probabilities(population);
+        let mut rng = thread_rng();
+        (0..count)
+            .map(|_| Ok(population[self.select_index(&probabilities, &mut rng)?].clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn agent(id: u64, points: u64) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, "101010".to_string()), points)
+    }
+
+    #[test]
+    fn test_temperature_schedules() {
+        let linear = TemperatureSchedule::Linear {
+            initial: 10.0,
+            final_temperature: 1.0,
+            generations: 9,
+        };
+        assert_eq!(linear.temperature(0), 10.0);
+        assert_eq!(linear.temperature(9), 1.0);
+        assert_eq!(linear.temperature(100), 1.0);
+
+        let exponential = TemperatureSchedule::Exponential {
+            initial: 8.0,
+            decay: 0.5,
+            minimum: 1.5,
+        };
+        assert_eq!(exponential.temperature(1), 4.0);
+        assert_eq!(exponential.temperature(3), 1.5);
+    }
+
+    #[test]
+    fn test_cooling_increases_pressure() {
+        let population = vec![agent(1, 10), agent(2, 20)];
+        let mut selection = BoltzmannSelection::new(TemperatureSchedule::Exponential {
+            initial: 100.0,
+            decay: 0.1,
+            minimum: 0.01,
+        })
+        .unwrap();
+
+        let hot = selection.probabilities(&population);
+        assert!((hot[0] - 0.5).abs() < 0.05);
+
+        selection.set_generation(4);
+        let cold = selection.probabilities(&population);
+        assert!(cold[1] > 0.999);
+    }
+
+    #[test]
+    fn test_large_fitness_does_not_overflow() {
+        let population = vec![agent(1, 1_000_000), agent(2, 1_000_001)];
+        let selection = BoltzmannSelection::new(TemperatureSchedule::Constant(1.0)).unwrap();
+        let probabilities = selection.probabilities(&population);
+        assert!(probabilities.iter().all(|p| p.is_finite()));
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_schedule() {
+        assert!(BoltzmannSelection::new(TemperatureSchedule::Constant(0.0)).is_err());
+        assert!(BoltzmannSelection::new(TemperatureSchedule::Exponential {
+            initial: 1.0,
+            decay: 1.5,
+            minimum: 0.1,
+        })
+        .is_err());
+    }
+}
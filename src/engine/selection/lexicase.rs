@@ -0,0 +1,174 @@
+/// レキシケース選択の実装
+///
+/// 合計ポイントではなく、対戦相手ごとのスコア（ケース）を個別に評価します。
+/// ケースをランダムな順に並べ、各ケースで最高スコアの個体だけを残す絞り込みを
+/// 1体になるまで繰り返します。特定の相手に強い「専門家」が生き残りやすく、
+/// 平均的な個体ばかりが選ばれるのを防ぎます。
+use super::{check_parents, check_survivors};
+use crate::core::{errors::*, traits::*, types::*};
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng, RngCore};
+
+/// レキシケース選択戦略
+///
+/// # フィールド
+/// * `scores` - `scores[i][j]`は個体群のi番目の個体がケースj（j番目の対戦相手）で得たスコア
+#[derive(Debug, Clone)]
+pub struct LexicaseSelection {
+    scores: Vec<Vec<Points>>,
+}
+
+impl LexicaseSelection {
+    /// 個体ごと・ケースごとのスコア表から作成
+    ///
+    /// スコア表の行は選択に渡す個体群と同じ順序で並んでいる必要があります。
+    ///
+    /// # エラー
+    /// スコア表が空、または行ごとのケース数が揃っていない場合
+    pub fn new(scores: Vec<Vec<Points>>) -> GAResult<Self> {
+        let cases = scores
+            .first()
+            .map(Vec::len)
+            .ok_or(GAError::EmptyPopulation)?;
+        if scores.iter().any(|row| row.len() != cases) {
+            return Err(GAError::ValidationError(
+                "Every individual must have a score for every case".to_string(),
+            ));
+        }
+
+        Ok(Self { scores })
+    }
+
+    /// 個体群の総当たり対戦からスコア表を作成
+    ///
+    /// ケースjは個体群のj番目の個体との対戦で、`play(a, b)`は`a`が`b`との対戦で
+    /// 得たポイントを返す関数です。
+    ///
+    /// # エラー
+    /// 個体群が空の場合
+    pub fn from_matches<T, F>(population: &[T], mut play: F) -> GAResult<Self>
+    where
+        T: Agent,
+        F: FnMut(&T, &T) -> Points,
+    {
+        let scores = population
+            .iter()
+            .map(|agent| {
+                population
+                    .iter()
+                    .map(|opponent| play(agent, opponent))
+                    .collect()
+            })
+            .collect();
+        Self::new(scores)
+    }
+
+    pub fn case_count(&self) -> usize {
+        self.scores[0].len()
+    }
+
+    /// レキシケースで1体のインデックスを選択
+    ///
+    /// # エラー
+    /// 個体数がスコア表の行数と一致しない場合
+    pub fn select_index<R: Rng>(&self, population_size: usize, rng: &mut R) -> GAResult<usize> {
+        if population_size != self.scores.len() {
+            return Err(GAError::InvalidPopulationSize(population_size));
+        }
+
+        let mut cases: Vec<usize> = (0..self.case_count()).collect();
+        cases.shuffle(rng);
+
+        let mut candidates: Vec<usize> = (0..population_size).collect();
+        for case in cases {
+            if candidates.len() <= 1 {
+                break;
+            }
+            let best = candidates
+                .iter()
+                .map(|&i| self.scores[i][case])
+                .max()
+                .unwrap_or(0);
+            candidates.retain(|&i| self.scores[i][case] == best);
+        }
+
+        Ok(*candidates.choose(rng).unwrap_or(&0))
+    }
+
+    /// 1体を選択
+    ///
+    /// # エラー
+    /// 個体群が空、または個体数がスコア表の行数と一致しない場合
+    fn select_one<T: Agent, R: Rng>(&self, population: &[T], rng: &mut R) -> GAResult<T> {
+        check_parents(population)?;
+        let index = self.select_index(population.len(), rng)?;
+        Ok(population[index].clone())
+    }
+}
+
+impl<T: Agent> SelectionStrategy<T> for LexicaseSelection {
+    fn select_parents_with_rng(
+        &self,
+        population: &[T],
+        mut rng: &mut dyn RngCore,
+    ) -> GAResult<(T, T)> {
+        Ok((
+            self.select_one(population, &mut rng)?,
+            self.select_one(population, &mut rng)?,
+        ))
+    }
+
+    fn select_survivors(&self, population: &[T], count: usize) -> GAResult<Vec<T>> {
+        check_survivors(population, count)?;
+        if count == population.len() {
+            return Ok(population.to_vec());
+        }
+
+        let mut rng = thread_rng();
+        (0..count)
+            .map(|_| self.select_one(population, &mut rng))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn agent(id: u64, points: u64) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, "101010".to_string()), points)
+    }
+
+    #[test]
+    fn test_specialists_survive_over_generalist() {
+        // 個体0と1はそれぞれ片方のケースの専門家、個体2は合計最大だがどちらでも最高ではない
+        let selection = LexicaseSelection::new(vec![vec![10, 0], vec![0, 10], vec![9, 9]]).unwrap();
+        let mut rng = thread_rng();
+        let mut counts = [0; 3];
+        for _ in 0..200 {
+            counts[selection.select_index(3, &mut rng).unwrap()] += 1;
+        }
+
+        assert_eq!(counts[2], 0);
+        assert!(counts[0] > 0 && counts[1] > 0);
+    }
+
+    #[test]
+    fn test_from_matches_builds_square_table() {
+        let population: Vec<ModelAgent> = (0..4).map(|i| agent(i, 0)).collect();
+        let selection =
+            LexicaseSelection::from_matches(&population, |a, b| a.id * 10 + b.id).unwrap();
+        assert_eq!(selection.case_count(), 4);
+        assert_eq!(selection.scores[2][3], 23);
+    }
+
+    #[test]
+    fn test_invalid_tables() {
+        assert!(LexicaseSelection::new(Vec::new()).is_err());
+        assert!(LexicaseSelection::new(vec![vec![1, 2], vec![3]]).is_err());
+
+        let selection = LexicaseSelection::new(vec![vec![1], vec![2]]).unwrap();
+        assert!(selection.select_index(3, &mut thread_rng()).is_err());
+    }
+}
@@ -1,7 +1,170 @@
+pub mod boltzmann;
+pub mod lexicase;
+pub mod normalization;
+pub mod pressure;
 pub mod rank;
 pub mod roulette;
+pub mod sus;
 pub mod tournament;
+pub mod truncation;
 
+pub use boltzmann::{BoltzmannSelection, TemperatureSchedule};
+pub use lexicase::LexicaseSelection;
+pub use normalization::FitnessNormalization;
+pub use pressure::SelectionPressure;
 pub use rank::RankSelection;
 pub use roulette::RouletteSelection;
+pub use sus::StochasticUniversalSampling;
 pub use tournament::TournamentSelection;
+pub use truncation::TruncationSelection;
+
+use crate::core::errors::{GAError, GAResult};
+use crate::core::traits::Agent;
+
+/// 親を選択できる個体群かどうかを検証
+///
+/// # エラー
+/// 個体群が空の場合
+pub(crate) fn check_parents<T>(population: &[T]) -> GAResult<()> {
+    if population.is_empty() {
+        return Err(GAError::EmptyPopulation);
+    }
+    Ok(())
+}
+
+/// 個体群から`count`体の生存者を選択できるかどうかを検証
+///
+/// # エラー
+/// 個体群が空、または`count`が個体数より多い場合
+pub(crate) fn check_survivors<T>(population: &[T], count: usize) -> GAResult<()> {
+    check_parents(population)?;
+    if count > population.len() {
+        return Err(GAError::InsufficientCandidates(population.len()));
+    }
+    Ok(())
+}
+
+/// 全個体の適応度が等しい（全員0の場合を含む）かどうか
+///
+/// 適応度に差がない個体群では、各選択戦略は一様に選択します。
+pub(crate) fn has_uniform_fitness<T: Agent>(population: &[T]) -> bool {
+    population
+        .windows(2)
+        .all(|pair| pair[0].fitness() == pair[1].fitness())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::traits::SelectionStrategy;
+    use crate::core::types::Objective;
+    use crate::engine::diversity::FitnessSharing;
+    use crate::engine::multi_objective::Nsga2;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    type Strategy = Box<dyn SelectionStrategy<ModelAgent>>;
+
+    fn agent(id: u64, points: u64) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, format!("{:06b}", id % 64)), points)
+    }
+
+    /// 個体数`n`の個体群に使える全ての選択戦略
+    fn strategies(n: usize) -> Vec<(&'static str, Strategy)> {
+        vec![
+            ("roulette", Box::new(RouletteSelection::new())),
+            ("tournament", Box::new(TournamentSelection::new(3).unwrap())),
+            ("rank", Box::new(RankSelection::new(1.5).unwrap())),
+            (
+                "boltzmann",
+                Box::new(BoltzmannSelection::new(TemperatureSchedule::Constant(1.0)).unwrap()),
+            ),
+            ("truncation", Box::new(TruncationSelection::half())),
+            ("sus", Box::new(StochasticUniversalSampling::new())),
+            (
+                "lexicase",
+                Box::new(LexicaseSelection::new(vec![vec![0, 0]; n.max(1)]).unwrap()),
+            ),
+            ("sharing", Box::new(FitnessSharing::default())),
+            (
+                "nsga2",
+                Box::new(Nsga2::new(vec![Objective::Score, Objective::Cooperation]).unwrap()),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_empty_population_is_an_error() {
+        let empty: Vec<ModelAgent> = Vec::new();
+        for (name, strategy) in strategies(0) {
+            assert!(
+                matches!(
+                    strategy.select_parents(&empty),
+                    Err(GAError::EmptyPopulation)
+                ),
+                "{name}"
+            );
+            assert!(
+                matches!(
+                    strategy.select_survivors(&empty, 1),
+                    Err(GAError::EmptyPopulation)
+                ),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_single_agent_is_always_selected() {
+        let population = vec![agent(7, 3)];
+        for (name, strategy) in strategies(1) {
+            let (parent1, parent2) = strategy.select_parents(&population).unwrap();
+            assert_eq!((parent1.id, parent2.id), (7, 7), "{name}");
+            let survivors = strategy.select_survivors(&population, 1).unwrap();
+            assert_eq!(survivors.len(), 1, "{name}");
+            assert_eq!(survivors[0].id, 7, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_all_zero_fitness_selects_every_agent() {
+        let population: Vec<ModelAgent> = (0..4).map(|i| agent(i, 0)).collect();
+        assert!(has_uniform_fitness(&population));
+
+        for (name, strategy) in strategies(population.len()) {
+            let mut selected = [false; 4];
+            for _ in 0..500 {
+                let (parent1, parent2) = strategy.select_parents(&population).unwrap();
+                selected[parent1.id as usize] = true;
+                selected[parent2.id as usize] = true;
+            }
+            // NSGA-IIは同順位の個体を混雑距離で比べるため、一様性は検証しない
+            if name != "nsga2" {
+                assert_eq!(selected, [true; 4], "{name}");
+            }
+            assert_eq!(
+                strategy.select_survivors(&population, 2).unwrap().len(),
+                2,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_too_many_survivors_is_an_error() {
+        let population: Vec<ModelAgent> = (0..3).map(|i| agent(i, i * 10)).collect();
+        for (name, strategy) in strategies(population.len()) {
+            assert!(
+                matches!(
+                    strategy.select_survivors(&population, 4),
+                    Err(GAError::InsufficientCandidates(3))
+                ),
+                "{name}"
+            );
+            assert_eq!(
+                strategy.select_survivors(&population, 3).unwrap().len(),
+                3,
+                "{name}"
+            );
+        }
+    }
+}
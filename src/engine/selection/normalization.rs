@@ -0,0 +1,238 @@
+/// 適応度の正規化
+///
+/// `Agent::points`は全ての対戦とラウンドにわたって累積されるため、そのままでは
+/// 適応度が個体群サイズと`rounds_per_generation`に比例して大きくなります。
+/// 選択の前に累積ポイントを尺度によらない値へ変換する方式を提供します。
+///
+/// 選択戦略は整数の適応度を扱うため、実数になる正規化値は`FITNESS_SCALE`倍して
+/// 整数に丸めます。計算は`u128`または`f64`で行い、結果は`Points`の範囲に
+/// 飽和させるため、どれだけ大きな累積ポイントでもオーバーフローしません。
+use crate::core::errors::*;
+use crate::core::types::Points;
+use std::fmt;
+use std::str::FromStr;
+
+/// 実数の正規化値を整数の適応度にする倍率
+pub const FITNESS_SCALE: Points = 1000;
+
+/// zスコア正規化で加えるオフセット
+///
+/// 平均より`Z_SCORE_OFFSET`標準偏差以上低い個体の適応度は0になります。
+pub const Z_SCORE_OFFSET: f64 = 3.0;
+
+/// 選択に使う適応度の正規化方式
+///
+/// 文字列表現は`raw`、`interaction`、`round`、`zscore`、`rank`です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FitnessNormalization {
+    /// 累積ポイントをそのまま使う（従来の動作）
+    #[default]
+    Raw,
+    /// 1対戦あたりの平均利得（`FITNESS_SCALE`倍）
+    PerInteraction,
+    /// 1ラウンドあたりのポイント（`FITNESS_SCALE`倍）
+    PerRound,
+    /// 個体群内のzスコアに`Z_SCORE_OFFSET`を加えた値（`FITNESS_SCALE`倍、負は0）
+    ZScore,
+    /// 個体群内の順位（最下位が1、同点は同順位）
+    Rank,
+}
+
+impl FitnessNormalization {
+    /// 個体群内の他の個体に依存する方式かどうか
+    ///
+    /// zスコアと順位は個体群全体から計算するため、子を1体ずつ評価して
+    /// 親と比較する置換方式とは組み合わせられません。
+    pub fn is_relative(&self) -> bool {
+        matches!(
+            self,
+            FitnessNormalization::ZScore | FitnessNormalization::Rank
+        )
+    }
+
+    /// 個体群の累積ポイントを正規化
+    ///
+    /// # 引数
+    /// * `points` - 各個体の累積ポイント
+    /// * `rounds` - 1世代のラウンド数
+    /// * `opponents` - 1ラウンドあたりの各個体の対戦数
+    ///
+    /// # 戻り値
+    /// `points`と同じ順の正規化された適応度
+    pub fn normalize(&self, points: &[Points], rounds: u64, opponents: u64) -> Vec<Points> {
+        match self {
+            FitnessNormalization::Raw
+            | FitnessNormalization::PerInteraction
+            | FitnessNormalization::PerRound => points
+                .iter()
+                .map(|&p| self.normalize_one(p, rounds, opponents))
+                .collect(),
+            FitnessNormalization::ZScore => z_scores(points),
+            FitnessNormalization::Rank => ranks(points),
+        }
+    }
+
+    /// 1体分の累積ポイントを正規化
+    ///
+    /// 置換方式で新しく評価した子に使います。個体群に依存する方式
+    /// （`is_relative`）では単体で値が決まらないため、ポイントをそのまま返します。
+    ///
+    /// # 引数
+    /// * `points` - 累積ポイント
+    /// * `rounds` - 1世代のラウンド数
+    /// * `opponents` - 1ラウンドあたりの対戦数
+    pub fn normalize_one(&self, points: Points, rounds: u64, opponents: u64) -> Points {
+        match self {
+            FitnessNormalization::PerInteraction => {
+                scaled_ratio(points, rounds.saturating_mul(opponents))
+            }
+            FitnessNormalization::PerRound => scaled_ratio(points, rounds),
+            _ => points,
+        }
+    }
+}
+
+/// `points * FITNESS_SCALE / divisor`を丸めて飽和させた値（`divisor`が0なら1として扱う）
+fn scaled_ratio(points: Points, divisor: u64) -> Points {
+    let divisor = divisor.max(1) as u128;
+    let scaled = (points as u128 * FITNESS_SCALE as u128 + divisor / 2) / divisor;
+    scaled.min(Points::MAX as u128) as Points
+}
+
+fn z_scores(points: &[Points]) -> Vec<Points> {
+    let n = points.len().max(1) as f64;
+    let mean = points.iter().map(|&p| p as f64).sum::<f64>() / n;
+    let variance = points
+        .iter()
+        .map(|&p| (p as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    let std_dev = variance.sqrt();
+
+    points
+        .iter()
+        .map(|&p| {
+            let z = if std_dev > 0.0 {
+                (p as f64 - mean) / std_dev
+            } else {
+                0.0
+            };
+            ((z + Z_SCORE_OFFSET).max(0.0) * FITNESS_SCALE as f64).round() as Points
+        })
+        .collect()
+}
+
+fn ranks(points: &[Points]) -> Vec<Points> {
+    let mut sorted = points.to_vec();
+    sorted.sort_unstable();
+    points
+        .iter()
+        .map(|p| sorted.partition_point(|q| q < p) as Points + 1)
+        .collect()
+}
+
+/// ルーレット選択で選択圧を高めるための適応度の二乗
+///
+/// `f64`で計算するため、`Points`の最大値の二乗を個体数分合計しても
+/// オーバーフローしません（2^53までの値は誤差なく表現されます）。
+pub fn squared_fitness(points: Points) -> f64 {
+    let points = points as f64;
+    points * points
+}
+
+impl fmt::Display for FitnessNormalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitnessNormalization::Raw => write!(f, "raw"),
+            FitnessNormalization::PerInteraction => write!(f, "interaction"),
+            FitnessNormalization::PerRound => write!(f, "round"),
+            FitnessNormalization::ZScore => write!(f, "zscore"),
+            FitnessNormalization::Rank => write!(f, "rank"),
+        }
+    }
+}
+
+impl FromStr for FitnessNormalization {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "raw" => Ok(FitnessNormalization::Raw),
+            "interaction" => Ok(FitnessNormalization::PerInteraction),
+            "round" => Ok(FitnessNormalization::PerRound),
+            "zscore" => Ok(FitnessNormalization::ZScore),
+            "rank" => Ok(FitnessNormalization::Rank),
+            _ => Err(GAError::ValidationError(format!(
+                "Invalid fitness normalization: {s}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        for name in ["raw", "interaction", "round", "zscore", "rank"] {
+            let normalization: FitnessNormalization = name.parse().unwrap();
+            assert_eq!(normalization.to_string(), name);
+        }
+        assert!("mean".parse::<FitnessNormalization>().is_err());
+    }
+
+    #[test]
+    fn test_linear_modes_remove_population_and_round_scaling() {
+        // 平均利得3の個体は、対戦数やラウンド数によらず同じ適応度になる
+        let small = FitnessNormalization::PerInteraction.normalize(&[3 * 9 * 2], 2, 9);
+        let large = FitnessNormalization::PerInteraction.normalize(&[3 * 999 * 50], 50, 999);
+        assert_eq!(small, vec![3 * FITNESS_SCALE]);
+        assert_eq!(small, large);
+
+        assert_eq!(
+            FitnessNormalization::PerRound.normalize(&[30, 45], 3, 9),
+            vec![10 * FITNESS_SCALE, 15 * FITNESS_SCALE]
+        );
+        assert_eq!(
+            FitnessNormalization::Raw.normalize(&[30, 45], 3, 9),
+            vec![30, 45]
+        );
+    }
+
+    #[test]
+    fn test_relative_modes() {
+        assert_eq!(
+            FitnessNormalization::Rank.normalize(&[50, 10, 50, 30], 1, 3),
+            vec![3, 1, 3, 2]
+        );
+
+        let z = FitnessNormalization::ZScore.normalize(&[10, 20, 30], 1, 2);
+        assert_eq!(z[1], 3 * FITNESS_SCALE);
+        assert!(z[0] < z[1] && z[1] < z[2]);
+        assert_eq!(z[1] - z[0], z[2] - z[1]);
+
+        let uniform = FitnessNormalization::ZScore.normalize(&[7, 7], 1, 1);
+        assert_eq!(uniform, vec![3 * FITNESS_SCALE; 2]);
+    }
+
+    #[test]
+    fn test_extreme_points_do_not_overflow() {
+        let points = [Points::MAX, Points::MAX - 1, 0];
+        for normalization in [
+            FitnessNormalization::Raw,
+            FitnessNormalization::PerInteraction,
+            FitnessNormalization::PerRound,
+            FitnessNormalization::ZScore,
+            FitnessNormalization::Rank,
+        ] {
+            assert_eq!(normalization.normalize(&points, 0, 0).len(), 3);
+        }
+        assert_eq!(
+            FitnessNormalization::PerRound.normalize_one(Points::MAX, 1, 1),
+            Points::MAX
+        );
+        assert_eq!(squared_fitness(5), 25.0);
+        assert!((squared_fitness(Points::MAX) * 1e6).is_finite());
+    }
+}
@@ -0,0 +1,178 @@
+/// 選択圧の診断
+///
+/// 任意の`SelectionStrategy`について、適応度比例選択の期待子孫数と、実際に
+/// 選択を繰り返して得られた子孫数（親として選ばれた回数）を比較します。
+/// 戦略ごとの偏り（bias）、ばらつき（variance）、選択強度（intensity）を
+/// 同じ尺度で比較できます。
+use crate::core::{errors::*, traits::*};
+
+/// 選択圧の測定結果
+///
+/// # フィールド
+/// * `expected` - 適応度比例選択での各個体の期待子孫数（合計は個体数）
+/// * `realised` - 試行平均での各個体の実際の子孫数
+/// * `variance` - 各個体の子孫数の試行間分散
+/// * `intensity` - 選択強度（選ばれた親の平均適応度と個体群平均の差を標準偏差で割った値）
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionPressure {
+    pub expected: Vec<f64>,
+    pub realised: Vec<f64>,
+    pub variance: Vec<f64>,
+    pub intensity: f64,
+}
+
+impl SelectionPressure {
+    /// 選択戦略の選択圧を測定
+    ///
+    /// 1試行では`select_parents`を個体数の半分（切り上げ）回呼び出し、
+    /// 親として選ばれた回数を各個体の子孫数として数えます。
+    ///
+    /// # 引数
+    /// * `strategy` - 測定する選択戦略
+    /// * `population` - 評価済みの個体群
+    /// * `trials` - 試行回数
+    ///
+    /// # エラー
+    /// 個体群が空、または試行回数が0の場合
+    pub fn measure<T, S>(strategy: &S, population: &[T], trials: usize) -> GAResult<Self>
+    where
+        T: Agent,
+        S: SelectionStrategy<T>,
+    {
+        if population.is_empty() {
+            return Err(GAError::EmptyPopulation);
+        }
+        if trials == 0 {
+            return Err(GAError::ValidationError(
+                "At least one trial is required".to_string(),
+            ));
+        }
+
+        let n = population.len();
+        let fitness: Vec<f64> = population.iter().map(|a| a.fitness() as f64).collect();
+        let total: f64 = fitness.iter().sum();
+        let expected = if total > 0.0 {
+            fitness.iter().map(|f| f * n as f64 / total).collect()
+        } else {
+            vec![1.0; n]
+        };
+
+        let mut sum = vec![0.0; n];
+        let mut sum_squares = vec![0.0; n];
+        let mut selected_fitness = 0.0;
+        let mut selected_count = 0usize;
+
+        for _ in 0..trials {
+            let mut counts = vec![0.0; n];
+            for _ in 0..n.div_ceil(2) {
+                let (parent1, parent2) = strategy.select_parents(population)?;
+                for parent in [parent1, parent2] {
+                    if let Some(i) = Self::index_of(population, &parent) {
+                        counts[i] += 1.0;
+                    }
+                    selected_fitness += parent.fitness() as f64;
+                    selected_count += 1;
+                }
+            }
+            for i in 0..n {
+                sum[i] += counts[i];
+                sum_squares[i] += counts[i] * counts[i];
+            }
+        }
+
+        let trials = trials as f64;
+        let realised: Vec<f64> = sum.iter().map(|s| s / trials).collect();
+        let variance = sum_squares
+            .iter()
+            .zip(&realised)
+            .map(|(sq, mean)| (sq / trials - mean * mean).max(0.0))
+            .collect();
+
+        let mean = total / n as f64;
+        let std_dev = (fitness.iter().map(|f| (f - mean).powi(2)).sum::<f64>() / n as f64).sqrt();
+        let intensity = if std_dev > 0.0 {
+            (selected_fitness / selected_count as f64 - mean) / std_dev
+        } else {
+            0.0
+        };
+
+        Ok(Self {
+            expected,
+            realised,
+            variance,
+            intensity,
+        })
+    }
+
+    /// 期待子孫数と実際の子孫数の平均絶対誤差
+    pub fn bias(&self) -> f64 {
+        self.expected
+            .iter()
+            .zip(&self.realised)
+            .map(|(e, r)| (e - r).abs())
+            .sum::<f64>()
+            / self.expected.len() as f64
+    }
+
+    /// 子孫数の試行間分散の平均
+    pub fn mean_variance(&self) -> f64 {
+        self.variance.iter().sum::<f64>() / self.variance.len() as f64
+    }
+
+    /// 選ばれた親を個体群中の位置に対応付ける（ID・DNA・ポイントが一致する最初の個体）
+    fn index_of<T: Agent>(population: &[T], agent: &T) -> Option<usize> {
+        population.iter().position(|candidate| {
+            candidate.id() == agent.id()
+                && candidate.points() == agent.points()
+                && candidate.dna() == agent.dna()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::selection::{
+        RouletteSelection, StochasticUniversalSampling, TruncationSelection,
+    };
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn population() -> Vec<ModelAgent> {
+        (1..=4)
+            .map(|i| BaseModel::with_points(&ModelAgent::new(i, "101010".to_string()), i * 10))
+            .collect()
+    }
+
+    #[test]
+    fn test_sus_has_lower_variance_than_roulette() {
+        let population = population();
+        let roulette =
+            SelectionPressure::measure(&RouletteSelection::with_linear_fitness(), &population, 300)
+                .unwrap();
+        let sus = SelectionPressure::measure(&StochasticUniversalSampling::new(), &population, 300)
+            .unwrap();
+
+        assert_eq!(sus.expected, vec![0.4, 0.8, 1.2, 1.6]);
+        assert!(sus.mean_variance() < roulette.mean_variance());
+        assert!(sus.intensity > 0.0);
+    }
+
+    #[test]
+    fn test_truncation_is_stronger_than_proportional() {
+        let population = population();
+        let truncation =
+            SelectionPressure::measure(&TruncationSelection::new(0.25).unwrap(), &population, 50)
+                .unwrap();
+
+        // 最良個体のみが選ばれるため、子孫数は4体分すべて最良個体に集中する
+        assert_eq!(truncation.realised, vec![0.0, 0.0, 0.0, 4.0]);
+        assert!(truncation.bias() > 1.0);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let empty: Vec<ModelAgent> = Vec::new();
+        assert!(SelectionPressure::measure(&RouletteSelection::new(), &empty, 10).is_err());
+        assert!(SelectionPressure::measure(&RouletteSelection::new(), &population(), 0).is_err());
+    }
+}
@@ -1,6 +1,7 @@
+use super::{check_survivors, has_uniform_fitness};
 #[allow(unused_imports)]
 use crate::core::{errors::*, traits::*, types::*};
-use rand::{thread_rng, Rng};
+use rand::{thread_rng, Rng, RngCore};
 
 /// Rank-based selection strategy
 #[derive(Debug, Clone)]
@@ -45,13 +46,22 @@ impl RankSelection {
         probabilities
     }
 
-    fn select_by_rank<T: Agent>(&self, sorted_population: &[T]) -> GAResult<T> {
+    fn select_by_rank<T: Agent, R: Rng>(
+        &self,
+        sorted_population: &[T],
+        rng: &mut R,
+    ) -> GAResult<T> {
         if sorted_population.is_empty() {
             return Err(GAError::EmptyPopulation);
         }
 
+        // 適応度に差がなければ順位は並び順だけで決まるため、一様に選ぶ
+        if has_uniform_fitness(sorted_population) {
+            let index = rng.gen_range(0..sorted_population.len());
+            return Ok(sorted_population[index].clone());
+        }
+
         let probabilities = self.calculate_rank_probabilities(sorted_population.len());
-        let mut rng = thread_rng();
         let mut cumulative_prob = 0.0;
         let random_value: f64 = rng.gen();
 
@@ -80,39 +90,29 @@ impl Default for RankSelection {
 }
 
 impl<T: Agent> SelectionStrategy<T> for RankSelection {
-    fn select_parents(&self, population: &[T]) -> (T, T) {
+    fn select_parents_with_rng(
+        &self,
+        population: &[T],
+        mut rng: &mut dyn RngCore,
+    ) -> GAResult<(T, T)> {
         let sorted_population = self.sort_population_by_fitness(population);
-
-        let parent1 = self
-            .select_by_rank(&sorted_population)
-            .unwrap_or_else(|_| population[0].clone());
-        let parent2 = self
-            .select_by_rank(&sorted_population)
-            .unwrap_or_else(|_| population[0].clone());
-
-        (parent1, parent2)
+        Ok((
+            self.select_by_rank(&sorted_population, &mut rng)?,
+            self.select_by_rank(&sorted_population, &mut rng)?,
+        ))
     }
 
-    fn select_survivors(&self, population: &[T], count: usize) -> Vec<T> {
-        if count >= population.len() {
-            return population.to_vec();
+    fn select_survivors(&self, population: &[T], count: usize) -> GAResult<Vec<T>> {
+        check_survivors(population, count)?;
+        if count == population.len() {
+            return Ok(population.to_vec());
         }
 
         let sorted_population = self.sort_population_by_fitness(population);
-        let mut survivors = Vec::with_capacity(count);
-
-        for _ in 0..count {
-            if let Ok(survivor) = self.select_by_rank(&sorted_population) {
-                survivors.push(survivor);
-            }
-        }
-
-        // Fill remaining slots if needed
-        while survivors.len() < count && !population.is_empty() {
-            survivors.push(sorted_population[sorted_population.len() - 1].clone());
-        }
-
-        survivors
+        let mut rng = thread_rng();
+        (0..count)
+            .map(|_| self.select_by_rank(&sorted_population, &mut rng))
+            .collect()
     }
 }
 
@@ -138,7 +138,7 @@ mod tests {
             self.clone()
         }
 
-        fn mutate(&self, _rate: MutationRate) -> Self {
+        fn mutate_with_rng<R: Rng>(&self, _rate: MutationRate, _rng: &mut R) -> Self {
             self.clone()
         }
 
@@ -275,7 +275,7 @@ mod tests {
         ];
 
         let selection = RankSelection::linear();
-        let (parent1, parent2) = selection.select_parents(&population);
+        let (parent1, parent2) = selection.select_parents(&population).unwrap();
 
         assert!(population.iter().any(|a| a.id == parent1.id));
         assert!(population.iter().any(|a| a.id == parent2.id));
@@ -302,7 +302,7 @@ mod tests {
         ];
 
         let selection = RankSelection::linear();
-        let survivors = selection.select_survivors(&population, 2);
+        let survivors = selection.select_survivors(&population, 2).unwrap();
 
         assert_eq!(survivors.len(), 2);
         for survivor in &survivors {
@@ -315,7 +315,7 @@ mod tests {
         let population: Vec<TestAgent> = vec![];
         let selection = RankSelection::linear();
 
-        let result = selection.select_by_rank(&population);
+        let result = selection.select_by_rank(&population, &mut thread_rng());
         assert!(matches!(result, Err(GAError::EmptyPopulation)));
     }
 
@@ -3,8 +3,10 @@
 /// ルーレット選択は、各個体の適応度に比例した確率で個体を選択する手法です。
 /// 適応度が高い個体ほど選択される確率が高くなりますが、適応度の低い個体にも
 /// 選択される可能性を残すことで、多様性を保持します。
+use super::check_survivors;
+use super::normalization::squared_fitness;
 use crate::core::{errors::*, traits::*, types::*};
-use rand::{thread_rng, Rng};
+use rand::{thread_rng, Rng, RngCore};
 
 /// ルーレット選択戦略の実装構造体
 ///
@@ -56,47 +58,50 @@ impl RouletteSelection {
     /// * `points` - 個体の獲得ポイント
     ///
     /// # 戻り値
-    /// 計算された適応度値
-    fn calculate_fitness(&self, points: Points) -> Fitness {
+    /// 計算された適応度値（二乗してもオーバーフローしないよう`f64`）
+    fn calculate_fitness(&self, points: Points) -> f64 {
         if self.use_squared_fitness {
             // 適応度を二乗することで選択圧を高める
-            points * points
+            squared_fitness(points)
         } else {
             // 線形な適応度を使用
-            points
+            points as f64
         }
     }
 
-    fn select_single<T: Agent>(&self, population: &[T]) -> GAResult<T> {
+    fn select_single<T: Agent, R: Rng>(&self, population: &[T], rng: &mut R) -> GAResult<T> {
         if population.is_empty() {
             return Err(GAError::EmptyPopulation);
         }
 
-        let total_fitness: Fitness = population
+        let total_fitness: f64 = population
             .iter()
             .map(|agent| self.calculate_fitness(agent.points()))
             .sum();
 
-        if total_fitness == 0 {
+        if total_fitness <= 0.0 {
             // If all fitness is 0, select randomly
-            let mut rng = thread_rng();
             let index = rng.gen_range(0..population.len());
             return Ok(population[index].clone());
         }
 
-        let mut rng = thread_rng();
-        let mut selection_point = rng.gen_range(0..total_fitness) as i64;
+        let mut selection_point = rng.gen_range(0.0..total_fitness);
 
         for agent in population {
             let fitness = self.calculate_fitness(agent.points());
-            selection_point -= fitness as i64;
-            if selection_point <= 0 {
+            if selection_point < fitness {
                 return Ok(agent.clone());
             }
+            selection_point -= fitness;
         }
 
-        // Fallback to first agent (should never reach here)
-        Ok(population[0].clone())
+        // Rounding can leave a tiny remainder; fall back to the last selectable agent
+        Ok(population
+            .iter()
+            .rev()
+            .find(|agent| agent.points() > 0)
+            .unwrap_or(&population[0])
+            .clone())
     }
 }
 
@@ -107,34 +112,27 @@ impl Default for RouletteSelection {
 }
 
 impl<T: Agent> SelectionStrategy<T> for RouletteSelection {
-    fn select_parents(&self, population: &[T]) -> (T, T) {
-        let parent1 = self
-            .select_single(population)
-            .unwrap_or_else(|_| population[0].clone());
-        let parent2 = self
-            .select_single(population)
-            .unwrap_or_else(|_| population[0].clone());
-        (parent1, parent2)
+    fn select_parents_with_rng(
+        &self,
+        population: &[T],
+        mut rng: &mut dyn RngCore,
+    ) -> GAResult<(T, T)> {
+        Ok((
+            self.select_single(population, &mut rng)?,
+            self.select_single(population, &mut rng)?,
+        ))
     }
 
-    fn select_survivors(&self, population: &[T], count: usize) -> Vec<T> {
-        if count >= population.len() {
-            return population.to_vec();
-        }
-
-        let mut survivors = Vec::with_capacity(count);
-        for _ in 0..count {
-            if let Ok(survivor) = self.select_single(population) {
-                survivors.push(survivor);
-            }
-        }
-
-        // Fill remaining slots if needed
-        while survivors.len() < count && !population.is_empty() {
-            survivors.push(population[0].clone());
+    fn select_survivors(&self, population: &[T], count: usize) -> GAResult<Vec<T>> {
+        check_survivors(population, count)?;
+        if count == population.len() {
+            return Ok(population.to_vec());
         }
 
-        survivors
+        let mut rng = thread_rng();
+        (0..count)
+            .map(|_| self.select_single(population, &mut rng))
+            .collect()
     }
 }
 
@@ -160,7 +158,7 @@ mod tests {
             self.clone()
         }
 
-        fn mutate(&self, _rate: MutationRate) -> Self {
+        fn mutate_with_rng<R: Rng>(&self, _rate: MutationRate, _rng: &mut R) -> Self {
             self.clone()
         }
 
@@ -238,7 +236,7 @@ mod tests {
         ];
 
         let selection = RouletteSelection::new();
-        let (parent1, parent2) = selection.select_parents(&population);
+        let (parent1, parent2) = selection.select_parents(&population).unwrap();
 
         assert!(population.iter().any(|a| a.id == parent1.id));
         assert!(population.iter().any(|a| a.id == parent2.id));
@@ -265,7 +263,7 @@ mod tests {
         ];
 
         let selection = RouletteSelection::new();
-        let survivors = selection.select_survivors(&population, 2);
+        let survivors = selection.select_survivors(&population, 2).unwrap();
 
         assert_eq!(survivors.len(), 2);
         for survivor in &survivors {
@@ -278,7 +276,7 @@ mod tests {
         let population: Vec<TestAgent> = vec![];
         let selection = RouletteSelection::new();
 
-        let result = selection.select_single(&population);
+        let result = selection.select_single(&population, &mut thread_rng());
         assert!(matches!(result, Err(GAError::EmptyPopulation)));
     }
 
@@ -298,7 +296,7 @@ mod tests {
         ];
 
         let selection = RouletteSelection::new();
-        let result = selection.select_single(&population);
+        let result = selection.select_single(&population, &mut thread_rng());
         assert!(result.is_ok());
     }
 
@@ -307,7 +305,26 @@ mod tests {
         let selection_squared = RouletteSelection::new();
         let selection_linear = RouletteSelection::with_linear_fitness();
 
-        assert_eq!(selection_squared.calculate_fitness(5), 25);
-        assert_eq!(selection_linear.calculate_fitness(5), 5);
+        assert_eq!(selection_squared.calculate_fitness(5), 25.0);
+        assert_eq!(selection_linear.calculate_fitness(5), 5.0);
+    }
+
+    #[test]
+    fn test_large_fitness_does_not_overflow() {
+        let population: Vec<TestAgent> = (0..1000)
+            .map(|id| TestAgent {
+                id,
+                points: Points::MAX - id,
+                dna: "101010".to_string(),
+            })
+            .collect();
+
+        let selection = RouletteSelection::new();
+        for _ in 0..100 {
+            let selected = selection
+                .select_single(&population, &mut thread_rng())
+                .unwrap();
+            assert!(selected.id < 1000);
+        }
     }
 }
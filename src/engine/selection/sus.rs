@@ -0,0 +1,146 @@
+/// 確率的普遍抽出（Stochastic Universal Sampling, SUS）の実装
+///
+/// ルーレット選択と同じく適応度に比例した確率で個体を選びますが、1回の回転で
+/// 等間隔に並んだ複数のポインタを使って必要な個体を一度に選びます。
+/// 各個体が選ばれる回数は期待値の切り捨てと切り上げの間に必ず収まるため、
+/// ルーレット選択よりも選択回数のばらつきが小さくなります。
+use super::check_survivors;
+use crate::core::{errors::*, traits::*};
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng, RngCore};
+
+/// 確率的普遍抽出による選択戦略
+///
+/// 適応度には`fitness()`の値をそのまま（線形に）使用します。
+#[derive(Debug, Clone, Default)]
+pub struct StochasticUniversalSampling;
+
+impl StochasticUniversalSampling {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 適応度に比例して`count`個の個体インデックスを選択
+    ///
+    /// 選ばれた順序による偏りを避けるため、結果はシャッフルして返します。
+    /// 全個体の適応度が0の場合は一様に選択します。
+    ///
+    /// # エラー
+    /// 個体群が空の場合
+    pub fn sample_indices<T: Agent>(&self, population: &[T], count: usize) -> GAResult<Vec<usize>> {
+        self.sample_indices_with_rng(population, count, &mut thread_rng())
+    }
+
+    /// 与えられた乱数生成器で`count`体分の添字を選択
+    ///
+    /// # エラー
+    /// 個体群が空の場合
+    pub fn sample_indices_with_rng<T: Agent, R: Rng>(
+        &self,
+        population: &[T],
+        count: usize,
+        rng: &mut R,
+    ) -> GAResult<Vec<usize>> {
+        if population.is_empty() {
+            return Err(GAError::EmptyPopulation);
+        }
+
+        let total: f64 = population.iter().map(|a| a.fitness() as f64).sum();
+        if total == 0.0 {
+            return Ok((0..count)
+                .map(|_| rng.gen_range(0..population.len()))
+                .collect());
+        }
+
+        let spacing = total / count as f64;
+        let start = rng.gen::<f64>() * spacing;
+        let mut indices = Vec::with_capacity(count);
+        let mut cumulative = 0.0;
+        let mut current = 0;
+
+        for (i, agent) in population.iter().enumerate() {
+            cumulative += agent.fitness() as f64;
+            while current < count && start + current as f64 * spacing < cumulative {
+                indices.push(i);
+                current += 1;
+            }
+        }
+
+        // 浮動小数点誤差で取りこぼしたポインタは最後の個体に割り当てる
+        indices.resize(count, population.len() - 1);
+        indices.shuffle(rng);
+        Ok(indices)
+    }
+}
+
+impl<T: Agent> SelectionStrategy<T> for StochasticUniversalSampling {
+    fn select_parents_with_rng(
+        &self,
+        population: &[T],
+        mut rng: &mut dyn RngCore,
+    ) -> GAResult<(T, T)> {
+        let indices = self.sample_indices_with_rng(population, 2, &mut rng)?;
+        Ok((
+            population[indices[0]].clone(),
+            population[indices[1]].clone(),
+        ))
+    }
+
+    fn select_survivors(&self, population: &[T], count: usize) -> GAResult<Vec<T>> {
+        check_survivors(population, count)?;
+        if count == population.len() {
+            return Ok(population.to_vec());
+        }
+
+        let indices = self.sample_indices(population, count)?;
+        Ok(indices.into_iter().map(|i| population[i].clone()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn agent(id: u64, points: u64) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, "101010".to_string()), points)
+    }
+
+    #[test]
+    fn test_counts_stay_within_expected_bounds() {
+        // 適応度 10:20:30:40 で10個選ぶと、期待値は 1, 2, 3, 4 で端数がない
+        let population: Vec<ModelAgent> = (1..=4).map(|i| agent(i, i * 10)).collect();
+        let sus = StochasticUniversalSampling::new();
+
+        for _ in 0..50 {
+            let indices = sus.sample_indices(&population, 10).unwrap();
+            let mut counts = [0; 4];
+            for i in indices {
+                counts[i] += 1;
+            }
+            assert_eq!(counts, [1, 2, 3, 4]);
+        }
+    }
+
+    #[test]
+    fn test_zero_fitness_and_empty_population() {
+        let population: Vec<ModelAgent> = (0..3).map(|i| agent(i, 0)).collect();
+        let sus = StochasticUniversalSampling::new();
+        assert_eq!(sus.sample_indices(&population, 5).unwrap().len(), 5);
+
+        let empty: Vec<ModelAgent> = Vec::new();
+        assert!(matches!(
+            sus.sample_indices(&empty, 1),
+            Err(GAError::EmptyPopulation)
+        ));
+    }
+
+    #[test]
+    fn test_select_survivors_size() {
+        let population: Vec<ModelAgent> = (1..=5).map(|i| agent(i, i)).collect();
+        let survivors = StochasticUniversalSampling::new()
+            .select_survivors(&population, 3)
+            .unwrap();
+        assert_eq!(survivors.len(), 3);
+    }
+}
@@ -1,3 +1,4 @@
+use super::check_survivors;
 /// トーナメント選択戦略の実装
 ///
 /// トーナメント選択は、個体群からランダムに選んだ小グループ（トーナメント）の中で
@@ -5,7 +6,7 @@
 /// 実装が簡単で効率的な選択手法として広く使用されています。
 #[allow(unused_imports)]
 use crate::core::{errors::*, traits::*, types::*};
-use rand::{thread_rng, Rng};
+use rand::{thread_rng, Rng, RngCore};
 
 /// トーナメント選択戦略の実装構造体
 ///
@@ -65,13 +66,12 @@ impl TournamentSelection {
         Self { tournament_size: 3 }
     }
 
-    fn run_tournament<T: Agent>(&self, population: &[T]) -> GAResult<T> {
+    fn run_tournament<T: Agent, R: Rng>(&self, population: &[T], rng: &mut R) -> GAResult<T> {
         if population.is_empty() {
             return Err(GAError::EmptyPopulation);
         }
 
         let tournament_size = self.tournament_size.min(population.len());
-        let mut rng = thread_rng();
 
         let mut best_agent = None;
         let mut best_fitness = 0;
@@ -100,34 +100,27 @@ impl Default for TournamentSelection {
 }
 
 impl<T: Agent> SelectionStrategy<T> for TournamentSelection {
-    fn select_parents(&self, population: &[T]) -> (T, T) {
-        let parent1 = self
-            .run_tournament(population)
-            .unwrap_or_else(|_| population[0].clone());
-        let parent2 = self
-            .run_tournament(population)
-            .unwrap_or_else(|_| population[0].clone());
-        (parent1, parent2)
+    fn select_parents_with_rng(
+        &self,
+        population: &[T],
+        mut rng: &mut dyn RngCore,
+    ) -> GAResult<(T, T)> {
+        Ok((
+            self.run_tournament(population, &mut rng)?,
+            self.run_tournament(population, &mut rng)?,
+        ))
     }
 
-    fn select_survivors(&self, population: &[T], count: usize) -> Vec<T> {
-        if count >= population.len() {
-            return population.to_vec();
-        }
-
-        let mut survivors = Vec::with_capacity(count);
-        for _ in 0..count {
-            if let Ok(survivor) = self.run_tournament(population) {
-                survivors.push(survivor);
-            }
-        }
-
-        // Fill remaining slots if needed
-        while survivors.len() < count && !population.is_empty() {
-            survivors.push(population[0].clone());
+    fn select_survivors(&self, population: &[T], count: usize) -> GAResult<Vec<T>> {
+        check_survivors(population, count)?;
+        if count == population.len() {
+            return Ok(population.to_vec());
         }
 
-        survivors
+        let mut rng = thread_rng();
+        (0..count)
+            .map(|_| self.run_tournament(population, &mut rng))
+            .collect()
     }
 }
 
@@ -153,7 +146,7 @@ mod tests {
             self.clone()
         }
 
-        fn mutate(&self, _rate: MutationRate) -> Self {
+        fn mutate_with_rng<R: Rng>(&self, _rate: MutationRate, _rng: &mut R) -> Self {
             self.clone()
         }
 
@@ -251,7 +244,9 @@ mod tests {
         // 複数回実行して統計的に妥当な結果が得られることを検証
         let mut results = std::collections::HashMap::new();
         for _ in 0..100 {
-            let winner = selection.run_tournament(&population).unwrap();
+            let winner = selection
+                .run_tournament(&population, &mut thread_rng())
+                .unwrap();
             *results.entry(winner.id).or_insert(0) += 1;
         }
 
@@ -285,7 +280,7 @@ mod tests {
         ];
 
         let selection = TournamentSelection::new(2).unwrap();
-        let (parent1, parent2) = selection.select_parents(&population);
+        let (parent1, parent2) = selection.select_parents(&population).unwrap();
 
         assert!(population.iter().any(|a| a.id == parent1.id));
         assert!(population.iter().any(|a| a.id == parent2.id));
@@ -312,7 +307,7 @@ mod tests {
         ];
 
         let selection = TournamentSelection::new(2).unwrap();
-        let survivors = selection.select_survivors(&population, 2);
+        let survivors = selection.select_survivors(&population, 2).unwrap();
 
         assert_eq!(survivors.len(), 2);
         for survivor in &survivors {
@@ -325,7 +320,7 @@ mod tests {
         let population: Vec<TestAgent> = vec![];
         let selection = TournamentSelection::new(2).unwrap();
 
-        let result = selection.run_tournament(&population);
+        let result = selection.run_tournament(&population, &mut thread_rng());
         assert!(matches!(result, Err(GAError::EmptyPopulation)));
     }
 
@@ -345,7 +340,7 @@ mod tests {
         ];
 
         let selection = TournamentSelection::new(5).unwrap(); // Larger than population
-        let result = selection.run_tournament(&population);
+        let result = selection.run_tournament(&population, &mut thread_rng());
         assert!(result.is_ok());
     }
 }
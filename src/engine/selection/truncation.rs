@@ -0,0 +1,116 @@
+/// 切り捨て選択の実装
+///
+/// 適応度の上位から一定割合の個体だけを親の候補とし、その中から一様に選択します。
+/// 候補外の個体は一切選ばれないため、非常に強い選択圧を持ちます。
+use super::{check_parents, check_survivors, has_uniform_fitness};
+use crate::core::{errors::*, traits::*};
+use rand::{Rng, RngCore};
+
+/// 切り捨て選択戦略
+///
+/// # フィールド
+/// * `proportion` - 親の候補として残す上位個体の割合（0.0より大きく1.0以下）
+#[derive(Debug, Clone)]
+pub struct TruncationSelection {
+    proportion: f64,
+}
+
+impl TruncationSelection {
+    /// 候補として残す割合を指定して作成
+    ///
+    /// # エラー
+    /// 割合が(0.0, 1.0]の範囲外の場合
+    pub fn new(proportion: f64) -> GAResult<Self> {
+        if !(proportion > 0.0 && proportion <= 1.0) {
+            return Err(GAError::ValidationError(
+                "Truncation proportion must be in (0.0, 1.0]".to_string(),
+            ));
+        }
+
+        Ok(Self { proportion })
+    }
+
+    /// 上位半分を候補とする切り捨て選択を作成
+    pub fn half() -> Self {
+        Self { proportion: 0.5 }
+    }
+
+    /// 候補となる上位個体のインデックス（適応度の降順、最低1個体）
+    ///
+    /// 適応度に差がない場合は上位を決められないため、全個体を候補とします。
+    fn candidates<T: Agent>(&self, population: &[T]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..population.len()).collect();
+        if has_uniform_fitness(population) {
+            return order;
+        }
+        order.sort_by_key(|&i| std::cmp::Reverse(population[i].fitness()));
+
+        let size = ((population.len() as f64 * self.proportion).ceil() as usize).max(1);
+        order.truncate(size);
+        order
+    }
+}
+
+impl<T: Agent> SelectionStrategy<T> for TruncationSelection {
+    fn select_parents_with_rng(&self, population: &[T], rng: &mut dyn RngCore) -> GAResult<(T, T)> {
+        check_parents(population)?;
+        let candidates = self.candidates(population);
+        let parent1 = candidates[rng.gen_range(0..candidates.len())];
+        let parent2 = candidates[rng.gen_range(0..candidates.len())];
+        Ok((population[parent1].clone(), population[parent2].clone()))
+    }
+
+    fn select_survivors(&self, population: &[T], count: usize) -> GAResult<Vec<T>> {
+        check_survivors(population, count)?;
+        if count == population.len() {
+            return Ok(population.to_vec());
+        }
+
+        let mut order: Vec<usize> = (0..population.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(population[i].fitness()));
+        Ok(order
+            .into_iter()
+            .take(count)
+            .map(|i| population[i].clone())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn agent(id: u64, points: u64) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, "101010".to_string()), points)
+    }
+
+    #[test]
+    fn test_only_top_individuals_are_parents() {
+        let population: Vec<ModelAgent> = (0..10).map(|i| agent(i, i)).collect();
+        let selection = TruncationSelection::new(0.2).unwrap();
+
+        for _ in 0..100 {
+            let (parent1, parent2) = selection.select_parents(&population).unwrap();
+            assert!(parent1.get_points() >= 8);
+            assert!(parent2.get_points() >= 8);
+        }
+    }
+
+    #[test]
+    fn test_select_survivors_keeps_best() {
+        let population: Vec<ModelAgent> = vec![agent(0, 5), agent(1, 50), agent(2, 20)];
+        let survivors = TruncationSelection::half()
+            .select_survivors(&population, 2)
+            .unwrap();
+        let points: Vec<u64> = survivors.iter().map(|a| a.get_points()).collect();
+        assert_eq!(points, vec![50, 20]);
+    }
+
+    #[test]
+    fn test_invalid_proportion() {
+        assert!(TruncationSelection::new(0.0).is_err());
+        assert!(TruncationSelection::new(1.5).is_err());
+        assert!(TruncationSelection::new(1.0).is_ok());
+    }
+}
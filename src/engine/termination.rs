@@ -0,0 +1,381 @@
+/// 終了条件
+///
+/// 固定の世代数に加えて、停滞（最良適応度が一定世代改善しない）、遺伝子型の収束
+/// （多様性の低下・単一遺伝子型の優占）、目標平均利得の到達、実行時間の上限、
+/// 外部からのキャンセルによって進化を打ち切るための仕組みを提供します。
+/// 複数の条件を組み合わせた場合は、いずれか1つが満たされた時点で終了します。
+use crate::core::{errors::*, traits::*, types::*};
+use crate::engine::genetic::diversity_metric;
+use crate::engine::selection::normalization::{FitnessNormalization, FITNESS_SCALE};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 終了条件
+///
+/// 文字列表現は`stagnation:N`、`diversity:D`、`dominance:F`、`target:P`、`time:SECONDS`です。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TerminationCriterion {
+    /// 最良適応度が`generations`世代連続で改善しない
+    Stagnation { generations: usize },
+    /// 多様性（平均ペアワイズハミング距離）が`threshold`を下回った
+    DiversityBelow { threshold: f64 },
+    /// 単一の遺伝子型の頻度が`frequency`以上になった
+    GenotypeDominance { frequency: f64 },
+    /// 1回の対戦あたりの平均利得が`average`以上になった
+    ///
+    /// 累積ポイントではなく`FitnessNormalization::PerInteraction`で換算した値と
+    /// 比較するため、閾値の意味は個体数や`rounds_per_generation`によりません。
+    TargetAveragePayoff { average: f64 },
+    /// 実行開始からの経過時間が`limit`を超えた
+    WallClock { limit: Duration },
+}
+
+impl TerminationCriterion {
+    /// 条件のパラメータを検証
+    pub fn validate(&self) -> GAResult<()> {
+        let valid = match *self {
+            TerminationCriterion::Stagnation { generations } => generations > 0,
+            TerminationCriterion::DiversityBelow { threshold } => (0.0..=1.0).contains(&threshold),
+            TerminationCriterion::GenotypeDominance { frequency } => {
+                frequency > 0.0 && frequency <= 1.0
+            }
+            TerminationCriterion::TargetAveragePayoff { average } => average >= 0.0,
+            TerminationCriterion::WallClock { limit } => !limit.is_zero(),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(GAError::ValidationError(format!(
+                "Invalid termination criterion: {self}"
+            )))
+        }
+    }
+}
+
+impl fmt::Display for TerminationCriterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TerminationCriterion::Stagnation { generations } => {
+                write!(f, "stagnation:{generations}")
+            }
+            TerminationCriterion::DiversityBelow { threshold } => {
+                write!(f, "diversity:{threshold}")
+            }
+            TerminationCriterion::GenotypeDominance { frequency } => {
+                write!(f, "dominance:{frequency}")
+            }
+            TerminationCriterion::TargetAveragePayoff { average } => write!(f, "target:{average}"),
+            TerminationCriterion::WallClock { limit } => write!(f, "time:{}", limit.as_secs_f64()),
+        }
+    }
+}
+
+impl FromStr for TerminationCriterion {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GAError::ValidationError(format!("Invalid termination criterion: {s}"));
+        let (name, value) = s.split_once(':').ok_or_else(invalid)?;
+        let number = value.parse::<f64>().map_err(|_| invalid())?;
+
+        let criterion = match name {
+            "stagnation" => TerminationCriterion::Stagnation {
+                generations: value.parse().map_err(|_| invalid())?,
+            },
+            "diversity" => TerminationCriterion::DiversityBelow { threshold: number },
+            "dominance" => TerminationCriterion::GenotypeDominance { frequency: number },
+            "target" => TerminationCriterion::TargetAveragePayoff { average: number },
+            "time" => TerminationCriterion::WallClock {
+                limit: Duration::try_from_secs_f64(number).map_err(|_| invalid())?,
+            },
+            _ => return Err(invalid()),
+        };
+
+        criterion.validate()?;
+        Ok(criterion)
+    }
+}
+
+/// 外部から実行中の進化を中断するためのトークン
+///
+/// クローンしたトークンは同じ状態を共有するため、別スレッドやシグナルハンドラから
+/// `cancel`を呼ぶと、次の世代の終わりに進化が停止します。
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// キャンセルを要求
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// 進化が終了した理由
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TerminationReason {
+    /// 設定された世代数をすべて実行した
+    #[default]
+    GenerationLimit,
+    /// 終了条件が満たされた
+    Criterion(TerminationCriterion),
+    /// キャンセルトークンで中断された
+    Cancelled,
+}
+
+impl fmt::Display for TerminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TerminationReason::GenerationLimit => write!(f, "generation limit"),
+            TerminationReason::Criterion(criterion) => write!(f, "{criterion}"),
+            TerminationReason::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// 終了の記録
+///
+/// # フィールド
+/// * `reason` - 終了した理由
+/// * `generation` - 終了条件が満たされた世代（世代数上限の場合は最後に実行した世代）
+/// * `population` - 共進化で終了条件を満たした集団の添字（A=0、B=1）。
+///   単一集団のシミュレーション、世代数上限、キャンセルでは`None`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Termination {
+    pub reason: TerminationReason,
+    pub generation: Generation,
+    pub population: Option<usize>,
+}
+
+/// 世代ごとに終了条件を判定する監視器
+///
+/// 停滞判定のための最良適応度の履歴と、実行時間判定のための開始時刻を保持します。
+/// 目標平均利得の判定には1世代のラウンド数（既定値1）を使います。
+#[derive(Debug, Clone)]
+pub struct TerminationMonitor {
+    criteria: Vec<TerminationCriterion>,
+    cancellation: Option<CancellationToken>,
+    started: Instant,
+    best_fitness: Option<Fitness>,
+    last_improvement: Generation,
+    rounds_per_generation: usize,
+}
+
+impl TerminationMonitor {
+    /// 終了条件とキャンセルトークンを指定して作成（開始時刻は作成時）
+    pub fn new(
+        criteria: Vec<TerminationCriterion>,
+        cancellation: Option<CancellationToken>,
+    ) -> Self {
+        Self {
+            criteria,
+            cancellation,
+            started: Instant::now(),
+            best_fitness: None,
+            last_improvement: 0,
+            rounds_per_generation: 1,
+        }
+    }
+
+    /// 累積ポイントを1回の対戦あたりの利得に換算するための1世代のラウンド数を設定
+    pub fn with_rounds_per_generation(mut self, rounds: usize) -> Self {
+        self.rounds_per_generation = rounds;
+        self
+    }
+
+    /// 評価済みの個体群で終了条件を判定
+    ///
+    /// # 引数
+    /// * `generation` - 現在の世代
+    /// * `agents` - この世代で評価された個体群
+    /// * `opponents` - 1ラウンドあたりの各個体の対戦数
+    ///
+    /// # 戻り値
+    /// 終了すべき場合はその理由、継続する場合は`None`
+    pub fn check<T: Agent>(
+        &mut self,
+        generation: Generation,
+        agents: &[T],
+        opponents: usize,
+    ) -> Option<TerminationReason> {
+        if let Some(best) = agents.iter().map(|a| a.fitness()).max() {
+            if self.best_fitness.map_or(true, |previous| best > previous) {
+                self.best_fitness = Some(best);
+                self.last_improvement = generation;
+            }
+        }
+
+        if self
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Some(TerminationReason::Cancelled);
+        }
+
+        self.criteria
+            .iter()
+            .find(|criterion| self.is_met(criterion, generation, agents, opponents))
+            .map(|&criterion| TerminationReason::Criterion(criterion))
+    }
+
+    fn is_met<T: Agent>(
+        &self,
+        criterion: &TerminationCriterion,
+        generation: Generation,
+        agents: &[T],
+        opponents: usize,
+    ) -> bool {
+        if agents.is_empty() {
+            return false;
+        }
+
+        match *criterion {
+            TerminationCriterion::Stagnation { generations } => {
+                generation - self.last_improvement >= generations
+            }
+            TerminationCriterion::DiversityBelow { threshold } => {
+                diversity_metric(agents) < threshold
+            }
+            TerminationCriterion::GenotypeDominance { frequency } => {
+                let mut counts: HashMap<&str, usize> = HashMap::new();
+                for agent in agents {
+                    *counts.entry(agent.dna_binary()).or_default() += 1;
+                }
+                let most_common = counts.values().copied().max().unwrap_or(0);
+                most_common as f64 / agents.len() as f64 >= frequency
+            }
+            TerminationCriterion::TargetAveragePayoff { average } => {
+                let points: Vec<Points> = agents.iter().map(|a| a.points()).collect();
+                let payoffs = FitnessNormalization::PerInteraction.normalize(
+                    &points,
+                    self.rounds_per_generation as u64,
+                    opponents as u64,
+                );
+                let total = payoffs.iter().map(|&p| p as f64).sum::<f64>();
+                total / (FITNESS_SCALE as f64 * agents.len() as f64) >= average
+            }
+            TerminationCriterion::WallClock { limit } => self.started.elapsed() >= limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent as ModelAgent, BaseModel};
+
+    fn agent(id: u64, dna: &str, points: u64) -> ModelAgent {
+        BaseModel::with_points(&ModelAgent::new(id, dna.to_string()), points)
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for s in [
+            "stagnation:50",
+            "diversity:0.05",
+            "dominance:0.9",
+            "target:2.5",
+            "time:1.5",
+        ] {
+            let criterion: TerminationCriterion = s.parse().unwrap();
+            assert_eq!(criterion.to_string(), s);
+        }
+        assert!("stagnation:0".parse::<TerminationCriterion>().is_err());
+        assert!("dominance:1.5".parse::<TerminationCriterion>().is_err());
+        assert!("stagnation".parse::<TerminationCriterion>().is_err());
+        assert!("forever:1".parse::<TerminationCriterion>().is_err());
+    }
+
+    #[test]
+    fn test_stagnation() {
+        let criterion = TerminationCriterion::Stagnation { generations: 2 };
+        let mut monitor = TerminationMonitor::new(vec![criterion], None);
+        let population = vec![agent(0, "1010", 10)];
+
+        assert_eq!(monitor.check(0, &population, 1), None);
+        assert_eq!(monitor.check(1, &population, 1), None);
+        assert_eq!(
+            monitor.check(2, &population, 1),
+            Some(TerminationReason::Criterion(criterion))
+        );
+
+        // 改善すれば停滞カウントはリセットされる
+        let mut monitor = TerminationMonitor::new(vec![criterion], None);
+        monitor.check(0, &population, 1);
+        monitor.check(1, &[agent(0, "1010", 20)], 1);
+        assert_eq!(monitor.check(2, &population, 1), None);
+    }
+
+    #[test]
+    fn test_convergence_criteria() {
+        let converged = vec![
+            agent(0, "1111", 1),
+            agent(1, "1111", 1),
+            agent(2, "1111", 1),
+            agent(3, "0000", 1),
+        ];
+        let dominance = TerminationCriterion::GenotypeDominance { frequency: 0.75 };
+        let diversity = TerminationCriterion::DiversityBelow { threshold: 0.4 };
+
+        let mut monitor = TerminationMonitor::new(vec![dominance], None);
+        assert!(monitor.check(0, &converged, 1).is_some());
+
+        // 平均ハミング距離は 3/6 = 0.5 なので閾値0.4では終了しない
+        let mut monitor = TerminationMonitor::new(vec![diversity], None);
+        assert!(monitor.check(0, &converged, 1).is_none());
+    }
+
+    #[test]
+    fn test_target_payoff_and_cancellation() {
+        // 5ラウンド・相手2体では1回の対戦あたりの利得は累積ポイントの1/10
+        let target = TerminationCriterion::TargetAveragePayoff { average: 1.5 };
+        let token = CancellationToken::new();
+        let mut monitor = TerminationMonitor::new(vec![target], Some(token.clone()))
+            .with_rounds_per_generation(5);
+
+        assert_eq!(
+            monitor.check(0, &[agent(0, "1", 10), agent(1, "0", 10)], 2),
+            None
+        );
+        assert_eq!(
+            monitor.check(1, &[agent(0, "1", 10), agent(1, "0", 20)], 2),
+            Some(TerminationReason::Criterion(target))
+        );
+        // 相手が多ければ同じ累積ポイントでも目標に届かない
+        assert_eq!(
+            monitor.check(2, &[agent(0, "1", 10), agent(1, "0", 20)], 4),
+            None
+        );
+
+        token.cancel();
+        assert_eq!(
+            monitor.check(3, &[agent(0, "1", 0)], 2),
+            Some(TerminationReason::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_wall_clock() {
+        let criterion = TerminationCriterion::WallClock {
+            limit: Duration::from_nanos(1),
+        };
+        let mut monitor = TerminationMonitor::new(vec![criterion], None);
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(monitor.check(0, &[agent(0, "1", 0)], 1).is_some());
+    }
+}
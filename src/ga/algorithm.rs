@@ -1,6 +1,9 @@
+use crate::core::errors::{GAError, GAResult};
+use crate::core::parallel::{derive_seed, map_indices, stream_rng};
+use crate::engine::selection::normalization::squared_fitness;
 use crate::models::model::{AgentId, BaseModel, Dna, Model, Points};
 use crate::strategies::utils::StrategyOperation;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 use crate::models::game;
 use crate::models::game::Game;
@@ -10,12 +13,20 @@ pub trait GAOperation<T: BaseModel> {
     fn get_dna_list(&self) -> Vec<String>;
 }
 
+/// 子の生成と次世代のゲームのシードを導出するときの系列番号
+const OFFSPRING_STREAM: u64 = 1;
+const NEXT_GAME_STREAM: u64 = 2;
+
+/// 評価済みの世代
+///
+/// `seed`は評価したゲームのシードで、子の生成と次世代のゲームの乱数はここから導出します。
 pub struct GA<T: BaseModel> {
     pub old_agents: Vec<Box<T>>,
     pub mutation_rate: f64,
     pub population: usize,
     pub dna_length: usize,
     pub num_games: usize,
+    pub seed: u64,
 }
 
 impl<T: Model> GAOperation<T> for GA<T> {
@@ -34,21 +45,40 @@ impl<T: Model> GAOperation<T> for GA<T> {
     }
 }
 
-pub fn create_next_generation<T, U>(ga: GA<T>, strategy: U) -> Game<T, U>
+/// ルーレット選択・交叉・突然変異で次世代のゲームを作成
+///
+/// 各子は`ga.seed`と子の添字から導出した専用の乱数生成器で生成するため、
+/// `parallel`機能で並列に生成しても同じシードからは同じ次世代になります。
+///
+/// # エラー
+/// 評価済みの個体群が空で親を選択できない場合は`GAError::EmptyPopulation`
+pub fn create_next_generation<T, U>(ga: GA<T>, strategy: U) -> GAResult<Game<T, U>>
 where
     T: Model,
     U: StrategyOperation<T>,
 {
-    let agents = (0..ga.population)
-        .map(|i| {
-            Box::from(T::new(
-                i as AgentId,
-                generate_offspring_dna(&ga.old_agents, ga.population, ga.mutation_rate),
-            ))
-        })
-        .collect::<Vec<Box<T>>>();
-
-    game::generate_next_game::<T, U>(
+    let fitness_sum = ga
+        .old_agents
+        .iter()
+        .map(|a| squared_fitness(a.get_points()))
+        .sum();
+    let offspring_seed = derive_seed(ga.seed, &[OFFSPRING_STREAM]);
+
+    let agents = map_indices(ga.population, |i| {
+        let mut rng = stream_rng(offspring_seed, &[i as u64]);
+        let dna = generate_offspring_dna(
+            &ga.old_agents,
+            ga.population,
+            fitness_sum,
+            ga.mutation_rate,
+            &mut rng,
+        )?;
+        Ok(Box::from(T::new(i as AgentId, dna)))
+    })
+    .into_iter()
+    .collect::<GAResult<Vec<_>>>()?;
+
+    Ok(game::generate_next_game::<T, U>(
         ga.population,
         ga.mutation_rate,
         ga.num_games,
@@ -56,57 +86,67 @@ where
         agents,
         strategy,
     )
+    .with_seed(derive_seed(ga.seed, &[NEXT_GAME_STREAM])))
 }
 
-fn generate_offspring_dna<T: Model>(
+fn generate_offspring_dna<T: Model, R: Rng>(
     agents: &[Box<T>],
     population: usize,
+    fitness_sum: f64,
     mutation_rate: f64,
-) -> Dna {
-    let (parent1, parent2) = select_parents(agents, population);
+    rng: &mut R,
+) -> GAResult<Dna> {
+    let (parent1, parent2) = select_parents(agents, population, fitness_sum, rng)?;
 
-    let mut rng = thread_rng();
     let cross_point = rng.gen_range(0..parent1.get_dna_length());
 
     let offspring = parent1.crossover(&parent2, cross_point);
-    offspring
-        .mutation(mutation_rate)
+    Ok(offspring
+        .mutation_with_rng(mutation_rate, rng)
         .get_dna_binary()
-        .to_string()
+        .to_string())
 }
 
-fn select_parents<T: BaseModel>(agents: &[Box<T>], population: usize) -> (T, T) {
-    let fitness_sum = agents
-        .iter()
-        .map(|a| {
-            let points = a.get_points();
-            points * points
-        })
-        .sum();
-
-    let parent1 = roulette_wheel_selection(agents, population, fitness_sum);
-    let parent2 = roulette_wheel_selection(agents, population, fitness_sum);
+fn select_parents<T: BaseModel, R: Rng>(
+    agents: &[Box<T>],
+    population: usize,
+    fitness_sum: f64,
+    rng: &mut R,
+) -> GAResult<(T, T)> {
+    let parent1 = roulette_wheel_selection(agents, population, fitness_sum, rng)?;
+    let parent2 = roulette_wheel_selection(agents, population, fitness_sum, rng)?;
 
-    (parent1, parent2)
+    Ok((parent1, parent2))
 }
 
-fn roulette_wheel_selection<T: BaseModel>(
+fn roulette_wheel_selection<T: BaseModel, R: Rng>(
     agents: &[Box<T>],
     _population: usize,
-    fitness_sum: u64,
-) -> T {
-    let mut rng = thread_rng();
-    let mut selection_point = rng.gen_range(0..fitness_sum) as i64;
+    fitness_sum: f64,
+    rng: &mut R,
+) -> GAResult<T> {
+    let first = agents.first().ok_or(GAError::EmptyPopulation)?;
+    if fitness_sum <= 0.0 {
+        // 全員のポイントが0なら一様に選ぶ
+        return Ok((*agents[rng.gen_range(0..agents.len())]).clone());
+    }
 
+    // 二乗適応度は`f64`で扱うため、累積ポイントが大きくてもオーバーフローしない
+    let mut selection_point = rng.gen_range(0.0..fitness_sum);
     for agent in agents {
-        let fitness = agent.get_points();
-        selection_point -= (fitness * fitness) as i64;
-        if selection_point <= 0 {
-            return (**agent).clone();
+        let fitness = squared_fitness(agent.get_points());
+        if selection_point < fitness {
+            return Ok((**agent).clone());
         }
+        selection_point -= fitness;
     }
 
-    (**agents.first().expect("Empty agents list")).clone()
+    Ok((**agents
+        .iter()
+        .rev()
+        .find(|agent| agent.get_points() > 0)
+        .unwrap_or(first))
+    .clone())
 }
 
 #[test]
@@ -117,20 +157,35 @@ fn points_sum_test() {
         Agent {
             id: 1,
             points: 10,
-            dna: "11110000".to_string(),
+            dna: "11110000".to_string().into(),
+            genome: "11110000".parse().unwrap(),
             active: true,
+            mutation_rate: None,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
         },
         Agent {
             id: 2,
             points: 20,
-            dna: "11110000".to_string(),
+            dna: "11110000".to_string().into(),
+            genome: "11110000".parse().unwrap(),
             active: true,
+            mutation_rate: None,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
         },
         Agent {
             id: 3,
             points: 30,
-            dna: "11110000".to_string(),
+            dna: "11110000".to_string().into(),
+            genome: "11110000".parse().unwrap(),
             active: true,
+            mutation_rate: None,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
         },
     ];
     let sum_points: u64 = agents.iter().map(|a| a.get_points()).sum();
@@ -145,22 +200,117 @@ fn selection_test() {
         Box::new(Agent {
             id: 1,
             points: 0,
-            dna: "11110000".to_string(),
+            dna: "11110000".to_string().into(),
+            genome: "11110000".parse().unwrap(),
             active: true,
+            mutation_rate: None,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
         }),
         Box::new(Agent {
             id: 2,
             points: 60,
-            dna: "11110000".to_string(),
+            dna: "11110000".to_string().into(),
+            genome: "11110000".parse().unwrap(),
             active: true,
+            mutation_rate: None,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
         }),
         Box::new(Agent {
             id: 3,
             points: 0,
-            dna: "11110000".to_string(),
+            dna: "11110000".to_string().into(),
+            genome: "11110000".parse().unwrap(),
             active: true,
+            mutation_rate: None,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
         }),
     ];
-    let selected = roulette_wheel_selection(&agents, 3, 3600);
+    let selected = roulette_wheel_selection(&agents, 3, 3600.0, &mut rand::thread_rng()).unwrap();
     assert_eq!(selected.id, 2);
 }
+
+#[test]
+fn next_generation_is_reproducible_test() {
+    use crate::models::game::GameOperation;
+    use crate::models::model::Agent;
+    use crate::strategies::utils::RouletteSelectionStrategy;
+
+    let ga = || GA {
+        old_agents: (0..6)
+            .map(|i| Box::new(Agent::new(i, format!("{:06b}", i * 9)).with_points(i + 1)))
+            .collect(),
+        mutation_rate: 0.1,
+        population: 6,
+        dna_length: 6,
+        num_games: 1,
+        seed: 99,
+    };
+
+    let first = create_next_generation(ga(), RouletteSelectionStrategy::default()).unwrap();
+    let second = create_next_generation(ga(), RouletteSelectionStrategy::default()).unwrap();
+    assert_eq!(first.get_dna_list(), second.get_dna_list());
+    assert_eq!(first.seed(), second.seed());
+    assert_ne!(first.seed(), 99);
+}
+
+#[test]
+fn large_points_selection_does_not_overflow_test() {
+    use crate::models::model::Agent;
+
+    // 大きな個体群で長時間走らせた場合に相当する累積ポイント
+    let old_agents: Vec<Box<Agent>> = (0..1000)
+        .map(|i| Box::new(Agent::new(i, format!("{:08b}", i % 256)).with_points(Points::MAX - i)))
+        .collect();
+    let fitness_sum: f64 = old_agents
+        .iter()
+        .map(|a| squared_fitness(a.get_points()))
+        .sum();
+    assert!(fitness_sum.is_finite());
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..100 {
+        let selected = roulette_wheel_selection(&old_agents, 1000, fitness_sum, &mut rng).unwrap();
+        assert!(selected.id < 1000);
+    }
+
+    let zero: Vec<Box<Agent>> = (0..4)
+        .map(|i| Box::new(Agent::new(i, "0000".to_string())))
+        .collect();
+    assert!(
+        roulette_wheel_selection(&zero, 4, 0.0, &mut rng)
+            .unwrap()
+            .id
+            < 4
+    );
+}
+
+#[test]
+fn empty_population_selection_is_an_error_test() {
+    use crate::models::model::Agent;
+    use crate::strategies::utils::RouletteSelectionStrategy;
+
+    let empty: Vec<Box<Agent>> = Vec::new();
+    assert!(matches!(
+        roulette_wheel_selection(&empty, 0, 0.0, &mut rand::thread_rng()),
+        Err(GAError::EmptyPopulation)
+    ));
+
+    let ga = GA::<Agent> {
+        old_agents: Vec::new(),
+        mutation_rate: 0.1,
+        population: 4,
+        dna_length: 4,
+        num_games: 1,
+        seed: 1,
+    };
+    assert!(matches!(
+        create_next_generation(ga, RouletteSelectionStrategy::default()),
+        Err(GAError::EmptyPopulation)
+    ));
+}
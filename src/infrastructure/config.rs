@@ -3,8 +3,24 @@
 /// このモジュールでは、遺伝的アルゴリズムの実行に必要な全ての設定パラメータを
 /// 管理します。設定の妥当性検証、デフォルト値の提供、ビルダーパターンによる
 /// 柔軟な設定構築などの機能を提供します。
+use crate::core::errors::{GAError, GAResult};
 use crate::core::types::*;
+use crate::engine::aging::AgeStructure;
+use crate::engine::crossover::CrossoverScheme;
+use crate::engine::diversity::DiversityScheme;
+use crate::engine::dynamics::{ImitationRule, Neighbourhood};
+use crate::engine::genetic::ReplacementModel;
+use crate::engine::group_selection::GroupSelection;
+use crate::engine::learning::Learning;
+use crate::engine::multi_objective::validate_objectives;
+use crate::engine::mutation::{IndelMutation, MutationScheme};
+use crate::engine::novelty::NoveltySearch;
+use crate::engine::selection::FitnessNormalization;
+use crate::engine::termination::TerminationCriterion;
+use crate::strategies::decoding::Decoding;
 use std::fmt;
+use std::fs;
+use std::path::Path;
 
 /// 遺伝的アルゴリズムシミュレーションの設定構造体
 ///
@@ -20,6 +36,25 @@ use std::fmt;
 /// * `dna_length` - DNA（戦略）の長さ
 /// * `report_interval` - 進捗報告の間隔
 /// * `elite_size` - エリート保存する個体数
+/// * `diversity_scheme` - 多様性維持スキーム
+/// * `mutation_scheme` - 突然変異率の制御スキーム
+/// * `replacement_model` - 世代交代（置換）モデル
+/// * `objectives` - 多目的最適化の目的関数（2つ以上でNSGA-IIを使用）
+/// * `termination` - 世代数以外の終了条件
+/// * `hall_of_fame_size` - 殿堂に保存する遺伝子型の最大数
+/// * `hall_of_fame_opponents` - 殿堂の遺伝子型を評価時の追加の対戦相手にするかどうか
+/// * `crossover_scheme` - 交叉方式（長さの異なる親の扱い）
+/// * `indel` - DNA長を変化させる挿入・欠失突然変異
+/// * `decoding` - 対戦時にDNAを表現型（協力確率）に変換するデコード方式
+/// * `novelty` - 行動の新規性による評価（新規性探索）
+/// * `seed` - 乱数のシード（再現可能な実行）
+/// * `fitness_normalization` - 選択前の累積ポイントの正規化方式
+/// * `match_cache` - 決定的な対戦の結果をキャッシュするかどうか
+/// * `age_structure` - 年齢と寿命を持つ個体による齢構造モード
+/// * `learning` - 世代内の協力確率の学習（ボールドウィン型・ラマルク型）
+/// * `imitation` - 遺伝的な世代交代の代わりに使う模倣による戦略の更新規則
+/// * `neighbourhood` - 模倣ダイナミクスでの対戦相手と手本の近傍構造
+/// * `group_selection` - グループ内で対戦し、個体とグループの2段階で選択する集団選択
 #[derive(Debug, Clone)]
 pub struct Config {
     /// 実行する世代数
@@ -62,6 +97,129 @@ pub struct Config {
     /// 各世代で確実に次世代に引き継がれる優秀な個体の数です。
     /// 多すぎると多様性が失われ、少なすぎると良い解が失われる可能性があります。
     pub elite_size: usize,
+
+    /// 多様性維持スキーム
+    ///
+    /// 個体群が単一の遺伝子型へ早期収束するのを防ぐための選択・置換方式です。
+    /// `DiversityScheme::None`の場合は従来のルーレット選択による世代交代を行います。
+    pub diversity_scheme: DiversityScheme,
+
+    /// 突然変異率の制御スキーム
+    ///
+    /// `mutation_rate`を基準値として、世代ごとの実効突然変異率を決定します。
+    /// `MutationScheme::Fixed`の場合は全世代で`mutation_rate`をそのまま使用します。
+    pub mutation_scheme: MutationScheme,
+
+    /// 世代交代（置換）モデル
+    ///
+    /// 世代交代型、定常状態型、(μ+λ)/(μ,λ)型から選択します。
+    /// 決定論的クラウディングと制限トーナメント置換は独自の置換を行うため、
+    /// 世代交代型以外とは組み合わせられません。
+    pub replacement_model: ReplacementModel,
+
+    /// 多目的最適化の目的関数
+    ///
+    /// 2つ以上指定するとNSGA-IIによる多目的進化を行い、報告間隔ごとに
+    /// パレートフロントを出力します。空または1つの場合は獲得ポイントのみで
+    /// 選択する従来の単一目的進化になります。
+    pub objectives: Vec<Objective>,
+
+    /// 世代数以外の終了条件
+    ///
+    /// いずれかの条件が満たされた世代で進化を打ち切ります。
+    /// 空の場合は`generations`世代をすべて実行します。
+    pub termination: Vec<TerminationCriterion>,
+
+    /// 殿堂に保存する遺伝子型の最大数
+    ///
+    /// 実行全体で獲得ポイントの高かった遺伝子型を重複なしで保存します。
+    /// 0の場合は殿堂を使用しません。
+    pub hall_of_fame_size: usize,
+
+    /// 殿堂の遺伝子型を評価時の追加の対戦相手にするかどうか
+    ///
+    /// 有効にすると、各個体は個体群内の対戦に加えて殿堂の全遺伝子型と対戦します。
+    /// 過去の戦略に負ける戦略が選ばれにくくなり、戦略の循環を抑えます。
+    pub hall_of_fame_opponents: bool,
+
+    /// 交叉方式
+    ///
+    /// 相同交叉は親の長さの組を保ち、messy交叉は両親で独立に切断点を選ぶため
+    /// 子のDNA長が変化します。長さの等しい親に対する相同交叉は一点交叉と同じです。
+    pub crossover_scheme: CrossoverScheme,
+
+    /// 挿入・欠失突然変異
+    ///
+    /// 指定するとDNA長（戦略の記憶の深さ）そのものが進化します。
+    /// `None`の場合はビット反転のみで、DNA長は`dna_length`に固定されます。
+    pub indel: Option<IndelMutation>,
+
+    /// 遺伝子型から表現型へのデコード方式
+    ///
+    /// 対戦時にDNAを協力確率に変換する方法です。既定の`Unary`は'1'の割合を
+    /// 協力確率とし、`Binary`・`Gray`・索引表では同じ戦略のまま遺伝子型と
+    /// 表現型の対応だけを変えられます。
+    pub decoding: Decoding,
+
+    /// 新規性探索
+    ///
+    /// 指定すると、選択に使う評価値を固定のプローブに対する行動の新規性
+    /// （またはポイントとの混合）に置き換えます。`None`の場合はポイントのみです。
+    pub novelty: Option<NoveltySearch>,
+
+    /// 乱数のシード
+    ///
+    /// 指定すると初期個体群と各世代の対戦のシードをここから導出します。
+    /// 従来の世代交代（既定の設定）では選択・交叉・突然変異もシードから導出するため、
+    /// 同じシードの実行は`parallel`機能の有無によらず同一の結果になります。
+    /// `None`の場合は実行ごとにランダムです。
+    pub seed: Option<u64>,
+
+    /// 選択に使う適応度の正規化方式
+    ///
+    /// 累積ポイントは個体群サイズと`rounds_per_generation`に比例するため、
+    /// 選択の前に正規化して尺度をそろえます。報告値は累積ポイントのままです。
+    /// `FitnessNormalization::Raw`の場合は従来どおり累積ポイントで選択します。
+    pub fitness_normalization: FitnessNormalization,
+
+    /// 決定的な対戦の結果を遺伝子型の組ごとにキャッシュするかどうか
+    ///
+    /// 乱数を使う組は自動的にキャッシュを使わないため、結果は変わりません。
+    pub match_cache: bool,
+
+    /// 齢構造モード
+    ///
+    /// 指定すると、個体は年齢を持って複数の世代を生き延び、成熟した個体が
+    /// 生涯のポイントに応じて繁殖し、死亡した個体の枠を子が埋めます。
+    /// `None`の場合は毎世代、置換モデルに従って個体群を置き換えます。
+    pub age_structure: Option<AgeStructure>,
+
+    /// 世代内の学習
+    ///
+    /// 指定すると、各個体は世代の始めに遺伝子型の協力確率を持ち、対戦で受け取った
+    /// 利得から協力確率を学習します。ラマルク型では学習した協力確率を繁殖の前に
+    /// 遺伝子型に書き戻します。`None`の場合、協力確率は遺伝子型で固定です。
+    pub learning: Option<Learning>,
+
+    /// 模倣ダイナミクス（文化進化）
+    ///
+    /// 指定すると、選択・交叉による世代交代の代わりに、各個体が近傍の手本の戦略を
+    /// 更新規則に従って写します。写した戦略には突然変異率で模倣の誤りが入ります。
+    /// `None`の場合は遺伝的な世代交代です。
+    pub imitation: Option<ImitationRule>,
+
+    /// 模倣ダイナミクスの近傍構造
+    ///
+    /// 構造化された近傍（環・格子）では、各個体は近傍の個体とのみ対戦し、
+    /// 近傍の個体だけを手本にします。既定はよく混ざった個体群です。
+    pub neighbourhood: Neighbourhood,
+
+    /// 集団（マルチレベル）選択
+    ///
+    /// 指定すると、個体群を同じ大きさのグループに分けて各個体は同じグループの
+    /// 個体とのみ対戦し、個体はグループ内で、グループはグループ全体の獲得ポイントで
+    /// 選択されます。`None`の場合は個体群全体で対戦し、個体のみが選択されます。
+    pub group_selection: Option<GroupSelection>,
 }
 
 impl Config {
@@ -81,9 +239,143 @@ impl Config {
             dna_length: DEFAULT_DNA_LENGTH,
             report_interval: DEFAULT_REPORT_INTERVAL,
             elite_size: DEFAULT_ELITE_SIZE,
+            diversity_scheme: DiversityScheme::None,
+            mutation_scheme: MutationScheme::Fixed,
+            replacement_model: ReplacementModel::Generational,
+            objectives: Vec::new(),
+            termination: Vec::new(),
+            hall_of_fame_size: 0,
+            hall_of_fame_opponents: false,
+            crossover_scheme: CrossoverScheme::Homologous,
+            indel: None,
+            decoding: Decoding::Unary,
+            novelty: None,
+            seed: None,
+            fitness_normalization: FitnessNormalization::Raw,
+            match_cache: false,
+            age_structure: None,
+            learning: None,
+            imitation: None,
+            neighbourhood: Neighbourhood::WellMixed,
+            group_selection: None,
         }
     }
 
+    /// NSGA-IIによる多目的進化を行うかどうか
+    pub fn is_multi_objective(&self) -> bool {
+        self.objectives.len() >= 2
+    }
+
+    /// DNA長が進化する（個体ごとに異なり得る）かどうか
+    pub fn is_variable_length(&self) -> bool {
+        self.indel.is_some() || self.crossover_scheme == CrossoverScheme::Messy
+    }
+
+    /// 設定を再現するコマンドラインオプションの列
+    ///
+    /// CLIで指定できない`rounds_per_generation`は含みません。
+    ///
+    /// # 戻り値
+    /// オプション名と値の組（値をとらないフラグの値は空文字列）
+    pub fn to_options(&self) -> Vec<(String, String)> {
+        let mut options = vec![
+            ("--generations", self.generations.to_string()),
+            ("--population", self.population.to_string()),
+            ("--mutation-rate", self.mutation_rate.to_string()),
+            ("--dna-length", self.dna_length.to_string()),
+            ("--report-interval", self.report_interval.to_string()),
+            ("--elite-size", self.elite_size.to_string()),
+            ("--diversity", self.diversity_scheme.to_string()),
+            ("--mutation-scheme", self.mutation_scheme.to_string()),
+            ("--replacement", self.replacement_model.to_string()),
+            ("--crossover", self.crossover_scheme.to_string()),
+            ("--decoding", self.decoding.to_string()),
+            (
+                "--fitness-normalization",
+                self.fitness_normalization.to_string(),
+            ),
+        ];
+        if let Some(indel) = &self.indel {
+            options.push(("--indel", indel.to_string()));
+        }
+        if let Some(seed) = self.seed {
+            options.push(("--seed", seed.to_string()));
+        }
+        if self.match_cache {
+            options.push(("--match-cache", String::new()));
+        }
+        if let Some(age_structure) = &self.age_structure {
+            options.push(("--age-structure", age_structure.to_string()));
+        }
+        if let Some(learning) = &self.learning {
+            options.push(("--learning", learning.to_string()));
+        }
+        if let Some(imitation) = &self.imitation {
+            options.push(("--imitation", imitation.to_string()));
+        }
+        if self.neighbourhood.is_structured() {
+            options.push(("--neighbourhood", self.neighbourhood.to_string()));
+        }
+        if let Some(group_selection) = &self.group_selection {
+            options.push(("--group-selection", group_selection.to_string()));
+        }
+        if let Some(novelty) = &self.novelty {
+            options.push((
+                "--novelty",
+                format!(
+                    "{}:{}:{}",
+                    novelty.neighbours, novelty.archive_threshold, novelty.novelty_weight
+                ),
+            ));
+        }
+        if !self.objectives.is_empty() {
+            let objectives: Vec<String> = self.objectives.iter().map(|o| o.to_string()).collect();
+            options.push(("--objectives", objectives.join(",")));
+        }
+        for criterion in &self.termination {
+            options.push(("--stop", criterion.to_string()));
+        }
+        if self.hall_of_fame_size > 0 {
+            let opponents = if self.hall_of_fame_opponents {
+                ":opponents"
+            } else {
+                ""
+            };
+            options.push((
+                "--hall-of-fame",
+                format!("{}{opponents}", self.hall_of_fame_size),
+            ));
+        }
+
+        options
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect()
+    }
+
+    /// 設定をオプションファイルの形式に変換
+    ///
+    /// 1行に1つのオプションを`--name value`の形式で書きます。
+    /// `#`で始まる行はコメントで、CLIの`--config`で読み込めます。
+    pub fn to_options_file(&self) -> String {
+        let mut contents = String::from("# ga-sim options (load with --config FILE)\n");
+        for (name, value) in self.to_options() {
+            contents += format!("{name} {value}").trim_end();
+            contents.push('\n');
+        }
+        contents
+    }
+
+    /// 設定をオプションファイルとして書き出す
+    ///
+    /// # エラー
+    /// ファイルの書き込みに失敗した場合
+    pub fn write_options_file<P: AsRef<Path>>(&self, path: P) -> GAResult<()> {
+        fs::write(path.as_ref(), self.to_options_file()).map_err(|e| {
+            GAError::OutputError(format!("Failed to write {}: {e}", path.as_ref().display()))
+        })
+    }
+
     pub fn validate(&self) -> Result<(), ConfigError> {
         if self.population == 0 {
             return Err(ConfigError::InvalidPopulation);
@@ -97,6 +389,146 @@ impl Config {
         if self.elite_size >= self.population {
             return Err(ConfigError::InvalidEliteSize);
         }
+        if self.diversity_scheme.validate().is_err() {
+            return Err(ConfigError::InvalidDiversityScheme);
+        }
+        if self.mutation_scheme.validate().is_err() {
+            return Err(ConfigError::InvalidMutationScheme);
+        }
+        if self.replacement_model.validate(self.population).is_err() {
+            return Err(ConfigError::InvalidReplacementModel);
+        }
+        if self.replacement_model != ReplacementModel::Generational
+            && matches!(
+                self.diversity_scheme,
+                DiversityScheme::DeterministicCrowding
+                    | DiversityScheme::RestrictedTournament { .. }
+                    | DiversityScheme::Speciation { .. }
+            )
+        {
+            return Err(ConfigError::IncompatibleReplacementModel);
+        }
+        if validate_objectives(&self.objectives).is_err() {
+            return Err(ConfigError::InvalidObjectives);
+        }
+        if self.is_multi_objective()
+            && (self.diversity_scheme != DiversityScheme::None
+                || self.replacement_model != ReplacementModel::Generational)
+        {
+            return Err(ConfigError::IncompatibleObjectives);
+        }
+        if self.termination.iter().any(|c| c.validate().is_err()) {
+            return Err(ConfigError::InvalidTermination);
+        }
+        if self.hall_of_fame_opponents && self.hall_of_fame_size == 0 {
+            return Err(ConfigError::InvalidHallOfFame);
+        }
+        if let Some(indel) = &self.indel {
+            if indel.validate().is_err()
+                || !(indel.min_length..=indel.max_length).contains(&self.dna_length)
+            {
+                return Err(ConfigError::InvalidIndelMutation);
+            }
+        }
+        if self.decoding.validate().is_err() {
+            return Err(ConfigError::InvalidDecoding);
+        }
+        if let Some(novelty) = &self.novelty {
+            if novelty.validate().is_err() {
+                return Err(ConfigError::InvalidNoveltySearch);
+            }
+            if self.replacement_model != ReplacementModel::Generational
+                || self.is_multi_objective()
+                || matches!(
+                    self.diversity_scheme,
+                    DiversityScheme::DeterministicCrowding
+                        | DiversityScheme::RestrictedTournament { .. }
+                )
+            {
+                return Err(ConfigError::IncompatibleNoveltySearch);
+            }
+        }
+        if self.fitness_normalization.is_relative()
+            && (self.replacement_model != ReplacementModel::Generational
+                || self.is_multi_objective()
+                || matches!(
+                    self.diversity_scheme,
+                    DiversityScheme::DeterministicCrowding
+                        | DiversityScheme::RestrictedTournament { .. }
+                ))
+        {
+            return Err(ConfigError::IncompatibleFitnessNormalization);
+        }
+        if let Some(age_structure) = &self.age_structure {
+            if age_structure.validate().is_err() {
+                return Err(ConfigError::InvalidAgeStructure);
+            }
+            if self.replacement_model != ReplacementModel::Generational
+                || self.is_multi_objective()
+                || self.diversity_scheme != DiversityScheme::None
+            {
+                return Err(ConfigError::IncompatibleAgeStructure);
+            }
+        }
+        if let Some(learning) = &self.learning {
+            if learning.validate().is_err() {
+                return Err(ConfigError::InvalidLearning);
+            }
+            if self.replacement_model != ReplacementModel::Generational
+                || matches!(
+                    self.diversity_scheme,
+                    DiversityScheme::DeterministicCrowding
+                        | DiversityScheme::RestrictedTournament { .. }
+                )
+            {
+                return Err(ConfigError::IncompatibleLearning);
+            }
+        }
+        if let Some(imitation) = &self.imitation {
+            if imitation.validate().is_err() {
+                return Err(ConfigError::InvalidImitation);
+            }
+            if self.replacement_model != ReplacementModel::Generational
+                || self.is_multi_objective()
+                || self.diversity_scheme != DiversityScheme::None
+                || self.age_structure.is_some()
+            {
+                return Err(ConfigError::IncompatibleImitation);
+            }
+        }
+        if self.neighbourhood.validate(self.population).is_err() {
+            return Err(ConfigError::InvalidNeighbourhood);
+        }
+        if self.neighbourhood.is_structured()
+            && (self.imitation.is_none() || self.learning.is_some())
+        {
+            return Err(ConfigError::IncompatibleNeighbourhood);
+        }
+        if let Some(group_selection) = &self.group_selection {
+            if group_selection.validate(self.population).is_err() {
+                return Err(ConfigError::InvalidGroupSelection);
+            }
+            if self.replacement_model != ReplacementModel::Generational
+                || self.is_multi_objective()
+                || self.diversity_scheme != DiversityScheme::None
+                || self.age_structure.is_some()
+                || self.learning.is_some()
+                || self.imitation.is_some()
+            {
+                return Err(ConfigError::IncompatibleGroupSelection);
+            }
+        }
+        if self.is_variable_length()
+            && (self.replacement_model != ReplacementModel::Generational
+                || self.is_multi_objective()
+                || matches!(
+                    self.diversity_scheme,
+                    DiversityScheme::DeterministicCrowding
+                        | DiversityScheme::RestrictedTournament { .. }
+                ))
+        {
+            return Err(ConfigError::IncompatibleVariableLength);
+        }
         Ok(())
     }
 }
@@ -113,6 +545,30 @@ pub enum ConfigError {
     InvalidMutationRate,
     InvalidDnaLength,
     InvalidEliteSize,
+    InvalidDiversityScheme,
+    InvalidMutationScheme,
+    InvalidReplacementModel,
+    IncompatibleReplacementModel,
+    InvalidObjectives,
+    IncompatibleObjectives,
+    InvalidTermination,
+    InvalidHallOfFame,
+    InvalidIndelMutation,
+    IncompatibleVariableLength,
+    InvalidDecoding,
+    InvalidNoveltySearch,
+    IncompatibleNoveltySearch,
+    IncompatibleFitnessNormalization,
+    InvalidAgeStructure,
+    IncompatibleAgeStructure,
+    InvalidLearning,
+    IncompatibleLearning,
+    InvalidImitation,
+    IncompatibleImitation,
+    InvalidNeighbourhood,
+    IncompatibleNeighbourhood,
+    InvalidGroupSelection,
+    IncompatibleGroupSelection,
 }
 
 impl fmt::Display for ConfigError {
@@ -126,6 +582,116 @@ impl fmt::Display for ConfigError {
             ConfigError::InvalidEliteSize => {
                 write!(f, "Elite size must be less than population size")
             }
+            ConfigError::InvalidDiversityScheme => {
+                write!(f, "Diversity scheme parameters are out of range")
+            }
+            ConfigError::InvalidMutationScheme => {
+                write!(f, "Mutation scheme parameters are out of range")
+            }
+            ConfigError::InvalidReplacementModel => {
+                write!(
+                    f,
+                    "Replacement model parameters are invalid for this population size"
+                )
+            }
+            ConfigError::IncompatibleReplacementModel => {
+                write!(
+                    f,
+                    "Crowding, RTR and speciation diversity schemes require the generational replacement model"
+                )
+            }
+            ConfigError::InvalidObjectives => write!(f, "Objectives must not contain duplicates"),
+            ConfigError::IncompatibleObjectives => {
+                write!(
+                    f,
+                    "Multi-objective evolution requires no diversity scheme and the generational replacement model"
+                )
+            }
+            ConfigError::InvalidTermination => {
+                write!(f, "Termination criterion parameters are out of range")
+            }
+            ConfigError::InvalidIndelMutation => {
+                write!(
+                    f,
+                    "Indel mutation parameters are out of range or exclude the initial DNA length"
+                )
+            }
+            ConfigError::IncompatibleVariableLength => {
+                write!(
+                    f,
+                    "Variable-length genomes require the generational replacement model, a single objective and no crowding or RTR"
+                )
+            }
+            ConfigError::InvalidDecoding => {
+                write!(f, "Lookup table length must be a power of two (at least 2)")
+            }
+            ConfigError::InvalidNoveltySearch => {
+                write!(f, "Novelty search parameters are out of range")
+            }
+            ConfigError::IncompatibleNoveltySearch => {
+                write!(
+                    f,
+                    "Novelty search requires the generational replacement model, a single objective and no crowding or RTR"
+                )
+            }
+            ConfigError::IncompatibleFitnessNormalization => {
+                write!(
+                    f,
+                    "Z-score and rank fitness normalization require the generational replacement model, a single objective and no crowding or RTR"
+                )
+            }
+            ConfigError::InvalidAgeStructure => {
+                write!(f, "Age structure parameters are out of range")
+            }
+            ConfigError::IncompatibleAgeStructure => {
+                write!(
+                    f,
+                    "Age structure requires the generational replacement model, a single objective and no diversity scheme"
+                )
+            }
+            ConfigError::InvalidLearning => write!(f, "Learning parameters are out of range"),
+            ConfigError::IncompatibleLearning => {
+                write!(
+                    f,
+                    "Learning requires the generational replacement model and no crowding or RTR"
+                )
+            }
+            ConfigError::InvalidImitation => {
+                write!(f, "Imitation rule parameters are out of range")
+            }
+            ConfigError::IncompatibleImitation => {
+                write!(
+                    f,
+                    "Imitation requires the generational replacement model, a single objective, no diversity scheme and no age structure"
+                )
+            }
+            ConfigError::InvalidNeighbourhood => {
+                write!(f, "Neighbourhood does not fit the population size")
+            }
+            ConfigError::IncompatibleNeighbourhood => {
+                write!(
+                    f,
+                    "A structured neighbourhood requires an imitation rule and no learning"
+                )
+            }
+            ConfigError::InvalidGroupSelection => {
+                write!(
+                    f,
+                    "Group count must divide the population into groups of at least 2 and the split probability must be between 0.0 and 1.0"
+                )
+            }
+            ConfigError::IncompatibleGroupSelection => {
+                write!(
+                    f,
+                    "Group selection requires the generational replacement model, a single objective and no diversity scheme, age structure, learning or imitation"
+                )
+            }
+            ConfigError::InvalidHallOfFame => {
+                write!(
+                    f,
+                    "Hall of fame opponents require a hall of fame size greater than 0"
+                )
+            }
         }
     }
 }
@@ -173,6 +739,97 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn diversity_scheme(mut self, scheme: DiversityScheme) -> Self {
+        self.config.diversity_scheme = scheme;
+        self
+    }
+
+    pub fn mutation_scheme(mut self, scheme: MutationScheme) -> Self {
+        self.config.mutation_scheme = scheme;
+        self
+    }
+
+    pub fn replacement_model(mut self, model: ReplacementModel) -> Self {
+        self.config.replacement_model = model;
+        self
+    }
+
+    pub fn objectives(mut self, objectives: Vec<Objective>) -> Self {
+        self.config.objectives = objectives;
+        self
+    }
+
+    pub fn termination(mut self, criteria: Vec<TerminationCriterion>) -> Self {
+        self.config.termination = criteria;
+        self
+    }
+
+    pub fn hall_of_fame(mut self, size: usize, opponents: bool) -> Self {
+        self.config.hall_of_fame_size = size;
+        self.config.hall_of_fame_opponents = opponents;
+        self
+    }
+
+    pub fn crossover_scheme(mut self, scheme: CrossoverScheme) -> Self {
+        self.config.crossover_scheme = scheme;
+        self
+    }
+
+    pub fn indel(mut self, indel: IndelMutation) -> Self {
+        self.config.indel = Some(indel);
+        self
+    }
+
+    pub fn decoding(mut self, decoding: Decoding) -> Self {
+        self.config.decoding = decoding;
+        self
+    }
+
+    pub fn novelty(mut self, novelty: NoveltySearch) -> Self {
+        self.config.novelty = Some(novelty);
+        self
+    }
+
+    pub fn fitness_normalization(mut self, normalization: FitnessNormalization) -> Self {
+        self.config.fitness_normalization = normalization;
+        self
+    }
+
+    pub fn match_cache(mut self, enabled: bool) -> Self {
+        self.config.match_cache = enabled;
+        self
+    }
+
+    pub fn age_structure(mut self, age_structure: AgeStructure) -> Self {
+        self.config.age_structure = Some(age_structure);
+        self
+    }
+
+    pub fn learning(mut self, learning: Learning) -> Self {
+        self.config.learning = Some(learning);
+        self
+    }
+
+    pub fn imitation(mut self, imitation: ImitationRule) -> Self {
+        self.config.imitation = Some(imitation);
+        self
+    }
+
+    pub fn neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.config.neighbourhood = neighbourhood;
+        self
+    }
+
+    pub fn group_selection(mut self, group_selection: GroupSelection) -> Self {
+        self.config.group_selection = Some(group_selection);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
     pub fn build(self) -> Result<Config, ConfigError> {
         self.config.validate()?;
         Ok(self.config)
@@ -4,8 +4,24 @@
 /// インターフェースを提供します。ユーザーがコマンドライン引数を通じて
 /// シミュレーションパラメータを指定できる機能を実装しています。
 use crate::core::errors::{GAError, GAResult};
+use crate::core::types::Objective;
+use crate::domain::tuning::{ParameterRange, Tuner, TuningMethod, TuningObjective};
+use crate::engine::aging::AgeStructure;
+use crate::engine::crossover::CrossoverScheme;
+use crate::engine::diversity::DiversityScheme;
+use crate::engine::dynamics::{ImitationRule, Neighbourhood};
+use crate::engine::genetic::ReplacementModel;
+use crate::engine::group_selection::GroupSelection;
+use crate::engine::learning::Learning;
+use crate::engine::multi_objective::parse_objectives;
+use crate::engine::mutation::{IndelMutation, MutationScheme};
+use crate::engine::novelty::NoveltySearch;
+use crate::engine::selection::FitnessNormalization;
+use crate::engine::termination::TerminationCriterion;
 use crate::infrastructure::config::ConfigBuilder;
+use crate::strategies::decoding::Decoding;
 use std::env;
+use std::fs;
 
 /// コマンドライン引数を表現する構造体
 ///
@@ -20,6 +36,33 @@ use std::env;
 /// * `dna_length` - DNA長
 /// * `report_interval` - レポート間隔
 /// * `elite_size` - エリートサイズ
+/// * `diversity` - 多様性維持スキーム
+/// * `mutation_scheme` - 突然変異率の制御スキーム
+/// * `replacement` - 世代交代（置換）モデル
+/// * `objectives` - 多目的最適化の目的関数
+/// * `termination` - 世代数以外の終了条件
+/// * `hall_of_fame` - 殿堂のサイズと、殿堂を対戦相手にするかどうか
+/// * `export_hall_of_fame` - 殿堂を書き出す戦略ファイルのパス
+/// * `crossover` - 交叉方式
+/// * `indel` - 挿入・欠失突然変異
+/// * `decoding` - 遺伝子型から表現型へのデコード方式
+/// * `novelty` - 新規性探索
+/// * `seed` - 乱数のシード
+/// * `fitness_normalization` - 選択前の適応度の正規化方式
+/// * `match_cache` - 決定的な対戦の結果のキャッシュ
+/// * `age_structure` - 齢構造モード
+/// * `learning` - 世代内の学習
+/// * `imitation` - 模倣ダイナミクスの更新規則
+/// * `neighbourhood` - 模倣ダイナミクスの近傍構造
+/// * `group_selection` - 集団選択
+/// * `tune` - ハイパーパラメータ探索の方式
+/// * `tune_parameters` - ハイパーパラメータ探索の探索空間
+/// * `tune_objective` - ハイパーパラメータ探索の目的関数
+/// * `tune_replicates` - ハイパーパラメータ探索の1点あたりの反復回数
+/// * `tune_output` - 最良の設定を書き出すオプションファイルのパス
+/// * `coevolution` - 2集団の共進化モード
+/// * `population_a` - 集団Aのみに適用するオプション（--a-*）
+/// * `population_b` - 集団Bのみに適用するオプション（--b-*）
 /// * `help` - ヘルプ表示フラグ
 pub struct CliArgs {
     /// 実行する世代数（--generations）
@@ -34,6 +77,60 @@ pub struct CliArgs {
     pub report_interval: Option<usize>,
     /// エリートサイズ（--elite-size）
     pub elite_size: Option<usize>,
+    /// 多様性維持スキーム（--diversity）
+    pub diversity: Option<DiversityScheme>,
+    /// 突然変異率の制御スキーム（--mutation-scheme）
+    pub mutation_scheme: Option<MutationScheme>,
+    /// 世代交代（置換）モデル（--replacement）
+    pub replacement: Option<ReplacementModel>,
+    /// 多目的最適化の目的関数（--objectives）
+    pub objectives: Option<Vec<Objective>>,
+    /// 世代数以外の終了条件（--stop、複数指定可）
+    pub termination: Option<Vec<TerminationCriterion>>,
+    /// 殿堂のサイズと、殿堂を対戦相手にするかどうか（--hall-of-fame）
+    pub hall_of_fame: Option<(usize, bool)>,
+    /// 殿堂を書き出す戦略ファイルのパス（--export-hall-of-fame）
+    pub export_hall_of_fame: Option<String>,
+    /// 交叉方式（--crossover）
+    pub crossover: Option<CrossoverScheme>,
+    /// 挿入・欠失突然変異（--indel）
+    pub indel: Option<IndelMutation>,
+    /// 遺伝子型から表現型へのデコード方式（--decoding）
+    pub decoding: Option<Decoding>,
+    /// 新規性探索（--novelty）
+    pub novelty: Option<NoveltySearch>,
+    /// 乱数のシード（--seed）
+    pub seed: Option<u64>,
+    /// 選択前の適応度の正規化方式（--fitness-normalization）
+    pub fitness_normalization: Option<FitnessNormalization>,
+    /// 決定的な対戦の結果をキャッシュするかどうか（--match-cache）
+    pub match_cache: bool,
+    /// 齢構造モード（--age-structure）
+    pub age_structure: Option<AgeStructure>,
+    /// 世代内の学習（--learning）
+    pub learning: Option<Learning>,
+    /// 模倣ダイナミクスの更新規則（--imitation）
+    pub imitation: Option<ImitationRule>,
+    /// 模倣ダイナミクスの近傍構造（--neighbourhood）
+    pub neighbourhood: Option<Neighbourhood>,
+    /// 集団選択（--group-selection）
+    pub group_selection: Option<GroupSelection>,
+    /// ハイパーパラメータ探索の方式（--tune）
+    pub tune: Option<TuningMethod>,
+    /// ハイパーパラメータ探索の探索空間（--tune-param、複数指定可）
+    pub tune_parameters: Option<Vec<ParameterRange>>,
+    /// ハイパーパラメータ探索の目的関数（--tune-objective）
+    pub tune_objective: Option<TuningObjective>,
+    /// ハイパーパラメータ探索の1点あたりの反復回数（--tune-replicates）
+    pub tune_replicates: Option<usize>,
+    /// 最良の設定を書き出すオプションファイルのパス（--tune-output）
+    pub tune_output: Option<String>,
+    /// 2集団の共進化モード（--coevolve）
+    pub coevolution: bool,
+    /// 集団Aのみに適用するオプション（--a-population など）
+    pub population_a: Option<Box<CliArgs>>,
+    /// 集団Bのみに適用するオプション（--b-population など）
+    pub population_b: Option<Box<CliArgs>>,
     /// ヘルプ表示フラグ（--help or -h）
     pub help: bool,
 }
@@ -53,6 +150,22 @@ impl CliArgs {
     /// * 必要な値が不足している場合
     pub fn parse() -> GAResult<Self> {
         let args: Vec<String> = env::args().collect();
+        Self::parse_args(&args)
+    }
+
+    /// 引数列（先頭はプログラム名）を解析してCliArgsを作成
+    ///
+    /// `--a-`・`--b-`で始まるオプションは接頭辞を外して集団A・B用の
+    /// CliArgsとして解析します。これらは`--coevolve`と併用する必要があります。
+    /// `--config FILE`はその位置にファイル内のオプションを展開するため、
+    /// 後に書いたオプションがファイルの値を上書きします。
+    ///
+    /// # エラー
+    /// `parse`と同様のエラーに加え、集団別オプションが不正な場合、
+    /// またはオプションファイルを読み込めない場合
+    pub fn parse_args(args: &[String]) -> GAResult<Self> {
+        let args = Self::expand_config_files(args)?;
+        let mut side_args: [Vec<String>; 2] = [vec![String::new()], vec![String::new()]];
         let mut cli_args = CliArgs {
             generations: None,
             population: None,
@@ -60,15 +173,68 @@ impl CliArgs {
             dna_length: None,
             report_interval: None,
             elite_size: None,
+            diversity: None,
+            mutation_scheme: None,
+            replacement: None,
+            objectives: None,
+            termination: None,
+            hall_of_fame: None,
+            export_hall_of_fame: None,
+            crossover: None,
+            indel: None,
+            decoding: None,
+            novelty: None,
+            seed: None,
+            fitness_normalization: None,
+            match_cache: false,
+            age_structure: None,
+            learning: None,
+            imitation: None,
+            neighbourhood: None,
+            group_selection: None,
+            tune: None,
+            tune_parameters: None,
+            tune_objective: None,
+            tune_replicates: None,
+            tune_output: None,
+            coevolution: false,
+            population_a: None,
+            population_b: None,
             help: false,
         };
 
         let mut i = 1;
         while i < args.len() {
+            let side = if args[i].starts_with("--a-") {
+                Some(0)
+            } else if args[i].starts_with("--b-") {
+                Some(1)
+            } else {
+                None
+            };
+            if let Some(side) = side {
+                if i + 1 >= args.len() {
+                    return Err(GAError::ValidationError(format!(
+                        "Missing value for {}",
+                        args[i]
+                    )));
+                }
+                side_args[side].push(format!("--{}", &args[i][4..]));
+                side_args[side].push(args[i + 1].clone());
+                i += 2;
+                continue;
+            }
+
             match args[i].as_str() {
                 "-h" | "--help" => {
                     cli_args.help = true;
                 }
+                "--coevolve" => {
+                    cli_args.coevolution = true;
+                }
+                "--match-cache" => {
+                    cli_args.match_cache = true;
+                }
                 "-g" | "--generations" => {
                     i += 1;
                     if i >= args.len() {
@@ -135,6 +301,224 @@ impl CliArgs {
                         GAError::ValidationError("Invalid elite size value".to_string())
                     })?);
                 }
+                "--diversity" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for diversity scheme".to_string(),
+                        ));
+                    }
+                    cli_args.diversity = Some(args[i].parse()?);
+                }
+                "--mutation-scheme" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for mutation scheme".to_string(),
+                        ));
+                    }
+                    cli_args.mutation_scheme = Some(args[i].parse()?);
+                }
+                "--replacement" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for replacement model".to_string(),
+                        ));
+                    }
+                    cli_args.replacement = Some(args[i].parse()?);
+                }
+                "--crossover" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for crossover scheme".to_string(),
+                        ));
+                    }
+                    cli_args.crossover = Some(args[i].parse()?);
+                }
+                "--indel" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for indel mutation".to_string(),
+                        ));
+                    }
+                    cli_args.indel = Some(args[i].parse()?);
+                }
+                "--decoding" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for decoding".to_string(),
+                        ));
+                    }
+                    cli_args.decoding = Some(args[i].parse()?);
+                }
+                "--novelty" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for novelty search".to_string(),
+                        ));
+                    }
+                    cli_args.novelty = Some(args[i].parse()?);
+                }
+                "--age-structure" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for age structure".to_string(),
+                        ));
+                    }
+                    cli_args.age_structure = Some(args[i].parse()?);
+                }
+                "--learning" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for learning".to_string(),
+                        ));
+                    }
+                    cli_args.learning = Some(args[i].parse()?);
+                }
+                "--imitation" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for imitation".to_string(),
+                        ));
+                    }
+                    cli_args.imitation = Some(args[i].parse()?);
+                }
+                "--neighbourhood" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for neighbourhood".to_string(),
+                        ));
+                    }
+                    cli_args.neighbourhood = Some(args[i].parse()?);
+                }
+                "--group-selection" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for group selection".to_string(),
+                        ));
+                    }
+                    cli_args.group_selection = Some(args[i].parse()?);
+                }
+                "--fitness-normalization" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for fitness normalization".to_string(),
+                        ));
+                    }
+                    cli_args.fitness_normalization = Some(args[i].parse()?);
+                }
+                "--seed" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for seed".to_string(),
+                        ));
+                    }
+                    cli_args.seed =
+                        Some(args[i].parse().map_err(|_| {
+                            GAError::ValidationError("Invalid seed value".to_string())
+                        })?);
+                }
+                "--tune" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for tuning method".to_string(),
+                        ));
+                    }
+                    cli_args.tune = Some(args[i].parse()?);
+                }
+                "--tune-param" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for tuned parameter".to_string(),
+                        ));
+                    }
+                    cli_args
+                        .tune_parameters
+                        .get_or_insert_with(Vec::new)
+                        .push(args[i].parse()?);
+                }
+                "--tune-objective" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for tuning objective".to_string(),
+                        ));
+                    }
+                    cli_args.tune_objective = Some(args[i].parse()?);
+                }
+                "--tune-replicates" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for tuning replicates".to_string(),
+                        ));
+                    }
+                    cli_args.tune_replicates = Some(args[i].parse().map_err(|_| {
+                        GAError::ValidationError("Invalid tuning replicates value".to_string())
+                    })?);
+                }
+                "--tune-output" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for tuning output path".to_string(),
+                        ));
+                    }
+                    cli_args.tune_output = Some(args[i].clone());
+                }
+                "--stop" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for termination criterion".to_string(),
+                        ));
+                    }
+                    cli_args
+                        .termination
+                        .get_or_insert_with(Vec::new)
+                        .push(args[i].parse()?);
+                }
+                "--hall-of-fame" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for hall of fame".to_string(),
+                        ));
+                    }
+                    cli_args.hall_of_fame = Some(Self::parse_hall_of_fame(&args[i])?);
+                }
+                "--export-hall-of-fame" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for hall of fame export path".to_string(),
+                        ));
+                    }
+                    cli_args.export_hall_of_fame = Some(args[i].clone());
+                }
+                "--objectives" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(GAError::ValidationError(
+                            "Missing value for objectives".to_string(),
+                        ));
+                    }
+                    cli_args.objectives = Some(parse_objectives(&args[i])?);
+                }
                 _ => {
                     return Err(GAError::ValidationError(format!(
                         "Unknown argument: {}",
@@ -145,12 +529,160 @@ impl CliArgs {
             i += 1;
         }
 
+        let [side_a, side_b] = side_args;
+        cli_args.population_a = Self::parse_side(&side_a)?;
+        cli_args.population_b = Self::parse_side(&side_b)?;
+        if !cli_args.coevolution
+            && (cli_args.population_a.is_some() || cli_args.population_b.is_some())
+        {
+            return Err(GAError::ValidationError(
+                "Population-specific options (--a-*, --b-*) require --coevolve".to_string(),
+            ));
+        }
+        if [&cli_args.population_a, &cli_args.population_b]
+            .iter()
+            .any(|side| side.as_ref().is_some_and(|side| side.decoding.is_some()))
+        {
+            return Err(GAError::ValidationError(
+                "Both populations share the decoding; use --decoding with --coevolve".to_string(),
+            ));
+        }
+        if cli_args.coevolution && cli_args.export_hall_of_fame.is_some() {
+            return Err(GAError::ValidationError(
+                "Use --a-export-hall-of-fame or --b-export-hall-of-fame with --coevolve"
+                    .to_string(),
+            ));
+        }
+        if cli_args.tune.is_none()
+            && (cli_args.tune_parameters.is_some()
+                || cli_args.tune_objective.is_some()
+                || cli_args.tune_replicates.is_some()
+                || cli_args.tune_output.is_some())
+        {
+            return Err(GAError::ValidationError(
+                "Tuning options (--tune-*) require --tune".to_string(),
+            ));
+        }
+        if cli_args.tune.is_some() && cli_args.coevolution {
+            return Err(GAError::ValidationError(
+                "--tune cannot be combined with --coevolve".to_string(),
+            ));
+        }
+
         Ok(cli_args)
     }
 
+    /// `--config FILE`をファイル内のオプションに置き換えた引数列を作成
+    ///
+    /// オプションファイルは空白区切りの引数を並べたもので、空行と`#`で始まる行は
+    /// 無視します（`Config::write_options_file`の出力を読み込めます）。
+    ///
+    /// # エラー
+    /// パスが指定されていない場合、ファイルを読み込めない場合、
+    /// またはファイル内に`--config`がある場合
+    fn expand_config_files(args: &[String]) -> GAResult<Vec<String>> {
+        let mut expanded = Vec::with_capacity(args.len());
+        let mut iter = args.iter();
+        expanded.extend(iter.next().cloned());
+
+        while let Some(arg) = iter.next() {
+            if arg != "--config" {
+                expanded.push(arg.clone());
+                continue;
+            }
+
+            let path = iter.next().ok_or_else(|| {
+                GAError::ValidationError("Missing value for config file".to_string())
+            })?;
+            let contents = fs::read_to_string(path).map_err(|e| {
+                GAError::ConfigurationFileError(format!("Failed to read {path}: {e}"))
+            })?;
+            for token in contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .flat_map(str::split_whitespace)
+            {
+                if token == "--config" {
+                    return Err(GAError::ConfigurationFileError(format!(
+                        "Nested --config in {path}"
+                    )));
+                }
+                expanded.push(token.to_string());
+            }
+        }
+
+        Ok(expanded)
+    }
+
+    /// 集団別オプション（接頭辞を外したもの）を解析
+    fn parse_side(args: &[String]) -> GAResult<Option<Box<CliArgs>>> {
+        if args.len() <= 1 {
+            return Ok(None);
+        }
+
+        let side = Self::parse_args(args)?;
+        if side.coevolution || side.population_a.is_some() || side.population_b.is_some() {
+            return Err(GAError::ValidationError(
+                "Invalid population-specific option".to_string(),
+            ));
+        }
+        Ok(Some(Box::new(side)))
+    }
+
+    /// `SIZE`または`SIZE:opponents`形式の殿堂の指定を解析
+    fn parse_hall_of_fame(value: &str) -> GAResult<(usize, bool)> {
+        let invalid = || GAError::ValidationError(format!("Invalid hall of fame value: {value}"));
+        let (size, opponents) = match value.split_once(':') {
+            Some((size, "opponents")) => (size, true),
+            Some(_) => return Err(invalid()),
+            None => (value, false),
+        };
+        Ok((size.parse().map_err(|_| invalid())?, opponents))
+    }
+
     pub fn to_config_builder(self) -> ConfigBuilder {
-        let mut builder = ConfigBuilder::new();
+        self.apply_to(ConfigBuilder::new())
+    }
+
+    /// ハイパーパラメータ探索を作成
+    ///
+    /// 探索対象以外のパラメータは他のオプションで指定した基準の設定を使用します。
+    ///
+    /// # エラー
+    /// `--tune`が指定されていない場合、基準の設定が不正な場合、
+    /// または探索空間が不正な場合
+    pub fn to_tuner(self) -> GAResult<Tuner> {
+        let method = self.tune.ok_or_else(|| {
+            GAError::ValidationError("Hyperparameter search requires --tune".to_string())
+        })?;
+        let base = self.apply_to(ConfigBuilder::new()).build()?;
 
+        let mut tuner = Tuner::new(base, self.tune_parameters.unwrap_or_default(), method)?
+            .with_objective(self.tune_objective.unwrap_or_default());
+        if let Some(replicates) = self.tune_replicates {
+            tuner = tuner.with_replicates(replicates);
+        }
+        Ok(tuner)
+    }
+
+    /// 共進化モード用に集団A・Bそれぞれの設定ビルダーを作成
+    ///
+    /// 共通オプションを両方に適用した後、集団別オプションで上書きします。
+    pub fn to_coevolution_builders(self) -> (ConfigBuilder, ConfigBuilder) {
+        let builder = |side: &Option<Box<CliArgs>>| {
+            let builder = self.apply_to(ConfigBuilder::new());
+            match side {
+                Some(side) => side.apply_to(builder),
+                None => builder,
+            }
+        };
+
+        (builder(&self.population_a), builder(&self.population_b))
+    }
+
+    /// 指定されたオプションをビルダーに適用
+    fn apply_to(&self, mut builder: ConfigBuilder) -> ConfigBuilder {
         if let Some(generations) = self.generations {
             builder = builder.generations(generations);
         }
@@ -169,6 +701,60 @@ impl CliArgs {
         if let Some(elite_size) = self.elite_size {
             builder = builder.elite_size(elite_size);
         }
+        if let Some(diversity) = self.diversity {
+            builder = builder.diversity_scheme(diversity);
+        }
+        if let Some(mutation_scheme) = self.mutation_scheme {
+            builder = builder.mutation_scheme(mutation_scheme);
+        }
+        if let Some(replacement) = self.replacement {
+            builder = builder.replacement_model(replacement);
+        }
+        if let Some(objectives) = &self.objectives {
+            builder = builder.objectives(objectives.clone());
+        }
+        if let Some(termination) = &self.termination {
+            builder = builder.termination(termination.clone());
+        }
+        if let Some(crossover) = self.crossover {
+            builder = builder.crossover_scheme(crossover);
+        }
+        if let Some(indel) = self.indel {
+            builder = builder.indel(indel);
+        }
+        if let Some(decoding) = &self.decoding {
+            builder = builder.decoding(decoding.clone());
+        }
+        if let Some(novelty) = self.novelty {
+            builder = builder.novelty(novelty);
+        }
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed);
+        }
+        if let Some(normalization) = self.fitness_normalization {
+            builder = builder.fitness_normalization(normalization);
+        }
+        if self.match_cache {
+            builder = builder.match_cache(true);
+        }
+        if let Some(age_structure) = self.age_structure {
+            builder = builder.age_structure(age_structure);
+        }
+        if let Some(learning) = self.learning {
+            builder = builder.learning(learning);
+        }
+        if let Some(imitation) = self.imitation {
+            builder = builder.imitation(imitation);
+        }
+        if let Some(neighbourhood) = self.neighbourhood {
+            builder = builder.neighbourhood(neighbourhood);
+        }
+        if let Some(group_selection) = self.group_selection {
+            builder = builder.group_selection(group_selection);
+        }
+        if let Some((size, opponents)) = self.hall_of_fame {
+            builder = builder.hall_of_fame(size, opponents);
+        }
 
         builder
     }
@@ -186,11 +772,553 @@ impl CliArgs {
         println!("    -d, --dna-length <NUM>       DNA string length [default: 6]");
         println!("    -r, --report-interval <NUM>  Report every N generations [default: 5000]");
         println!("    -e, --elite-size <NUM>       Number of elite individuals [default: 2]");
+        println!("        --diversity <SCHEME>     Diversity scheme: none, sharing[:RADIUS], crowding, rtr[:WINDOW],");
+        println!("                                 species[:THRESHOLD[:TARGET]] [default: none]");
+        println!("        --mutation-scheme <SCHEME>");
+        println!("                                 Mutation rate control: fixed, linear[:FINAL], exp[:DECAY],");
+        println!("                                 hyper[:RATE[:THRESHOLD]], one-fifth[:FACTOR], self-adaptive [default: fixed]");
+        println!("        --replacement <MODEL>    Replacement model: generational, steady:K[:worst|random|oldest|parent],");
+        println!(
+            "                                 plus:LAMBDA, comma:LAMBDA [default: generational]"
+        );
+        println!("        --crossover <SCHEME>     Crossover for unequal-length parents: homologous, messy [default: homologous]");
+        println!("        --indel <INS:DEL[:MIN[:MAX]]>");
+        println!("                                 Insertion/deletion rates per gene so DNA length evolves [default length range: 1-64]");
+        println!("        --decoding <DECODING>    Genotype-to-phenotype map: unary, binary, gray, lookup:TABLE [default: unary]");
+        println!("        --novelty <K[:THRESHOLD[:WEIGHT]]>");
+        println!("                                 Score by behavioural novelty (k nearest neighbours), optionally blended with points");
+        println!("        --fitness-normalization <MODE>");
+        println!("                                 Fitness used for selection: raw, interaction, round, zscore, rank [default: raw]");
+        println!("        --match-cache            Reuse outcomes of deterministic matches across pairs and generations");
+        println!(
+            "        --age-structure <lifespan:MAX[:MATURITY]|gompertz:BASE:GROWTH[:MATURITY]>"
+        );
+        println!("                                 Agents age, breed on lifetime payoff once mature and die by lifespan or mortality");
+        println!("        --learning <baldwin|lamarck[:RATE[:ASPIRATION]]>");
+        println!("                                 Learn cooperation from payoffs within a generation; lamarck writes it back to the genome");
+        println!("        --imitation <fermi[:BETA]|best|proportional>");
+        println!("                                 Copy strategies from neighbours by social learning instead of breeding");
+        println!("        --neighbourhood <well-mixed|ring:RADIUS|lattice:WIDTH>");
+        println!("                                 Who imitators play and copy from [default: well-mixed]");
+        println!("        --group-selection <groups:COUNT[:SPLIT]>");
+        println!("                                 Play within groups; groups split by total payoff (reports Price equation terms)");
+        println!("        --seed <NUM>             Random seed for reproducible runs (identical with or without --features parallel)");
+        println!("        --objectives <LIST>      Comma-separated objectives for NSGA-II: score, cooperation, complexity");
+        println!("        --stop <CRITERION>       Stop early: stagnation:N, diversity:D, dominance:F, target:P (mean payoff per match), time:SECS (repeatable)");
+        println!("        --hall-of-fame <SIZE[:opponents]>");
+        println!("                                 Keep the best SIZE genotypes; with :opponents, also evaluate against them");
+        println!("        --export-hall-of-fame <PATH>");
+        println!("                                 Write the hall of fame as a strategy file after the run");
+        println!(
+            "        --config <PATH>          Read options from a file (later options override it)"
+        );
+        println!("        --tune <METHOD>          Hyperparameter search: grid:STEPS, random:N, lhs:N, meta-ga:POP:GENS");
+        println!("        --tune-param <NAME=MIN..MAX>");
+        println!("                                 Tuned range: population, mutation-rate, dna-length, lambda (repeatable)");
+        println!("        --tune-objective <OBJECTIVE>");
+        println!("                                 payoff (mean payoff per match), cooperation[:THRESHOLD] (generations to reach it), diversity [default: payoff]");
+        println!("        --tune-replicates <NUM>  Simulations per search point [default: 3]");
+        println!(
+            "        --tune-output <PATH>     Write the best configuration as an options file"
+        );
+        println!(
+            "        --coevolve               Co-evolve two populations that only play each other"
+        );
+        println!("        --a-<OPTION> <VALUE>     Long option applied to population A only (with --coevolve)");
+        println!("        --b-<OPTION> <VALUE>     Long option applied to population B only (with --coevolve)");
         println!("    -h, --help                   Print this help message");
         println!();
         println!("EXAMPLES:");
         println!("    ga_prisoners_dilemma");
         println!("    ga_prisoners_dilemma -g 10000 -p 50 -m 0.05");
         println!("    ga_prisoners_dilemma --population 100 --mutation-rate 0.02");
+        println!("    ga_prisoners_dilemma --diversity sharing:0.25");
+        println!("    ga_prisoners_dilemma --diversity species:0.3:4 -p 50");
+        println!("    ga_prisoners_dilemma --mutation-scheme hyper:0.2:0.05");
+        println!("    ga_prisoners_dilemma --replacement steady:2:oldest");
+        println!("    ga_prisoners_dilemma --indel 0.01:0.01:2:16 --crossover messy");
+        println!("    ga_prisoners_dilemma --decoding gray --dna-length 8");
+        println!("    ga_prisoners_dilemma --novelty 10:0.05:0.5");
+        println!("    ga_prisoners_dilemma --fitness-normalization interaction -p 200");
+        println!("    ga_prisoners_dilemma --match-cache -p 200 -g 1000");
+        println!("    ga_prisoners_dilemma --age-structure lifespan:8:2");
+        println!("    ga_prisoners_dilemma --learning lamarck:0.2 -p 50");
+        println!("    ga_prisoners_dilemma --imitation fermi:2 --neighbourhood lattice:10 -p 100");
+        println!("    ga_prisoners_dilemma --group-selection groups:5:0.2 -p 50");
+        println!("    ga_prisoners_dilemma --seed 42 -g 1000");
+        println!("    ga_prisoners_dilemma --objectives score,cooperation");
+        println!("    ga_prisoners_dilemma --stop stagnation:500 --stop time:60");
+        println!(
+            "    ga_prisoners_dilemma --hall-of-fame 10:opponents --export-hall-of-fame best.txt"
+        );
+        println!("    ga_prisoners_dilemma --coevolve --a-population 30 --b-mutation-rate 0.05");
+        println!("    ga_prisoners_dilemma -g 500 --tune lhs:20 --tune-param mutation-rate=0.001..0.1 --tune-param population=10..50 --tune-output best.conf");
+        println!("    ga_prisoners_dilemma --config best.conf -g 5000");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{DEFAULT_MUTATION_RATE, DEFAULT_POPULATION};
+    use crate::engine::aging::Mortality;
+    use crate::engine::learning::Inheritance;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        std::iter::once("ga_prisoners_dilemma")
+            .chain(list.iter().copied())
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_population_specific_options() {
+        let cli = CliArgs::parse_args(&args(&[
+            "--coevolve",
+            "-g",
+            "10",
+            "--a-population",
+            "30",
+            "--b-mutation-rate",
+            "0.05",
+        ]))
+        .unwrap();
+        assert!(cli.coevolution);
+
+        let (a, b) = cli.to_coevolution_builders();
+        let (a, b) = (a.build().unwrap(), b.build().unwrap());
+        assert_eq!((a.generations, b.generations), (10, 10));
+        assert_eq!(a.population, 30);
+        assert_eq!(b.population, DEFAULT_POPULATION);
+        assert_eq!(b.mutation_rate, 0.05);
+        assert_eq!(a.mutation_rate, DEFAULT_MUTATION_RATE);
+    }
+
+    #[test]
+    fn test_population_specific_options_require_coevolve() {
+        assert!(CliArgs::parse_args(&args(&["--a-population", "30"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--coevolve", "--a-coevolve", "1"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--coevolve", "--b-population"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--coevolve", "--b-decoding", "gray"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--coevolve", "--decoding", "gray"])).is_ok());
+    }
+
+    #[test]
+    fn test_variable_length_options() {
+        let config = CliArgs::parse_args(&args(&[
+            "--indel",
+            "0.01:0.02:2:16",
+            "--crossover",
+            "messy",
+        ]))
+        .unwrap()
+        .to_config_builder()
+        .build()
+        .unwrap();
+        assert_eq!(config.crossover_scheme, CrossoverScheme::Messy);
+        assert_eq!(
+            config.indel,
+            Some(IndelMutation::new(0.01, 0.02).with_length_range(2, 16))
+        );
+        assert!(config.is_variable_length());
+
+        assert!(CliArgs::parse_args(&args(&["--crossover", "uniform"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--indel", "0.01:0.01:8:16"]))
+            .unwrap()
+            .to_config_builder()
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_hall_of_fame_options() {
+        let cli = CliArgs::parse_args(&args(&[
+            "--hall-of-fame",
+            "8:opponents",
+            "--export-hall-of-fame",
+            "best.txt",
+        ]))
+        .unwrap();
+        assert_eq!(cli.export_hall_of_fame.as_deref(), Some("best.txt"));
+        let config = cli.to_config_builder().build().unwrap();
+        assert_eq!(config.hall_of_fame_size, 8);
+        assert!(config.hall_of_fame_opponents);
+
+        assert!(CliArgs::parse_args(&args(&["--hall-of-fame", "8:rivals"])).is_err());
+        assert!(
+            CliArgs::parse_args(&args(&["--hall-of-fame", "0:opponents"]))
+                .unwrap()
+                .to_config_builder()
+                .build()
+                .is_err()
+        );
+        assert!(
+            CliArgs::parse_args(&args(&["--coevolve", "--export-hall-of-fame", "best.txt"]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_decoding_option() {
+        let config = CliArgs::parse_args(&args(&["--decoding", "lookup:0111"]))
+            .unwrap()
+            .to_config_builder()
+            .build()
+            .unwrap();
+        assert_eq!(config.decoding, Decoding::lookup_table("0111").unwrap());
+        assert_eq!(
+            CliArgs::parse_args(&args(&[]))
+                .unwrap()
+                .to_config_builder()
+                .build()
+                .unwrap()
+                .decoding,
+            Decoding::Unary
+        );
+
+        assert!(CliArgs::parse_args(&args(&["--decoding", "lookup:011"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--decoding"])).is_err());
+    }
+
+    #[test]
+    fn test_novelty_option() {
+        let config = CliArgs::parse_args(&args(&["--novelty", "10:0.1:0.5"]))
+            .unwrap()
+            .to_config_builder()
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.novelty,
+            Some(
+                NoveltySearch::new(10)
+                    .with_archive_threshold(0.1)
+                    .with_novelty_weight(0.5)
+            )
+        );
+
+        assert!(CliArgs::parse_args(&args(&["--novelty", "0"])).is_err());
+        assert!(
+            CliArgs::parse_args(&args(&["--novelty", "10", "--replacement", "plus:4"]))
+                .unwrap()
+                .to_config_builder()
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_age_structure_option() {
+        let config = CliArgs::parse_args(&args(&["--age-structure", "gompertz:0.05:0.2:3"]))
+            .unwrap()
+            .to_config_builder()
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.age_structure,
+            Some(
+                AgeStructure::new(Mortality::Gompertz {
+                    base: 0.05,
+                    growth: 0.2
+                })
+                .with_maturity(3)
+            )
+        );
+        assert!(config.to_options().contains(&(
+            "--age-structure".to_string(),
+            "gompertz:0.05:0.2:3".to_string()
+        )));
+
+        assert!(CliArgs::parse_args(&args(&["--age-structure", "lifespan:2:3"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--age-structure"])).is_err());
+        assert!(CliArgs::parse_args(&args(&[
+            "--age-structure",
+            "lifespan:5",
+            "--diversity",
+            "crowding"
+        ]))
+        .unwrap()
+        .to_config_builder()
+        .build()
+        .is_err());
+    }
+
+    #[test]
+    fn test_speciation_option() {
+        let config = CliArgs::parse_args(&args(&["--diversity", "species:0.25:4"]))
+            .unwrap()
+            .to_config_builder()
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.diversity_scheme,
+            DiversityScheme::Speciation {
+                threshold: 0.25,
+                target_species: 4
+            }
+        );
+        assert!(config
+            .to_options()
+            .contains(&("--diversity".to_string(), "species:0.25:4".to_string())));
+
+        assert!(CliArgs::parse_args(&args(&["--diversity", "species:2"])).is_err());
+        assert!(CliArgs::parse_args(&args(&[
+            "--diversity",
+            "species",
+            "--replacement",
+            "steady:2"
+        ]))
+        .unwrap()
+        .to_config_builder()
+        .build()
+        .is_err());
+    }
+
+    #[test]
+    fn test_learning_option() {
+        let config = CliArgs::parse_args(&args(&["--learning", "lamarck:0.2:2.5"]))
+            .unwrap()
+            .to_config_builder()
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.learning,
+            Some(
+                Learning::new(Inheritance::Lamarckian)
+                    .with_rate(0.2)
+                    .with_aspiration(2.5)
+            )
+        );
+        assert!(config
+            .to_options()
+            .contains(&("--learning".to_string(), "lamarck:0.2:2.5".to_string())));
+
+        assert!(CliArgs::parse_args(&args(&["--learning", "baldwin:0"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--learning"])).is_err());
+        assert!(
+            CliArgs::parse_args(&args(&["--learning", "baldwin", "--diversity", "crowding"]))
+                .unwrap()
+                .to_config_builder()
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_imitation_options() {
+        let config = CliArgs::parse_args(&args(&[
+            "--imitation",
+            "fermi:2",
+            "--neighbourhood",
+            "lattice:5",
+            "-p",
+            "25",
+        ]))
+        .unwrap()
+        .to_config_builder()
+        .build()
+        .unwrap();
+        assert_eq!(
+            config.imitation,
+            Some(ImitationRule::Fermi { intensity: 2.0 })
+        );
+        assert_eq!(config.neighbourhood, Neighbourhood::Lattice { width: 5 });
+        let options = config.to_options();
+        assert!(options.contains(&("--imitation".to_string(), "fermi:2".to_string())));
+        assert!(options.contains(&("--neighbourhood".to_string(), "lattice:5".to_string())));
+
+        assert!(CliArgs::parse_args(&args(&["--imitation", "fermi:-1"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--neighbourhood"])).is_err());
+        // 格子の幅は個体数を割り切る必要があり、構造化された近傍には模倣が必要
+        for invalid in [
+            &[
+                "--imitation",
+                "best",
+                "--neighbourhood",
+                "lattice:3",
+                "-p",
+                "20",
+            ][..],
+            &["--neighbourhood", "ring:2"],
+            &["--imitation", "proportional", "--replacement", "steady:2"],
+        ] {
+            assert!(CliArgs::parse_args(&args(invalid))
+                .unwrap()
+                .to_config_builder()
+                .build()
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_group_selection_option() {
+        let config = CliArgs::parse_args(&args(&["--group-selection", "groups:4:0.2"]))
+            .unwrap()
+            .to_config_builder()
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.group_selection,
+            Some(GroupSelection::new(4).with_split_probability(0.2))
+        );
+        assert!(config
+            .to_options()
+            .contains(&("--group-selection".to_string(), "groups:4:0.2".to_string())));
+
+        assert!(CliArgs::parse_args(&args(&["--group-selection", "groups"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--group-selection"])).is_err());
+        for invalid in [
+            &["--group-selection", "groups:3"][..],
+            &["--group-selection", "groups:2:2"],
+            &["--group-selection", "groups:2", "--imitation", "best"],
+        ] {
+            assert!(CliArgs::parse_args(&args(invalid))
+                .unwrap()
+                .to_config_builder()
+                .build()
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_tuning_options() {
+        let cli = CliArgs::parse_args(&args(&[
+            "-g",
+            "5",
+            "--tune",
+            "grid:2",
+            "--tune-param",
+            "population=4..8",
+            "--tune-param",
+            "mutation-rate=0.0..0.1",
+            "--tune-objective",
+            "cooperation:0.8",
+            "--tune-replicates",
+            "1",
+        ]))
+        .unwrap();
+        assert_eq!(cli.tune, Some(TuningMethod::Grid { steps: 2 }));
+        assert_eq!(cli.tune_parameters.as_ref().map(Vec::len), Some(2));
+        let report = cli.to_tuner().unwrap().run().unwrap();
+        assert_eq!(report.trials.len(), 4);
+
+        assert!(CliArgs::parse_args(&args(&["--tune-param", "population=4..8"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--tune", "grid:2", "--coevolve"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--tune", "grid:2"]))
+            .unwrap()
+            .to_tuner()
+            .is_err());
+    }
+
+    #[test]
+    fn test_config_file_round_trip() {
+        let config = CliArgs::parse_args(&args(&[
+            "-g",
+            "7",
+            "--population",
+            "12",
+            "--mutation-scheme",
+            "hyper:0.2:0.05",
+            "--decoding",
+            "gray",
+            "--novelty",
+            "5:0.1:0.5",
+            "--stop",
+            "stagnation:3",
+            "--hall-of-fame",
+            "4:opponents",
+        ]))
+        .unwrap()
+        .to_config_builder()
+        .build()
+        .unwrap();
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        config.write_options_file(file.path()).unwrap();
+        let path = file.path().to_str().unwrap();
+
+        let loaded = CliArgs::parse_args(&args(&["--config", path]))
+            .unwrap()
+            .to_config_builder()
+            .build()
+            .unwrap();
+        assert_eq!(loaded.to_options(), config.to_options());
+
+        // 後に書いたオプションがファイルの値を上書きする
+        let overridden = CliArgs::parse_args(&args(&["--config", path, "-g", "9"]))
+            .unwrap()
+            .to_config_builder()
+            .build()
+            .unwrap();
+        assert_eq!(overridden.generations, 9);
+        assert_eq!(overridden.population, 12);
+
+        assert!(CliArgs::parse_args(&args(&["--config", "/nonexistent/options"])).is_err());
+        assert!(CliArgs::parse_args(&args(&["--config"])).is_err());
+    }
+
+    #[test]
+    fn test_fitness_normalization_option() {
+        let config = CliArgs::parse_args(&args(&["--fitness-normalization", "zscore"]))
+            .unwrap()
+            .to_config_builder()
+            .build()
+            .unwrap();
+        assert_eq!(config.fitness_normalization, FitnessNormalization::ZScore);
+
+        assert!(CliArgs::parse_args(&args(&["--fitness-normalization", "mean"])).is_err());
+        assert!(CliArgs::parse_args(&args(&[
+            "--fitness-normalization",
+            "rank",
+            "--replacement",
+            "steady:2"
+        ]))
+        .unwrap()
+        .to_config_builder()
+        .build()
+        .is_err());
+        assert!(CliArgs::parse_args(&args(&[
+            "--fitness-normalization",
+            "interaction",
+            "--replacement",
+            "steady:2"
+        ]))
+        .unwrap()
+        .to_config_builder()
+        .build()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_match_cache_option() {
+        let config = CliArgs::parse_args(&args(&["--match-cache"]))
+            .unwrap()
+            .to_config_builder()
+            .build()
+            .unwrap();
+        assert!(config.match_cache);
+        assert!(config.to_options_file().contains("--match-cache\n"));
+        assert!(!crate::infrastructure::config::Config::default().match_cache);
+    }
+
+    #[test]
+    fn test_seed_option() {
+        let run = || {
+            let config = CliArgs::parse_args(&args(&["--seed", "7", "-g", "30", "-p", "8"]))
+                .unwrap()
+                .to_config_builder()
+                .build()
+                .unwrap();
+            crate::domain::simulation::Simulation::new(config)
+                .unwrap()
+                .quiet()
+                .run()
+                .unwrap()
+        };
+        let (first, second) = (run(), run());
+        assert_eq!(first.config.seed, Some(7));
+        assert_eq!(first.payoff_history, second.payoff_history);
+        assert_eq!(first.final_result.dna_list, second.final_result.dna_list);
+
+        assert!(CliArgs::parse_args(&args(&["--seed", "-1"])).is_err());
     }
 }
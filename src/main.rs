@@ -4,7 +4,9 @@
 ///
 /// 使用例: `cargo run -- --generations 1000 --population 50 --mutation-rate 0.02`
 use ga_prisoners_dilemma::core::errors::GAResult;
+use ga_prisoners_dilemma::domain::coevolution::CoevolutionSimulation;
 use ga_prisoners_dilemma::domain::simulation::Simulation;
+use ga_prisoners_dilemma::engine::hall_of_fame::HallOfFame;
 use ga_prisoners_dilemma::interface::cli::CliArgs;
 use std::process;
 
@@ -27,12 +29,50 @@ fn run() -> GAResult<()> {
         return Ok(());
     }
 
+    // 探索モードでは設定を変えながらシミュレーションを繰り返し、最良の設定を書き出す
+    if args.tune.is_some() {
+        let output = args.tune_output.clone();
+        let report = args.to_tuner()?.run()?;
+        report.print_table();
+
+        if let (Some(path), Some(best)) = (output, report.best()) {
+            best.config.write_options_file(&path)?;
+            println!("\nBest configuration written to {path}");
+        }
+        return Ok(());
+    }
+
+    // 共進化モードでは集団ごとの設定で2集団を進化させる
+    if args.coevolution {
+        let exports = [&args.population_a, &args.population_b].map(|side| {
+            side.as_ref()
+                .and_then(|side| side.export_hall_of_fame.clone())
+        });
+        let (builder_a, builder_b) = args.to_coevolution_builders();
+        let simulation = CoevolutionSimulation::new(builder_a.build()?, builder_b.build()?)?;
+        let result = simulation.run()?;
+
+        for (population, path) in result.populations.iter().zip(exports) {
+            if let Some(path) = path {
+                HallOfFame::with_entries(population.hall_of_fame.clone())
+                    .write_strategy_file(path)?;
+            }
+        }
+        return Ok(());
+    }
+
     // 設定を構築
+    let export_path = args.export_hall_of_fame.clone();
     let config = args.to_config_builder().build()?;
 
     // シミュレーションを作成・実行
     let simulation = Simulation::new(config)?;
-    let _result = simulation.run()?;
+    let result = simulation.run()?;
+
+    // 殿堂を戦略ファイルとして書き出す
+    if let Some(path) = export_path {
+        HallOfFame::with_entries(result.hall_of_fame).write_strategy_file(path)?;
+    }
 
     Ok(())
 }
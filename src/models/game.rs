@@ -1,7 +1,14 @@
 use super::model::{BaseModel, Model, Points};
+use crate::core::parallel::{derive_seed, map_indices, stream_rng};
+use crate::engine::learning::Learning;
 use crate::ga::algorithm::GA;
-use crate::strategies::utils::StrategyOperation;
+use crate::strategies::cache::{cached_payoffs, MatchCache};
+use crate::strategies::utils::{calculate_payoff, Choice, StrategyOperation};
 use rand::{thread_rng, Rng};
+use std::sync::Arc;
+
+/// 対戦の乱数列を導出するときの系列番号
+const MATCH_STREAM: u64 = 0;
 
 pub trait GameOperation<T, U>
 where
@@ -17,6 +24,15 @@ where
     fn play_round(&mut self);
 }
 
+/// 1世代分の対戦を行うゲーム
+///
+/// 各ラウンドの全ての組の対戦は、ゲームのシード・ラウンド番号・組の添字から
+/// 導出した専用の乱数生成器で評価するため、`parallel`機能で並列に評価しても
+/// 同じシードからは同じ結果になります。
+/// 対戦結果のキャッシュを設定すると、決定的な組の対戦結果を世代をまたいで再利用します。
+/// 学習を設定すると、各個体はラウンドごとに受け取った利得で協力確率を更新します。
+/// 対戦する組を設定すると（構造化された近傍や集団選択）、総当たりの代わりに
+/// その組だけを対戦させます。
 pub struct Game<T: BaseModel, U: StrategyOperation<T>> {
     agents: Vec<Box<T>>,
     mutation_rate: f64,
@@ -24,6 +40,46 @@ pub struct Game<T: BaseModel, U: StrategyOperation<T>> {
     dna_length: usize,
     rounds_per_generation: usize,
     strategy: U,
+    seed: u64,
+    round: u64,
+    match_cache: Option<Arc<MatchCache>>,
+    learning: Option<Learning>,
+    pairs: Option<Vec<(usize, usize)>>,
+}
+
+impl<T, U> Game<T, U>
+where
+    T: BaseModel,
+    U: StrategyOperation<T>,
+{
+    /// 対戦と次世代の生成に使う乱数のシードを設定
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self.round = 0;
+        self
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// 決定的な組の対戦結果を記憶・再利用するキャッシュを設定
+    pub fn with_match_cache(mut self, cache: Arc<MatchCache>) -> Self {
+        self.match_cache = Some(cache);
+        self
+    }
+
+    /// 対戦の利得から協力確率を学習させる
+    pub fn with_learning(mut self, learning: Learning) -> Self {
+        self.learning = Some(learning);
+        self
+    }
+
+    /// 総当たりの代わりに対戦させる個体の添字の組`(i, j)`を設定
+    pub fn with_pairs(mut self, pairs: Vec<(usize, usize)>) -> Self {
+        self.pairs = Some(pairs);
+        self
+    }
 }
 
 impl<T, U> GameOperation<T, U> for Game<T, U>
@@ -69,21 +125,95 @@ where
             population: self.population,
             num_games: self.rounds_per_generation,
             dna_length: self.dna_length,
+            seed: self.seed,
         })
     }
 
+    /// 全ての組（対戦する組を設定している場合はその組）を1回ずつ対戦させる
+    ///
+    /// 組ごとの利得を先に（`parallel`機能では並列に）計算し、
+    /// 各個体のポイントへの加算は後からまとめて行います。
+    /// 学習を設定している場合、全ての組の行動はラウンド開始時の協力確率で決め、
+    /// 協力確率の更新は組の順に後からまとめて行います。
     fn play_round(&mut self) {
-        for i in 0..self.agents.len() {
-            for j in (i + 1)..self.agents.len() {
-                let (updated_i, updated_j) =
-                    self.strategy.play_match(&*self.agents[i], &*self.agents[j]);
-                self.agents[i] = Box::new(updated_i);
-                self.agents[j] = Box::new(updated_j);
+        let n = self.agents.len();
+        let pairs: Vec<(usize, usize)> = self.pairs.clone().unwrap_or_else(|| {
+            (0..n)
+                .flat_map(|i| ((i + 1)..n).map(move |j| (i, j)))
+                .collect()
+        });
+        let round_seed = derive_seed(self.seed, &[MATCH_STREAM, self.round]);
+        self.round += 1;
+
+        let agents = &self.agents;
+        let strategy = &self.strategy;
+        let payoffs = match self.learning {
+            Some(learning) => {
+                let choices = map_indices(pairs.len(), |k| {
+                    let (i, j) = pairs[k];
+                    let mut rng = stream_rng(round_seed, &[i as u64, j as u64]);
+                    strategy.match_choices(&*agents[i], &*agents[j], &mut rng)
+                });
+                self.learn(&learning, &pairs, &choices)
+            }
+            None => {
+                let cache = self.match_cache.as_deref();
+                map_indices(pairs.len(), |k| {
+                    let (i, j) = pairs[k];
+                    let mut rng = stream_rng(round_seed, &[i as u64, j as u64]);
+                    cached_payoffs(cache, strategy, &*agents[i], &*agents[j], &mut rng)
+                })
             }
+        };
+
+        // 累積ポイントは飽和加算し、長時間の実行でもオーバーフローさせない
+        let mut totals: Vec<Points> = vec![0; n];
+        for (&(i, j), (points_i, points_j)) in pairs.iter().zip(payoffs) {
+            totals[i] = totals[i].saturating_add(points_i);
+            totals[j] = totals[j].saturating_add(points_j);
+        }
+        for (agent, total) in self.agents.iter_mut().zip(totals) {
+            **agent = agent.with_points(agent.get_points().saturating_add(total));
         }
     }
 }
 
+impl<T, U> Game<T, U>
+where
+    T: Model,
+    U: StrategyOperation<T>,
+{
+    /// 1ラウンドの全ての組の行動から利得を求め、各個体の協力確率を更新
+    ///
+    /// # 戻り値
+    /// 組ごとの両者の利得
+    fn learn(
+        &mut self,
+        learning: &Learning,
+        pairs: &[(usize, usize)],
+        choices: &[(Choice, Choice)],
+    ) -> Vec<(Points, Points)> {
+        pairs
+            .iter()
+            .zip(choices)
+            .map(|(&(i, j), (choice_i, choice_j))| {
+                let payoff_i = calculate_payoff(choice_i, choice_j);
+                let payoff_j = calculate_payoff(choice_j, choice_i);
+                for (index, choice, payoff) in [(i, choice_i, payoff_i), (j, choice_j, payoff_j)] {
+                    let agent = &self.agents[index];
+                    let probability = learning.reinforce(
+                        self.strategy.cooperation(agent),
+                        *choice == Choice::Cooperate,
+                        payoff,
+                    );
+                    *self.agents[index] = agent.with_learned_cooperation(probability);
+                }
+                (payoff_i, payoff_j)
+            })
+            .collect()
+    }
+}
+
 pub fn new_game<T, U>(
     population: usize,
     mutation_rate: f64,
@@ -106,6 +236,11 @@ where
         dna_length,
         rounds_per_generation,
         strategy,
+        seed: thread_rng().gen(),
+        round: 0,
+        match_cache: None,
+        learning: None,
+        pairs: None,
     }
 }
 
@@ -128,6 +263,11 @@ where
         dna_length,
         rounds_per_generation,
         strategy,
+        seed: thread_rng().gen(),
+        round: 0,
+        match_cache: None,
+        learning: None,
+        pairs: None,
     }
 }
 
@@ -143,8 +283,13 @@ fn game_creation_test() {
     use crate::models::model::Agent;
     use crate::strategies::utils::RouletteSelectionStrategy;
 
-    let game =
-        new_game::<Agent, RouletteSelectionStrategy>(10, 0.1, 6, 6, RouletteSelectionStrategy {});
+    let game = new_game::<Agent, RouletteSelectionStrategy>(
+        10,
+        0.1,
+        6,
+        6,
+        RouletteSelectionStrategy::default(),
+    );
 
     assert_eq!(game.get_population(), 10);
     for dna in game.get_dna_list() {
@@ -161,20 +306,35 @@ fn play_round_test() {
         Box::new(Agent {
             id: 1,
             points: 0,
-            dna: "11111111".to_string(),
+            dna: "11111111".to_string().into(),
+            genome: "11111111".parse().unwrap(),
             active: true,
+            mutation_rate: None,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
         }),
         Box::new(Agent {
             id: 2,
             points: 0,
-            dna: "11111111".to_string(),
+            dna: "11111111".to_string().into(),
+            genome: "11111111".parse().unwrap(),
             active: true,
+            mutation_rate: None,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
         }),
         Box::new(Agent {
             id: 3,
             points: 0,
-            dna: "11111111".to_string(),
+            dna: "11111111".to_string().into(),
+            genome: "11111111".parse().unwrap(),
             active: true,
+            mutation_rate: None,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
         }),
     ];
 
@@ -184,7 +344,12 @@ fn play_round_test() {
         agents,
         dna_length: 8,
         rounds_per_generation: 1,
-        strategy: RouletteSelectionStrategy {},
+        strategy: RouletteSelectionStrategy::default(),
+        seed: 0,
+        round: 0,
+        match_cache: None,
+        learning: None,
+        pairs: None,
     };
 
     game.play_round();
@@ -193,3 +358,96 @@ fn play_round_test() {
     assert_eq!(game.agents[1].get_points(), 6);
     assert_eq!(game.agents[2].get_points(), 6);
 }
+
+#[test]
+fn play_round_with_pairs_test() {
+    use crate::models::model::Agent;
+    use crate::strategies::utils::RouletteSelectionStrategy;
+
+    // 組を設定すると、その組以外の個体とは対戦しない
+    let agents: Vec<Box<Agent>> = (0..3)
+        .map(|i| Box::new(Agent::new(i, "11111111".to_string())))
+        .collect();
+    let mut game = generate_next_game(3, 0.0, 2, 8, agents, RouletteSelectionStrategy::default())
+        .with_pairs(vec![(0, 1)]);
+    game.run_generation().unwrap();
+
+    assert_eq!(game.get_points_list(), vec![6, 6, 0]);
+}
+
+#[test]
+fn play_round_matches_sequential_evaluation_test() {
+    use crate::models::model::Agent;
+    use crate::strategies::utils::RouletteSelectionStrategy;
+
+    let dna = ["110100", "000111", "101010", "111110", "010000"];
+    let agents: Vec<Box<Agent>> = dna
+        .iter()
+        .enumerate()
+        .map(|(i, dna)| Box::new(Agent::new(i as u64, dna.to_string())))
+        .collect();
+    let strategy = RouletteSelectionStrategy::default();
+
+    let mut game = generate_next_game(5, 0.0, 3, 6, agents.clone(), strategy.clone()).with_seed(42);
+    for _ in 0..3 {
+        game.play_round();
+    }
+
+    // 組ごとの乱数列で1組ずつ順に対戦させた結果と一致する
+    let mut expected: Vec<Points> = vec![0; agents.len()];
+    for round in 0..3 {
+        let round_seed = derive_seed(42, &[MATCH_STREAM, round]);
+        for i in 0..agents.len() {
+            for j in (i + 1)..agents.len() {
+                let mut rng = stream_rng(round_seed, &[i as u64, j as u64]);
+                let (points_i, points_j) =
+                    strategy.match_payoffs(&*agents[i], &*agents[j], &mut rng);
+                expected[i] += points_i;
+                expected[j] += points_j;
+            }
+        }
+    }
+    assert_eq!(game.get_points_list(), expected);
+
+    let mut replay = generate_next_game(5, 0.0, 3, 6, agents, strategy).with_seed(42);
+    replay.run_generation().unwrap();
+    assert_eq!(replay.get_points_list(), expected);
+}
+
+#[test]
+fn learning_game_updates_cooperation_test() {
+    use crate::engine::learning::{Inheritance, Learning};
+    use crate::models::model::Agent;
+    use crate::strategies::utils::RouletteSelectionStrategy;
+
+    // 全員が裏切る個体群では相互裏切り（1 < 期待水準2）が裏切りを弱める
+    let agents: Vec<Box<Agent>> = (0..4)
+        .map(|i| Box::new(Agent::new(i, "0000".to_string())))
+        .collect();
+    let learning = Learning::new(Inheritance::Baldwinian).with_rate(0.5);
+    let mut game = generate_next_game(
+        4,
+        0.0,
+        5,
+        4,
+        agents.clone(),
+        RouletteSelectionStrategy::default(),
+    )
+    .with_seed(7)
+    .with_learning(learning);
+    game.run_generation().unwrap();
+
+    for agent in &game.agents {
+        let learned = agent.learned_cooperation().unwrap();
+        assert!(learned > 0.0 && learned <= 1.0);
+        // 遺伝子型は学習で変化しない
+        assert_eq!(agent.get_dna_binary(), "0000");
+    }
+
+    // 同じシードでは同じ結果になる
+    let mut replay = generate_next_game(4, 0.0, 5, 4, agents, RouletteSelectionStrategy::default())
+        .with_seed(7)
+        .with_learning(learning);
+    replay.run_generation().unwrap();
+    assert_eq!(replay.get_points_list(), game.get_points_list());
+}
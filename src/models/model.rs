@@ -1,12 +1,18 @@
+use crate::core::genome::BitGenome;
+use crate::core::traits::Genome;
+use crate::engine::mutation::{self_adapt_rate, self_adaptation_tau};
 use core::num::ParseIntError;
 use rand::Rng;
+use std::sync::OnceLock;
 
 pub type Dna = String;
 pub type AgentId = u64;
 pub type Points = u64;
 
-pub trait BaseModel: Clone {
+pub trait BaseModel: Clone + Send + Sync {
     fn mutation(&self, mutation_rate: f64) -> Self;
+    /// 与えられた乱数生成器で突然変異を適用
+    fn mutation_with_rng<R: Rng>(&self, mutation_rate: f64, rng: &mut R) -> Self;
     fn crossover(&self, other: &Self, crossing_point: usize) -> Self;
     fn with_points(&self, points: Points) -> Self;
     fn get_choice(&self) -> Result<u32, ParseIntError>;
@@ -19,14 +25,146 @@ pub trait BaseModel: Clone {
 
 pub trait Model: BaseModel {
     fn get_dna_binary(&self) -> &str;
+
+    /// 対戦で表現型を読むための二値の遺伝子型
+    fn genome_bits(&self) -> &BitGenome;
+
+    /// 世代内の学習で変化した協力確率（学習していない場合は`None`）
+    fn learned_cooperation(&self) -> Option<f64> {
+        None
+    }
+
+    /// 学習した協力確率を持つ個体を返す（学習を保持しないモデルでは変化しない）
+    fn with_learned_cooperation(&self, _probability: f64) -> Self {
+        self.clone()
+    }
 }
 
+/// 遺伝子型`G`を持つエージェント
+///
+/// 既定の遺伝子型は二値の`BitGenome`で、囚人のジレンマの対戦（`BaseModel`）は
+/// 二値の遺伝子型でのみ使用できます。その他の遺伝子型（`RealGenome`など）でも
+/// コアトレイトを通じて個体群・選択・交叉を利用できます。
 #[derive(Clone, Debug)]
-pub struct Agent {
+pub struct Agent<G: Genome = BitGenome> {
     pub(crate) id: AgentId,
     pub(crate) points: Points,
-    pub(crate) dna: Dna,
+    /// レポート用の文字列表現（`genome`と常に同じ内容、初めて参照したときに生成する）
+    pub(crate) dna: OnceLock<Dna>,
+    /// 対戦・交叉・突然変異で使用する遺伝子型
+    pub(crate) genome: G,
     pub(crate) active: bool,
+    /// 自己適応型の突然変異率遺伝子（自己適応モード以外では`None`）
+    pub(crate) mutation_rate: Option<f64>,
+    /// 生き延びた世代数（齢構造モード以外では常に0）
+    pub(crate) age: u32,
+    /// 生涯に獲得した選択用のポイントの合計（齢構造モード以外では常に0）
+    pub(crate) lifetime_points: Points,
+    /// この世代に学習した協力確率（学習モード以外、またはまだ対戦していない場合は`None`）
+    pub(crate) learned: Option<f64>,
+}
+
+impl Agent {
+    /// 自己適応型の突然変異率遺伝子を持つエージェントを作成
+    pub fn with_mutation_rate_gene(id: AgentId, dna: Dna, mutation_rate: f64) -> Self {
+        Self {
+            mutation_rate: Some(mutation_rate),
+            ..<Self as BaseModel>::new(id, dna)
+        }
+    }
+
+    /// 学習した協力確率を書き戻した遺伝子型を持つ個体（ラマルク型の学習）
+    ///
+    /// 年齢やポイントなどの個体の状態は保ったまま遺伝子型を`dna`に置き換え、
+    /// 学習した協力確率は消去します。
+    pub(crate) fn with_acquired_dna(&self, dna: Dna) -> Self {
+        Agent {
+            genome: BitGenome::from_dna_lossy(&dna),
+            dna: OnceLock::from(dna),
+            learned: None,
+            ..self.clone()
+        }
+    }
+}
+
+impl<G: Genome> Agent<G> {
+    /// 遺伝子型からエージェントを作成
+    pub fn from_genome(id: AgentId, genome: G) -> Self {
+        Self {
+            id,
+            points: 0,
+            dna: OnceLock::new(),
+            genome,
+            active: true,
+            mutation_rate: None,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
+        }
+    }
+
+    /// 自己適応型の突然変異率遺伝子を取得
+    pub fn mutation_rate_gene(&self) -> Option<f64> {
+        self.mutation_rate
+    }
+
+    /// 生き延びた世代数
+    pub fn age(&self) -> u32 {
+        self.age
+    }
+
+    /// 生涯に獲得した選択用のポイントの合計
+    pub fn lifetime_points(&self) -> Points {
+        self.lifetime_points
+    }
+
+    /// 保持している遺伝子型
+    pub fn genome(&self) -> &G {
+        &self.genome
+    }
+
+    /// 遺伝子型の文字列表現（初回の参照時に生成して保持する）
+    fn dna_string(&self) -> &Dna {
+        self.dna.get_or_init(|| self.genome.to_string())
+    }
+
+    /// 遺伝子型から子を作成（文字列表現は参照されるまで生成しない）
+    fn child(&self, genome: G, mutation_rate: Option<f64>) -> Self {
+        Agent {
+            id: self.id,
+            points: 0,
+            dna: OnceLock::new(),
+            genome,
+            active: true,
+            mutation_rate,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
+        }
+    }
+
+    fn crossover_genome(&self, other: &Self, point: usize) -> Self {
+        let mutation_rate = match (self.mutation_rate, other.mutation_rate) {
+            (Some(a), Some(b)) => Some((a + b) / 2.0),
+            (a, b) => a.or(b),
+        };
+        self.child(self.genome.crossover(&other.genome, point), mutation_rate)
+    }
+
+    /// 突然変異を適用
+    ///
+    /// 突然変異率遺伝子を持つ場合は、引数の率の代わりに遺伝子を自己適応させた
+    /// 値を使用し、その値を子の遺伝子として引き継ぎます。
+    fn mutate_genome<R: Rng>(&self, mutation_rate: f64, rng: &mut R) -> Self {
+        let gene = self.mutation_rate.map(|rate| {
+            let tau = self_adaptation_tau(self.genome.len());
+            self_adapt_rate(rate, tau, rng)
+        });
+        let mutation_rate = gene.unwrap_or(mutation_rate);
+
+        let genome = self.genome.mutate(mutation_rate, rng);
+        self.child(genome, gene)
+    }
 }
 
 impl BaseModel for Agent {
@@ -35,83 +173,172 @@ impl BaseModel for Agent {
     }
 
     fn get_choice(&self) -> Result<u32, ParseIntError> {
-        u32::from_str_radix(&self.dna, 2)
+        // 32ビットに収まる場合は文字列を解析せずにゲノムから直接求める
+        match self.genome.to_u64() {
+            Some(value) if !self.genome.is_empty() && value <= u32::MAX as u64 => Ok(value as u32),
+            _ => u32::from_str_radix(self.dna_string(), 2),
+        }
     }
 
     fn with_points(&self, points: Points) -> Agent {
         Agent {
             points,
-            id: self.id,
-            dna: self.dna.clone(),
-            active: self.active,
+            ..self.clone()
         }
     }
 
     fn crossover(&self, other: &Agent, crossing_point: usize) -> Agent {
-        let head = &self.dna[..crossing_point.min(self.dna.len())];
-        let tail = &other.dna[crossing_point.min(other.dna.len())..];
-
-        Agent {
-            id: self.id,
-            points: 0,
-            dna: format!("{head}{tail}"),
-            active: true,
-        }
+        self.crossover_genome(other, crossing_point)
     }
 
     fn mutation(&self, mutation_rate: f64) -> Agent {
-        let new_dna: String = self
-            .dna
-            .chars()
-            .map(|c| mutate_bit(c, mutation_rate))
-            .collect();
+        self.mutate_genome(mutation_rate, &mut rand::thread_rng())
+    }
 
-        Agent {
-            id: self.id,
-            points: 0,
-            dna: new_dna,
-            active: true,
-        }
+    fn mutation_with_rng<R: Rng>(&self, mutation_rate: f64, rng: &mut R) -> Agent {
+        self.mutate_genome(mutation_rate, rng)
     }
 
     fn get_dna_length(&self) -> usize {
-        self.dna.len()
+        self.genome.len()
     }
 
     fn get_dna_sum(&self) -> u64 {
-        self.dna.chars().filter(|&c| c == '1').count() as u64
+        self.genome.count_ones()
     }
 
     fn get_dna(&self) -> &str {
-        &self.dna
+        self.dna_string()
     }
 
     fn new(id: AgentId, dna: Dna) -> Self {
         Self {
             id,
             points: 0,
-            dna,
+            genome: BitGenome::from_dna_lossy(&dna),
+            dna: OnceLock::from(dna),
             active: true,
+            mutation_rate: None,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
         }
     }
 }
 
 impl Model for Agent {
     fn get_dna_binary(&self) -> &str {
-        &self.dna
+        self.dna_string()
+    }
+
+    fn genome_bits(&self) -> &BitGenome {
+        &self.genome
+    }
+
+    fn learned_cooperation(&self) -> Option<f64> {
+        self.learned
+    }
+
+    fn with_learned_cooperation(&self, probability: f64) -> Agent {
+        Agent {
+            learned: Some(probability),
+            ..self.clone()
+        }
+    }
+}
+
+impl<G: Genome> crate::core::traits::BaseEntity for Agent<G> {
+    fn id(&self) -> AgentId {
+        self.id
+    }
+}
+
+impl<G: Genome> crate::core::traits::GeneticOperations for Agent<G> {
+    fn crossover(&self, other: &Self, point: usize) -> Self {
+        self.crossover_genome(other, point)
+    }
+
+    fn mutate_with_rng<R: Rng>(&self, rate: f64, rng: &mut R) -> Self {
+        self.mutate_genome(rate, rng)
+    }
+
+    fn fitness(&self) -> crate::core::types::Fitness {
+        self.points
+    }
+}
+
+impl<G: Genome> crate::core::traits::DnaOperations for Agent<G> {
+    fn dna(&self) -> &Dna {
+        self.dna_string()
+    }
+
+    fn dna_length(&self) -> usize {
+        self.genome.len()
+    }
+
+    fn dna_sum(&self) -> u64 {
+        self.genome.gene_sum().round() as u64
+    }
+
+    fn dna_binary(&self) -> &str {
+        self.dna_string()
+    }
+
+    fn differing_genes(&self, other: &Self) -> usize {
+        self.genome.distance(&other.genome).round() as usize
+    }
+}
+
+impl<G: Genome> crate::core::traits::Agent for Agent<G> {
+    fn points(&self) -> Points {
+        self.points
+    }
+
+    fn with_points(&self, points: Points) -> Self {
+        Agent {
+            points,
+            ..self.clone()
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn activate(&mut self) {
+        self.active = true;
+    }
+
+    fn deactivate(&mut self) {
+        self.active = false;
+    }
+}
+
+impl<G: Genome> crate::core::traits::GenomeOperations for Agent<G> {
+    type Genome = G;
+
+    fn genome(&self) -> &G {
+        &self.genome
+    }
+
+    fn with_genome(&self, genome: G) -> Self {
+        self.child(genome, self.mutation_rate)
     }
 }
 
-fn mutate_bit(bit: char, mutation_rate: f64) -> char {
-    let mut rng = rand::thread_rng();
-    if rng.gen::<f64>() < mutation_rate {
-        match bit {
-            '0' => '1',
-            '1' => '0',
-            _ => bit,
+impl crate::core::traits::VariableLengthDna for Agent {
+    fn with_dna(&self, dna: Dna) -> Self {
+        Agent {
+            id: self.id,
+            points: 0,
+            genome: BitGenome::from_dna_lossy(&dna),
+            dna: OnceLock::from(dna),
+            active: true,
+            mutation_rate: self.mutation_rate,
+            age: 0,
+            lifetime_points: 0,
+            learned: None,
         }
-    } else {
-        bit
     }
 }
 
@@ -130,3 +357,51 @@ fn dna_operation_test() {
     m1 = m1.mutation(0.2);
     assert_eq!(8, m1.get_dna_binary().len());
 }
+
+#[test]
+fn lazy_dna_string_test() {
+    let m1: Agent = BaseModel::new(1, "11110000".to_string());
+    let m2: Agent = BaseModel::new(2, "00001111".to_string());
+
+    // 子の文字列表現は参照されるまで生成しない
+    let child = m1.crossover(&m2, 2);
+    assert!(child.dna.get().is_none());
+    assert_eq!("11001111", child.get_dna());
+    assert_eq!(Some(&"11001111".to_string()), child.dna.get());
+    assert_eq!("11001111", child.clone().get_dna_binary());
+}
+
+#[test]
+fn mutation_rate_gene_test() {
+    let plain: Agent = BaseModel::new(1, "1010".to_string());
+    let adaptive = Agent::with_mutation_rate_gene(2, "0101".to_string(), 0.1);
+    assert_eq!(plain.mutation(0.1).mutation_rate_gene(), None);
+
+    let child = BaseModel::crossover(&adaptive, &plain, 2);
+    assert_eq!(child.mutation_rate_gene(), Some(0.1));
+
+    let other = Agent::with_mutation_rate_gene(3, "1111".to_string(), 0.3);
+    let child = BaseModel::crossover(&adaptive, &other, 2);
+    assert!((child.mutation_rate_gene().unwrap() - 0.2).abs() < 1e-12);
+
+    let mutated = child.mutation(0.0);
+    assert!(mutated.mutation_rate_gene().is_some());
+}
+
+#[test]
+fn real_genome_agent_test() {
+    use crate::core::genome::RealGenome;
+    use crate::core::traits::{DnaOperations, GeneticOperations, GenomeOperations};
+
+    let a = Agent::from_genome(1, RealGenome::new(vec![0.25, 0.75]).unwrap());
+    let b = Agent::from_genome(2, RealGenome::new(vec![1.0, 0.0]).unwrap());
+    assert_eq!(a.dna(), "0.25,0.75");
+    assert_eq!(a.dna_sum(), 1);
+    assert_eq!(a.differing_genes(&b), 2);
+
+    let child = GeneticOperations::crossover(&a, &b, 1);
+    assert_eq!(child.genome().genes(), &[0.25, 0.0]);
+    assert_eq!(child.dna(), "0.25,0");
+    assert_eq!(GeneticOperations::mutate(&child, 0.0).dna(), "0.25,0");
+    assert_eq!(a.with_genome(b.genome().clone()).dna(), "1,0");
+}
@@ -0,0 +1,395 @@
+/// 決定的な対戦の結果のキャッシュ
+///
+/// 戦略が決定的な場合、同じ遺伝子型の組の対戦は常に同じ結果になります。
+/// 対戦結果を（戦略の設定, 遺伝子型, 遺伝子型）をキーとして記憶し、
+/// 同じ世代の別の組や後の世代で再利用します。
+/// 検索はキーのハッシュ値で行い、DNA文字列や遺伝子型を複製しません。
+/// 記憶した結果はハッシュ値で選んだシャードに分けて置くため、並列評価でも
+/// 別の組の検索どうしが同じロックを待つことはまれです。
+/// 記憶する組の数には上限があり、シャードが上限に達すると、そのシャードの
+/// 結果をすべて捨てて記憶し直します。
+///
+/// 乱数を使う組（`StrategyOperation::is_deterministic`がfalse）は
+/// キャッシュを使わずに毎回対戦させるため、結果はキャッシュの有無で変わりません。
+use crate::core::genome::BitGenome;
+use crate::models::model::{Model, Points};
+use crate::strategies::utils::StrategyOperation;
+use rand::Rng;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// シャードの数
+const SHARDS: usize = 16;
+
+/// 既定で記憶する組の数の上限
+pub const DEFAULT_CACHE_CAPACITY: usize = 1 << 16;
+
+/// 記憶した1組の対戦結果: 戦略の設定、順序をそろえた遺伝子型の組、両者の利得
+///
+/// ハッシュ値が衝突しても別の組の結果を返さないよう、キーそのものも保持します。
+#[derive(Debug)]
+struct MatchEntry {
+    settings: String,
+    first: BitGenome,
+    second: BitGenome,
+    payoffs: (Points, Points),
+}
+
+impl MatchEntry {
+    fn matches(&self, settings: &str, first: &BitGenome, second: &BitGenome) -> bool {
+        self.settings == settings && self.first == *first && self.second == *second
+    }
+}
+
+/// 1つのシャードに記憶した結果
+///
+/// # フィールド
+/// * `entries` - ハッシュ値ごとの記憶した結果
+/// * `len` - 記憶している組の数
+#[derive(Debug, Default)]
+struct Shard {
+    entries: HashMap<u64, Vec<MatchEntry>>,
+    len: usize,
+}
+
+/// 対戦結果のキャッシュ
+///
+/// 内部で排他制御するため、`parallel`機能で並列に評価する対戦からも共有できます。
+#[derive(Debug)]
+pub struct MatchCache {
+    shards: [Mutex<Shard>; SHARDS],
+    shard_capacity: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    bypassed: AtomicU64,
+    evictions: AtomicU64,
+}
+
+/// キャッシュの利用統計
+///
+/// # フィールド
+/// * `hits` - キャッシュした結果を再利用した対戦の数
+/// * `misses` - 決定的だがキャッシュになく、対戦させて記憶した数
+/// * `bypassed` - 乱数を使うためキャッシュを使わなかった対戦の数
+/// * `evictions` - 上限に達してシャードを空にした回数
+/// * `entries` - 記憶している遺伝子型の組の数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MatchCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub bypassed: u64,
+    pub evictions: u64,
+    pub entries: usize,
+}
+
+impl MatchCacheStats {
+    /// 全対戦に占めるキャッシュ再利用の割合（対戦がなければ0）
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses + self.bypassed;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+impl fmt::Display for MatchCacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {} bypassed ({:.1}% hit rate, {} entries, {} evictions)",
+            self.hits,
+            self.misses,
+            self.bypassed,
+            self.hit_rate() * 100.0,
+            self.entries,
+            self.evictions
+        )
+    }
+}
+
+impl Default for MatchCache {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CACHE_CAPACITY)
+    }
+}
+
+impl MatchCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 記憶する組の数の上限を指定して作成
+    ///
+    /// 上限はシャードごとに均等に割り当てます（各シャード最低1組）。
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            shards: Default::default(),
+            shard_capacity: capacity.div_ceil(SHARDS).max(1),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            bypassed: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    /// 記憶する組の数の上限
+    pub fn capacity(&self) -> usize {
+        self.shard_capacity * SHARDS
+    }
+
+    /// 1回の対戦での両者の利得を、可能ならキャッシュから取得
+    ///
+    /// 決定的な組はキャッシュを引き、なければ対戦させて記憶します。
+    /// 乱数を使う組は`rng`で毎回対戦させます。
+    ///
+    /// # 引数
+    /// * `strategy` - 対戦に使う戦略
+    /// * `agent1` - 1体目の個体
+    /// * `agent2` - 2体目の個体
+    /// * `rng` - 乱数を使う組の対戦に使う乱数生成器
+    ///
+    /// # 戻り値
+    /// `agent1`と`agent2`の利得
+    pub fn payoffs<T, U, R>(
+        &self,
+        strategy: &U,
+        agent1: &T,
+        agent2: &T,
+        rng: &mut R,
+    ) -> (Points, Points)
+    where
+        T: Model,
+        U: StrategyOperation<T>,
+        R: Rng,
+    {
+        if !strategy.is_deterministic(agent1, agent2) {
+            self.bypassed.fetch_add(1, Ordering::Relaxed);
+            return strategy.match_payoffs(agent1, agent2, rng);
+        }
+
+        let (genome1, genome2) = (agent1.genome_bits(), agent2.genome_bits());
+        let swapped = (genome1.len(), genome1.words()) > (genome2.len(), genome2.words());
+        let (first, second) = if swapped {
+            (genome2, genome1)
+        } else {
+            (genome1, genome2)
+        };
+        let order = |(first, second): (Points, Points)| {
+            if swapped {
+                (second, first)
+            } else {
+                (first, second)
+            }
+        };
+
+        let settings = strategy.settings_key();
+        let mut hasher = DefaultHasher::new();
+        (settings, first, second).hash(&mut hasher);
+        let hash = hasher.finish();
+        let shard = &self.shards[hash as usize % SHARDS];
+
+        let cached = Self::lock(shard).entries.get(&hash).and_then(|entries| {
+            entries
+                .iter()
+                .find(|entry| entry.matches(settings, first, second))
+                .map(|entry| entry.payoffs)
+        });
+        if let Some(payoffs) = cached {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return order(payoffs);
+        }
+
+        // 対戦はロックの外で行う（並列評価で同じ組が重複しても結果は同じ）
+        let payoffs = strategy.match_payoffs(agent1, agent2, rng);
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let mut shard = Self::lock(shard);
+        let known = shard.entries.get(&hash).is_some_and(|entries| {
+            entries
+                .iter()
+                .any(|entry| entry.matches(settings, first, second))
+        });
+        if !known {
+            if shard.len >= self.shard_capacity {
+                shard.entries.clear();
+                shard.len = 0;
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+            shard.entries.entry(hash).or_default().push(MatchEntry {
+                settings: settings.to_string(),
+                first: first.clone(),
+                second: second.clone(),
+                payoffs: order(payoffs),
+            });
+            shard.len += 1;
+        }
+        payoffs
+    }
+
+    /// これまでの利用統計
+    pub fn stats(&self) -> MatchCacheStats {
+        MatchCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            bypassed: self.bypassed.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            entries: self.shards.iter().map(|shard| Self::lock(shard).len).sum(),
+        }
+    }
+
+    fn lock(shard: &Mutex<Shard>) -> MutexGuard<'_, Shard> {
+        // 記憶している結果は常に完全なので、他のスレッドのパニック後も使い続けられる
+        shard
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// 1回の対戦での両者の利得（キャッシュがあれば経由する）
+pub fn cached_payoffs<T, U, R>(
+    cache: Option<&MatchCache>,
+    strategy: &U,
+    agent1: &T,
+    agent2: &T,
+    rng: &mut R,
+) -> (Points, Points)
+where
+    T: Model,
+    U: StrategyOperation<T>,
+    R: Rng,
+{
+    match cache {
+        Some(cache) => cache.payoffs(strategy, agent1, agent2, rng),
+        None => strategy.match_payoffs(agent1, agent2, rng),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::{Agent, BaseModel};
+    use crate::strategies::decoding::Decoding;
+    use crate::strategies::utils::{RouletteSelectionStrategy, ThresholdSelectionStrategy};
+
+    #[test]
+    fn test_deterministic_matches_are_reused_in_either_order() {
+        let cache = MatchCache::new();
+        let strategy = ThresholdSelectionStrategy::default();
+        let cooperator = Agent::new(0, "0111".to_string());
+        let defector = Agent::new(1, "1000".to_string());
+        let mut rng = rand::thread_rng();
+
+        let first = cache.payoffs(&strategy, &cooperator, &defector, &mut rng);
+        let second = cache.payoffs(&strategy, &defector, &cooperator, &mut rng);
+        assert_eq!(first, (0, 5));
+        assert_eq!(second, (5, 0));
+        assert_eq!(
+            cache.stats(),
+            MatchCacheStats {
+                hits: 1,
+                misses: 1,
+                bypassed: 0,
+                evictions: 0,
+                entries: 1,
+            }
+        );
+        assert_eq!(cache.stats().hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_many_pairs_are_spread_over_shards() {
+        let cache = MatchCache::new();
+        let strategy = ThresholdSelectionStrategy::default();
+        let agents: Vec<Agent> = (0..16).map(|i| Agent::new(i, format!("{i:06b}"))).collect();
+        let mut rng = rand::thread_rng();
+
+        let play = |rng: &mut _| -> Vec<(Points, Points)> {
+            agents
+                .iter()
+                .flat_map(|a| agents.iter().map(move |b| (a, b)))
+                .map(|(a, b)| cache.payoffs(&strategy, a, b, rng))
+                .collect()
+        };
+        let first = play(&mut rng);
+        let second = play(&mut rng);
+        assert_eq!(first, second);
+
+        // 順序をそろえた組（自分自身との組を含む）ごとに1つ記憶する
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 16 * 17 / 2);
+        assert_eq!(stats.misses, 16 * 17 / 2);
+        assert_eq!(stats.hits, 2 * 16 * 16 - stats.misses);
+    }
+
+    #[test]
+    fn test_entries_stay_within_capacity() {
+        let cache = MatchCache::with_capacity(32);
+        let strategy = ThresholdSelectionStrategy::default();
+        let agents: Vec<Agent> = (0..16).map(|i| Agent::new(i, format!("{i:06b}"))).collect();
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..2 {
+            for a in &agents {
+                for b in &agents {
+                    assert_eq!(
+                        cache.payoffs(&strategy, a, b, &mut rng),
+                        strategy.match_payoffs(a, b, &mut rng)
+                    );
+                    assert!(cache.stats().entries <= cache.capacity());
+                }
+            }
+        }
+
+        // 136組は上限を超えるため、シャードを空にして記憶し直している
+        let stats = cache.stats();
+        assert_eq!(cache.capacity(), 32);
+        assert!(stats.evictions > 0);
+        assert!(stats.misses > 16 * 17 / 2);
+        assert_eq!(stats.hits + stats.misses, 2 * 16 * 16);
+    }
+
+    #[test]
+    fn test_stochastic_matches_bypass_the_cache() {
+        let cache = MatchCache::new();
+        let strategy = RouletteSelectionStrategy::default();
+        let mixed = Agent::new(0, "0110".to_string());
+        let defector = Agent::new(1, "0000".to_string());
+        let cooperator = Agent::new(2, "1111".to_string());
+        let mut rng = rand::thread_rng();
+
+        cache.payoffs(&strategy, &mixed, &defector, &mut rng);
+        cache.payoffs(&strategy, &mixed, &defector, &mut rng);
+        // 協力確率が0か1の個体どうしは乱数によらない
+        assert_eq!(
+            cache.payoffs(&strategy, &defector, &cooperator, &mut rng),
+            (5, 0)
+        );
+        assert_eq!(
+            cache.payoffs(&strategy, &cooperator, &defector, &mut rng),
+            (0, 5)
+        );
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.bypassed), (1, 1, 2));
+    }
+
+    #[test]
+    fn test_settings_are_part_of_the_key() {
+        let cache = MatchCache::new();
+        let binary = ThresholdSelectionStrategy::default();
+        let unary = ThresholdSelectionStrategy::with_decoding(Decoding::Unary);
+        let agent1 = Agent::new(0, "1000".to_string());
+        let agent2 = Agent::new(1, "0001".to_string());
+        let mut rng = rand::thread_rng();
+
+        // 2進数では1000は裏切り・0001は協力、単進数ではどちらも協力
+        assert_eq!(cache.payoffs(&binary, &agent1, &agent2, &mut rng), (5, 0));
+        assert_eq!(cache.payoffs(&unary, &agent1, &agent2, &mut rng), (3, 3));
+        assert_eq!(cache.stats().entries, 2);
+    }
+}
@@ -0,0 +1,440 @@
+/// 遺伝子型から表現型への変換（デコード）
+///
+/// 戦略は「表現型の値をどう行動に結びつけるか」（しきい値で決める、確率として使う）を
+/// 決め、デコーダは「DNAをどう表現型の値に写すか」を決めます。両者を分けることで、
+/// 同じ戦略のまま遺伝子型と表現型の対応（1ビットの変化が表現型をどれだけ動かすか）を
+/// 比較できます。
+use crate::core::errors::{GAError, GAResult};
+use crate::core::genome::BitGenome;
+use crate::models::model::Model;
+use crate::strategies::utils::cooperation_probability;
+use std::fmt;
+use std::str::FromStr;
+
+/// DNAを0.0-1.0の表現型の値に写すデコード方式
+///
+/// 文字列表現は`binary`、`gray`、`unary`、`lookup:TABLE`（例: `lookup:0111`）です。
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Decoding {
+    /// DNAを先頭を最上位ビットとする2進小数として読む
+    ///
+    /// 値の近い表現型同士でも多くのビットが異なることがあり（ハミングの崖）、
+    /// 例えば`0111`と`1000`は隣り合う値ですが4ビット異なります。
+    Binary,
+    /// DNAをグレイコードとして読み、2進数に戻してから2進小数とする
+    ///
+    /// 隣り合う値が常に1ビット違いになるため、ハミングの崖がありません。
+    Gray,
+    /// '1'の数の割合（位置によらない）
+    ///
+    /// 1ビットの変化は常に表現型を`1/L`だけ動かします。
+    #[default]
+    Unary,
+    /// DNAを表の索引幅ごとに区切り、各区間を表で0/1に写した値の平均
+    ///
+    /// 表の長さが2^kのとき、DNAを先頭からkビットずつ区切って索引とします。
+    /// 余った末尾のビットは無視します。冗長な（中立な変異を含む）対応を作れます。
+    LookupTable(Vec<bool>),
+}
+
+impl Decoding {
+    /// 表の文字列（'0'/'1'の並び）から索引表デコードを作成
+    ///
+    /// # エラー
+    /// 表の長さが2以上の2の冪でない場合、または'0'/'1'以外の文字を含む場合
+    pub fn lookup_table(table: &str) -> GAResult<Self> {
+        let entries = table
+            .chars()
+            .map(|c| match c {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                _ => Err(GAError::ValidationError(format!(
+                    "Lookup table must contain only 0 and 1: {table}"
+                ))),
+            })
+            .collect::<GAResult<Vec<bool>>>()?;
+        let decoding = Decoding::LookupTable(entries);
+        decoding.validate()?;
+        Ok(decoding)
+    }
+
+    /// パラメータの妥当性を検証
+    ///
+    /// # エラー
+    /// 索引表の長さが2以上の2の冪でない場合
+    pub fn validate(&self) -> GAResult<()> {
+        match self {
+            Decoding::LookupTable(table) if table.len() < 2 || !table.len().is_power_of_two() => {
+                Err(GAError::ValidationError(format!(
+                    "Lookup table length must be a power of two (at least 2), got {}",
+                    table.len()
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// DNAを0.0-1.0の表現型の値に変換
+    ///
+    /// 空のDNAは0.0になります。
+    pub fn decode(&self, dna: &str) -> f64 {
+        self.decode_genome(&BitGenome::from_dna_lossy(dna))
+    }
+
+    /// ゲノムを0.0-1.0の表現型の値に変換
+    ///
+    /// 文字列を経由せずワード列から直接計算し、メモリを確保しません。
+    /// 空のゲノムは0.0になります。
+    pub fn decode_genome(&self, genome: &BitGenome) -> f64 {
+        if genome.is_empty() {
+            return 0.0;
+        }
+
+        match self {
+            Decoding::Binary => binary_fraction(genome.words().iter().copied()),
+            Decoding::Gray => {
+                // 各ビットを先頭からそのビットまでの排他的論理和に置き換えると2進数になる
+                let last = genome.words().len() - 1;
+                let unused = genome.words().len() * u64::BITS as usize - genome.len();
+                let mut parity = 0u64;
+                binary_fraction(genome.words().iter().enumerate().map(|(k, &word)| {
+                    let mut binary = prefix_xor(word) ^ parity;
+                    parity = if binary >> 63 == 1 { u64::MAX } else { 0 };
+                    if k == last {
+                        binary &= u64::MAX >> unused;
+                    }
+                    binary
+                }))
+            }
+            Decoding::Unary => cooperation_probability(genome.count_ones(), genome.len()),
+            Decoding::LookupTable(table) => {
+                let width = (table.len().trailing_zeros() as usize).max(1);
+                let count = genome.len() / width;
+                if count == 0 {
+                    return 0.0;
+                }
+                let ones = (0..count)
+                    .filter(|chunk| {
+                        let index = (chunk * width..(chunk + 1) * width)
+                            .fold(0, |acc, i| acc << 1 | genome.get(i) as usize);
+                        table[index]
+                    })
+                    .count();
+                ones as f64 / count as f64
+            }
+        }
+    }
+
+    /// 個体の表現型の値
+    ///
+    /// 個体が保持するゲノムのワード列から直接計算し、DNA文字列は走査しません。
+    pub fn phenotype<T: Model>(&self, agent: &T) -> f64 {
+        self.decode_genome(agent.genome_bits())
+    }
+
+    /// 各遺伝子を1つ反転したときの表現型の変化量
+    ///
+    /// 遺伝子型と表現型の対応の局所性を調べるために使用します。
+    /// 結果の`i`番目は`i`番目の遺伝子を反転したときの変化量の絶対値です。
+    pub fn flip_effects(&self, dna: &str) -> Vec<f64> {
+        let original = self.decode(dna);
+        let mut bytes = dna.as_bytes().to_vec();
+        (0..bytes.len())
+            .map(|i| {
+                bytes[i] = if bytes[i] == b'1' { b'0' } else { b'1' };
+                let flipped = self.decode(std::str::from_utf8(&bytes).unwrap_or_default());
+                bytes[i] = dna.as_bytes()[i];
+                (flipped - original).abs()
+            })
+            .collect()
+    }
+
+    /// 表現型の値が`target`に近づくようにDNAを書き換える
+    ///
+    /// '1'の割合では必要な数の遺伝子だけを先頭から反転し、それ以外の遺伝子は変えません。
+    /// 2進小数とグレイコードでは`target`に最も近い符号を直接求めます。
+    /// 索引表では、表現型を最も`target`に近づける1遺伝子の反転を、近づかなくなるまで繰り返します。
+    /// 学習した協力確率を遺伝子型に書き戻す（ラマルク型の学習）ために使用します。
+    pub fn encode_towards(&self, dna: &str, target: f64) -> String {
+        let to_dna = |bits: Vec<bool>| bits.iter().map(|&b| if b { '1' } else { '0' }).collect();
+        match self {
+            Decoding::Binary => to_dna(closest_binary(dna.len(), target)),
+            Decoding::Gray => {
+                let binary = closest_binary(dna.len(), target);
+                to_dna(
+                    (0..binary.len())
+                        .map(|i| binary[i] ^ (i > 0 && binary[i - 1]))
+                        .collect(),
+                )
+            }
+            Decoding::Unary => {
+                let ones = dna.bytes().filter(|&b| b == b'1').count();
+                let wanted = (target.clamp(0.0, 1.0) * dna.len() as f64).round() as usize;
+                let (from, to, count) = if wanted > ones {
+                    (b'0', b'1', wanted - ones)
+                } else {
+                    (b'1', b'0', ones - wanted)
+                };
+                let mut remaining = count;
+                dna.bytes()
+                    .map(|byte| {
+                        if byte == from && remaining > 0 {
+                            remaining -= 1;
+                            to as char
+                        } else {
+                            byte as char
+                        }
+                    })
+                    .collect()
+            }
+            Decoding::LookupTable(_) => self.flip_towards(dna, target),
+        }
+    }
+
+    /// 表現型を最も`target`に近づける1遺伝子の反転を、近づかなくなるまで繰り返す
+    fn flip_towards(&self, dna: &str, target: f64) -> String {
+        let mut bytes = dna.as_bytes().to_vec();
+        let decode = |bytes: &[u8]| self.decode(std::str::from_utf8(bytes).unwrap_or_default());
+        let flip = |byte: u8| if byte == b'1' { b'0' } else { b'1' };
+        let mut error = (decode(&bytes) - target).abs();
+
+        loop {
+            let mut best: Option<(usize, f64)> = None;
+            for i in 0..bytes.len() {
+                bytes[i] = flip(bytes[i]);
+                let flipped = (decode(&bytes) - target).abs();
+                bytes[i] = flip(bytes[i]);
+                if flipped < best.map_or(error, |(_, e)| e) {
+                    best = Some((i, flipped));
+                }
+            }
+            match best {
+                Some((i, flipped)) => {
+                    bytes[i] = flip(bytes[i]);
+                    error = flipped;
+                }
+                None => break,
+            }
+        }
+
+        String::from_utf8(bytes).unwrap_or_else(|_| dna.to_string())
+    }
+}
+
+/// 2進小数として読んだ値が`target`に最も近い`length`ビットの符号（先頭が最上位）
+///
+/// `target`を2倍しながら上位から桁を決め、余りが最下位の桁の半分以上なら切り上げます
+/// （全ての桁が1の最大値は切り上げません）。
+fn closest_binary(length: usize, target: f64) -> Vec<bool> {
+    let mut rest = target.clamp(0.0, 1.0);
+    let mut bits: Vec<bool> = (0..length)
+        .map(|_| {
+            rest *= 2.0;
+            let bit = rest >= 1.0;
+            if bit {
+                rest -= 1.0;
+            }
+            bit
+        })
+        .collect();
+    if rest >= 0.5 {
+        if let Some(last_zero) = bits.iter().rposition(|&b| !b) {
+            bits[last_zero] = true;
+            bits[last_zero + 1..].fill(false);
+        }
+    }
+    bits
+}
+
+/// ワード列（各ワードの下位ビットが先頭側）を先頭を最上位とする2進小数（0.0-1.0）として読む
+///
+/// 長さに制限がないよう、各ワードを32ビットずつに分けて重みを掛けて加算します。
+/// 53ビットを超える下位のビットは浮動小数点の精度の範囲で丸められます。
+fn binary_fraction<I: IntoIterator<Item = u64>>(words: I) -> f64 {
+    const HALF: f64 = 4_294_967_296.0;
+    let mut weight = 1.0;
+    let mut value = 0.0;
+    for word in words {
+        let reversed = word.reverse_bits();
+        weight /= HALF;
+        value += (reversed >> 32) as f64 * weight;
+        weight /= HALF;
+        value += (reversed & u32::MAX as u64) as f64 * weight;
+        if weight == 0.0 {
+            break;
+        }
+    }
+    value
+}
+
+/// 各ビットを、下位（先頭側）からそのビットまでの排他的論理和に置き換える
+fn prefix_xor(mut word: u64) -> u64 {
+    for shift in [1, 2, 4, 8, 16, 32] {
+        word ^= word << shift;
+    }
+    word
+}
+
+impl fmt::Display for Decoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decoding::Binary => write!(f, "binary"),
+            Decoding::Gray => write!(f, "gray"),
+            Decoding::Unary => write!(f, "unary"),
+            Decoding::LookupTable(table) => {
+                let table: String = table.iter().map(|&b| if b { '1' } else { '0' }).collect();
+                write!(f, "lookup:{table}")
+            }
+        }
+    }
+}
+
+impl FromStr for Decoding {
+    type Err = GAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None => match s {
+                "binary" => Ok(Decoding::Binary),
+                "gray" => Ok(Decoding::Gray),
+                "unary" => Ok(Decoding::Unary),
+                _ => Err(GAError::ValidationError(format!("Invalid decoding: {s}"))),
+            },
+            Some(("lookup", table)) => Decoding::lookup_table(table),
+            Some(_) => Err(GAError::ValidationError(format!("Invalid decoding: {s}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_and_gray_decoding() {
+        assert_eq!(Decoding::Binary.decode("0000"), 0.0);
+        assert_eq!(Decoding::Binary.decode("0111"), 7.0 / 16.0);
+        assert_eq!(Decoding::Binary.decode("1000"), 8.0 / 16.0);
+
+        // グレイコードでは7 = 0100、8 = 1100で、隣り合う値が1ビット違い
+        assert_eq!(Decoding::Gray.decode("0100"), 7.0 / 16.0);
+        assert_eq!(Decoding::Gray.decode("1100"), 8.0 / 16.0);
+
+        // 長いDNAでもオーバーフローしない
+        let long = format!("0{}", "1".repeat(99));
+        assert!((Decoding::Binary.decode(&long) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_genome_decoding_matches_bitwise_reference() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        // 1ビットずつ重みを加算する素朴な実装と、ワード境界をまたぐ長さで一致する
+        let reference = |bits: &[bool]| {
+            let mut weight = 0.5;
+            bits.iter().fold(0.0, |value, &bit| {
+                let value = if bit { value + weight } else { value };
+                weight /= 2.0;
+                value
+            })
+        };
+        let mut rng = StdRng::seed_from_u64(3);
+        for len in [1, 7, 63, 64, 65, 130] {
+            let genome = BitGenome::random(len, &mut rng);
+            let bits: Vec<bool> = (0..len).map(|i| genome.get(i)).collect();
+            let gray: Vec<bool> = bits
+                .iter()
+                .scan(false, |previous, &bit| {
+                    *previous ^= bit;
+                    Some(*previous)
+                })
+                .collect();
+
+            let binary = Decoding::Binary.decode_genome(&genome);
+            assert!((binary - reference(&bits)).abs() < 1e-15, "{len}");
+            let decoded = Decoding::Gray.decode_genome(&genome);
+            assert!((decoded - reference(&gray)).abs() < 1e-15, "{len}");
+            assert_eq!(binary, Decoding::Binary.decode(&genome.to_string()));
+        }
+
+        // 先頭64ビットより後ろにしか1がなくても0にはならない
+        let tail = format!("{}1", "0".repeat(99));
+        assert_eq!(Decoding::Binary.decode(&tail), 0.5f64.powi(100));
+    }
+
+    #[test]
+    fn test_unary_and_lookup_decoding() {
+        assert_eq!(Decoding::Unary.decode("1010"), 0.5);
+        assert_eq!(Decoding::Unary.decode(""), 0.0);
+
+        // 2ビットずつ区切り、00のみ0に写す
+        let lookup = Decoding::lookup_table("0111").unwrap();
+        assert_eq!(lookup.decode("000110"), 2.0 / 3.0);
+        assert_eq!(lookup.decode("0"), 0.0);
+        assert!(Decoding::lookup_table("011").is_err());
+        assert!(Decoding::lookup_table("01x1").is_err());
+    }
+
+    #[test]
+    fn test_flip_effects() {
+        // 2進数では先頭ビットの反転が表現型を0.5動かす（ハミングの崖）
+        let binary = Decoding::Binary.flip_effects("0111");
+        assert_eq!(binary[0], 0.5);
+        assert_eq!(Decoding::Unary.flip_effects("0111"), vec![0.25; 4]);
+    }
+
+    #[test]
+    fn test_encode_towards() {
+        // '1'の割合では必要な数だけ反転し、それ以外の遺伝子は変えない
+        assert_eq!(Decoding::Unary.encode_towards("0000", 0.5), "1100");
+        assert_eq!(Decoding::Unary.encode_towards("1110", 0.3), "0010");
+        assert_eq!(Decoding::Binary.encode_towards("0000", 0.6), "1010");
+        assert_eq!(Decoding::Gray.encode_towards("0100", 7.0 / 16.0), "0100");
+
+        let lookup = Decoding::lookup_table("0111").unwrap();
+        let encoded = lookup.encode_towards("000000", 1.0);
+        assert_eq!(lookup.decode(&encoded), 1.0);
+        assert_eq!(Decoding::Unary.encode_towards("", 0.5), "");
+    }
+
+    #[test]
+    fn test_encode_towards_finds_closest_code() {
+        // 全ての符号を調べた最小の誤差と一致する
+        for length in 1..=6 {
+            let codes: Vec<String> = (0..1usize << length)
+                .map(|code| format!("{code:0length$b}"))
+                .collect();
+            for decoding in [Decoding::Binary, Decoding::Gray, Decoding::Unary] {
+                for target in [0.0, 0.1, 0.3, 0.5, 0.62, 0.97, 1.0] {
+                    let best = codes
+                        .iter()
+                        .map(|code| (decoding.decode(code) - target).abs())
+                        .fold(f64::INFINITY, f64::min);
+                    let encoded = decoding.encode_towards(&codes[0], target);
+                    assert_eq!(encoded.len(), length);
+                    assert!(
+                        ((decoding.decode(&encoded) - target).abs() - best).abs() < 1e-12,
+                        "{decoding} {length} {target}"
+                    );
+                }
+            }
+        }
+
+        // 長いDNAでも直接求まる
+        let dna = "0".repeat(4096);
+        for decoding in [Decoding::Binary, Decoding::Gray, Decoding::Unary] {
+            let encoded = decoding.encode_towards(&dna, 0.3);
+            assert!((decoding.decode(&encoded) - 0.3).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_decoding_round_trip() {
+        for text in ["binary", "gray", "unary", "lookup:0110"] {
+            let decoding: Decoding = text.parse().unwrap();
+            assert_eq!(decoding.to_string(), text);
+        }
+        assert!("hex".parse::<Decoding>().is_err());
+        assert!("lookup:".parse::<Decoding>().is_err());
+    }
+}
@@ -1 +1,3 @@
+pub mod cache;
+pub mod decoding;
 pub mod utils;
@@ -1,52 +1,157 @@
-use crate::models::model::{BaseModel, Model};
+use crate::core::traits::BehaviourDescriptor;
+use crate::core::types::PayoffMatrix;
+use crate::models::model::{BaseModel, Model, Points};
+use crate::strategies::decoding::Decoding;
 use rand::Rng;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Choice {
     Cooperate,
     Defect,
 }
 
-pub trait StrategyOperation<T>
+pub trait StrategyOperation<T>: Send + Sync
 where
     T: BaseModel,
 {
     fn play_match(&self, agent1: &T, agent2: &T) -> (T, T);
+
+    /// 1回の対戦での両者の行動を、与えられた乱数生成器で決定
+    fn match_choices<R: Rng>(&self, agent1: &T, agent2: &T, rng: &mut R) -> (Choice, Choice);
+
+    /// 1回の対戦での両者の利得を、与えられた乱数生成器で計算
+    ///
+    /// 個体のポイントは変更しません。対戦ごとに独立した乱数生成器を与えることで、
+    /// 対戦を並列に評価しても結果が変わらないようにできます。
+    fn match_payoffs<R: Rng>(&self, agent1: &T, agent2: &T, rng: &mut R) -> (Points, Points) {
+        let (choice1, choice2) = self.match_choices(agent1, agent2, rng);
+        (
+            calculate_payoff(&choice1, &choice2),
+            calculate_payoff(&choice2, &choice1),
+        )
+    }
+
+    /// 個体が協力する確率（世代内の学習で変化した値があればそれを使う）
+    fn cooperation(&self, agent: &T) -> f64;
+
+    /// この組の対戦結果が乱数によらず遺伝子型だけで決まるかどうか
+    ///
+    /// trueを返す組は対戦結果を`MatchCache`で再利用できます。
+    /// 既定では常にfalse（キャッシュを使わない）です。
+    fn is_deterministic(&self, _agent1: &T, _agent2: &T) -> bool {
+        false
+    }
+
+    /// 対戦結果に影響する戦略の設定を表す文字列（`MatchCache`のキーの一部）
+    ///
+    /// 対戦ごとに引かれるため、戦略の作成時に一度だけ作った文字列を返します。
+    fn settings_key(&self) -> &str {
+        ""
+    }
+
     fn new() -> Self;
 }
 
+/// 表現型の値がしきい値（0.5）未満なら協力する決定的戦略
+///
+/// 既定のデコードは`Binary`で、DNAの先頭ビットが0なら協力します。
 #[derive(Clone)]
-pub struct ThresholdSelectionStrategy {}
+pub struct ThresholdSelectionStrategy {
+    decoding: Decoding,
+    settings_key: String,
+}
 
+/// 表現型の値を協力確率とする確率的戦略
+///
+/// 既定のデコードは`Unary`で、DNA中の'1'の割合が協力確率になります。
 #[derive(Clone)]
-pub struct RouletteSelectionStrategy {}
+pub struct RouletteSelectionStrategy {
+    decoding: Decoding,
+    settings_key: String,
+}
+
+impl ThresholdSelectionStrategy {
+    /// 指定したデコード方式で遺伝子型を読む戦略を作成
+    pub fn with_decoding(decoding: Decoding) -> Self {
+        Self {
+            settings_key: format!("threshold:{decoding}"),
+            decoding,
+        }
+    }
+
+    pub fn decoding(&self) -> &Decoding {
+        &self.decoding
+    }
+}
+
+impl Default for ThresholdSelectionStrategy {
+    fn default() -> Self {
+        Self::with_decoding(Decoding::Binary)
+    }
+}
+
+impl Default for RouletteSelectionStrategy {
+    fn default() -> Self {
+        Self::with_decoding(Decoding::default())
+    }
+}
+
+impl RouletteSelectionStrategy {
+    /// 指定したデコード方式で遺伝子型を読む戦略を作成
+    pub fn with_decoding(decoding: Decoding) -> Self {
+        Self {
+            settings_key: format!("probabilistic:{decoding}"),
+            decoding,
+        }
+    }
+
+    pub fn decoding(&self) -> &Decoding {
+        &self.decoding
+    }
+}
 
 impl<T> StrategyOperation<T> for ThresholdSelectionStrategy
 where
     T: Model,
 {
     fn play_match(&self, agent1: &T, agent2: &T) -> (T, T) {
-        let choice1 = agent1
-            .get_choice()
-            .map(|v| get_threshold_choice(v as u64, agent1.get_dna_binary().len()))
-            .unwrap_or(Choice::Defect);
+        let (points1, points2) = self.match_payoffs(agent1, agent2, &mut rand::thread_rng());
 
-        let choice2 = agent2
-            .get_choice()
-            .map(|v| get_threshold_choice(v as u64, agent2.get_dna_binary().len()))
-            .unwrap_or(Choice::Defect);
+        (
+            agent1.with_points(agent1.get_points().saturating_add(points1)),
+            agent2.with_points(agent2.get_points().saturating_add(points2)),
+        )
+    }
 
-        let points1 = calculate_payoff(&choice1, &choice2);
-        let points2 = calculate_payoff(&choice2, &choice1);
+    /// 学習した協力確率を持つ個体はその確率で、それ以外はしきい値で行動を決める
+    fn match_choices<R: Rng>(&self, agent1: &T, agent2: &T, rng: &mut R) -> (Choice, Choice) {
+        let mut choose = |agent: &T| match agent.learned_cooperation() {
+            Some(probability) => get_probabilistic_choice(probability, rng),
+            None => get_threshold_choice(self.decoding.phenotype(agent)),
+        };
+        (choose(agent1), choose(agent2))
+    }
 
-        (
-            agent1.with_points(agent1.get_points() + points1),
-            agent2.with_points(agent2.get_points() + points2),
+    fn cooperation(&self, agent: &T) -> f64 {
+        agent.learned_cooperation().unwrap_or(
+            match get_threshold_choice(self.decoding.phenotype(agent)) {
+                Choice::Cooperate => 1.0,
+                Choice::Defect => 0.0,
+            },
         )
     }
 
+    /// 学習した協力確率を持たない個体どうしの対戦は決定的
+    fn is_deterministic(&self, agent1: &T, agent2: &T) -> bool {
+        agent1.learned_cooperation().is_none() && agent2.learned_cooperation().is_none()
+    }
+
+    fn settings_key(&self) -> &str {
+        &self.settings_key
+    }
+
     fn new() -> Self {
-        Self {}
+        Self::default()
     }
 }
 
@@ -55,50 +160,114 @@ where
     T: Model,
 {
     fn play_match(&self, agent1: &T, agent2: &T) -> (T, T) {
-        let choice1 = get_probabilistic_choice(agent1.get_dna_sum(), agent1.get_dna_binary().len());
-        let choice2 = get_probabilistic_choice(agent2.get_dna_sum(), agent2.get_dna_binary().len());
+        let (points1, points2) = self.match_payoffs(agent1, agent2, &mut rand::thread_rng());
 
-        let points1 = calculate_payoff(&choice1, &choice2);
-        let points2 = calculate_payoff(&choice2, &choice1);
+        (
+            agent1.with_points(agent1.get_points().saturating_add(points1)),
+            agent2.with_points(agent2.get_points().saturating_add(points2)),
+        )
+    }
 
+    fn match_choices<R: Rng>(&self, agent1: &T, agent2: &T, rng: &mut R) -> (Choice, Choice) {
         (
-            agent1.with_points(agent1.get_points() + points1),
-            agent2.with_points(agent2.get_points() + points2),
+            get_probabilistic_choice(self.cooperation(agent1), rng),
+            get_probabilistic_choice(self.cooperation(agent2), rng),
         )
     }
 
+    fn cooperation(&self, agent: &T) -> f64 {
+        agent
+            .learned_cooperation()
+            .unwrap_or_else(|| self.decoding.phenotype(agent))
+    }
+
+    /// 協力確率が0か1の個体どうしの対戦だけが決定的
+    fn is_deterministic(&self, agent1: &T, agent2: &T) -> bool {
+        let certain = |agent: &T| {
+            let probability = self.cooperation(agent);
+            probability <= 0.0 || probability >= 1.0
+        };
+        certain(agent1) && certain(agent2)
+    }
+
+    fn settings_key(&self) -> &str {
+        &self.settings_key
+    }
+
     fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+}
+
+impl<T> BehaviourDescriptor<T> for ThresholdSelectionStrategy
+where
+    T: Model,
+{
+    fn describe(&self, agent: &T, probes: &[T]) -> Vec<f64> {
+        let cooperation = |agent: &T| match get_threshold_choice(self.decoding.phenotype(agent)) {
+            Choice::Cooperate => 1.0,
+            Choice::Defect => 0.0,
+        };
+        describe_behaviour(cooperation(agent), probes.iter().map(cooperation))
     }
 }
 
-fn calculate_payoff(my_choice: &Choice, opponent_choice: &Choice) -> u64 {
-    match (my_choice, opponent_choice) {
-        (Choice::Cooperate, Choice::Cooperate) => 3,
-        (Choice::Cooperate, Choice::Defect) => 0,
-        (Choice::Defect, Choice::Cooperate) => 5,
-        (Choice::Defect, Choice::Defect) => 1,
+impl<T> BehaviourDescriptor<T> for RouletteSelectionStrategy
+where
+    T: Model,
+{
+    fn describe(&self, agent: &T, probes: &[T]) -> Vec<f64> {
+        describe_behaviour(
+            self.decoding.phenotype(agent),
+            probes.iter().map(|probe| self.decoding.phenotype(probe)),
+        )
     }
 }
 
-fn get_threshold_choice(dna_value: u64, dna_length: usize) -> Choice {
-    let threshold = 1u64 << (dna_length - 1);
-    if dna_value < threshold {
+/// 協力確率と、各プローブに対する期待報酬（最大報酬で正規化）を並べた行動特性
+fn describe_behaviour<I: IntoIterator<Item = f64>>(cooperation: f64, probes: I) -> Vec<f64> {
+    let matrix = PayoffMatrix::default();
+    let max_payoff = matrix.temptation.max(1) as f64;
+    std::iter::once(cooperation)
+        .chain(
+            probes
+                .into_iter()
+                .map(|q| matrix.expected_payoff(cooperation, q) / max_payoff),
+        )
+        .collect()
+}
+
+pub(crate) fn calculate_payoff(my_choice: &Choice, opponent_choice: &Choice) -> u64 {
+    PayoffMatrix::default().payoff(
+        matches!(my_choice, Choice::Cooperate),
+        matches!(opponent_choice, Choice::Cooperate),
+    )
+}
+
+/// 確率的戦略（RouletteSelectionStrategy）での協力確率
+///
+/// DNA中の'1'の割合をそのまま協力する確率とします。
+pub fn cooperation_probability(ones_count: u64, dna_length: usize) -> f64 {
+    if dna_length == 0 {
+        return 0.0;
+    }
+    ones_count as f64 / dna_length as f64
+}
+
+fn get_threshold_choice(phenotype: f64) -> Choice {
+    if phenotype < 0.5 {
         Choice::Cooperate
     } else {
         Choice::Defect
     }
 }
 
-fn get_probabilistic_choice(ones_count: u64, dna_length: usize) -> Choice {
-    if ones_count == 0 {
+fn get_probabilistic_choice<R: Rng>(phenotype: f64, rng: &mut R) -> Choice {
+    if phenotype <= 0.0 {
         return Choice::Defect;
     }
 
-    let mut rng = rand::thread_rng();
-    let cooperation_probability = ones_count as f64 / dna_length as f64;
-
-    if rng.gen::<f64>() < cooperation_probability {
+    if rng.gen::<f64>() < phenotype {
         Choice::Cooperate
     } else {
         Choice::Defect
@@ -126,3 +295,39 @@ fn agent_points_test() {
     let agent = agent.with_points(6);
     assert_eq!(agent.get_points(), 6);
 }
+
+#[test]
+fn threshold_decoding_test() {
+    use crate::models::model::Agent;
+
+    // 2進数では0111は協力、1000は裏切り
+    let binary = ThresholdSelectionStrategy::default();
+    let cooperator = Agent::new(1, "0111".to_string());
+    let defector = Agent::new(2, "1000".to_string());
+    let (c, d) = binary.play_match(&cooperator, &defector);
+    assert_eq!((c.get_points(), d.get_points()), (0, 5));
+
+    // '1'の割合で読むと0111は裏切り、1000は協力
+    let unary = ThresholdSelectionStrategy::with_decoding(Decoding::Unary);
+    let (c, d) = unary.play_match(&cooperator, &defector);
+    assert_eq!((c.get_points(), d.get_points()), (5, 0));
+}
+
+#[test]
+fn behaviour_descriptor_test() {
+    use crate::models::model::Agent;
+
+    let probes = [
+        Agent::new(0, "0000".to_string()),
+        Agent::new(1, "1111".to_string()),
+    ];
+    let strategy = RouletteSelectionStrategy::default();
+
+    // 協力確率0.5: 常に裏切る相手に(0.5 * 0 + 0.5 * 1) / 5、常に協力する相手に(0.5 * 3 + 0.5 * 5) / 5
+    let behaviour = strategy.describe(&Agent::new(2, "1100".to_string()), &probes);
+    assert_eq!(behaviour, vec![0.5, 0.1, 0.8]);
+
+    let threshold = ThresholdSelectionStrategy::default();
+    let behaviour = threshold.describe(&Agent::new(2, "0111".to_string()), &probes);
+    assert_eq!(behaviour, vec![1.0, 0.6, 0.0]);
+}
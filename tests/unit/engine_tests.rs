@@ -135,19 +135,19 @@ mod tests {
 
         // Test RouletteSelection
         let roulette = RouletteSelection::new();
-        let (p1, p2) = roulette.select_parents(&population);
+        let (p1, p2) = roulette.select_parents(&population).unwrap();
         assert!(population.iter().any(|a| a.id == p1.id));
         assert!(population.iter().any(|a| a.id == p2.id));
 
         // Test TournamentSelection
         let tournament = TournamentSelection::new(2).unwrap();
-        let (p1, p2) = tournament.select_parents(&population);
+        let (p1, p2) = tournament.select_parents(&population).unwrap();
         assert!(population.iter().any(|a| a.id == p1.id));
         assert!(population.iter().any(|a| a.id == p2.id));
 
         // Test RankSelection
         let rank = RankSelection::linear();
-        let (p1, p2) = rank.select_parents(&population);
+        let (p1, p2) = rank.select_parents(&population).unwrap();
         assert!(population.iter().any(|a| a.id == p1.id));
         assert!(population.iter().any(|a| a.id == p2.id));
     }